@@ -0,0 +1,5 @@
+-- ============================================================================
+-- User Notification Preferences
+-- ============================================================================
+ALTER TABLE users
+    ADD COLUMN payment_emails_enabled BOOLEAN NOT NULL DEFAULT TRUE;
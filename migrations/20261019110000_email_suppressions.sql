@@ -0,0 +1,14 @@
+-- ============================================================================
+-- Email Suppressions Table
+-- ============================================================================
+-- Addresses that bounced, complained or unsubscribed. Checked before every send
+-- except sign-in mail.
+CREATE TABLE email_suppressions (
+    suppression_id SERIAL PRIMARY KEY,
+    email CITEXT UNIQUE NOT NULL,
+    reason TEXT NOT NULL CHECK (reason IN ('bounce', 'complaint', 'unsubscribe')),
+    details TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_email_suppressions_created_at ON email_suppressions(created_at DESC);
@@ -0,0 +1,35 @@
+-- ============================================================================
+-- Contact Inquiries
+-- ============================================================================
+-- Messages from the contact form, kept so they survive email failures and can
+-- be worked through from the admin inbox.
+CREATE TABLE contact_inquiries (
+    inquiry_id SERIAL PRIMARY KEY,
+    user_id INTEGER REFERENCES users(user_id) ON DELETE SET NULL,
+    email CITEXT NOT NULL,
+    message TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'new' CHECK (status IN ('new', 'in_progress', 'resolved')),
+    assigned_to INTEGER REFERENCES users(user_id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_contact_inquiries_status ON contact_inquiries(status);
+CREATE INDEX idx_contact_inquiries_created_at ON contact_inquiries(created_at DESC);
+
+CREATE TRIGGER update_contact_inquiries_updated_at
+    BEFORE UPDATE ON contact_inquiries
+    FOR EACH ROW
+    EXECUTE FUNCTION update_updated_at_column();
+
+-- Internal notes and email replies, shown as the inquiry's thread.
+CREATE TABLE contact_inquiry_entries (
+    entry_id SERIAL PRIMARY KEY,
+    inquiry_id INTEGER NOT NULL REFERENCES contact_inquiries(inquiry_id) ON DELETE CASCADE,
+    author_id INTEGER REFERENCES users(user_id) ON DELETE SET NULL,
+    kind TEXT NOT NULL CHECK (kind IN ('note', 'reply')),
+    body TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_contact_inquiry_entries_inquiry_id ON contact_inquiry_entries(inquiry_id, created_at);
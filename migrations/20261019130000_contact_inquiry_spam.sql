@@ -0,0 +1,14 @@
+-- ============================================================================
+-- Contact Inquiry Spam Scoring
+-- ============================================================================
+-- Quarantined inquiries (quarantined_at set) are kept out of the inbox and are
+-- not emailed until an admin releases them.
+ALTER TABLE contact_inquiries
+    ADD COLUMN content_hash TEXT,
+    ADD COLUMN spam_score INTEGER NOT NULL DEFAULT 0,
+    ADD COLUMN spam_reasons TEXT[] NOT NULL DEFAULT '{}',
+    ADD COLUMN quarantined_at TIMESTAMPTZ;
+
+CREATE INDEX idx_contact_inquiries_content_hash ON contact_inquiries(content_hash, created_at);
+CREATE INDEX idx_contact_inquiries_quarantined_at ON contact_inquiries(quarantined_at DESC)
+    WHERE quarantined_at IS NOT NULL;
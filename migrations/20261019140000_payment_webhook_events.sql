@@ -0,0 +1,18 @@
+-- ============================================================================
+-- Payment Webhook Events Table
+-- ============================================================================
+-- Raw payment gateway webhook deliveries, kept for auditing. Events are never
+-- trusted directly: the payment is re-queried from the gateway and `outcome`
+-- records what that did to the order.
+CREATE TABLE payment_webhook_events (
+    event_id SERIAL PRIMARY KEY,
+    event_type TEXT,
+    payment_key TEXT,
+    payload TEXT NOT NULL,
+    outcome TEXT,
+    received_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    processed_at TIMESTAMPTZ
+);
+
+CREATE INDEX idx_payment_webhook_events_payment_key ON payment_webhook_events(payment_key);
+CREATE INDEX idx_payment_webhook_events_received_at ON payment_webhook_events(received_at DESC);
@@ -0,0 +1,20 @@
+-- ============================================================================
+-- Refunds
+-- ============================================================================
+-- Full and partial refunds of paid orders. An order whose refunds add up to its
+-- price is 'refunded'; anything less is 'partially_refunded'.
+ALTER TABLE orders DROP CONSTRAINT orders_payment_status_check;
+ALTER TABLE orders ADD CONSTRAINT orders_payment_status_check
+    CHECK (payment_status IN ('pending', 'paid', 'failed', 'cancelled', 'refunded', 'partially_refunded'));
+
+CREATE TABLE refunds (
+    refund_id SERIAL PRIMARY KEY,
+    order_id UUID NOT NULL REFERENCES orders(order_id) ON DELETE CASCADE,
+    amount INTEGER NOT NULL CHECK (amount > 0),
+    reason TEXT NOT NULL,
+    -- NULL when the refund was made outside the app (e.g. the gateway dashboard)
+    refunded_by INTEGER REFERENCES users(user_id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_refunds_order_id ON refunds(order_id);
@@ -0,0 +1,23 @@
+-- ============================================================================
+-- Order Events Table
+-- ============================================================================
+-- Append-only history of order status transitions, written in the same
+-- transaction as the status change. `from_status` is NULL for order creation.
+CREATE TABLE order_events (
+    event_id SERIAL PRIMARY KEY,
+    order_id UUID NOT NULL REFERENCES orders(order_id) ON DELETE CASCADE,
+    from_status TEXT,
+    to_status TEXT NOT NULL,
+    source TEXT NOT NULL CHECK (source IN ('checkout', 'webhook', 'admin', 'system')),
+    payment_key TEXT,
+    actor_id INTEGER REFERENCES users(user_id) ON DELETE SET NULL,
+    note TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_order_events_order_id ON order_events(order_id, created_at);
+
+-- Existing orders start their history at their current status
+INSERT INTO order_events (order_id, from_status, to_status, source, payment_key, note, created_at)
+SELECT order_id, NULL, payment_status, 'system', payment_key, 'Recorded before event history', created_at
+FROM orders;
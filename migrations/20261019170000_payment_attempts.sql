@@ -0,0 +1,19 @@
+-- ============================================================================
+-- Payment Attempts Table
+-- ============================================================================
+-- One row per payment key the checkout tried to confirm, with the gateway's
+-- answer. Lets a repeated success redirect reuse the stored outcome instead of
+-- confirming again. 'errored' means the gateway could not be reached, so the
+-- outcome is unknown and a retry may confirm again.
+CREATE TABLE payment_attempts (
+    payment_key TEXT PRIMARY KEY,
+    order_id UUID NOT NULL REFERENCES orders(order_id) ON DELETE CASCADE,
+    amount INTEGER NOT NULL,
+    status TEXT NOT NULL CHECK (status IN ('confirming', 'succeeded', 'failed', 'errored')),
+    gateway_code TEXT,
+    gateway_message TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    completed_at TIMESTAMPTZ
+);
+
+CREATE INDEX idx_payment_attempts_order_id ON payment_attempts(order_id, created_at);
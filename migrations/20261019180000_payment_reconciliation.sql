@@ -0,0 +1,40 @@
+-- ============================================================================
+-- Payment Reconciliation
+-- ============================================================================
+-- Each run compares recent orders with the gateway's records. A mismatch stays
+-- open (one per order and kind) until an admin fixes or dismisses it.
+CREATE TABLE reconciliation_runs (
+    run_id SERIAL PRIMARY KEY,
+    window_start TIMESTAMPTZ NOT NULL,
+    window_end TIMESTAMPTZ NOT NULL,
+    checked_count INTEGER NOT NULL DEFAULT 0,
+    finding_count INTEGER NOT NULL DEFAULT 0,
+    -- Orders the gateway could not be asked about (network or API errors)
+    error_count INTEGER NOT NULL DEFAULT 0,
+    started_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    finished_at TIMESTAMPTZ
+);
+
+CREATE TABLE reconciliation_findings (
+    finding_id SERIAL PRIMARY KEY,
+    run_id INTEGER NOT NULL REFERENCES reconciliation_runs(run_id) ON DELETE CASCADE,
+    order_id UUID NOT NULL REFERENCES orders(order_id) ON DELETE CASCADE,
+    kind TEXT NOT NULL CHECK (kind IN ('paid_not_recorded', 'amount_mismatch', 'unknown_payment', 'status_mismatch')),
+    local_status TEXT NOT NULL,
+    local_amount INTEGER NOT NULL,
+    gateway_status TEXT,
+    gateway_amount INTEGER,
+    gateway_payment_key TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    resolved_at TIMESTAMPTZ,
+    resolved_by INTEGER REFERENCES users(user_id) ON DELETE SET NULL,
+    resolution TEXT
+);
+
+CREATE UNIQUE INDEX idx_reconciliation_findings_open
+    ON reconciliation_findings(order_id, kind) WHERE resolved_at IS NULL;
+CREATE INDEX idx_reconciliation_findings_run_id ON reconciliation_findings(run_id);
+
+ALTER TABLE order_events DROP CONSTRAINT order_events_source_check;
+ALTER TABLE order_events ADD CONSTRAINT order_events_source_check
+    CHECK (source IN ('checkout', 'webhook', 'admin', 'system', 'reconciliation'));
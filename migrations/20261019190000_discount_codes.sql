@@ -0,0 +1,40 @@
+-- ============================================================================
+-- Discount Codes
+-- ============================================================================
+-- Admin-managed promotion codes applied on the quote page. A code is only
+-- redeemed (counted against its limits) when the payment is confirmed.
+CREATE TABLE discount_codes (
+    discount_code_id SERIAL PRIMARY KEY,
+    -- Stored upper-case; codes are matched case-insensitively
+    code TEXT NOT NULL UNIQUE CHECK (code = UPPER(code) AND LENGTH(code) > 0),
+    kind TEXT NOT NULL CHECK (kind IN ('percentage', 'fixed')),
+    -- Percent off (1-100) or KRW off, depending on kind
+    value INTEGER NOT NULL CHECK (value > 0 AND (kind <> 'percentage' OR value <= 100)),
+    min_order_amount INTEGER NOT NULL DEFAULT 0 CHECK (min_order_amount >= 0),
+    -- NULL for no limit
+    max_redemptions INTEGER CHECK (max_redemptions > 0),
+    per_user_limit INTEGER CHECK (per_user_limit > 0),
+    expires_at TIMESTAMPTZ,
+    is_active BOOLEAN NOT NULL DEFAULT TRUE,
+    redemption_count INTEGER NOT NULL DEFAULT 0,
+    created_by INTEGER REFERENCES users(user_id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE discount_redemptions (
+    redemption_id SERIAL PRIMARY KEY,
+    discount_code_id INTEGER NOT NULL REFERENCES discount_codes(discount_code_id) ON DELETE CASCADE,
+    order_id UUID NOT NULL UNIQUE REFERENCES orders(order_id) ON DELETE CASCADE,
+    user_id INTEGER NOT NULL REFERENCES users(user_id) ON DELETE CASCADE,
+    amount INTEGER NOT NULL,
+    redeemed_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_discount_redemptions_code_user ON discount_redemptions(discount_code_id, user_id);
+
+-- price_amount stays the amount charged; list_price_amount is the price before discount
+ALTER TABLE orders ADD COLUMN list_price_amount INTEGER;
+UPDATE orders SET list_price_amount = price_amount;
+ALTER TABLE orders ALTER COLUMN list_price_amount SET NOT NULL;
+ALTER TABLE orders ADD COLUMN discount_amount INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE orders ADD COLUMN discount_code_id INTEGER REFERENCES discount_codes(discount_code_id) ON DELETE SET NULL;
@@ -0,0 +1,40 @@
+-- ============================================================================
+-- Credit Wallet
+-- ============================================================================
+-- Prepaid credits: topped up with one gateway payment, then spent on orders
+-- without a checkout. users.credit_balance is the balance; credit_transactions
+-- is its ledger, one row per change.
+ALTER TABLE users ADD COLUMN credit_balance INTEGER NOT NULL DEFAULT 0 CHECK (credit_balance >= 0);
+
+CREATE TABLE credit_topups (
+    topup_id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id INTEGER NOT NULL REFERENCES users(user_id) ON DELETE CASCADE,
+    -- Sent to the gateway as the order ID; prefixed TOP- to stay apart from orders
+    order_number TEXT NOT NULL UNIQUE,
+    amount INTEGER NOT NULL CHECK (amount > 0),
+    status TEXT NOT NULL DEFAULT 'pending' CHECK (status IN ('pending', 'paid', 'failed')),
+    payment_key TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    paid_at TIMESTAMPTZ
+);
+
+CREATE INDEX idx_credit_topups_user_id ON credit_topups(user_id);
+
+CREATE TABLE credit_transactions (
+    transaction_id SERIAL PRIMARY KEY,
+    user_id INTEGER NOT NULL REFERENCES users(user_id) ON DELETE CASCADE,
+    kind TEXT NOT NULL CHECK (kind IN ('top_up', 'spend', 'refund', 'adjustment')),
+    -- Positive adds credits, negative removes them
+    amount INTEGER NOT NULL CHECK (amount <> 0),
+    balance_after INTEGER NOT NULL CHECK (balance_after >= 0),
+    order_id UUID REFERENCES orders(order_id) ON DELETE SET NULL,
+    topup_id UUID UNIQUE REFERENCES credit_topups(topup_id) ON DELETE SET NULL,
+    note TEXT,
+    -- Admin who made an adjustment
+    created_by INTEGER REFERENCES users(user_id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_credit_transactions_user_id ON credit_transactions(user_id, created_at DESC);
+-- An order is paid with credits at most once
+CREATE UNIQUE INDEX idx_credit_transactions_spend ON credit_transactions(order_id) WHERE kind = 'spend';
@@ -0,0 +1,33 @@
+-- ============================================================================
+-- Pricing Rules
+-- ============================================================================
+-- Versioned prices. A rule is never edited: a change is a new version with its
+-- own effective date, and the rule in effect is the latest one that has started.
+-- Rates are KRW per 1,000 characters so volume tiers can go below ₩1/character.
+CREATE TABLE pricing_rules (
+    pricing_rule_id SERIAL PRIMARY KEY,
+    price_per_thousand INTEGER NOT NULL CHECK (price_per_thousand >= 0),
+    minimum_amount INTEGER NOT NULL CHECK (minimum_amount >= 0),
+    effective_from TIMESTAMPTZ NOT NULL,
+    note TEXT,
+    created_by INTEGER REFERENCES users(user_id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_pricing_rules_effective_from ON pricing_rules(effective_from DESC);
+
+-- Characters from starts_at on are charged at the tier's rate (graduated)
+CREATE TABLE pricing_rule_tiers (
+    pricing_rule_id INTEGER NOT NULL REFERENCES pricing_rules(pricing_rule_id) ON DELETE CASCADE,
+    starts_at INTEGER NOT NULL CHECK (starts_at > 0),
+    price_per_thousand INTEGER NOT NULL CHECK (price_per_thousand >= 0),
+    PRIMARY KEY (pricing_rule_id, starts_at)
+);
+
+-- The prices that were compiled in until now: ₩1 per character, ₩100 minimum
+INSERT INTO pricing_rules (price_per_thousand, minimum_amount, effective_from, note)
+VALUES (1000, 100, '1970-01-01T00:00:00Z', 'Initial prices');
+
+ALTER TABLE orders ADD COLUMN pricing_rule_id INTEGER REFERENCES pricing_rules(pricing_rule_id);
+UPDATE orders SET pricing_rule_id = (SELECT MIN(pricing_rule_id) FROM pricing_rules);
+ALTER TABLE orders ALTER COLUMN pricing_rule_id SET NOT NULL;
@@ -0,0 +1,19 @@
+-- ============================================================================
+-- Order Tax Breakdown
+-- ============================================================================
+-- Supply amount and VAT for each order. price_amount stays the gross amount
+-- charged, so net_amount + tax_amount = price_amount.
+ALTER TABLE orders ADD COLUMN net_amount INTEGER;
+ALTER TABLE orders ADD COLUMN tax_amount INTEGER;
+ALTER TABLE orders ADD COLUMN tax_rate_percent INTEGER;
+
+-- Prices so far were VAT-inclusive at 10%
+UPDATE orders
+SET net_amount = ROUND(price_amount * 100 / 110.0)::integer,
+    tax_amount = price_amount - ROUND(price_amount * 100 / 110.0)::integer,
+    tax_rate_percent = 10;
+
+ALTER TABLE orders ALTER COLUMN net_amount SET NOT NULL;
+ALTER TABLE orders ALTER COLUMN tax_amount SET NOT NULL;
+ALTER TABLE orders ALTER COLUMN tax_rate_percent SET NOT NULL;
+ALTER TABLE orders ADD CONSTRAINT orders_tax_adds_up CHECK (net_amount + tax_amount = price_amount);
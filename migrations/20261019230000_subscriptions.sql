@@ -0,0 +1,64 @@
+-- ============================================================================
+-- Subscriptions
+-- ============================================================================
+-- Monthly plans that include a number of characters of analysis, charged
+-- through a card billing key. subscription_charges records every attempt to
+-- charge a subscription, including dunning retries.
+CREATE TABLE subscription_plans (
+    plan_id SERIAL PRIMARY KEY,
+    name TEXT NOT NULL,
+    -- Charged every period, VAT included
+    price_amount INTEGER NOT NULL CHECK (price_amount > 0),
+    included_characters INTEGER NOT NULL CHECK (included_characters > 0),
+    is_active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+INSERT INTO subscription_plans (name, price_amount, included_characters)
+VALUES ('Basic', 9900, 100000), ('Pro', 29000, 500000);
+
+CREATE TABLE subscriptions (
+    subscription_id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id INTEGER NOT NULL REFERENCES users(user_id) ON DELETE CASCADE,
+    plan_id INTEGER NOT NULL REFERENCES subscription_plans(plan_id),
+    -- incomplete: waiting for a card; past_due: a renewal failed and is being retried
+    status TEXT NOT NULL DEFAULT 'incomplete' CHECK (status IN ('incomplete', 'active', 'past_due', 'ended')),
+    -- Identifies the customer to the gateway; the billing key is only valid with it
+    customer_key TEXT NOT NULL UNIQUE,
+    billing_key TEXT,
+    -- Masked card number shown to the user
+    card_label TEXT,
+    current_period_start TIMESTAMPTZ,
+    current_period_end TIMESTAMPTZ,
+    characters_used INTEGER NOT NULL DEFAULT 0 CHECK (characters_used >= 0),
+    cancel_at_period_end BOOLEAN NOT NULL DEFAULT FALSE,
+    -- Failed renewal charges since the last successful one
+    failed_attempts INTEGER NOT NULL DEFAULT 0,
+    next_retry_at TIMESTAMPTZ,
+    ended_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_subscriptions_user_id ON subscriptions(user_id, created_at DESC);
+-- At most one live subscription per user
+CREATE UNIQUE INDEX idx_subscriptions_live ON subscriptions(user_id) WHERE status IN ('active', 'past_due');
+
+CREATE TABLE subscription_charges (
+    charge_id SERIAL PRIMARY KEY,
+    subscription_id UUID NOT NULL REFERENCES subscriptions(subscription_id) ON DELETE CASCADE,
+    -- Sent to the gateway as the order ID; prefixed SUB- to stay apart from orders
+    order_number TEXT NOT NULL UNIQUE,
+    amount INTEGER NOT NULL CHECK (amount > 0),
+    status TEXT NOT NULL DEFAULT 'pending' CHECK (status IN ('pending', 'paid', 'failed')),
+    payment_key TEXT,
+    failure_message TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    settled_at TIMESTAMPTZ
+);
+
+CREATE INDEX idx_subscription_charges_subscription_id ON subscription_charges(subscription_id, created_at DESC);
+-- A charge left pending by a gateway timeout is retried under the same order number
+CREATE UNIQUE INDEX idx_subscription_charges_pending ON subscription_charges(subscription_id) WHERE status = 'pending';
+
+-- Orders covered by a subscription's included characters rather than paid for
+ALTER TABLE orders ADD COLUMN subscription_id UUID REFERENCES subscriptions(subscription_id) ON DELETE SET NULL;
@@ -0,0 +1,22 @@
+-- ============================================================================
+-- Virtual Accounts
+-- ============================================================================
+-- Bank transfer payments. Confirming a virtual account payment issues an account
+-- number instead of capturing money; the order waits in 'awaiting_deposit' until
+-- the gateway reports the transfer, or is cancelled once the due date passes.
+ALTER TABLE orders DROP CONSTRAINT orders_payment_status_check;
+ALTER TABLE orders ADD CONSTRAINT orders_payment_status_check
+    CHECK (payment_status IN ('pending', 'awaiting_deposit', 'paid', 'failed', 'cancelled', 'refunded', 'partially_refunded'));
+
+CREATE TABLE virtual_accounts (
+    order_id UUID PRIMARY KEY REFERENCES orders(order_id) ON DELETE CASCADE,
+    payment_key TEXT NOT NULL,
+    -- Two-digit bank code as reported by the gateway
+    bank_code TEXT NOT NULL,
+    account_number TEXT NOT NULL,
+    -- The account stops accepting transfers after this
+    due_at TIMESTAMPTZ NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_virtual_accounts_due_at ON virtual_accounts(due_at);
@@ -0,0 +1,15 @@
+-- ============================================================================
+-- Order Payment Methods
+-- ============================================================================
+-- The payment method the customer picked at checkout. NULL for orders that never
+-- went through the payment window, such as those paid with credits or covered by
+-- a subscription.
+ALTER TABLE orders ADD COLUMN payment_method TEXT
+    CHECK (payment_method IN ('card', 'transfer', 'virtual_account', 'mobile_phone', 'kakao_pay', 'naver_pay', 'toss_pay'));
+
+-- Before the picker, checkout offered card and virtual account payments only
+UPDATE orders SET payment_method = 'card' WHERE payment_key IS NOT NULL;
+UPDATE orders SET payment_method = 'virtual_account'
+    WHERE order_id IN (SELECT order_id FROM virtual_accounts);
+
+CREATE INDEX idx_orders_payment_method ON orders(payment_method);
@@ -0,0 +1,26 @@
+-- ============================================================================
+-- Cash Receipts
+-- ============================================================================
+-- 현금영수증 requested at checkout for account transfer and virtual account
+-- payments. The receipt is issued through the gateway once the order is paid and
+-- cancelled, in whole or in part, as the order is refunded.
+CREATE TABLE cash_receipts (
+    order_id UUID PRIMARY KEY REFERENCES orders(order_id) ON DELETE CASCADE,
+    receipt_type TEXT NOT NULL CHECK (receipt_type IN ('income_deduction', 'expense_proof')),
+    -- Phone, cash receipt card or business registration number, digits only
+    identifier TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'requested' CHECK (status IN ('requested', 'issued', 'failed', 'cancelled')),
+    -- Set once issued
+    receipt_key TEXT,
+    issue_number TEXT,
+    receipt_url TEXT,
+    issued_amount INTEGER,
+    -- Part of the issued amount cancelled for refunds
+    cancelled_amount INTEGER NOT NULL DEFAULT 0,
+    -- Why the gateway refused to issue the receipt
+    failure_message TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_cash_receipts_status ON cash_receipts(status);
@@ -0,0 +1,80 @@
+-- ============================================================================
+-- Products and Order Items
+-- ============================================================================
+-- What the site sells. The kind decides which metadata an order item of the
+-- product carries; text analysis is the first kind.
+CREATE TABLE products (
+    product_id SERIAL PRIMARY KEY,
+    code TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    kind TEXT NOT NULL CHECK (kind IN ('text_analysis')),
+    active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+INSERT INTO products (code, name, kind) VALUES ('text_analysis', 'Text Analysis', 'text_analysis');
+
+-- The lines of an order. The order's list price is the sum of quantity × unit
+-- price over its items; discounts, tax and refunds stay on the order.
+CREATE TABLE order_items (
+    order_item_id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    order_id UUID NOT NULL REFERENCES orders(order_id) ON DELETE CASCADE,
+    product_id INTEGER NOT NULL REFERENCES products(product_id),
+    quantity INTEGER NOT NULL CHECK (quantity > 0),
+    unit_price_amount INTEGER NOT NULL CHECK (unit_price_amount >= 0),
+    -- Product-specific details, tagged with the product kind, e.g. the uploaded
+    -- file of a text analysis
+    metadata JSONB NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_order_items_order_id ON order_items(order_id);
+CREATE INDEX idx_order_items_product_id ON order_items(product_id);
+
+-- Every existing order was a single text analysis
+INSERT INTO order_items (order_id, product_id, quantity, unit_price_amount, metadata, created_at)
+SELECT
+    o.order_id,
+    p.product_id,
+    1,
+    o.list_price_amount,
+    jsonb_build_object(
+        'kind', 'text_analysis',
+        'filename', o.filename,
+        'file_size', o.file_size,
+        'text_content', o.text_content,
+        'text_length', o.text_length
+    ),
+    o.created_at
+FROM orders o
+CROSS JOIN products p
+WHERE p.code = 'text_analysis';
+
+ALTER TABLE orders
+    DROP COLUMN filename,
+    DROP COLUMN file_size,
+    DROP COLUMN text_content,
+    DROP COLUMN text_length;
+
+-- An order's items with their product, in the shape the application decodes
+-- into `OrderItem`, so order queries can load them in the same statement.
+CREATE FUNCTION order_items_json(p_order_id UUID) RETURNS JSONB
+LANGUAGE sql STABLE AS $$
+    SELECT COALESCE(
+        jsonb_agg(
+            jsonb_build_object(
+                'order_item_id', i.order_item_id,
+                'product_code', p.code,
+                'product_name', p.name,
+                'quantity', i.quantity,
+                'unit_price_amount', i.unit_price_amount,
+                'metadata', i.metadata
+            )
+            ORDER BY i.created_at, i.order_item_id
+        ),
+        '[]'::jsonb
+    )
+    FROM order_items i
+    JOIN products p ON p.product_id = i.product_id
+    WHERE i.order_id = p_order_id
+$$;
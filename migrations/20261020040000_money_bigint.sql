@@ -0,0 +1,37 @@
+-- ============================================================================
+-- Money Amounts as BIGINT
+-- ============================================================================
+-- Amounts are won (KRW), the shop currency. INTEGER tops out around 2.1 billion
+-- won, which totals and balances pass quickly; BIGINT matches the application's
+-- `Money` type. Per-thousand-character rates stay INTEGER.
+ALTER TABLE orders
+    ALTER COLUMN list_price_amount TYPE BIGINT,
+    ALTER COLUMN discount_amount TYPE BIGINT,
+    ALTER COLUMN price_amount TYPE BIGINT,
+    ALTER COLUMN net_amount TYPE BIGINT,
+    ALTER COLUMN tax_amount TYPE BIGINT;
+
+ALTER TABLE order_items ALTER COLUMN unit_price_amount TYPE BIGINT;
+ALTER TABLE payment_attempts ALTER COLUMN amount TYPE BIGINT;
+ALTER TABLE refunds ALTER COLUMN amount TYPE BIGINT;
+
+ALTER TABLE cash_receipts
+    ALTER COLUMN issued_amount TYPE BIGINT,
+    ALTER COLUMN cancelled_amount TYPE BIGINT;
+
+ALTER TABLE users ALTER COLUMN credit_balance TYPE BIGINT;
+ALTER TABLE credit_topups ALTER COLUMN amount TYPE BIGINT;
+
+ALTER TABLE credit_transactions
+    ALTER COLUMN amount TYPE BIGINT,
+    ALTER COLUMN balance_after TYPE BIGINT;
+
+ALTER TABLE discount_codes ALTER COLUMN min_order_amount TYPE BIGINT;
+ALTER TABLE discount_redemptions ALTER COLUMN amount TYPE BIGINT;
+ALTER TABLE pricing_rules ALTER COLUMN minimum_amount TYPE BIGINT;
+ALTER TABLE subscription_plans ALTER COLUMN price_amount TYPE BIGINT;
+ALTER TABLE subscription_charges ALTER COLUMN amount TYPE BIGINT;
+
+ALTER TABLE reconciliation_findings
+    ALTER COLUMN local_amount TYPE BIGINT,
+    ALTER COLUMN gateway_amount TYPE BIGINT;
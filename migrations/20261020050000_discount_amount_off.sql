@@ -0,0 +1,19 @@
+-- ============================================================================
+-- Fixed Discount Amounts as BIGINT
+-- ============================================================================
+-- `value` held either a percentage or a won amount, so it stayed INTEGER when
+-- the other amounts moved to BIGINT. Each kind now has its own column: the
+-- percentage stays INTEGER and the won amount is BIGINT like the rest.
+ALTER TABLE discount_codes
+    ADD COLUMN percent_off INTEGER CHECK (percent_off BETWEEN 1 AND 100),
+    ADD COLUMN amount_off BIGINT CHECK (amount_off > 0);
+
+UPDATE discount_codes SET percent_off = value WHERE kind = 'percentage';
+UPDATE discount_codes SET amount_off = value WHERE kind = 'fixed';
+
+ALTER TABLE discount_codes
+    DROP COLUMN value,
+    ADD CONSTRAINT discount_codes_off_matches_kind CHECK (
+        (kind = 'percentage' AND percent_off IS NOT NULL AND amount_off IS NULL)
+        OR (kind = 'fixed' AND amount_off IS NOT NULL AND percent_off IS NULL)
+    );
@@ -1,7 +1,7 @@
 use axum::extract::FromRef;
 use sqlx::PgPool;
 
-use crate::email::EmailConfig;
+use crate::{constants, email::EmailConfig, models::payment_method::PaymentMethod, payment::SharedGateway, proof_of_work::ProofOfWork, signing::{self, Signer}, tax::TaxPolicy};
 
 #[derive(Debug, thiserror::Error)]
 pub enum ConfigError {
@@ -11,34 +11,189 @@ pub enum ConfigError {
     InvalidValue(String, String),
     #[error("Email configuration error: {0}")]
     Email(#[from] crate::email::EmailError),
+    #[error("Payment gateway error: {0}")]
+    Payment(#[from] crate::payment::PaymentError),
+}
+
+#[derive(Clone)]
+pub struct TossConfig {
+    client_key: String,
+    secret_key: String,
+    api_base_url: String,
+}
+
+impl TossConfig {
+    fn from_env() -> Result<Self, ConfigError> {
+        let client_key = dotenvy::var("TOSS_CLIENT_KEY")
+            .map_err(|_| ConfigError::MissingVar("TOSS_CLIENT_KEY".to_string()))?;
+
+        let secret_key = dotenvy::var("TOSS_SECRET_KEY")
+            .map_err(|_| ConfigError::MissingVar("TOSS_SECRET_KEY".to_string()))?;
+
+        let api_base_url = dotenvy::var("TOSS_API_BASE_URL")
+            .unwrap_or_else(|_| constants::payment::TOSS_API_BASE_URL.to_string());
+
+        Ok(Self {
+            client_key,
+            secret_key,
+            api_base_url,
+        })
+    }
+
+    pub fn client_key(&self) -> &str {
+        &self.client_key
+    }
+
+    pub fn secret_key(&self) -> &str {
+        &self.secret_key
+    }
+
+    pub fn api_base_url(&self) -> &str {
+        &self.api_base_url
+    }
+}
+
+/// Payment provider selected by `PAYMENT_GATEWAY`.
+#[derive(Clone)]
+pub enum PaymentProvider {
+    Toss(TossConfig),
+    /// In-memory gateway with a mock checkout page (development and tests)
+    Fake,
 }
 
 #[derive(Clone)]
 pub struct PaymentConfig {
-    toss_client_key: String,
-    toss_secret_key: String,
+    provider: PaymentProvider,
+    methods: Vec<PaymentMethod>,
 }
 
 impl PaymentConfig {
     pub fn from_env() -> Result<Self, ConfigError> {
-        let toss_client_key = dotenvy::var("TOSS_CLIENT_KEY")
-            .map_err(|_| ConfigError::MissingVar("TOSS_CLIENT_KEY".to_string()))?;
+        let gateway = dotenvy::var("PAYMENT_GATEWAY").unwrap_or_else(|_| "toss".to_string());
 
-        let toss_secret_key = dotenvy::var("TOSS_SECRET_KEY")
-            .map_err(|_| ConfigError::MissingVar("TOSS_SECRET_KEY".to_string()))?;
+        let provider = match gateway.as_str() {
+            "toss" => PaymentProvider::Toss(TossConfig::from_env()?),
+            "fake" => PaymentProvider::Fake,
+            _ => {
+                return Err(ConfigError::InvalidValue(
+                    "PAYMENT_GATEWAY".to_string(),
+                    format!("must be 'toss' or 'fake', got '{}'", gateway),
+                ));
+            }
+        };
+
+        let methods = match dotenvy::var("PAYMENT_METHODS") {
+            Ok(value) => parse_payment_methods(&value)?,
+            Err(_) => PaymentMethod::ALL.to_vec(),
+        };
+
+        Ok(Self { provider, methods })
+    }
+
+    pub fn provider(&self) -> &PaymentProvider {
+        &self.provider
+    }
+
+    /// Methods offered on the checkout page, in the order they are listed.
+    pub fn methods(&self) -> &[PaymentMethod] {
+        &self.methods
+    }
+
+    pub fn is_fake(&self) -> bool {
+        matches!(self.provider, PaymentProvider::Fake)
+    }
+}
+
+/// Parses a comma-separated `PAYMENT_METHODS` list such as `card,virtual_account,kakao_pay`.
+fn parse_payment_methods(value: &str) -> Result<Vec<PaymentMethod>, ConfigError> {
+    let mut methods = Vec::new();
+    for name in value.split(',').map(str::trim).filter(|name| !name.is_empty()) {
+        let method = PaymentMethod::parse(name).ok_or_else(|| {
+            ConfigError::InvalidValue("PAYMENT_METHODS".to_string(), format!("unknown payment method '{}'", name))
+        })?;
+        if !methods.contains(&method) {
+            methods.push(method);
+        }
+    }
+
+    if methods.is_empty() {
+        return Err(ConfigError::InvalidValue(
+            "PAYMENT_METHODS".to_string(),
+            "must list at least one payment method".to_string(),
+        ));
+    }
+
+    Ok(methods)
+}
+
+/// How long a quote can be paid, and what happens to orders that were never paid.
+#[derive(Clone)]
+pub struct QuoteConfig {
+    validity: time::Duration,
+    purge_expired_content: bool,
+}
+
+impl QuoteConfig {
+    pub fn from_env() -> Result<Self, ConfigError> {
+        let validity_minutes = match dotenvy::var("QUOTE_VALIDITY_MINUTES") {
+            Ok(value) => value.parse::<i64>().ok().filter(|minutes| *minutes > 0).ok_or_else(|| {
+                ConfigError::InvalidValue("QUOTE_VALIDITY_MINUTES".to_string(), "must be a positive number".to_string())
+            })?,
+            Err(_) => constants::quotes::DEFAULT_VALIDITY_MINUTES,
+        };
+        let purge_expired_content = dotenvy::var("PURGE_EXPIRED_ORDER_CONTENT").is_ok_and(|value| value == "true");
 
         Ok(Self {
-            toss_client_key,
-            toss_secret_key,
+            validity: time::Duration::minutes(validity_minutes),
+            purge_expired_content,
         })
     }
 
-    pub fn toss_client_key(&self) -> &str {
-        &self.toss_client_key
+    pub fn validity(&self) -> time::Duration {
+        self.validity
     }
 
-    pub fn toss_secret_key(&self) -> &str {
-        &self.toss_secret_key
+    /// Whether expired orders lose their uploaded text.
+    pub fn purge_expired_content(&self) -> bool {
+        self.purge_expired_content
+    }
+}
+
+/// When the nightly reconciliation runs and how far back it looks.
+#[derive(Clone)]
+pub struct ReconciliationConfig {
+    hour_utc: u8,
+    window: time::Duration,
+}
+
+impl ReconciliationConfig {
+    pub fn from_env() -> Result<Self, ConfigError> {
+        let hour_utc = match dotenvy::var("RECONCILIATION_HOUR_UTC") {
+            Ok(value) => value.parse::<u8>().ok().filter(|hour| *hour < 24).ok_or_else(|| {
+                ConfigError::InvalidValue("RECONCILIATION_HOUR_UTC".to_string(), "must be an hour from 0 to 23".to_string())
+            })?,
+            Err(_) => constants::reconciliation::DEFAULT_HOUR_UTC,
+        };
+        let window_hours = match dotenvy::var("RECONCILIATION_WINDOW_HOURS") {
+            Ok(value) => value.parse::<i64>().ok().filter(|hours| *hours > 0).ok_or_else(|| {
+                ConfigError::InvalidValue("RECONCILIATION_WINDOW_HOURS".to_string(), "must be a positive number".to_string())
+            })?,
+            Err(_) => constants::reconciliation::DEFAULT_WINDOW_HOURS,
+        };
+
+        Ok(Self {
+            hour_utc,
+            window: time::Duration::hours(window_hours),
+        })
+    }
+
+    pub fn hour_utc(&self) -> u8 {
+        self.hour_utc
+    }
+
+    /// How far back from the start of a run orders are checked.
+    pub fn window(&self) -> time::Duration {
+        self.window
     }
 }
 
@@ -47,8 +202,13 @@ pub struct AppConfig {
     server_addr: String,
     database_url: String,
     site_name: String,
+    signer: Signer,
     email: EmailConfig,
     payment: PaymentConfig,
+    quotes: QuoteConfig,
+    reconciliation: ReconciliationConfig,
+    tax: TaxPolicy,
+    proof_of_work: ProofOfWork,
 }
 
 impl AppConfig {
@@ -62,15 +222,34 @@ impl AppConfig {
         let site_name = dotenvy::var("SITE_NAME")
             .map_err(|_| ConfigError::MissingVar("SITE_NAME".to_string()))?;
 
-        let email = EmailConfig::from_env()?;
+        let secret_key = dotenvy::var("SECRET_KEY")
+            .map_err(|_| ConfigError::MissingVar("SECRET_KEY".to_string()))?;
+        if secret_key.len() < signing::MIN_SECRET_LENGTH {
+            return Err(ConfigError::InvalidValue(
+                "SECRET_KEY".to_string(),
+                format!("must be at least {} characters", signing::MIN_SECRET_LENGTH),
+            ));
+        }
+        let signer = Signer::new(secret_key);
+
+        let email = EmailConfig::from_env(&site_name, signer.clone())?;
         let payment = PaymentConfig::from_env()?;
+        let quotes = QuoteConfig::from_env()?;
+        let reconciliation = ReconciliationConfig::from_env()?;
+        let tax = TaxPolicy::from_env()?;
+        let proof_of_work = ProofOfWork::from_env(signer.clone())?;
 
         Ok(Self {
             server_addr,
             database_url,
             site_name,
+            signer,
             email,
             payment,
+            quotes,
+            reconciliation,
+            tax,
+            proof_of_work,
         })
     }
 
@@ -86,6 +265,10 @@ impl AppConfig {
         &self.site_name
     }
 
+    pub fn signer(&self) -> &Signer {
+        &self.signer
+    }
+
     pub fn email(&self) -> &EmailConfig {
         &self.email
     }
@@ -94,6 +277,22 @@ impl AppConfig {
         &self.payment
     }
 
+    pub fn quotes(&self) -> &QuoteConfig {
+        &self.quotes
+    }
+
+    pub fn reconciliation(&self) -> &ReconciliationConfig {
+        &self.reconciliation
+    }
+
+    pub fn tax(&self) -> &TaxPolicy {
+        &self.tax
+    }
+
+    pub fn proof_of_work(&self) -> &ProofOfWork {
+        &self.proof_of_work
+    }
+
     pub fn base_url(&self) -> &str {
         self.email.base_url()
     }
@@ -103,10 +302,15 @@ impl AppConfig {
 pub struct AppState {
     db: PgPool,
     config: AppConfig,
+    payment_gateway: SharedGateway,
 }
 
 impl AppState {
-    pub fn new(db: PgPool, config: AppConfig) -> Self {
-        Self { db, config }
+    pub fn new(db: PgPool, config: AppConfig, payment_gateway: SharedGateway) -> Self {
+        Self {
+            db,
+            config,
+            payment_gateway,
+        }
     }
 }
@@ -25,31 +25,242 @@ pub mod messages {
     pub const CONTACT_SENT: &str = "Thank you for your message! We'll get back to you soon.";
     pub const PAYMENT_SUCCESS: &str = "Payment successful! Your order is complete.";
     pub const PAYMENT_FAILED: &str = "Payment failed. Please try again.";
+    pub const PAYMENT_IN_PROGRESS: &str = "Your payment is still being confirmed. Please check again shortly.";
+    pub const VIRTUAL_ACCOUNT_ISSUED: &str = "Your virtual account is ready. Transfer the total before the due date to complete your order.";
+    pub const DEPOSIT_RECEIVED: &str = "Deposit received! Your order is complete.";
+    pub const CASH_RECEIPT_SAVED: &str = "Cash receipt details saved. The receipt is issued once your transfer is complete.";
+    pub const CASH_RECEIPT_REMOVED: &str = "Cash receipt request removed";
     pub const SIGN_IN_REQUIRED: &str = "Please sign in to continue";
     pub const ORDER_ALREADY_PROCESSED: &str = "Order already processed";
+    pub const DUPLICATE_PAYMENT_CANCELLED: &str = "This order was already paid, so the new payment was cancelled.";
+    pub const DUPLICATE_PAYMENT_NOT_CANCELLED: &str = "This order was already paid. The new payment could not be cancelled automatically and will be refunded after review.";
+    pub const PAYMENT_METHOD_UNAVAILABLE: &str = "That payment method is not available, so the payment was cancelled. Please pay another way.";
+    pub const PAYMENT_METHOD_UNAVAILABLE_NOT_CANCELLED: &str = "That payment method is not available. The payment could not be cancelled automatically and will be refunded after review.";
+    pub const QUOTE_EXPIRED: &str = "This quote has expired. Please upload your file again for a new quote.";
     pub const ADMIN_ROLE_GRANTED: &str = "Admin role granted";
     pub const ADMIN_ROLE_REVOKED: &str = "Admin role revoked";
+    pub const NOTIFICATION_PREFERENCES_UPDATED: &str = "Notification preferences updated";
+    pub const INQUIRY_STATUS_UPDATED: &str = "Inquiry status updated";
+    pub const INQUIRY_ASSIGNED: &str = "Inquiry assignment updated";
+    pub const INQUIRY_NOTE_ADDED: &str = "Note added";
+    pub const INQUIRY_REPLY_SENT: &str = "Reply sent";
+    pub const INQUIRY_RELEASED: &str = "Inquiry moved to the inbox";
+    pub const REFUND_ISSUED: &str = "Refund issued";
+    pub const RECONCILIATION_FIX_APPLIED: &str = "Order updated to match the payment gateway";
+    pub const RECONCILIATION_FINDING_DISMISSED: &str = "Finding marked as reviewed";
+    pub const DISCOUNT_APPLIED: &str = "Discount code applied";
+    pub const DISCOUNT_REMOVED: &str = "Discount code removed";
+    pub const PRICING_RULE_CREATED: &str = "New pricing version saved";
+    pub const DISCOUNT_CODE_CREATED: &str = "Discount code created";
+    pub const DISCOUNT_CODE_UPDATED: &str = "Discount code updated";
+    pub const CREDITS_TOPPED_UP: &str = "Credits added to your balance";
+    pub const CREDITS_ADJUSTED: &str = "Credit balance adjusted";
+    pub const REFUNDED_TO_CREDITS: &str = "Refund added to the customer's credit balance";
+    pub const DISCOUNT_WITHDRAWN: &str = "Your discount code could not be redeemed and was removed from the quote. Please review the new total.";
+    pub const SUBSCRIPTION_STARTED: &str = "Your subscription is active";
+    pub const SUBSCRIPTION_CARD_UPDATED: &str = "Card updated and overdue payment received";
+    pub const SUBSCRIPTION_CANCELLED: &str = "Your subscription will end at the close of the current period";
+    pub const SUBSCRIPTION_RESUMED: &str = "Your subscription will renew as usual";
+    pub const SUBSCRIPTION_COVERED: &str = "This analysis is included in your subscription";
+    pub const SUBSCRIPTION_QUOTA_EXCEEDED: &str = "Your plan has too few characters left for this file, so it is charged as a regular order";
 }
 
 pub mod errors {
     pub const ORDER_NOT_FOUND: &str = "Order not found";
+    pub const PRODUCT_NOT_AVAILABLE: &str = "This product is not available";
     pub const TODO_NOT_FOUND: &str = "Todo not found";
     pub const PAYMENT_NOT_COMPLETED: &str = "Payment not completed";
+    pub const VIRTUAL_ACCOUNT_NOT_FOUND: &str = "No virtual account was issued for this order";
+    pub const RECEIPT_NOT_AVAILABLE: &str = "A receipt is available once the order is paid";
     pub const NOT_YOUR_ORDER: &str = "Not your order";
     pub const NO_FILE_PROVIDED: &str = "No file provided";
     pub const NO_FILE_CONTENT: &str = "No file content";
     pub const FORBIDDEN: &str = "You don't have permission to access this resource";
     pub const CANNOT_REVOKE_OWN_ADMIN: &str = "Cannot revoke your own admin role";
+    pub const EMAIL_NOT_FOUND: &str = "Email not found";
+    pub const SUPPRESSION_NOT_FOUND: &str = "Suppression not found";
+    pub const UNSUBSCRIBE_LINK_INVALID: &str = "This unsubscribe link is invalid.";
+    pub const NO_EMAIL_COLUMN: &str = "CSV must have an 'email' column";
+    pub const INQUIRY_NOT_FOUND: &str = "Inquiry not found";
+    pub const POW_FAILED: &str = "Browser verification failed. Please try again.";
+    pub const CONTACT_FORM_EXPIRED: &str = "This form has expired. Please send your message again.";
+    pub const INQUIRY_NOT_QUARANTINED: &str = "Inquiry is not quarantined";
+    pub const INQUIRY_ENTRY_EMPTY: &str = "Text cannot be empty";
+    pub const INQUIRY_ASSIGNEE_INVALID: &str = "Choose an admin to assign the inquiry to";
+    pub const ORDER_STATUS_CONFLICT: &str = "This order has already moved on and cannot be changed that way";
+    pub const REFUND_REASON_REQUIRED: &str = "A refund reason is required";
+    pub const REFUND_AMOUNT_INVALID: &str = "Refund amount must be positive and no more than the remaining paid amount";
+    pub const REFUND_FAILED: &str = "The payment gateway did not accept the refund";
+    pub const WEBHOOK_EVENT_NOT_FOUND: &str = "Webhook event not found";
+    pub const RECONCILIATION_FINDING_NOT_FOUND: &str = "Finding not found or already resolved";
+    pub const RECONCILIATION_FIX_NOT_AVAILABLE: &str = "This finding needs a manual review; mark it reviewed once handled";
+    pub const RECONCILIATION_FIX_FAILED: &str = "The gateway's record could not be applied";
+    pub const RECONCILIATION_RUN_FAILED: &str = "Reconciliation run failed";
+    pub const TOP_UP_AMOUNT_INVALID: &str = "Please choose one of the offered top-up amounts";
+    pub const TOP_UP_NOT_FOUND: &str = "Top-up not found";
+    pub const INSUFFICIENT_CREDITS: &str = "Not enough credits to pay for this order";
+    pub const CREDIT_ADJUSTMENT_INVALID: &str = "An adjustment needs a non-zero amount and a note, and cannot take the balance below zero";
+    pub const PRICING_RULE_NOT_FOUND: &str = "No pricing rule is in effect";
+    pub const PRICING_RATE_INVALID: &str = "The rate cannot be negative";
+    pub const PRICING_MINIMUM_INVALID: &str = "The minimum cannot be below the smallest amount the gateway charges";
+    pub const PRICING_TIERS_INVALID: &str = "Write one tier per line as 'characters = rate', with increasing character counts (at most 10)";
+    pub const PRICING_EFFECTIVE_DATE_INVALID: &str = "The effective date must be today or later (YYYY-MM-DD)";
+    pub const DISCOUNT_CODE_INVALID: &str = "This discount code is not valid";
+    pub const DISCOUNT_CODE_EXPIRED: &str = "This discount code has expired";
+    pub const DISCOUNT_CODE_EXHAUSTED: &str = "This discount code has been fully redeemed";
+    pub const DISCOUNT_CODE_USER_LIMIT: &str = "You have already used this discount code the maximum number of times";
+    pub const DISCOUNT_CODE_MINIMUM_NOT_MET: &str = "This order does not meet the discount code's minimum amount";
+    pub const DISCOUNT_NOT_APPLICABLE: &str = "Discounts can only be changed before payment";
+    pub const DISCOUNT_CODE_NOT_FOUND: &str = "Discount code not found";
+    pub const DISCOUNT_CODE_EXISTS: &str = "A discount code with that name already exists";
+    pub const DISCOUNT_CODE_FORMAT: &str = "Codes may only contain letters, digits, '-' and '_'";
+    pub const DISCOUNT_VALUE_INVALID: &str = "Percentages must be 1-100 and fixed amounts positive";
+    pub const DISCOUNT_MINIMUM_INVALID: &str = "The minimum order amount must be a whole number of won, 0 or more";
+    pub const DISCOUNT_LIMIT_INVALID: &str = "Limits must be whole numbers of at least 1";
+    pub const DISCOUNT_EXPIRY_INVALID: &str = "Expiry must be a date (YYYY-MM-DD)";
+    pub const INQUIRY_RECIPIENT_SUPPRESSED: &str = "The sender's address is on the suppression list, so the reply was not sent.";
+    pub const SUBSCRIPTION_PLAN_NOT_FOUND: &str = "Plan not found";
+    pub const SUBSCRIPTION_NOT_FOUND: &str = "Subscription not found";
+    pub const SUBSCRIPTION_ALREADY_ACTIVE: &str = "You already have a subscription";
+    pub const SUBSCRIPTION_CARD_NOT_NEEDED: &str = "This subscription does not need a card";
+    pub const SUBSCRIPTION_CARD_REJECTED: &str = "The card could not be registered";
+    pub const SUBSCRIPTION_CHARGE_FAILED: &str = "The card was registered but the payment failed";
+    pub const SUBSCRIPTION_QUOTA_EXCEEDED: &str = "Your plan does not have enough characters left for this file";
+    pub const CASH_RECEIPT_NOT_APPLICABLE: &str = "Cash receipt details can only be changed before payment";
+    pub const CASH_RECEIPT_INCOME_DEDUCTION_INVALID: &str = "Enter a mobile phone number or cash receipt card number";
+    pub const CASH_RECEIPT_EXPENSE_PROOF_INVALID: &str = "Enter a business registration number or mobile phone number";
+    pub const AMOUNT_OUT_OF_RANGE: &str = "The amount is too large to process";
+}
+
+pub mod spam {
+    /// Hidden form field only bots fill in.
+    pub const HONEYPOT_FIELD: &str = "website";
+    pub const MIN_FILL_SECONDS: i64 = 3;
+    pub const MAX_FORM_AGE_SECONDS: i64 = 2 * 60 * 60;
+    pub const QUARANTINE_THRESHOLD: i32 = 5;
+    pub const ALLOWED_LINKS: i32 = 2;
+    pub const EXCESS_LINK_POINTS: i32 = 2;
+    pub const BLOCKLISTED_TERM_POINTS: i32 = 3;
+    pub const BLOCKLISTED_TERMS: &[&str] = &[
+        "viagra", "cialis", "casino", "crypto investment", "seo services",
+        "backlinks", "guest post", "loan offer", "bitcoin doubler",
+    ];
+    pub const DUPLICATE_WINDOW_HOURS: i64 = 24;
+    pub const DUPLICATE_POINTS: i32 = 3;
+    /// Score for bot-only signals (honeypot filled, missing stamp, instant submit).
+    pub const BOT_SIGNAL_POINTS: i32 = 10;
+}
+
+pub mod proof_of_work {
+    pub const DEFAULT_BASE_DIFFICULTY: u32 = 14;
+    pub const DEFAULT_MAX_DIFFICULTY: u32 = 20;
+    /// Upper bound for configured difficulties; beyond this browsers take minutes.
+    pub const MAX_ALLOWED_DIFFICULTY: u32 = 24;
+    pub const CHALLENGE_TTL_SECONDS: i64 = 10 * 60;
+    pub const RATE_WINDOW_SECONDS: i64 = 10 * 60;
+    /// Each this many recent requests from an IP adds one bit (doubling the work).
+    pub const REQUESTS_PER_DIFFICULTY_STEP: u32 = 5;
+    pub const MAX_TRACKED_IPS: usize = 10_000;
 }
 
 pub mod pricing {
-    pub const PRICE_PER_CHARACTER: i32 = 1;
-    pub const MINIMUM_ORDER_AMOUNT: i32 = 100;
+    use crate::money::Money;
+
+    /// Smallest amount the gateway charges; rule minimums and discounts stay above it
+    pub const MINIMUM_ORDER_AMOUNT: Money = Money::krw(100);
+    pub const MAX_TIERS: usize = 10;
+    /// Character count the admin preview calculator starts with
+    pub const DEFAULT_PREVIEW_CHARACTERS: i32 = 10_000;
+}
+
+pub mod tax {
+    /// Korean VAT
+    pub const DEFAULT_RATE_PERCENT: i32 = 10;
+}
+
+pub mod quotes {
+    pub const DEFAULT_VALIDITY_MINUTES: i64 = 60;
+    /// Extra time before an expired order is cancelled, so a payment confirmed
+    /// right at the deadline is never cancelled underneath the customer
+    pub const EXPIRY_GRACE_MINUTES: i64 = 10;
+    pub const EXPIRY_SWEEP_INTERVAL_SECONDS: u64 = 5 * 60;
+    pub const EXPIRED_NOTE: &str = "Quote expired";
 }
 
 pub mod payment {
-    pub const TOSS_API_CONFIRM_URL: &str = "https://api.tosspayments.com/v1/payments/confirm";
-    pub const ORDER_NAME_PREFIX: &str = "Text Analysis";
+    pub const TOSS_API_BASE_URL: &str = "https://api.tosspayments.com";
+    pub const GATEWAY_CONNECT_TIMEOUT_SECONDS: u64 = 5;
+    /// Toss recommends allowing at least 30 seconds for payment confirmation
+    pub const GATEWAY_REQUEST_TIMEOUT_SECONDS: u64 = 30;
+    /// Toss webhook event type for payment status changes
+    pub const WEBHOOK_PAYMENT_STATUS_CHANGED: &str = "PAYMENT_STATUS_CHANGED";
+    /// Toss webhook event type for virtual account deposits (and their cancellation)
+    pub const WEBHOOK_DEPOSIT_CALLBACK: &str = "DEPOSIT_CALLBACK";
+    /// Attempt result code for a payment returned after its quote expired
+    pub const QUOTE_EXPIRED_CODE: &str = "QUOTE_EXPIRED";
+    /// Attempt result code for a payment stopped because its discount code ran out
+    pub const DISCOUNT_UNAVAILABLE_CODE: &str = "DISCOUNT_UNAVAILABLE";
+    /// Attempt result code for a payment cancelled because the order was paid another way
+    pub const ALREADY_PAID_CODE: &str = "ALREADY_PAID";
+    /// Cancellation reason sent for such a payment
+    pub const ALREADY_PAID_CANCEL_REASON: &str = "Order already paid by another method";
+    /// Attempt result code for a payment cancelled because its method is not offered
+    pub const METHOD_UNAVAILABLE_CODE: &str = "METHOD_UNAVAILABLE";
+    /// Cancellation reason sent for such a payment
+    pub const METHOD_UNAVAILABLE_CANCEL_REASON: &str = "Payment method not available";
+    /// Reason recorded for refunds found at the gateway but not made through the app
+    pub const GATEWAY_REFUND_REASON: &str = "Cancelled at the payment gateway";
+}
+
+pub mod products {
+    /// Product code of the text analysis sold from the analyzer page
+    pub const TEXT_ANALYSIS: &str = "text_analysis";
+}
+
+pub mod deposits {
+    /// Hours a virtual account accepts transfers after it is issued
+    pub const VALID_HOURS: i64 = 72;
+    /// Wait past the due date before cancelling, so a deposit reported late still settles
+    pub const EXPIRY_GRACE_MINUTES: i64 = 30;
+    pub const EXPIRY_SWEEP_INTERVAL_SECONDS: u64 = 10 * 60;
+    pub const EXPIRED_NOTE: &str = "Deposit due date passed";
+}
+
+pub mod cash_receipts {
+    pub const SWEEP_INTERVAL_SECONDS: u64 = 5 * 60;
+    /// Receipts issued or cancelled per sweep; the rest wait for the next one
+    pub const SWEEP_BATCH_SIZE: i64 = 100;
+}
+
+pub mod credits {
+    use crate::money::Money;
+
+    /// Top-up amounts offered on the dashboard
+    pub const TOP_UP_AMOUNTS: &[Money] = &[Money::krw(10_000), Money::krw(30_000), Money::krw(50_000), Money::krw(100_000)];
+    /// Gateway order ID prefix for top-ups; orders use `ORD-`
+    pub const TOP_UP_ORDER_PREFIX: &str = "TOP-";
+    pub const TOP_UP_ORDER_NAME: &str = "Credit Top-up";
+    pub const HISTORY_LIMIT: i64 = 20;
+    pub const PAID_WITH_CREDITS_NOTE: &str = "Paid with credits";
+}
+
+pub mod subscriptions {
+    /// Gateway order ID prefix for subscription charges; orders use `ORD-`
+    pub const ORDER_PREFIX: &str = "SUB-";
+    pub const CUSTOMER_KEY_PREFIX: &str = "cus_";
+    pub const PERIOD_DAYS: i64 = 30;
+    /// Days before each retry of a failed renewal; the subscription ends when they run out
+    pub const RETRY_DELAYS_DAYS: &[i64] = &[1, 3, 5];
+    pub const RENEWAL_SWEEP_INTERVAL_SECONDS: u64 = 10 * 60;
+    pub const CHARGE_HISTORY_LIMIT: i64 = 12;
+    pub const COVERED_NOTE: &str = "Included in subscription";
+}
+
+pub mod reconciliation {
+    pub const DEFAULT_HOUR_UTC: u8 = 3;
+    pub const DEFAULT_WINDOW_HOURS: i64 = 48;
+    pub const RECENT_RUNS_LIMIT: i64 = 10;
+    pub const DISMISSED_RESOLUTION: &str = "Marked as reviewed";
 }
 
 pub mod file_upload {
@@ -58,6 +269,8 @@ pub mod file_upload {
 
 pub mod logging {
     pub const UNKNOWN_CLIENT_IP: &str = "unknown";
+    /// Tracing target for events worth a security review (e.g. tampered payment amounts)
+    pub const SECURITY_TARGET: &str = "security";
 }
 
 pub mod admin {
@@ -0,0 +1,156 @@
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::{
+    data::errors::DataError,
+    models::cash_receipt::{CashReceipt, CashReceiptStatus, CashReceiptType},
+    money::Money,
+    payment::GatewayCashReceipt,
+};
+
+/// Saves the customer's cash receipt details for an order, replacing earlier ones.
+///
+/// Returns `false` once the receipt has left the requested state.
+pub async fn save_request(
+    db: &PgPool,
+    order_id: Uuid,
+    receipt_type: CashReceiptType,
+    identifier: &str,
+) -> Result<bool, DataError> {
+    let result = sqlx::query!(
+        r#"
+        INSERT INTO cash_receipts (order_id, receipt_type, identifier)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (order_id) DO UPDATE
+        SET receipt_type = $2, identifier = $3, updated_at = NOW()
+        WHERE cash_receipts.status = 'requested'
+        "#,
+        order_id,
+        receipt_type as CashReceiptType,
+        identifier
+    )
+    .execute(db)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+pub async fn remove_request(db: &PgPool, order_id: Uuid) -> Result<(), DataError> {
+    sqlx::query!(
+        r#"DELETE FROM cash_receipts WHERE order_id = $1 AND status = 'requested'"#,
+        order_id
+    )
+    .execute(db)
+    .await?;
+
+    Ok(())
+}
+
+/// An order's cash receipt held while it is issued or cancelled at the gateway.
+///
+/// The row stays locked from [`lock_receipt`] until one of the `record_*` calls,
+/// so a concurrent sync skips it instead of issuing or cancelling the receipt a
+/// second time. Dropping it releases the lock without changes.
+pub struct LockedCashReceipt {
+    tx: Transaction<'static, Postgres>,
+    order_id: Uuid,
+    pub receipt: CashReceipt,
+}
+
+/// Locks the order's cash receipt, or returns `None` if there is none or another
+/// sync is holding it.
+pub async fn lock_receipt(db: &PgPool, order_id: Uuid) -> Result<Option<LockedCashReceipt>, DataError> {
+    let mut tx = db.begin().await?;
+    let receipt = sqlx::query_as!(
+        CashReceipt,
+        r#"
+        SELECT
+            receipt_type as "receipt_type: CashReceiptType",
+            identifier,
+            status as "status: CashReceiptStatus",
+            receipt_key,
+            issue_number,
+            receipt_url,
+            issued_amount as "issued_amount: Money",
+            cancelled_amount as "cancelled_amount: Money",
+            failure_message
+        FROM cash_receipts
+        WHERE order_id = $1
+        FOR UPDATE SKIP LOCKED
+        "#,
+        order_id
+    )
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    Ok(receipt.map(|receipt| LockedCashReceipt { tx, order_id, receipt }))
+}
+
+impl LockedCashReceipt {
+    pub async fn record_issued(mut self, receipt: &GatewayCashReceipt, amount: Money) -> Result<(), DataError> {
+        sqlx::query!(
+            r#"
+            UPDATE cash_receipts
+            SET status = $2, receipt_key = $3, issue_number = $4, receipt_url = $5, issued_amount = $6,
+                failure_message = NULL, updated_at = NOW()
+            WHERE order_id = $1 AND status = 'requested'
+            "#,
+            self.order_id,
+            CashReceiptStatus::Issued as CashReceiptStatus,
+            receipt.receipt_key,
+            receipt.issue_number,
+            receipt.receipt_url,
+            amount as Money
+        )
+        .execute(&mut *self.tx)
+        .await?;
+
+        self.tx.commit().await?;
+        Ok(())
+    }
+
+    /// Records the gateway's refusal to issue the receipt; it is not retried.
+    pub async fn record_failed(mut self, message: &str) -> Result<(), DataError> {
+        sqlx::query!(
+            r#"
+            UPDATE cash_receipts SET status = $2, failure_message = $3, updated_at = NOW()
+            WHERE order_id = $1 AND status = 'requested'
+            "#,
+            self.order_id,
+            CashReceiptStatus::Failed as CashReceiptStatus,
+            message
+        )
+        .execute(&mut *self.tx)
+        .await?;
+
+        self.tx.commit().await?;
+        Ok(())
+    }
+
+    /// Raises the receipt's cancelled amount from `previous` to `cancelled_amount`,
+    /// closing it when nothing is left.
+    pub async fn record_cancellation(
+        mut self,
+        previous: Money,
+        cancelled_amount: Money,
+        fully_cancelled: bool,
+    ) -> Result<(), DataError> {
+        let status = if fully_cancelled { CashReceiptStatus::Cancelled } else { CashReceiptStatus::Issued };
+
+        sqlx::query!(
+            r#"
+            UPDATE cash_receipts SET status = $4, cancelled_amount = $3, updated_at = NOW()
+            WHERE order_id = $1 AND cancelled_amount = $2 AND status IN ('requested', 'issued')
+            "#,
+            self.order_id,
+            previous as Money,
+            cancelled_amount as Money,
+            status as CashReceiptStatus
+        )
+        .execute(&mut *self.tx)
+        .await?;
+
+        self.tx.commit().await?;
+        Ok(())
+    }
+}
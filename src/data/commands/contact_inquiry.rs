@@ -0,0 +1,169 @@
+use sqlx::PgPool;
+
+use crate::{
+    constants::{admin::ROLE_ADMIN, errors},
+    data::{ensure_rows_affected, errors::DataError, map_foreign_key_not_found},
+    models::contact::{InquiryEntryKind, InquiryStatus},
+    spam::SpamAssessment,
+};
+
+pub struct CreateInquiryParams<'a> {
+    pub user_id: Option<i32>,
+    pub email: &'a str,
+    pub message: &'a str,
+    pub content_hash: &'a str,
+    pub assessment: &'a SpamAssessment,
+}
+
+/// Stores an inquiry, quarantining it when the spam assessment says so.
+pub async fn create_inquiry(db: &PgPool, params: CreateInquiryParams<'_>) -> Result<i32, DataError> {
+    let result = sqlx::query!(
+        r#"
+        INSERT INTO contact_inquiries (user_id, email, message, content_hash, spam_score, spam_reasons, quarantined_at)
+        VALUES ($1, $2, $3, $4, $5, $6, CASE WHEN $7 THEN NOW() END)
+        RETURNING inquiry_id
+        "#,
+        params.user_id,
+        params.email,
+        params.message,
+        params.content_hash,
+        params.assessment.score,
+        &params.assessment.reasons,
+        params.assessment.is_spam()
+    )
+    .fetch_one(db)
+    .await?;
+
+    Ok(result.inquiry_id)
+}
+
+/// Moves a quarantined inquiry into the inbox. Returns false if it wasn't quarantined.
+pub async fn release_inquiry(db: &PgPool, inquiry_id: i32) -> Result<bool, DataError> {
+    let result = sqlx::query!(
+        "UPDATE contact_inquiries SET quarantined_at = NULL WHERE inquiry_id = $1 AND quarantined_at IS NOT NULL",
+        inquiry_id
+    )
+    .execute(db)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Deletes a quarantined inquiry; inquiries in the inbox are never deleted.
+pub async fn delete_quarantined_inquiry(db: &PgPool, inquiry_id: i32) -> Result<(), DataError> {
+    let result = sqlx::query!(
+        "DELETE FROM contact_inquiries WHERE inquiry_id = $1 AND quarantined_at IS NOT NULL",
+        inquiry_id
+    )
+    .execute(db)
+    .await?;
+
+    ensure_rows_affected(result, errors::INQUIRY_NOT_FOUND)
+}
+
+pub async fn update_inquiry_status(
+    db: &PgPool,
+    inquiry_id: i32,
+    status: InquiryStatus,
+) -> Result<(), DataError> {
+    let result = sqlx::query!(
+        "UPDATE contact_inquiries SET status = $2 WHERE inquiry_id = $1",
+        inquiry_id,
+        status as InquiryStatus
+    )
+    .execute(db)
+    .await?;
+
+    ensure_rows_affected(result, errors::INQUIRY_NOT_FOUND)
+}
+
+/// Assigns the inquiry to an admin, or unassigns it with `None`.
+pub async fn assign_inquiry(
+    db: &PgPool,
+    inquiry_id: i32,
+    assigned_to: Option<i32>,
+) -> Result<(), DataError> {
+    if let Some(user_id) = assigned_to {
+        let is_admin = sqlx::query!(
+            r#"SELECT EXISTS(SELECT 1 FROM user_roles WHERE user_id = $1 AND role = $2) as "is_admin!""#,
+            user_id,
+            ROLE_ADMIN
+        )
+        .fetch_one(db)
+        .await?
+        .is_admin;
+
+        if !is_admin {
+            return Err(DataError::InvalidInput("Inquiries can only be assigned to admins".to_string()));
+        }
+    }
+
+    let result = sqlx::query!(
+        "UPDATE contact_inquiries SET assigned_to = $2 WHERE inquiry_id = $1",
+        inquiry_id,
+        assigned_to
+    )
+    .execute(db)
+    .await?;
+
+    ensure_rows_affected(result, errors::INQUIRY_NOT_FOUND)
+}
+
+pub async fn add_note(db: &PgPool, inquiry_id: i32, author_id: i32, body: &str) -> Result<(), DataError> {
+    insert_entry(db, inquiry_id, author_id, InquiryEntryKind::Note, body).await
+}
+
+/// Records a sent reply and moves a new inquiry to in progress.
+pub async fn record_reply(db: &PgPool, inquiry_id: i32, author_id: i32, body: &str) -> Result<(), DataError> {
+    let mut tx = db.begin().await?;
+
+    sqlx::query!(
+        r#"
+        INSERT INTO contact_inquiry_entries (inquiry_id, author_id, kind, body)
+        VALUES ($1, $2, $3, $4)
+        "#,
+        inquiry_id,
+        author_id,
+        InquiryEntryKind::Reply as InquiryEntryKind,
+        body
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| map_foreign_key_not_found(e, errors::INQUIRY_NOT_FOUND))?;
+
+    sqlx::query!(
+        "UPDATE contact_inquiries SET status = $2 WHERE inquiry_id = $1 AND status = $3",
+        inquiry_id,
+        InquiryStatus::InProgress as InquiryStatus,
+        InquiryStatus::New as InquiryStatus
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(())
+}
+
+async fn insert_entry(
+    db: &PgPool,
+    inquiry_id: i32,
+    author_id: i32,
+    kind: InquiryEntryKind,
+    body: &str,
+) -> Result<(), DataError> {
+    sqlx::query!(
+        r#"
+        INSERT INTO contact_inquiry_entries (inquiry_id, author_id, kind, body)
+        VALUES ($1, $2, $3, $4)
+        "#,
+        inquiry_id,
+        author_id,
+        kind as InquiryEntryKind,
+        body
+    )
+    .execute(db)
+    .await
+    .map_err(|e| map_foreign_key_not_found(e, errors::INQUIRY_NOT_FOUND))?;
+
+    Ok(())
+}
@@ -0,0 +1,304 @@
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+use super::{
+    order::{lock_order, transition_order, OrderTransition},
+    refund::{insert_refund, RecordRefundParams},
+};
+use crate::{
+    constants::{credits, errors},
+    data::{amount_in_range, errors::DataError, map_row_not_found},
+    models::{
+        credit::{CreditTopUp, CreditTransactionKind},
+        order::{Order, OrderEventSource, PaymentStatus},
+    },
+    money::Money,
+};
+
+/// Outcome of [`pay_order_with_credits`].
+pub enum CreditPayment {
+    Paid(Box<Order>),
+    InsufficientBalance,
+    /// A gateway payment for the order is being confirmed
+    PaymentInProgress,
+}
+
+/// A ledger row; `balance_after` is the balance once `amount` is applied.
+struct NewTransaction<'a> {
+    user_id: i32,
+    kind: CreditTransactionKind,
+    amount: Money,
+    balance_after: Money,
+    order_id: Option<Uuid>,
+    topup_id: Option<Uuid>,
+    note: Option<&'a str>,
+    created_by: Option<i32>,
+}
+
+pub async fn create_topup(db: &PgPool, user_id: i32, amount: Money) -> Result<CreditTopUp, DataError> {
+    sqlx::query_as!(
+        CreditTopUp,
+        r#"
+        INSERT INTO credit_topups (user_id, order_number, amount)
+        VALUES ($1, $2, $3)
+        RETURNING
+            topup_id,
+            user_id,
+            order_number,
+            amount as "amount: Money",
+            status as "status: PaymentStatus"
+        "#,
+        user_id,
+        CreditTopUp::generate_order_number(user_id),
+        amount as Money
+    )
+    .fetch_one(db)
+    .await
+    .map_err(DataError::from)
+}
+
+/// Adds a paid top-up to the user's balance.
+///
+/// Returns `false` if the top-up was already credited, so the browser redirect
+/// and the webhook can both report the same payment.
+pub async fn complete_topup(db: &PgPool, topup_id: Uuid, payment_key: &str) -> Result<bool, DataError> {
+    let mut tx = db.begin().await?;
+
+    let topup = sqlx::query!(
+        r#"
+        SELECT user_id, amount as "amount: Money", status as "status: PaymentStatus"
+        FROM credit_topups
+        WHERE topup_id = $1
+        FOR UPDATE
+        "#,
+        topup_id
+    )
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|e| map_row_not_found(e, errors::TOP_UP_NOT_FOUND))?;
+
+    if topup.status == PaymentStatus::Paid {
+        return Ok(false);
+    }
+
+    sqlx::query!(
+        r#"UPDATE credit_topups SET status = 'paid', payment_key = $2, paid_at = NOW() WHERE topup_id = $1"#,
+        topup_id,
+        payment_key
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    let balance_after = add_to_balance(&mut tx, topup.user_id, topup.amount).await?;
+    insert_transaction(
+        &mut tx,
+        NewTransaction {
+            user_id: topup.user_id,
+            kind: CreditTransactionKind::TopUp,
+            amount: topup.amount,
+            balance_after,
+            order_id: None,
+            topup_id: Some(topup_id),
+            note: None,
+            created_by: None,
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+    Ok(true)
+}
+
+/// Marks a pending top-up as failed. Paid top-ups are left alone.
+pub async fn fail_topup(db: &PgPool, topup_id: Uuid) -> Result<(), DataError> {
+    sqlx::query!(
+        r#"UPDATE credit_topups SET status = 'failed' WHERE topup_id = $1 AND status = 'pending'"#,
+        topup_id
+    )
+    .execute(db)
+    .await?;
+
+    Ok(())
+}
+
+/// Pays a user's order from their balance, moving it to `paid` in the same
+/// transaction as the deduction.
+///
+/// Refused while a gateway payment for the order is being confirmed, which
+/// would otherwise charge the customer a second time.
+pub async fn pay_order_with_credits(db: &PgPool, order_id: Uuid, user_id: i32) -> Result<CreditPayment, DataError> {
+    let mut tx = db.begin().await?;
+
+    let order = lock_order(&mut tx, order_id).await?;
+    if order.user_id != user_id {
+        return Err(DataError::Unauthorized(errors::NOT_YOUR_ORDER));
+    }
+    order.payment_status.ensure_transition(PaymentStatus::Paid)?;
+
+    let confirming = sqlx::query_scalar!(
+        r#"
+        SELECT EXISTS(
+            SELECT 1 FROM payment_attempts WHERE order_id = $1 AND status = 'confirming'
+        ) as "exists!"
+        "#,
+        order_id
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+    if confirming {
+        return Ok(CreditPayment::PaymentInProgress);
+    }
+
+    let balance_after = sqlx::query_scalar!(
+        r#"
+        UPDATE users
+        SET credit_balance = credit_balance - $2
+        WHERE user_id = $1 AND credit_balance >= $2
+        RETURNING credit_balance as "credit_balance: Money"
+        "#,
+        user_id,
+        order.price_amount as Money
+    )
+    .fetch_optional(&mut *tx)
+    .await?;
+    let Some(balance_after) = balance_after else {
+        return Ok(CreditPayment::InsufficientBalance);
+    };
+
+    insert_transaction(
+        &mut tx,
+        NewTransaction {
+            user_id,
+            kind: CreditTransactionKind::Spend,
+            amount: amount_in_range(order.price_amount.checked_neg())?,
+            balance_after,
+            order_id: Some(order_id),
+            topup_id: None,
+            note: Some(&order.order_number),
+            created_by: None,
+        },
+    )
+    .await?;
+
+    let order = transition_order(
+        &mut tx,
+        order_id,
+        OrderTransition {
+            to: PaymentStatus::Paid,
+            payment_key: None,
+            payment_method: None,
+            source: OrderEventSource::Checkout,
+            actor_id: Some(user_id),
+            note: Some(credits::PAID_WITH_CREDITS_NOTE),
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+    Ok(CreditPayment::Paid(Box::new(order)))
+}
+
+/// Refunds an order paid with credits back to the wallet, returning its new status.
+pub async fn refund_order_to_credits(db: &PgPool, params: RecordRefundParams<'_>) -> Result<PaymentStatus, DataError> {
+    let mut tx = db.begin().await?;
+
+    let (order, to) = insert_refund(&mut tx, &params).await?;
+    let balance_after = add_to_balance(&mut tx, order.user_id, params.amount).await?;
+    insert_transaction(
+        &mut tx,
+        NewTransaction {
+            user_id: order.user_id,
+            kind: CreditTransactionKind::Refund,
+            amount: params.amount,
+            balance_after,
+            order_id: Some(order.order_id),
+            topup_id: None,
+            note: Some(params.reason),
+            created_by: params.refunded_by,
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+    Ok(to)
+}
+
+/// Adds (or, if negative, removes) credits by hand, returning the new balance.
+///
+/// Returns `None` without changing anything if the balance would go below zero.
+pub async fn adjust_balance(
+    db: &PgPool,
+    user_id: i32,
+    amount: Money,
+    note: &str,
+    admin_id: i32,
+) -> Result<Option<Money>, DataError> {
+    let mut tx = db.begin().await?;
+
+    let balance_after = sqlx::query_scalar!(
+        r#"
+        UPDATE users
+        SET credit_balance = credit_balance + $2
+        WHERE user_id = $1 AND credit_balance + $2 >= 0
+        RETURNING credit_balance as "credit_balance: Money"
+        "#,
+        user_id,
+        amount as Money
+    )
+    .fetch_optional(&mut *tx)
+    .await?;
+    let Some(balance_after) = balance_after else {
+        return Ok(None);
+    };
+
+    insert_transaction(
+        &mut tx,
+        NewTransaction {
+            user_id,
+            kind: CreditTransactionKind::Adjustment,
+            amount,
+            balance_after,
+            order_id: None,
+            topup_id: None,
+            note: Some(note),
+            created_by: Some(admin_id),
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+    Ok(Some(balance_after))
+}
+
+async fn add_to_balance(conn: &mut PgConnection, user_id: i32, amount: Money) -> Result<Money, DataError> {
+    sqlx::query_scalar!(
+        r#"UPDATE users SET credit_balance = credit_balance + $2 WHERE user_id = $1 RETURNING credit_balance as "credit_balance: Money""#,
+        user_id,
+        amount as Money
+    )
+    .fetch_one(&mut *conn)
+    .await
+    .map_err(DataError::from)
+}
+
+async fn insert_transaction(conn: &mut PgConnection, transaction: NewTransaction<'_>) -> Result<(), DataError> {
+    sqlx::query!(
+        r#"
+        INSERT INTO credit_transactions
+            (user_id, kind, amount, balance_after, order_id, topup_id, note, created_by)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        "#,
+        transaction.user_id,
+        transaction.kind as CreditTransactionKind,
+        transaction.amount as Money,
+        transaction.balance_after as Money,
+        transaction.order_id,
+        transaction.topup_id,
+        transaction.note,
+        transaction.created_by
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(())
+}
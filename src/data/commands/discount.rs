@@ -0,0 +1,255 @@
+use sqlx::{PgConnection, PgPool};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::{
+    constants::errors,
+    data::{ensure_rows_affected, errors::DataError},
+    models::{
+        discount::{DiscountCode, DiscountKind, NewDiscountCode},
+        order::Order,
+    },
+    money::Money,
+    tax::TaxBreakdown,
+};
+
+/// Outcome of [`reserve_redemption`].
+pub enum Redemption {
+    /// The order has no code, or its code was counted.
+    Reserved,
+    /// The code can no longer be used, with the reason to show the customer.
+    Unavailable(&'static str),
+}
+
+/// Creates a discount code, returning `None` if the code is already taken.
+pub async fn create_discount_code(
+    db: &PgPool,
+    code: &NewDiscountCode,
+    created_by: i32,
+) -> Result<Option<i32>, DataError> {
+    let discount_code_id = sqlx::query_scalar!(
+        r#"
+        INSERT INTO discount_codes
+            (code, kind, percent_off, amount_off, min_order_amount, max_redemptions, per_user_limit, expires_at, created_by)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+        ON CONFLICT (code) DO NOTHING
+        RETURNING discount_code_id
+        "#,
+        code.code,
+        code.kind as DiscountKind,
+        code.percent_off,
+        code.amount_off as Option<Money>,
+        code.min_order_amount as Money,
+        code.max_redemptions,
+        code.per_user_limit,
+        code.expires_at,
+        created_by
+    )
+    .fetch_optional(db)
+    .await?;
+
+    Ok(discount_code_id)
+}
+
+/// Switches a code on or off. Orders that already applied it keep their price
+/// until payment, when redemption fails for an inactive code.
+pub async fn toggle_discount_code(db: &PgPool, discount_code_id: i32) -> Result<(), DataError> {
+    let result = sqlx::query!(
+        r#"UPDATE discount_codes SET is_active = NOT is_active WHERE discount_code_id = $1"#,
+        discount_code_id
+    )
+    .execute(db)
+    .await?;
+
+    ensure_rows_affected(result, errors::DISCOUNT_CODE_NOT_FOUND)
+}
+
+/// Sets the discount on a user's unpaid order, replacing any code applied before.
+///
+/// The code is not redeemed here; see [`reserve_redemption`]. `tax` splits the
+/// discounted price.
+pub async fn apply_to_order(
+    db: &PgPool,
+    order_id: Uuid,
+    user_id: i32,
+    discount_code_id: i32,
+    discount_amount: Money,
+    tax: TaxBreakdown,
+) -> Result<(), DataError> {
+    let result = sqlx::query!(
+        r#"
+        UPDATE orders
+        SET discount_code_id = $3, discount_amount = $4,
+            price_amount = $5, net_amount = $6, tax_amount = $7, tax_rate_percent = $8
+        WHERE order_id = $1 AND user_id = $2 AND payment_status IN ('pending', 'failed')
+        "#,
+        order_id,
+        user_id,
+        discount_code_id,
+        discount_amount as Money,
+        tax.gross as Money,
+        tax.net as Money,
+        tax.tax as Money,
+        tax.rate_percent
+    )
+    .execute(db)
+    .await?;
+
+    ensure_rows_affected(result, errors::DISCOUNT_NOT_APPLICABLE)
+}
+
+/// Restores the list price of a user's unpaid order; `tax` splits the list price.
+pub async fn remove_from_order(db: &PgPool, order_id: Uuid, user_id: i32, tax: TaxBreakdown) -> Result<(), DataError> {
+    let result = sqlx::query!(
+        r#"
+        UPDATE orders
+        SET discount_code_id = NULL, discount_amount = 0,
+            price_amount = $3, net_amount = $4, tax_amount = $5, tax_rate_percent = $6
+        WHERE order_id = $1 AND user_id = $2 AND payment_status IN ('pending', 'failed')
+        "#,
+        order_id,
+        user_id,
+        tax.gross as Money,
+        tax.net as Money,
+        tax.tax as Money,
+        tax.rate_percent
+    )
+    .execute(db)
+    .await?;
+
+    ensure_rows_affected(result, errors::DISCOUNT_NOT_APPLICABLE)
+}
+
+/// Counts the order's code against its limits, just before the payment is confirmed.
+///
+/// The code row stays locked until commit, so concurrent checkouts cannot go past
+/// the redemption limits. Calling it again for the same order is a no-op.
+pub async fn reserve_redemption(db: &PgPool, order: &Order) -> Result<Redemption, DataError> {
+    let Some(discount_code_id) = order.discount_code_id else {
+        return Ok(Redemption::Reserved);
+    };
+
+    let mut tx = db.begin().await?;
+
+    let code = sqlx::query_as!(
+        DiscountCode,
+        r#"
+        SELECT
+            discount_code_id,
+            code,
+            kind as "kind: DiscountKind",
+            percent_off,
+            amount_off as "amount_off: Money",
+            min_order_amount as "min_order_amount: Money",
+            max_redemptions,
+            per_user_limit,
+            expires_at,
+            is_active,
+            redemption_count,
+            created_at
+        FROM discount_codes
+        WHERE discount_code_id = $1
+        FOR UPDATE
+        "#,
+        discount_code_id
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    let already_reserved = sqlx::query_scalar!(
+        r#"SELECT EXISTS(SELECT 1 FROM discount_redemptions WHERE order_id = $1) as "exists!""#,
+        order.order_id
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+    if already_reserved {
+        return Ok(Redemption::Reserved);
+    }
+
+    let user_redemptions = sqlx::query_scalar!(
+        r#"SELECT COUNT(*) as "count!" FROM discount_redemptions WHERE discount_code_id = $1 AND user_id = $2"#,
+        discount_code_id,
+        order.user_id
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    if let Err(reason) = code.check_usable(order.list_price_amount, user_redemptions, OffsetDateTime::now_utc()) {
+        return Ok(Redemption::Unavailable(reason));
+    }
+
+    insert_redemption(&mut tx, discount_code_id, order).await?;
+    tx.commit().await?;
+
+    Ok(Redemption::Reserved)
+}
+
+/// Records the redemption for an order paid without going through checkout
+/// (webhook, reconciliation). Limits are not checked: the customer already paid
+/// the discounted price.
+pub async fn record_redemption(db: &PgPool, order: &Order) -> Result<(), DataError> {
+    let Some(discount_code_id) = order.discount_code_id else {
+        return Ok(());
+    };
+
+    let mut tx = db.begin().await?;
+    insert_redemption(&mut tx, discount_code_id, order).await?;
+    tx.commit().await?;
+
+    Ok(())
+}
+
+/// Gives back a reservation after the payment was not confirmed.
+pub async fn release_redemption(db: &PgPool, order_id: Uuid) -> Result<(), DataError> {
+    let mut tx = db.begin().await?;
+
+    let released = sqlx::query_scalar!(
+        r#"DELETE FROM discount_redemptions WHERE order_id = $1 RETURNING discount_code_id"#,
+        order_id
+    )
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    if let Some(discount_code_id) = released {
+        sqlx::query!(
+            r#"UPDATE discount_codes SET redemption_count = redemption_count - 1 WHERE discount_code_id = $1"#,
+            discount_code_id
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    tx.commit().await?;
+    Ok(())
+}
+
+async fn insert_redemption(
+    conn: &mut PgConnection,
+    discount_code_id: i32,
+    order: &Order,
+) -> Result<(), DataError> {
+    let inserted = sqlx::query!(
+        r#"
+        INSERT INTO discount_redemptions (discount_code_id, order_id, user_id, amount)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (order_id) DO NOTHING
+        "#,
+        discount_code_id,
+        order.order_id,
+        order.user_id,
+        order.discount_amount as Money
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    if inserted.rows_affected() > 0 {
+        sqlx::query!(
+            r#"UPDATE discount_codes SET redemption_count = redemption_count + 1 WHERE discount_code_id = $1"#,
+            discount_code_id
+        )
+        .execute(&mut *conn)
+        .await?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,66 @@
+use sqlx::PgPool;
+
+use crate::{
+    constants::errors,
+    data::{ensure_rows_affected, errors::DataError},
+    models::email_suppression::SuppressionReason,
+};
+
+/// Suppresses an address. An existing suppression keeps its original reason.
+pub async fn add_suppression(
+    db: &PgPool,
+    email: &str,
+    reason: SuppressionReason,
+    details: Option<&str>,
+) -> Result<(), DataError> {
+    sqlx::query!(
+        r#"
+        INSERT INTO email_suppressions (email, reason, details)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (email) DO NOTHING
+        "#,
+        email,
+        reason as SuppressionReason,
+        details
+    )
+    .execute(db)
+    .await?;
+
+    Ok(())
+}
+
+/// Suppresses many addresses at once, returning how many were newly added.
+pub async fn import_suppressions(
+    db: &PgPool,
+    entries: &[(String, SuppressionReason)],
+    details: &str,
+) -> Result<u64, DataError> {
+    let emails: Vec<String> = entries.iter().map(|(email, _)| email.clone()).collect();
+    let reasons: Vec<String> = entries.iter().map(|(_, reason)| reason.as_str().to_string()).collect();
+
+    let result = sqlx::query!(
+        r#"
+        INSERT INTO email_suppressions (email, reason, details)
+        SELECT email, reason, $3 FROM UNNEST($1::text[], $2::text[]) AS t(email, reason)
+        ON CONFLICT (email) DO NOTHING
+        "#,
+        &emails,
+        &reasons,
+        details
+    )
+    .execute(db)
+    .await?;
+
+    Ok(result.rows_affected())
+}
+
+pub async fn remove_suppression(db: &PgPool, suppression_id: i32) -> Result<(), DataError> {
+    let result = sqlx::query!(
+        "DELETE FROM email_suppressions WHERE suppression_id = $1",
+        suppression_id
+    )
+    .execute(db)
+    .await?;
+
+    ensure_rows_affected(result, errors::SUPPRESSION_NOT_FOUND)
+}
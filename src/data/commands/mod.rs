@@ -1,5 +1,17 @@
 pub mod admin;
+pub mod cash_receipt;
+pub mod contact_inquiry;
+pub mod credit;
+pub mod discount;
+pub mod email_suppression;
 pub mod magic_link;
 pub mod order;
+pub mod payment_attempt;
+pub mod pricing;
+pub mod reconciliation;
+pub mod payment_webhook;
+pub mod refund;
+pub mod subscription;
 pub mod todo;
 pub mod user;
+pub mod virtual_account;
@@ -1,89 +1,325 @@
-use sqlx::PgPool;
+use sqlx::{PgConnection, PgPool, types::Json};
+use time::OffsetDateTime;
 use uuid::Uuid;
 
-use crate::{data::errors::DataError, models::order::{Order, PaymentStatus}};
+use crate::{
+    constants::{self, errors},
+    data::{amount_in_range, errors::DataError, map_row_not_found},
+    models::{
+        order::{Order, OrderEventSource, PaymentStatus},
+        order_item::{OrderItem, OrderItemMetadata},
+        payment_method::PaymentMethod,
+    },
+    money::{Currency, Money},
+    tax::TaxBreakdown,
+};
 
 pub struct CreateOrderParams {
     pub user_id: i32,
     pub user_email: String,
-    pub filename: String,
-    pub file_size: i32,
-    pub text_content: String,
-    pub text_length: i32,
-    pub price_amount: i32,
+    pub items: Vec<NewOrderItem>,
+    pub tax: TaxBreakdown,
+    /// Rule version the price was calculated with
+    pub pricing_rule_id: i32,
     pub order_number: String,
 }
 
+impl CreateOrderParams {
+    /// Price before any discount: the sum of the item amounts, or `None` if it overflows.
+    pub fn list_price_amount(&self) -> Option<Money> {
+        let amounts = self.items.iter().map(|item| item.unit_price_amount.checked_mul(i64::from(item.quantity)));
+        Money::checked_sum(Currency::Krw, amounts.collect::<Option<Vec<_>>>()?)
+    }
+}
+
+pub struct NewOrderItem {
+    pub product_id: i32,
+    pub quantity: i32,
+    /// Price from the pricing rule, before any discount
+    pub unit_price_amount: Money,
+    pub metadata: OrderItemMetadata,
+}
+
+/// A requested status change, checked against [`PaymentStatus::can_transition_to`].
+pub struct OrderTransition<'a> {
+    pub to: PaymentStatus,
+    /// Replaces the stored payment key when set.
+    pub payment_key: Option<&'a str>,
+    /// Replaces the stored payment method when set.
+    pub payment_method: Option<PaymentMethod>,
+    pub source: OrderEventSource,
+    pub actor_id: Option<i32>,
+    pub note: Option<&'a str>,
+}
+
 pub async fn create_order(db: &PgPool, params: CreateOrderParams) -> Result<Order, DataError> {
-    let order = sqlx::query_as!(
-        Order,
+    let mut tx = db.begin().await?;
+    let order = insert_order(&mut tx, params).await?;
+    tx.commit().await?;
+    Ok(order)
+}
+
+/// Inserts a pending order with its items and creation event.
+pub(super) async fn insert_order(conn: &mut PgConnection, params: CreateOrderParams) -> Result<Order, DataError> {
+    let list_price_amount = amount_in_range(params.list_price_amount())?;
+    let order_id = sqlx::query_scalar!(
         r#"
-        INSERT INTO orders(user_id, user_email, filename, file_size, text_content, text_length, price_amount, payment_status, order_number)
-        VALUES($1, $2, $3, $4, $5, $6, $7, $8, $9)
-        RETURNING
-            order_id,
-            user_id,
-            user_email,
-            filename,
-            file_size,
-            text_content,
-            text_length,
-            price_amount,
-            payment_status as "payment_status: PaymentStatus",
-            payment_key,
-            order_number,
-            created_at,
-            paid_at
+        INSERT INTO orders(user_id, user_email, list_price_amount, price_amount, net_amount, tax_amount, tax_rate_percent, payment_status, order_number, pricing_rule_id)
+        VALUES($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+        RETURNING order_id
         "#,
         params.user_id,
         params.user_email,
-        params.filename,
-        params.file_size,
-        params.text_content,
-        params.text_length,
-        params.price_amount,
+        list_price_amount as Money,
+        params.tax.gross as Money,
+        params.tax.net as Money,
+        params.tax.tax as Money,
+        params.tax.rate_percent,
         PaymentStatus::Pending as PaymentStatus,
-        params.order_number
+        params.order_number,
+        params.pricing_rule_id
     )
-    .fetch_one(db)
+    .fetch_one(&mut *conn)
     .await?;
 
-    Ok(order)
+    for item in &params.items {
+        sqlx::query!(
+            r#"
+            INSERT INTO order_items (order_id, product_id, quantity, unit_price_amount, metadata)
+            VALUES ($1, $2, $3, $4, $5)
+            "#,
+            order_id,
+            item.product_id,
+            item.quantity,
+            item.unit_price_amount as Money,
+            Json(&item.metadata) as _
+        )
+        .execute(&mut *conn)
+        .await?;
+    }
+
+    insert_event(
+        conn,
+        order_id,
+        None,
+        &OrderTransition {
+            to: PaymentStatus::Pending,
+            payment_key: None,
+            payment_method: None,
+            source: OrderEventSource::System,
+            actor_id: None,
+            note: None,
+        },
+    )
+    .await?;
+
+    lock_order(conn, order_id).await
 }
 
+/// Records the outcome of a payment attempt, along with the method the gateway
+/// says was used when given.
+///
+/// Fails with [`DataError::InvalidTransition`] when the order's current status does
+/// not allow it, e.g. a late failure for an order that is already paid.
 pub async fn update_order_payment(
     db: &PgPool,
     order_id: Uuid,
     payment_key: &str,
+    payment_method: Option<PaymentMethod>,
     payment_status: PaymentStatus,
+    source: OrderEventSource,
 ) -> Result<Order, DataError> {
+    let mut tx = db.begin().await?;
+
+    let order = transition_order(
+        &mut tx,
+        order_id,
+        OrderTransition {
+            to: payment_status,
+            payment_key: Some(payment_key),
+            payment_method,
+            source,
+            actor_id: None,
+            note: None,
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+    Ok(order)
+}
+
+/// Locks the order row until the surrounding transaction ends.
+pub(super) async fn lock_order(conn: &mut PgConnection, order_id: Uuid) -> Result<Order, DataError> {
+    sqlx::query_as!(
+        Order,
+        r#"
+        SELECT
+            order_id,
+            user_id,
+            user_email,
+            list_price_amount as "list_price_amount: Money",
+            discount_amount as "discount_amount: Money",
+            discount_code_id,
+            price_amount as "price_amount: Money",
+            net_amount as "net_amount: Money",
+            tax_amount as "tax_amount: Money",
+            tax_rate_percent,
+            payment_status as "payment_status: PaymentStatus",
+            payment_key,
+            payment_method as "payment_method: _",
+            order_number,
+            created_at,
+            paid_at,
+            order_items_json(order_id) as "items!: Json<Vec<OrderItem>>"
+        FROM orders
+        WHERE order_id = $1
+        FOR UPDATE
+        "#,
+        order_id
+    )
+    .fetch_one(conn)
+    .await
+    .map_err(|e| map_row_not_found(e, errors::ORDER_NOT_FOUND))
+}
+
+/// Applies a checked status change and appends it to `order_events`.
+/// Must run inside a transaction so the lock holds until commit.
+pub(super) async fn transition_order(
+    conn: &mut PgConnection,
+    order_id: Uuid,
+    transition: OrderTransition<'_>,
+) -> Result<Order, DataError> {
+    let from = lock_order(conn, order_id).await?.payment_status;
+    from.ensure_transition(transition.to)?;
+
     let order = sqlx::query_as!(
         Order,
         r#"
         UPDATE orders
-        SET payment_key = $2, payment_status = $3, paid_at = CASE WHEN $3 = 'paid' THEN NOW() ELSE paid_at END
+        SET payment_key = COALESCE($2, payment_key),
+            payment_method = COALESCE($4, payment_method),
+            payment_status = $3,
+            paid_at = CASE WHEN $3 = 'paid' THEN NOW() ELSE paid_at END
         WHERE order_id = $1
         RETURNING
             order_id,
             user_id,
             user_email,
-            filename,
-            file_size,
-            text_content,
-            text_length,
-            price_amount,
+            list_price_amount as "list_price_amount: Money",
+            discount_amount as "discount_amount: Money",
+            discount_code_id,
+            price_amount as "price_amount: Money",
+            net_amount as "net_amount: Money",
+            tax_amount as "tax_amount: Money",
+            tax_rate_percent,
             payment_status as "payment_status: PaymentStatus",
             payment_key,
+            payment_method as "payment_method: _",
             order_number,
             created_at,
-            paid_at
+            paid_at,
+            order_items_json(order_id) as "items!: Json<Vec<OrderItem>>"
         "#,
         order_id,
-        payment_key,
-        payment_status as PaymentStatus
+        transition.payment_key,
+        transition.to as PaymentStatus,
+        transition.payment_method as Option<PaymentMethod>
     )
-    .fetch_one(db)
+    .fetch_one(&mut *conn)
     .await?;
 
+    insert_event(conn, order_id, Some(from), &transition).await?;
+
     Ok(order)
 }
+
+async fn insert_event(
+    conn: &mut PgConnection,
+    order_id: Uuid,
+    from: Option<PaymentStatus>,
+    transition: &OrderTransition<'_>,
+) -> Result<(), DataError> {
+    sqlx::query!(
+        r#"
+        INSERT INTO order_events (order_id, from_status, to_status, source, payment_key, actor_id, note)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        "#,
+        order_id,
+        from as Option<PaymentStatus>,
+        transition.to as PaymentStatus,
+        transition.source as OrderEventSource,
+        transition.payment_key,
+        transition.actor_id,
+        transition.note
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+/// Cancels payable orders created before `created_before`, returning how many.
+///
+/// Each order is cancelled in its own transaction through the state machine, so
+/// an order paid in the meantime is skipped. Orders with a confirmation in
+/// flight are left alone.
+pub async fn expire_stale_orders(
+    db: &PgPool,
+    created_before: OffsetDateTime,
+    purge_content: bool,
+) -> Result<u64, DataError> {
+    let candidates = sqlx::query_scalar!(
+        r#"
+        SELECT o.order_id
+        FROM orders o
+        WHERE o.payment_status IN ('pending', 'failed')
+            AND o.created_at < $1
+            AND NOT EXISTS (
+                SELECT 1 FROM payment_attempts a
+                WHERE a.order_id = o.order_id AND a.status = 'confirming'
+            )
+        ORDER BY o.created_at
+        "#,
+        created_before
+    )
+    .fetch_all(db)
+    .await?;
+
+    let mut expired = 0;
+    for order_id in candidates {
+        let mut tx = db.begin().await?;
+
+        let transition = OrderTransition {
+            to: PaymentStatus::Cancelled,
+            payment_key: None,
+            payment_method: None,
+            source: OrderEventSource::System,
+            actor_id: None,
+            note: Some(constants::quotes::EXPIRED_NOTE),
+        };
+        match transition_order(&mut tx, order_id, transition).await {
+            Ok(_) => {}
+            Err(DataError::InvalidTransition { .. }) => continue,
+            Err(e) => return Err(e),
+        }
+
+        if purge_content {
+            sqlx::query!(
+                r#"
+                UPDATE order_items
+                SET metadata = jsonb_set(metadata, '{text_content}', '""')
+                WHERE order_id = $1 AND metadata->>'kind' = 'text_analysis'
+                "#,
+                order_id
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        expired += 1;
+    }
+
+    Ok(expired)
+}
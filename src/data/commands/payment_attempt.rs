@@ -0,0 +1,98 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use super::order::lock_order;
+use crate::{
+    data::errors::DataError,
+    models::payment_attempt::{PaymentAttempt, PaymentAttemptStatus},
+    money::Money,
+};
+
+pub enum AttemptStart {
+    /// The caller owns the attempt and should confirm it with the gateway.
+    Started,
+    /// The key was already confirmed (or is being confirmed); reuse this outcome.
+    Existing(PaymentAttempt),
+}
+
+/// Claims `payment_key` for a confirm request.
+///
+/// A key seen before is only claimed again if its last attempt errored, so
+/// concurrent or repeated redirects never confirm the same payment twice. The
+/// claim is made under the order's lock, so a credit payment either sees it or
+/// has already settled the order.
+pub async fn begin_attempt(db: &PgPool, payment_key: &str, order_id: Uuid, amount: Money) -> Result<AttemptStart, DataError> {
+    let mut tx = db.begin().await?;
+    lock_order(&mut tx, order_id).await?;
+
+    let claimed = sqlx::query_scalar!(
+        r#"
+        INSERT INTO payment_attempts (payment_key, order_id, amount, status)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (payment_key) DO UPDATE
+        SET status = EXCLUDED.status, gateway_code = NULL, gateway_message = NULL, completed_at = NULL
+        WHERE payment_attempts.status = $5
+        RETURNING payment_key
+        "#,
+        payment_key,
+        order_id,
+        amount as Money,
+        PaymentAttemptStatus::Confirming as PaymentAttemptStatus,
+        PaymentAttemptStatus::Errored as PaymentAttemptStatus
+    )
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    if claimed.is_some() {
+        tx.commit().await?;
+        return Ok(AttemptStart::Started);
+    }
+
+    let existing = sqlx::query_as!(
+        PaymentAttempt,
+        r#"
+        SELECT
+            payment_key,
+            order_id,
+            amount as "amount: Money",
+            status as "status: PaymentAttemptStatus",
+            gateway_code,
+            gateway_message,
+            created_at,
+            completed_at
+        FROM payment_attempts
+        WHERE payment_key = $1
+        "#,
+        payment_key
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(AttemptStart::Existing(existing))
+}
+
+/// Stores the gateway's answer to a confirm request.
+pub async fn complete_attempt(
+    db: &PgPool,
+    payment_key: &str,
+    status: PaymentAttemptStatus,
+    gateway_code: Option<&str>,
+    gateway_message: Option<&str>,
+) -> Result<(), DataError> {
+    sqlx::query!(
+        r#"
+        UPDATE payment_attempts
+        SET status = $2, gateway_code = $3, gateway_message = $4, completed_at = NOW()
+        WHERE payment_key = $1
+        "#,
+        payment_key,
+        status as PaymentAttemptStatus,
+        gateway_code,
+        gateway_message
+    )
+    .execute(db)
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,42 @@
+use sqlx::PgPool;
+
+use crate::{constants::errors, data::{ensure_rows_affected, errors::DataError}};
+
+/// Stores a webhook delivery as received, before any processing.
+pub async fn record_event(
+    db: &PgPool,
+    event_type: Option<&str>,
+    payment_key: Option<&str>,
+    payload: &str,
+) -> Result<i32, DataError> {
+    let event_id = sqlx::query_scalar!(
+        r#"
+        INSERT INTO payment_webhook_events (event_type, payment_key, payload)
+        VALUES ($1, $2, $3)
+        RETURNING event_id
+        "#,
+        event_type,
+        payment_key,
+        payload
+    )
+    .fetch_one(db)
+    .await?;
+
+    Ok(event_id)
+}
+
+pub async fn mark_event_processed(db: &PgPool, event_id: i32, outcome: &str) -> Result<(), DataError> {
+    let result = sqlx::query!(
+        r#"
+        UPDATE payment_webhook_events
+        SET outcome = $2, processed_at = NOW()
+        WHERE event_id = $1
+        "#,
+        event_id,
+        outcome
+    )
+    .execute(db)
+    .await?;
+
+    ensure_rows_affected(result, errors::WEBHOOK_EVENT_NOT_FOUND)
+}
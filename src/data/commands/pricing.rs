@@ -0,0 +1,40 @@
+use sqlx::PgPool;
+
+use crate::{data::errors::DataError, models::pricing::NewPricingRule, money::Money};
+
+/// Saves a new rule version with its tiers, returning its ID.
+pub async fn create_pricing_rule(db: &PgPool, rule: &NewPricingRule, created_by: i32) -> Result<i32, DataError> {
+    let mut tx = db.begin().await?;
+
+    let pricing_rule_id = sqlx::query_scalar!(
+        r#"
+        INSERT INTO pricing_rules (price_per_thousand, minimum_amount, effective_from, note, created_by)
+        VALUES ($1, $2, $3, $4, $5)
+        RETURNING pricing_rule_id
+        "#,
+        rule.price_per_thousand,
+        rule.minimum_amount as Money,
+        rule.effective_from,
+        rule.note,
+        created_by
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    for tier in &rule.tiers {
+        sqlx::query!(
+            r#"
+            INSERT INTO pricing_rule_tiers (pricing_rule_id, starts_at, price_per_thousand)
+            VALUES ($1, $2, $3)
+            "#,
+            pricing_rule_id,
+            tier.starts_at,
+            tier.price_per_thousand
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    tx.commit().await?;
+    Ok(pricing_rule_id)
+}
@@ -0,0 +1,111 @@
+use sqlx::PgPool;
+use time::OffsetDateTime;
+
+use crate::{
+    constants::errors,
+    data::{ensure_rows_affected, errors::DataError},
+    models::{
+        order::PaymentStatus,
+        reconciliation::{FindingKind, ReconciliationCandidate},
+    },
+    money::Money,
+    payment::GatewayPayment,
+};
+
+pub async fn start_run(
+    db: &PgPool,
+    window_start: OffsetDateTime,
+    window_end: OffsetDateTime,
+) -> Result<i32, DataError> {
+    let run_id = sqlx::query_scalar!(
+        r#"
+        INSERT INTO reconciliation_runs (window_start, window_end)
+        VALUES ($1, $2)
+        RETURNING run_id
+        "#,
+        window_start,
+        window_end
+    )
+    .fetch_one(db)
+    .await?;
+
+    Ok(run_id)
+}
+
+pub async fn finish_run(
+    db: &PgPool,
+    run_id: i32,
+    checked_count: i32,
+    finding_count: i32,
+    error_count: i32,
+) -> Result<(), DataError> {
+    sqlx::query!(
+        r#"
+        UPDATE reconciliation_runs
+        SET checked_count = $2, finding_count = $3, error_count = $4, finished_at = NOW()
+        WHERE run_id = $1
+        "#,
+        run_id,
+        checked_count,
+        finding_count,
+        error_count
+    )
+    .execute(db)
+    .await?;
+
+    Ok(())
+}
+
+/// Records a mismatch unless the same one is already open for the order.
+///
+/// Returns whether a new finding was recorded.
+pub async fn record_finding(
+    db: &PgPool,
+    run_id: i32,
+    candidate: &ReconciliationCandidate,
+    kind: FindingKind,
+    payment: Option<&GatewayPayment>,
+) -> Result<bool, DataError> {
+    let result = sqlx::query!(
+        r#"
+        INSERT INTO reconciliation_findings
+            (run_id, order_id, kind, local_status, local_amount, gateway_status, gateway_amount, gateway_payment_key)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        ON CONFLICT (order_id, kind) WHERE resolved_at IS NULL DO NOTHING
+        "#,
+        run_id,
+        candidate.order_id,
+        kind as FindingKind,
+        candidate.payment_status as PaymentStatus,
+        candidate.price_amount as Money,
+        payment.map(|p| p.status.as_str()),
+        payment.map(|p| p.total_amount) as Option<Money>,
+        payment.map(|p| p.payment_key.as_str())
+    )
+    .execute(db)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+pub async fn resolve_finding(
+    db: &PgPool,
+    finding_id: i32,
+    resolved_by: i32,
+    resolution: &str,
+) -> Result<(), DataError> {
+    let result = sqlx::query!(
+        r#"
+        UPDATE reconciliation_findings
+        SET resolved_at = NOW(), resolved_by = $2, resolution = $3
+        WHERE finding_id = $1 AND resolved_at IS NULL
+        "#,
+        finding_id,
+        resolved_by,
+        resolution
+    )
+    .execute(db)
+    .await?;
+
+    ensure_rows_affected(result, errors::RECONCILIATION_FINDING_NOT_FOUND)
+}
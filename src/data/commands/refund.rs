@@ -0,0 +1,140 @@
+use sqlx::{PgConnection, PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+use super::order::{OrderTransition, lock_order, transition_order};
+use crate::{
+    constants::{errors, payment},
+    data::{amount_in_range, errors::DataError},
+    models::order::{Order, OrderEventSource, PaymentStatus},
+    money::Money,
+};
+
+pub struct RecordRefundParams<'a> {
+    pub order_id: Uuid,
+    pub amount: Money,
+    pub reason: &'a str,
+    pub refunded_by: Option<i32>,
+    pub source: OrderEventSource,
+}
+
+/// A refund an admin is making through the gateway.
+///
+/// The order stays locked from [`begin_refund`] until [`PendingRefund::record`],
+/// so a cancellation webhook for the same refund waits and then finds it on file
+/// instead of recording it as a gateway refund. Dropping it releases the lock.
+pub struct PendingRefund {
+    tx: Transaction<'static, Postgres>,
+    /// Total of the refunds on file
+    pub refunded: Money,
+    /// Paid amount less the refunds on file; zero if the order is not refundable
+    pub refundable: Money,
+}
+
+pub async fn begin_refund(db: &PgPool, order_id: Uuid) -> Result<PendingRefund, DataError> {
+    let mut tx = db.begin().await?;
+    let order = lock_order(&mut tx, order_id).await?;
+    let refunded = refunded_total(&mut tx, order_id).await?;
+    let refundable = if order.payment_status.is_refundable() {
+        amount_in_range(order.price_amount.checked_sub(refunded))?
+    } else {
+        Money::zero(order.price_amount.currency)
+    };
+
+    Ok(PendingRefund { tx, refunded, refundable })
+}
+
+impl PendingRefund {
+    /// Records the refund the gateway made and moves the order to `refunded` or
+    /// `partially_refunded`, returning the new status.
+    pub async fn record(mut self, params: RecordRefundParams<'_>) -> Result<PaymentStatus, DataError> {
+        let (_, to) = insert_refund(&mut self.tx, &params).await?;
+        self.tx.commit().await?;
+
+        Ok(to)
+    }
+}
+
+/// Records whatever the gateway refunded beyond the refunds on file, e.g. a
+/// cancellation made in the gateway's dashboard. Returns the order's new status,
+/// or `None` if `gateway_refunded` is already on file.
+pub async fn record_gateway_refunds(
+    db: &PgPool,
+    order_id: Uuid,
+    gateway_refunded: Money,
+    source: OrderEventSource,
+) -> Result<Option<PaymentStatus>, DataError> {
+    let mut tx = db.begin().await?;
+    lock_order(&mut tx, order_id).await?;
+    let recorded = refunded_total(&mut tx, order_id).await?;
+    if gateway_refunded <= recorded {
+        return Ok(None);
+    }
+
+    let params = RecordRefundParams {
+        order_id,
+        amount: amount_in_range(gateway_refunded.checked_sub(recorded))?,
+        reason: payment::GATEWAY_REFUND_REASON,
+        refunded_by: None,
+        source,
+    };
+    let (_, to) = insert_refund(&mut tx, &params).await?;
+    tx.commit().await?;
+
+    Ok(Some(to))
+}
+
+/// Records a refund inside the caller's transaction. Returns the order as it
+/// was before the refund, and its new status.
+pub(super) async fn insert_refund(
+    conn: &mut PgConnection,
+    params: &RecordRefundParams<'_>,
+) -> Result<(Order, PaymentStatus), DataError> {
+    let order = lock_order(conn, params.order_id).await?;
+    let refunded = refunded_total(conn, params.order_id).await?;
+
+    let remaining = amount_in_range(order.price_amount.checked_sub(refunded))?;
+    if !params.amount.is_positive() || params.amount > remaining {
+        return Err(DataError::InvalidInput(errors::REFUND_AMOUNT_INVALID.to_string()));
+    }
+    let to = if params.amount == remaining { PaymentStatus::Refunded } else { PaymentStatus::PartiallyRefunded };
+
+    sqlx::query!(
+        r#"
+        INSERT INTO refunds (order_id, amount, reason, refunded_by)
+        VALUES ($1, $2, $3, $4)
+        "#,
+        params.order_id,
+        params.amount as Money,
+        params.reason,
+        params.refunded_by
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    let note = format!("Refunded {}: {}", params.amount, params.reason);
+    transition_order(
+        conn,
+        params.order_id,
+        OrderTransition {
+            to,
+            payment_key: None,
+            payment_method: None,
+            source: params.source,
+            actor_id: params.refunded_by,
+            note: Some(&note),
+        },
+    )
+    .await?;
+
+    Ok((order, to))
+}
+
+async fn refunded_total(conn: &mut PgConnection, order_id: Uuid) -> Result<Money, DataError> {
+    sqlx::query_scalar!(
+        r#"SELECT COALESCE(SUM(amount), 0)::bigint as "total!: Money" FROM refunds WHERE order_id = $1"#,
+        order_id
+    )
+    .fetch_one(&mut *conn)
+    .await
+    .map_err(DataError::from)
+}
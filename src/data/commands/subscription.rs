@@ -0,0 +1,354 @@
+use sqlx::PgPool;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use super::order::{insert_order, transition_order, CreateOrderParams, OrderTransition};
+use crate::{
+    constants::{errors, subscriptions},
+    data::{ensure_rows_affected, errors::DataError, map_row_not_found},
+    models::{
+        order::{Order, OrderEventSource, PaymentStatus},
+        subscription::{self, Subscription, SubscriptionStatus},
+    },
+    money::Money,
+};
+
+/// A charge waiting for the gateway's answer.
+pub struct PendingCharge {
+    pub charge_id: i32,
+    pub order_number: String,
+    pub amount: Money,
+    /// Left pending by an earlier attempt, which may have gone through at the gateway
+    pub resumed: bool,
+}
+
+/// Where a subscription stands after a failed charge.
+pub enum ChargeFailure {
+    /// The first charge failed; the subscription is still waiting for a card.
+    NotStarted,
+    /// A renewal failed and will be retried.
+    Retrying { next_retry_at: OffsetDateTime },
+    /// The last retry failed and the subscription has ended.
+    Ended,
+}
+
+/// Creates a subscription that starts once a card is registered and charged.
+pub async fn create_subscription(db: &PgPool, user_id: i32, plan_id: i32) -> Result<Uuid, DataError> {
+    sqlx::query_scalar!(
+        r#"
+        INSERT INTO subscriptions (user_id, plan_id, customer_key)
+        VALUES ($1, $2, $3)
+        RETURNING subscription_id
+        "#,
+        user_id,
+        plan_id,
+        Subscription::generate_customer_key()
+    )
+    .fetch_one(db)
+    .await
+    .map_err(DataError::from)
+}
+
+pub async fn save_card(
+    db: &PgPool,
+    subscription_id: Uuid,
+    billing_key: &str,
+    card_label: Option<&str>,
+) -> Result<(), DataError> {
+    let result = sqlx::query!(
+        r#"UPDATE subscriptions SET billing_key = $2, card_label = $3 WHERE subscription_id = $1"#,
+        subscription_id,
+        billing_key,
+        card_label
+    )
+    .execute(db)
+    .await?;
+
+    ensure_rows_affected(result, errors::SUBSCRIPTION_NOT_FOUND)
+}
+
+/// Returns the subscription's pending charge, or starts a new one.
+///
+/// A charge stays pending when the gateway could not be reached, and is retried
+/// under the same order number so the gateway never charges it twice.
+pub async fn start_charge(db: &PgPool, subscription: &Subscription) -> Result<PendingCharge, DataError> {
+    let pending = sqlx::query_as!(
+        PendingCharge,
+        r#"
+        SELECT charge_id, order_number, amount as "amount: Money", true as "resumed!"
+        FROM subscription_charges
+        WHERE subscription_id = $1 AND status = 'pending'
+        "#,
+        subscription.subscription_id
+    )
+    .fetch_optional(db)
+    .await?;
+    if let Some(pending) = pending {
+        return Ok(pending);
+    }
+
+    sqlx::query_as!(
+        PendingCharge,
+        r#"
+        INSERT INTO subscription_charges (subscription_id, order_number, amount)
+        VALUES ($1, $2, $3)
+        RETURNING charge_id, order_number, amount as "amount: Money", false as "resumed!"
+        "#,
+        subscription.subscription_id,
+        subscription.generate_order_number(),
+        subscription.price_amount as Money
+    )
+    .fetch_one(db)
+    .await
+    .map_err(DataError::from)
+}
+
+/// Records a successful charge and starts the next period with a fresh allowance.
+///
+/// A renewal continues from the end of the paid period; a first charge or a
+/// recovered overdue payment starts the period now. Returns `false` if the charge
+/// was no longer pending, so the renewal and the webhook can both report it.
+pub async fn record_charge_paid(
+    db: &PgPool,
+    subscription_id: Uuid,
+    charge_id: i32,
+    payment_key: &str,
+) -> Result<bool, DataError> {
+    let mut tx = db.begin().await?;
+
+    let settled = sqlx::query!(
+        r#"
+        UPDATE subscription_charges
+        SET status = 'paid', payment_key = $2, settled_at = NOW()
+        WHERE charge_id = $1 AND status = 'pending'
+        "#,
+        charge_id,
+        payment_key
+    )
+    .execute(&mut *tx)
+    .await?;
+    if settled.rows_affected() == 0 {
+        return Ok(false);
+    }
+
+    let result = sqlx::query!(
+        r#"
+        UPDATE subscriptions
+        SET
+            status = 'active',
+            current_period_start = CASE WHEN status = 'active' THEN current_period_end ELSE NOW() END,
+            current_period_end = CASE WHEN status = 'active' THEN current_period_end ELSE NOW() END
+                + make_interval(days => $2),
+            characters_used = 0,
+            failed_attempts = 0,
+            next_retry_at = NULL
+        WHERE subscription_id = $1
+        "#,
+        subscription_id,
+        subscriptions::PERIOD_DAYS as i32
+    )
+    .execute(&mut *tx)
+    .await?;
+    ensure_rows_affected(result, errors::SUBSCRIPTION_NOT_FOUND)?;
+
+    tx.commit().await?;
+    Ok(true)
+}
+
+/// Records a declined charge and moves a live subscription along the dunning schedule.
+pub async fn record_charge_failed(
+    db: &PgPool,
+    subscription_id: Uuid,
+    charge_id: i32,
+    message: &str,
+) -> Result<ChargeFailure, DataError> {
+    let mut tx = db.begin().await?;
+
+    sqlx::query!(
+        r#"
+        UPDATE subscription_charges
+        SET status = 'failed', failure_message = $2, settled_at = NOW()
+        WHERE charge_id = $1
+        "#,
+        charge_id,
+        message
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    let current = sqlx::query!(
+        r#"
+        SELECT status as "status: SubscriptionStatus", failed_attempts
+        FROM subscriptions
+        WHERE subscription_id = $1
+        FOR UPDATE
+        "#,
+        subscription_id
+    )
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|e| map_row_not_found(e, errors::SUBSCRIPTION_NOT_FOUND))?;
+
+    if !matches!(current.status, SubscriptionStatus::Active | SubscriptionStatus::PastDue) {
+        tx.commit().await?;
+        return Ok(ChargeFailure::NotStarted);
+    }
+
+    let failed_attempts = current.failed_attempts + 1;
+    let failure = match subscription::next_retry_at(failed_attempts, OffsetDateTime::now_utc()) {
+        Some(next_retry_at) => {
+            sqlx::query!(
+                r#"
+                UPDATE subscriptions
+                SET status = 'past_due', failed_attempts = $2, next_retry_at = $3
+                WHERE subscription_id = $1
+                "#,
+                subscription_id,
+                failed_attempts,
+                next_retry_at
+            )
+            .execute(&mut *tx)
+            .await?;
+            ChargeFailure::Retrying { next_retry_at }
+        }
+        None => {
+            sqlx::query!(
+                r#"
+                UPDATE subscriptions
+                SET status = 'ended', failed_attempts = $2, next_retry_at = NULL, ended_at = NOW()
+                WHERE subscription_id = $1
+                "#,
+                subscription_id,
+                failed_attempts
+            )
+            .execute(&mut *tx)
+            .await?;
+            ChargeFailure::Ended
+        }
+    };
+
+    tx.commit().await?;
+    Ok(failure)
+}
+
+/// Ends an active subscription whose cancellation took effect at the period end.
+pub async fn end_subscription(db: &PgPool, subscription_id: Uuid) -> Result<(), DataError> {
+    sqlx::query!(
+        r#"
+        UPDATE subscriptions
+        SET status = 'ended', ended_at = NOW()
+        WHERE subscription_id = $1 AND status = 'active'
+        "#,
+        subscription_id
+    )
+    .execute(db)
+    .await?;
+
+    Ok(())
+}
+
+/// Cancels a user's subscription: an active one runs to the end of its paid
+/// period, an overdue one ends now and is not retried.
+pub async fn cancel_subscription(db: &PgPool, subscription_id: Uuid, user_id: i32) -> Result<(), DataError> {
+    let result = sqlx::query!(
+        r#"
+        UPDATE subscriptions
+        SET
+            cancel_at_period_end = (status = 'active'),
+            ended_at = CASE WHEN status = 'past_due' THEN NOW() END,
+            next_retry_at = NULL,
+            status = CASE WHEN status = 'past_due' THEN 'ended' ELSE status END
+        WHERE subscription_id = $1 AND user_id = $2 AND status IN ('active', 'past_due')
+        "#,
+        subscription_id,
+        user_id
+    )
+    .execute(db)
+    .await?;
+
+    ensure_rows_affected(result, errors::SUBSCRIPTION_NOT_FOUND)
+}
+
+/// Undoes a cancellation before the period ends.
+pub async fn resume_subscription(db: &PgPool, subscription_id: Uuid, user_id: i32) -> Result<(), DataError> {
+    let result = sqlx::query!(
+        r#"
+        UPDATE subscriptions
+        SET cancel_at_period_end = FALSE
+        WHERE subscription_id = $1 AND user_id = $2 AND status = 'active' AND cancel_at_period_end
+        "#,
+        subscription_id,
+        user_id
+    )
+    .execute(db)
+    .await?;
+
+    ensure_rows_affected(result, errors::SUBSCRIPTION_NOT_FOUND)
+}
+
+/// Creates an already-paid order from the subscription's remaining characters.
+///
+/// The allowance is taken in the same transaction, so two uploads racing for
+/// the last characters cannot both be covered.
+pub async fn create_covered_order(
+    db: &PgPool,
+    subscription_id: Uuid,
+    params: CreateOrderParams,
+) -> Result<Order, DataError> {
+    let mut tx = db.begin().await?;
+    let user_id = params.user_id;
+    let characters: i32 = params
+        .items
+        .iter()
+        .filter_map(|item| item.metadata.text_analysis())
+        .map(|analysis| analysis.text_length)
+        .sum();
+
+    let result = sqlx::query!(
+        r#"
+        UPDATE subscriptions s
+        SET characters_used = s.characters_used + $3
+        FROM subscription_plans p
+        WHERE p.plan_id = s.plan_id
+            AND s.subscription_id = $1
+            AND s.user_id = $2
+            AND s.status = 'active'
+            AND s.current_period_end > NOW()
+            AND s.characters_used + $3 <= p.included_characters
+        "#,
+        subscription_id,
+        user_id,
+        characters
+    )
+    .execute(&mut *tx)
+    .await?;
+    if result.rows_affected() == 0 {
+        return Err(DataError::InvalidInput(errors::SUBSCRIPTION_QUOTA_EXCEEDED.to_string()));
+    }
+
+    let order = insert_order(&mut tx, params).await?;
+    sqlx::query!(
+        r#"UPDATE orders SET subscription_id = $2 WHERE order_id = $1"#,
+        order.order_id,
+        subscription_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    let order = transition_order(
+        &mut tx,
+        order.order_id,
+        OrderTransition {
+            to: PaymentStatus::Paid,
+            payment_key: None,
+            payment_method: None,
+            source: OrderEventSource::Checkout,
+            actor_id: Some(user_id),
+            note: Some(subscriptions::COVERED_NOTE),
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+    Ok(order)
+}
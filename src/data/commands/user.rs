@@ -19,3 +19,19 @@ pub async fn get_or_create_user(db: &PgPool, email: &str) -> Result<i32, DataErr
 
     Ok(row.user_id)
 }
+
+pub async fn set_payment_emails_enabled(
+    db: &PgPool,
+    user_id: i32,
+    enabled: bool,
+) -> Result<(), DataError> {
+    sqlx::query!(
+        "UPDATE users SET payment_emails_enabled = $2 WHERE user_id = $1",
+        user_id,
+        enabled
+    )
+    .execute(db)
+    .await?;
+
+    Ok(())
+}
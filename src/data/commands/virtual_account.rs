@@ -0,0 +1,83 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use super::order::{lock_order, transition_order, OrderTransition};
+use crate::{
+    constants::deposits,
+    data::errors::DataError,
+    models::{
+        order::{Order, OrderEventSource, PaymentStatus},
+        payment_method::PaymentMethod,
+    },
+    payment::GatewayVirtualAccount,
+};
+
+/// Stores the account the customer transfers to and moves the order to awaiting deposit.
+///
+/// An order whose earlier account expired unpaid gets the new account in its place.
+pub async fn record_issued(
+    db: &PgPool,
+    order_id: Uuid,
+    payment_key: &str,
+    account: &GatewayVirtualAccount,
+    source: OrderEventSource,
+) -> Result<Order, DataError> {
+    let mut tx = db.begin().await?;
+
+    let order = transition_order(
+        &mut tx,
+        order_id,
+        OrderTransition {
+            to: PaymentStatus::AwaitingDeposit,
+            payment_key: Some(payment_key),
+            payment_method: Some(PaymentMethod::VirtualAccount),
+            source,
+            actor_id: None,
+            note: None,
+        },
+    )
+    .await?;
+
+    sqlx::query!(
+        r#"
+        INSERT INTO virtual_accounts (order_id, payment_key, bank_code, account_number, due_at)
+        VALUES ($1, $2, $3, $4, $5)
+        ON CONFLICT (order_id) DO UPDATE
+        SET payment_key = $2, bank_code = $3, account_number = $4, due_at = $5, created_at = NOW()
+        "#,
+        order_id,
+        payment_key,
+        account.bank_code,
+        account.account_number,
+        account.due_date
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(order)
+}
+
+/// Cancels an order whose virtual account expired without a deposit.
+///
+/// Returns `false` when the order settled in the meantime.
+pub async fn expire(db: &PgPool, order_id: Uuid) -> Result<bool, DataError> {
+    let mut tx = db.begin().await?;
+
+    if lock_order(&mut tx, order_id).await?.payment_status != PaymentStatus::AwaitingDeposit {
+        return Ok(false);
+    }
+
+    let transition = OrderTransition {
+        to: PaymentStatus::Cancelled,
+        payment_key: None,
+        payment_method: None,
+        source: OrderEventSource::System,
+        actor_id: None,
+        note: Some(deposits::EXPIRED_NOTE),
+    };
+    transition_order(&mut tx, order_id, transition).await?;
+
+    tx.commit().await?;
+    Ok(true)
+}
@@ -1,5 +1,7 @@
 use thiserror::Error;
 
+use crate::models::order::PaymentStatus;
+
 /// Errors at the data access layer.
 ///
 /// Separates low-level database errors from application-level semantic errors
@@ -17,4 +19,7 @@ pub enum DataError {
 
     #[error("{0}")]
     InvalidInput(String),
+
+    #[error("Order cannot move from {} to {}", from.as_str(), to.as_str())]
+    InvalidTransition { from: PaymentStatus, to: PaymentStatus },
 }
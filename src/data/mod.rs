@@ -30,6 +30,16 @@ pub fn map_row_not_found(error: sqlx::Error, message: &'static str) -> DataError
     }
 }
 
+/// Maps a foreign key violation to DataError::NotFound.
+///
+/// Use when inserting a row that references one the caller did not load first.
+pub fn map_foreign_key_not_found(error: sqlx::Error, message: &'static str) -> DataError {
+    match error {
+        sqlx::Error::Database(ref e) if e.is_foreign_key_violation() => DataError::NotFound(message),
+        _ => DataError::Database(error),
+    }
+}
+
 /// Maps sqlx::Error::RowNotFound to DataError::Unauthorized.
 ///
 /// Use when a missing row indicates authorization failure rather than simple not-found.
@@ -39,3 +49,9 @@ pub fn map_row_unauthorized(error: sqlx::Error, message: &'static str) -> DataEr
         _ => DataError::Database(error),
     }
 }
+
+/// Maps the `None` of checked [`Money`](crate::money::Money) arithmetic, an
+/// overflow, to DataError::InvalidInput.
+pub fn amount_in_range<T>(value: Option<T>) -> Result<T, DataError> {
+    value.ok_or_else(|| DataError::InvalidInput(crate::constants::errors::AMOUNT_OUT_OF_RANGE.to_string()))
+}
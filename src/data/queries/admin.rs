@@ -1,10 +1,14 @@
-use sqlx::PgPool;
+use sqlx::{PgPool, types::Json};
 use crate::{
+    constants::admin::ROLE_ADMIN,
     data::errors::DataError,
     models::{
-        admin::{AdminStats, UserListItem, UserDetail, OrderListItem, OrderDetail},
+        admin::{AdminStats, AdminUser, UserListItem, UserDetail, OrderListItem, OrderDetail, ProductRevenue},
         order::PaymentStatus,
+        order_item::OrderItem,
+        payment_method::PaymentMethod,
     },
+    money::Money,
 };
 
 pub async fn get_admin_stats(db: &PgPool) -> Result<AdminStats, DataError> {
@@ -12,19 +16,50 @@ pub async fn get_admin_stats(db: &PgPool) -> Result<AdminStats, DataError> {
         r#"
         SELECT
             (SELECT COUNT(*) FROM users) as "total_users!",
-            (SELECT COUNT(*) FROM orders WHERE payment_status = 'paid') as "total_orders!",
-            (SELECT COALESCE(SUM(price_amount), 0) FROM orders WHERE payment_status = 'paid') as "total_revenue!",
+            (SELECT COUNT(*) FROM orders WHERE payment_status IN ('paid', 'partially_refunded')) as "total_orders!",
+            ((SELECT COALESCE(SUM(price_amount), 0) FROM orders WHERE payment_status IN ('paid', 'partially_refunded', 'refunded'))
+                - (SELECT COALESCE(SUM(amount), 0) FROM refunds))::bigint as "total_revenue!: Money",
+            (
+                SELECT COALESCE(SUM((o.price_amount - COALESCE(r.refunded, 0)) * o.net_amount / NULLIF(o.price_amount, 0)), 0)::bigint
+                FROM orders o
+                LEFT JOIN (SELECT order_id, SUM(amount)::bigint as refunded FROM refunds GROUP BY order_id) r ON r.order_id = o.order_id
+                WHERE o.payment_status IN ('paid', 'partially_refunded', 'refunded')
+            ) as "net_revenue!: Money",
             (SELECT COUNT(*) FROM orders WHERE payment_status = 'paid' AND created_at >= NOW() - INTERVAL '7 days') as "orders_last_7_days!"
         "#
     )
     .fetch_one(db)
     .await?;
 
+    let products = sqlx::query_as!(
+        ProductRevenue,
+        r#"
+        SELECT
+            p.name as product_name,
+            COALESCE(SUM(i.quantity), 0) as "quantity!",
+            COALESCE(SUM(
+                i.quantity * i.unit_price_amount * (o.price_amount - COALESCE(r.refunded, 0))
+                    / NULLIF(o.list_price_amount, 0)
+            ), 0)::bigint as "revenue!: Money"
+        FROM order_items i
+        JOIN orders o ON o.order_id = i.order_id
+        JOIN products p ON p.product_id = i.product_id
+        LEFT JOIN (SELECT order_id, SUM(amount)::bigint as refunded FROM refunds GROUP BY order_id) r ON r.order_id = o.order_id
+        WHERE o.payment_status IN ('paid', 'partially_refunded', 'refunded')
+        GROUP BY p.product_id, p.name
+        ORDER BY p.name
+        "#
+    )
+    .fetch_all(db)
+    .await?;
+
     Ok(AdminStats {
         total_users: result.total_users,
         total_orders: result.total_orders,
-        total_revenue: result.total_revenue as i32,
+        total_revenue: result.total_revenue,
+        net_revenue: result.net_revenue,
         orders_last_7_days: result.orders_last_7_days,
+        products,
     })
 }
 
@@ -43,7 +78,7 @@ pub async fn get_users_paginated(
             EXISTS(SELECT 1 FROM user_roles ur WHERE ur.user_id = u.user_id AND ur.role = 'admin') as "is_admin!",
             u.created_at,
             COUNT(CASE WHEN o.payment_status = 'paid' THEN 1 END) as "order_count!",
-            COALESCE(SUM(CASE WHEN o.payment_status = 'paid' THEN o.price_amount ELSE 0 END), 0) as total_spent
+            COALESCE(SUM(CASE WHEN o.payment_status = 'paid' THEN o.price_amount ELSE 0 END), 0)::bigint as "total_spent!: Money"
         FROM users u
         LEFT JOIN orders o ON u.user_id = o.user_id
         GROUP BY u.user_id, u.email, u.created_at
@@ -64,7 +99,7 @@ pub async fn get_users_paginated(
             is_admin: r.is_admin,
             created_at: r.created_at,
             order_count: r.order_count,
-            total_spent: r.total_spent.unwrap_or(0) as i32,
+            total_spent: r.total_spent,
         })
         .collect())
 }
@@ -90,12 +125,13 @@ pub async fn get_user_detail(db: &PgPool, user_id: i32) -> Result<UserDetail, Da
             u.email,
             EXISTS(SELECT 1 FROM user_roles ur WHERE ur.user_id = u.user_id AND ur.role = 'admin') as "is_admin!",
             u.created_at,
+            u.credit_balance as "credit_balance: Money",
             COUNT(CASE WHEN o.payment_status = 'paid' THEN 1 END) as "order_count!",
-            COALESCE(SUM(CASE WHEN o.payment_status = 'paid' THEN o.price_amount ELSE 0 END), 0) as total_spent
+            COALESCE(SUM(CASE WHEN o.payment_status = 'paid' THEN o.price_amount ELSE 0 END), 0)::bigint as "total_spent!: Money"
         FROM users u
         LEFT JOIN orders o ON u.user_id = o.user_id
         WHERE u.user_id = $1
-        GROUP BY u.user_id, u.email, u.created_at
+        GROUP BY u.user_id, u.email, u.created_at, u.credit_balance
         "#,
         user_id
     )
@@ -108,7 +144,8 @@ pub async fn get_user_detail(db: &PgPool, user_id: i32) -> Result<UserDetail, Da
         is_admin: result.is_admin,
         created_at: result.created_at,
         order_count: result.order_count,
-        total_spent: result.total_spent.unwrap_or(0) as i32,
+        total_spent: result.total_spent,
+        credit_balance: result.credit_balance,
     })
 }
 
@@ -127,8 +164,9 @@ pub async fn get_user_orders(
             order_id::text as "order_id!",
             order_number,
             user_email,
-            price_amount,
+            price_amount as "price_amount: Money",
             payment_status as "payment_status: PaymentStatus",
+            payment_method as "payment_method: PaymentMethod",
             created_at
         FROM orders
         WHERE user_id = $1
@@ -147,93 +185,58 @@ pub async fn get_user_orders(
 pub async fn get_orders_paginated(
     db: &PgPool,
     status_filter: Option<PaymentStatus>,
+    method_filter: Option<PaymentMethod>,
     page: i64,
     per_page: i64,
 ) -> Result<Vec<OrderListItem>, DataError> {
     let offset = (page - 1) * per_page;
 
-    let result = match status_filter {
-        Some(status) => {
-            sqlx::query_as!(
-                OrderListItem,
-                r#"
-                SELECT
-                    order_id::text as "order_id!",
-                    order_number,
-                    user_email,
-                    price_amount,
-                    payment_status as "payment_status: PaymentStatus",
-                    created_at
-                FROM orders
-                WHERE payment_status = $1
-                ORDER BY created_at DESC
-                LIMIT $2 OFFSET $3
-                "#,
-                status as PaymentStatus,
-                per_page,
-                offset
-            )
-            .fetch_all(db)
-            .await
-        }
-        None => {
-            sqlx::query_as!(
-                OrderListItem,
-                r#"
-                SELECT
-                    order_id::text as "order_id!",
-                    order_number,
-                    user_email,
-                    price_amount,
-                    payment_status as "payment_status: PaymentStatus",
-                    created_at
-                FROM orders
-                ORDER BY created_at DESC
-                LIMIT $1 OFFSET $2
-                "#,
-                per_page,
-                offset
-            )
-            .fetch_all(db)
-            .await
-        }
-    };
-
-    result.map_err(DataError::from)
+    sqlx::query_as!(
+        OrderListItem,
+        r#"
+        SELECT
+            order_id::text as "order_id!",
+            order_number,
+            user_email,
+            price_amount as "price_amount: Money",
+            payment_status as "payment_status: PaymentStatus",
+            payment_method as "payment_method: PaymentMethod",
+            created_at
+        FROM orders
+        WHERE ($1::text IS NULL OR payment_status = $1)
+            AND ($2::text IS NULL OR payment_method = $2)
+        ORDER BY created_at DESC
+        LIMIT $3 OFFSET $4
+        "#,
+        status_filter.map(|status| status.as_str()),
+        method_filter.map(|method| method.as_str()),
+        per_page,
+        offset
+    )
+    .fetch_all(db)
+    .await
+    .map_err(DataError::from)
 }
 
 pub async fn get_total_order_count(
     db: &PgPool,
     status_filter: Option<PaymentStatus>,
+    method_filter: Option<PaymentMethod>,
 ) -> Result<i64, DataError> {
-    match status_filter {
-        Some(status) => {
-            let result = sqlx::query!(
-                r#"
-                SELECT COUNT(*) as "count!"
-                FROM orders
-                WHERE payment_status = $1
-                "#,
-                status as PaymentStatus
-            )
-            .fetch_one(db)
-            .await?;
-
-            Ok(result.count)
-        }
-        None => {
-            let result = sqlx::query!(
-                r#"
-                SELECT COUNT(*) as "count!"
-                FROM orders
-                "#
-            )
-            .fetch_one(db)
-            .await?;
+    let result = sqlx::query!(
+        r#"
+        SELECT COUNT(*) as "count!"
+        FROM orders
+        WHERE ($1::text IS NULL OR payment_status = $1)
+            AND ($2::text IS NULL OR payment_method = $2)
+        "#,
+        status_filter.map(|status| status.as_str()),
+        method_filter.map(|method| method.as_str())
+    )
+    .fetch_one(db)
+    .await?;
 
-            Ok(result.count)
-        }
-    }
+    Ok(result.count)
 }
 
 pub async fn get_order_detail(db: &PgPool, order_id: &str) -> Result<OrderDetail, DataError> {
@@ -244,19 +247,27 @@ pub async fn get_order_detail(db: &PgPool, order_id: &str) -> Result<OrderDetail
         OrderDetail,
         r#"
         SELECT
-            order_id::text as "order_id!",
-            order_number,
-            user_id,
-            user_email,
-            price_amount,
-            payment_status as "payment_status: PaymentStatus",
-            created_at,
-            paid_at,
-            payment_key,
-            filename,
-            text_length
-        FROM orders
-        WHERE order_id = $1
+            o.order_id::text as "order_id!",
+            o.order_number,
+            o.user_id,
+            o.user_email,
+            o.list_price_amount as "list_price_amount: Money",
+            o.discount_amount as "discount_amount: Money",
+            d.code as "discount_code?",
+            o.price_amount as "price_amount: Money",
+            o.net_amount as "net_amount: Money",
+            o.tax_amount as "tax_amount: Money",
+            o.tax_rate_percent,
+            o.pricing_rule_id,
+            o.payment_status as "payment_status: PaymentStatus",
+            o.created_at,
+            o.paid_at,
+            o.payment_key,
+            o.payment_method as "payment_method: PaymentMethod",
+            order_items_json(o.order_id) as "items!: Json<Vec<OrderItem>>"
+        FROM orders o
+        LEFT JOIN discount_codes d ON d.discount_code_id = o.discount_code_id
+        WHERE o.order_id = $1
         "#,
         uuid_order_id
     )
@@ -264,3 +275,21 @@ pub async fn get_order_detail(db: &PgPool, order_id: &str) -> Result<OrderDetail
     .await
     .map_err(DataError::from)
 }
+
+/// Lists admins, e.g. as assignees for contact inquiries.
+pub async fn get_admins(db: &PgPool) -> Result<Vec<AdminUser>, DataError> {
+    sqlx::query_as!(
+        AdminUser,
+        r#"
+        SELECT u.user_id, u.email::text as "email!"
+        FROM users u
+        JOIN user_roles r ON r.user_id = u.user_id
+        WHERE r.role = $1
+        ORDER BY u.email
+        "#,
+        ROLE_ADMIN
+    )
+    .fetch_all(db)
+    .await
+    .map_err(DataError::from)
+}
@@ -0,0 +1,56 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{
+    data::errors::DataError,
+    models::cash_receipt::{CashReceipt, CashReceiptStatus, CashReceiptType},
+    money::Money,
+};
+
+pub async fn get_cash_receipt(db: &PgPool, order_id: Uuid) -> Result<Option<CashReceipt>, DataError> {
+    sqlx::query_as!(
+        CashReceipt,
+        r#"
+        SELECT
+            receipt_type as "receipt_type: CashReceiptType",
+            identifier,
+            status as "status: CashReceiptStatus",
+            receipt_key,
+            issue_number,
+            receipt_url,
+            issued_amount as "issued_amount: Money",
+            cancelled_amount as "cancelled_amount: Money",
+            failure_message
+        FROM cash_receipts
+        WHERE order_id = $1
+        "#,
+        order_id
+    )
+    .fetch_optional(db)
+    .await
+    .map_err(DataError::from)
+}
+
+/// Orders whose cash receipt is behind: requested for a paid transfer, or issued
+/// for more than what is left after refunds.
+pub async fn get_pending_order_ids(db: &PgPool, limit: i64) -> Result<Vec<Uuid>, DataError> {
+    sqlx::query_scalar!(
+        r#"
+        SELECT c.order_id
+        FROM cash_receipts c
+        JOIN orders o ON o.order_id = c.order_id
+        LEFT JOIN (SELECT order_id, SUM(amount)::bigint as refunded FROM refunds GROUP BY order_id) r ON r.order_id = c.order_id
+        WHERE o.payment_method IN ('transfer', 'virtual_account')
+            AND (
+                (c.status = 'requested' AND o.payment_status IN ('paid', 'partially_refunded', 'refunded'))
+                OR (c.status = 'issued' AND c.cancelled_amount < COALESCE(r.refunded, 0))
+            )
+        ORDER BY c.created_at
+        LIMIT $1
+        "#,
+        limit
+    )
+    .fetch_all(db)
+    .await
+    .map_err(DataError::from)
+}
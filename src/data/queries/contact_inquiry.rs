@@ -0,0 +1,171 @@
+use sqlx::PgPool;
+
+use crate::{
+    constants::errors,
+    data::{errors::DataError, map_row_not_found},
+    models::contact::{
+        ContactInquiry, InquiryEntry, InquiryEntryKind, InquiryListItem, InquiryStatus, QuarantinedInquiry,
+    },
+};
+
+pub async fn get_inquiries_paginated(
+    db: &PgPool,
+    status_filter: Option<InquiryStatus>,
+    page: i64,
+    per_page: i64,
+) -> Result<Vec<InquiryListItem>, DataError> {
+    let offset = (page - 1) * per_page;
+
+    sqlx::query_as!(
+        InquiryListItem,
+        r#"
+        SELECT
+            i.inquiry_id,
+            i.email::text as "email!",
+            i.message,
+            i.status as "status: InquiryStatus",
+            u.email::text as "assignee_email?",
+            i.created_at
+        FROM contact_inquiries i
+        LEFT JOIN users u ON u.user_id = i.assigned_to
+        WHERE i.quarantined_at IS NULL AND ($1::text IS NULL OR i.status = $1)
+        ORDER BY i.created_at DESC
+        LIMIT $2 OFFSET $3
+        "#,
+        status_filter.map(|status| status.as_str()),
+        per_page,
+        offset
+    )
+    .fetch_all(db)
+    .await
+    .map_err(DataError::from)
+}
+
+pub async fn get_total_inquiry_count(
+    db: &PgPool,
+    status_filter: Option<InquiryStatus>,
+) -> Result<i64, DataError> {
+    let result = sqlx::query!(
+        r#"
+        SELECT COUNT(*) as "count!"
+        FROM contact_inquiries
+        WHERE quarantined_at IS NULL AND ($1::text IS NULL OR status = $1)
+        "#,
+        status_filter.map(|status| status.as_str())
+    )
+    .fetch_one(db)
+    .await?;
+
+    Ok(result.count)
+}
+
+pub async fn get_inquiry(db: &PgPool, inquiry_id: i32) -> Result<ContactInquiry, DataError> {
+    sqlx::query_as!(
+        ContactInquiry,
+        r#"
+        SELECT
+            i.inquiry_id,
+            i.user_id,
+            i.email::text as "email!",
+            i.message,
+            i.status as "status: InquiryStatus",
+            i.assigned_to,
+            u.email::text as "assignee_email?",
+            i.spam_score,
+            i.spam_reasons,
+            i.quarantined_at,
+            i.created_at,
+            i.updated_at
+        FROM contact_inquiries i
+        LEFT JOIN users u ON u.user_id = i.assigned_to
+        WHERE i.inquiry_id = $1
+        "#,
+        inquiry_id
+    )
+    .fetch_one(db)
+    .await
+    .map_err(|e| map_row_not_found(e, errors::INQUIRY_NOT_FOUND))
+}
+
+/// Returns the inquiry's notes and replies, oldest first.
+pub async fn get_inquiry_entries(db: &PgPool, inquiry_id: i32) -> Result<Vec<InquiryEntry>, DataError> {
+    sqlx::query_as!(
+        InquiryEntry,
+        r#"
+        SELECT
+            e.kind as "kind: InquiryEntryKind",
+            e.body,
+            u.email::text as "author_email?",
+            e.created_at
+        FROM contact_inquiry_entries e
+        LEFT JOIN users u ON u.user_id = e.author_id
+        WHERE e.inquiry_id = $1
+        ORDER BY e.created_at, e.entry_id
+        "#,
+        inquiry_id
+    )
+    .fetch_all(db)
+    .await
+    .map_err(DataError::from)
+}
+
+pub async fn get_quarantined_inquiries_paginated(
+    db: &PgPool,
+    page: i64,
+    per_page: i64,
+) -> Result<Vec<QuarantinedInquiry>, DataError> {
+    let offset = (page - 1) * per_page;
+
+    sqlx::query_as!(
+        QuarantinedInquiry,
+        r#"
+        SELECT
+            inquiry_id,
+            email::text as "email!",
+            message,
+            spam_score,
+            spam_reasons,
+            created_at
+        FROM contact_inquiries
+        WHERE quarantined_at IS NOT NULL
+        ORDER BY quarantined_at DESC
+        LIMIT $1 OFFSET $2
+        "#,
+        per_page,
+        offset
+    )
+    .fetch_all(db)
+    .await
+    .map_err(DataError::from)
+}
+
+pub async fn get_total_quarantined_count(db: &PgPool) -> Result<i64, DataError> {
+    let result = sqlx::query!(
+        r#"
+        SELECT COUNT(*) as "count!"
+        FROM contact_inquiries
+        WHERE quarantined_at IS NOT NULL
+        "#
+    )
+    .fetch_one(db)
+    .await?;
+
+    Ok(result.count)
+}
+
+/// Counts inquiries with the same content hash received within the last `hours`.
+pub async fn count_recent_by_content_hash(db: &PgPool, content_hash: &str, hours: i64) -> Result<i64, DataError> {
+    let result = sqlx::query!(
+        r#"
+        SELECT COUNT(*) as "count!"
+        FROM contact_inquiries
+        WHERE content_hash = $1 AND created_at >= NOW() - make_interval(hours => $2::int)
+        "#,
+        content_hash,
+        hours as i32
+    )
+    .fetch_one(db)
+    .await?;
+
+    Ok(result.count)
+}
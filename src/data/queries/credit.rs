@@ -0,0 +1,98 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{
+    constants::errors,
+    data::{errors::DataError, map_row_not_found},
+    models::{
+        credit::{CreditTopUp, CreditTransaction, CreditTransactionKind},
+        order::PaymentStatus,
+    },
+    money::Money,
+};
+
+pub async fn get_balance(db: &PgPool, user_id: i32) -> Result<Money, DataError> {
+    sqlx::query_scalar!(r#"SELECT credit_balance as "credit_balance: Money" FROM users WHERE user_id = $1"#, user_id)
+        .fetch_one(db)
+        .await
+        .map_err(DataError::from)
+}
+
+/// The user's most recent ledger entries, newest first.
+pub async fn get_transactions(db: &PgPool, user_id: i32, limit: i64) -> Result<Vec<CreditTransaction>, DataError> {
+    sqlx::query_as!(
+        CreditTransaction,
+        r#"
+        SELECT
+            kind as "kind: CreditTransactionKind",
+            amount as "amount: Money",
+            balance_after as "balance_after: Money",
+            order_id,
+            note,
+            created_at
+        FROM credit_transactions
+        WHERE user_id = $1
+        ORDER BY created_at DESC, transaction_id DESC
+        LIMIT $2
+        "#,
+        user_id,
+        limit
+    )
+    .fetch_all(db)
+    .await
+    .map_err(DataError::from)
+}
+
+pub async fn get_topup_for_user(db: &PgPool, topup_id: Uuid, user_id: i32) -> Result<CreditTopUp, DataError> {
+    sqlx::query_as!(
+        CreditTopUp,
+        r#"
+        SELECT
+            topup_id,
+            user_id,
+            order_number,
+            amount as "amount: Money",
+            status as "status: PaymentStatus"
+        FROM credit_topups
+        WHERE topup_id = $1 AND user_id = $2
+        "#,
+        topup_id,
+        user_id
+    )
+    .fetch_one(db)
+    .await
+    .map_err(|e| map_row_not_found(e, errors::TOP_UP_NOT_FOUND))
+}
+
+pub async fn get_topup_by_order_number(db: &PgPool, order_number: &str) -> Result<Option<CreditTopUp>, DataError> {
+    let topup = sqlx::query_as!(
+        CreditTopUp,
+        r#"
+        SELECT
+            topup_id,
+            user_id,
+            order_number,
+            amount as "amount: Money",
+            status as "status: PaymentStatus"
+        FROM credit_topups
+        WHERE order_number = $1
+        "#,
+        order_number
+    )
+    .fetch_optional(db)
+    .await?;
+
+    Ok(topup)
+}
+
+/// Credits spent on an order, if it was paid from the wallet.
+pub async fn get_order_spend(db: &PgPool, order_id: Uuid) -> Result<Option<Money>, DataError> {
+    let amount = sqlx::query_scalar!(
+        r#"SELECT -amount as "amount!: Money" FROM credit_transactions WHERE order_id = $1 AND kind = 'spend'"#,
+        order_id
+    )
+    .fetch_optional(db)
+    .await?;
+
+    Ok(amount)
+}
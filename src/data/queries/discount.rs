@@ -0,0 +1,103 @@
+use sqlx::PgPool;
+
+use crate::{
+    data::errors::DataError,
+    models::discount::{DiscountCode, DiscountKind},
+    money::Money,
+};
+
+pub async fn get_discount_codes(db: &PgPool) -> Result<Vec<DiscountCode>, DataError> {
+    sqlx::query_as!(
+        DiscountCode,
+        r#"
+        SELECT
+            discount_code_id,
+            code,
+            kind as "kind: DiscountKind",
+            percent_off,
+            amount_off as "amount_off: Money",
+            min_order_amount as "min_order_amount: Money",
+            max_redemptions,
+            per_user_limit,
+            expires_at,
+            is_active,
+            redemption_count,
+            created_at
+        FROM discount_codes
+        ORDER BY created_at DESC
+        "#
+    )
+    .fetch_all(db)
+    .await
+    .map_err(DataError::from)
+}
+
+pub async fn get_discount_code(db: &PgPool, discount_code_id: i32) -> Result<Option<DiscountCode>, DataError> {
+    let code = sqlx::query_as!(
+        DiscountCode,
+        r#"
+        SELECT
+            discount_code_id,
+            code,
+            kind as "kind: DiscountKind",
+            percent_off,
+            amount_off as "amount_off: Money",
+            min_order_amount as "min_order_amount: Money",
+            max_redemptions,
+            per_user_limit,
+            expires_at,
+            is_active,
+            redemption_count,
+            created_at
+        FROM discount_codes
+        WHERE discount_code_id = $1
+        "#,
+        discount_code_id
+    )
+    .fetch_optional(db)
+    .await?;
+
+    Ok(code)
+}
+
+/// Looks up a code as typed by a customer; see [`DiscountCode::normalize`].
+pub async fn get_discount_code_by_code(db: &PgPool, code: &str) -> Result<Option<DiscountCode>, DataError> {
+    let code = sqlx::query_as!(
+        DiscountCode,
+        r#"
+        SELECT
+            discount_code_id,
+            code,
+            kind as "kind: DiscountKind",
+            percent_off,
+            amount_off as "amount_off: Money",
+            min_order_amount as "min_order_amount: Money",
+            max_redemptions,
+            per_user_limit,
+            expires_at,
+            is_active,
+            redemption_count,
+            created_at
+        FROM discount_codes
+        WHERE code = $1
+        "#,
+        DiscountCode::normalize(code)
+    )
+    .fetch_optional(db)
+    .await?;
+
+    Ok(code)
+}
+
+/// How many times a user has redeemed a code, for its per-user limit.
+pub async fn get_user_redemption_count(db: &PgPool, discount_code_id: i32, user_id: i32) -> Result<i64, DataError> {
+    let count = sqlx::query_scalar!(
+        r#"SELECT COUNT(*) as "count!" FROM discount_redemptions WHERE discount_code_id = $1 AND user_id = $2"#,
+        discount_code_id,
+        user_id
+    )
+    .fetch_one(db)
+    .await?;
+
+    Ok(count)
+}
@@ -0,0 +1,58 @@
+use sqlx::PgPool;
+
+use crate::{
+    data::errors::DataError,
+    models::email_suppression::{EmailSuppression, SuppressionReason},
+};
+
+pub async fn is_suppressed(db: &PgPool, email: &str) -> Result<bool, DataError> {
+    let result = sqlx::query!(
+        r#"SELECT EXISTS(SELECT 1 FROM email_suppressions WHERE email = $1) as "suppressed!""#,
+        email
+    )
+    .fetch_one(db)
+    .await?;
+
+    Ok(result.suppressed)
+}
+
+pub async fn get_suppressions_paginated(
+    db: &PgPool,
+    page: i64,
+    per_page: i64,
+) -> Result<Vec<EmailSuppression>, DataError> {
+    let offset = (page - 1) * per_page;
+
+    sqlx::query_as!(
+        EmailSuppression,
+        r#"
+        SELECT
+            suppression_id,
+            email::text as "email!",
+            reason as "reason: SuppressionReason",
+            details,
+            created_at
+        FROM email_suppressions
+        ORDER BY created_at DESC
+        LIMIT $1 OFFSET $2
+        "#,
+        per_page,
+        offset
+    )
+    .fetch_all(db)
+    .await
+    .map_err(DataError::from)
+}
+
+pub async fn get_total_suppression_count(db: &PgPool) -> Result<i64, DataError> {
+    let result = sqlx::query!(
+        r#"
+        SELECT COUNT(*) as "count!"
+        FROM email_suppressions
+        "#
+    )
+    .fetch_one(db)
+    .await?;
+
+    Ok(result.count)
+}
@@ -1,7 +1,15 @@
-use sqlx::PgPool;
+use sqlx::{PgPool, types::Json};
 use uuid::Uuid;
 
-use crate::{constants::errors, data::errors::DataError, models::order::{Order, OrderSummary}};
+use crate::{
+    constants::errors,
+    data::errors::DataError,
+    models::{
+        order::{Order, OrderEvent, OrderEventSource, OrderSummary, PaymentStatus},
+        order_item::OrderItem,
+    },
+    money::Money,
+};
 
 pub async fn get_order(db: &PgPool, order_id: Uuid) -> Result<Option<Order>, DataError> {
     let order = sqlx::query_as!(
@@ -11,16 +19,20 @@ pub async fn get_order(db: &PgPool, order_id: Uuid) -> Result<Option<Order>, Dat
             order_id,
             user_id,
             user_email,
-            filename,
-            file_size,
-            text_content,
-            text_length,
-            price_amount,
+            list_price_amount as "list_price_amount: Money",
+            discount_amount as "discount_amount: Money",
+            discount_code_id,
+            price_amount as "price_amount: Money",
+            net_amount as "net_amount: Money",
+            tax_amount as "tax_amount: Money",
+            tax_rate_percent,
             payment_status as "payment_status: _",
             payment_key,
+            payment_method as "payment_method: _",
             order_number,
             created_at,
-            paid_at
+            paid_at,
+            order_items_json(order_id) as "items!: Json<Vec<OrderItem>>"
         FROM orders
         WHERE order_id = $1
         "#,
@@ -40,16 +52,20 @@ pub async fn get_order_by_order_number(db: &PgPool, order_number: &str) -> Resul
             order_id,
             user_id,
             user_email,
-            filename,
-            file_size,
-            text_content,
-            text_length,
-            price_amount,
+            list_price_amount as "list_price_amount: Money",
+            discount_amount as "discount_amount: Money",
+            discount_code_id,
+            price_amount as "price_amount: Money",
+            net_amount as "net_amount: Money",
+            tax_amount as "tax_amount: Money",
+            tax_rate_percent,
             payment_status as "payment_status: _",
             payment_key,
+            payment_method as "payment_method: _",
             order_number,
             created_at,
-            paid_at
+            paid_at,
+            order_items_json(order_id) as "items!: Json<Vec<OrderItem>>"
         FROM orders
         WHERE order_number = $1
         "#,
@@ -61,6 +77,7 @@ pub async fn get_order_by_order_number(db: &PgPool, order_number: &str) -> Resul
     Ok(order)
 }
 
+/// Most recent orders of a user, leaving out cancelled (expired) quotes.
 pub async fn get_orders_for_user(
     db: &PgPool,
     user_id: i32,
@@ -71,15 +88,12 @@ pub async fn get_orders_for_user(
         r#"
         SELECT
             order_id,
-            filename,
-            file_size,
-            text_length,
-            price_amount,
+            price_amount as "price_amount: Money",
             payment_status as "payment_status: _",
             order_number,
             created_at
         FROM orders
-        WHERE user_id = $1
+        WHERE user_id = $1 AND payment_status <> 'cancelled'
         ORDER BY created_at DESC
         LIMIT $2
         "#,
@@ -115,3 +129,28 @@ pub async fn get_order_by_order_number_for_user(
     order.verify_ownership(user_id)?;
     Ok(order)
 }
+
+/// Status history of an order, oldest first.
+pub async fn get_order_events(db: &PgPool, order_id: Uuid) -> Result<Vec<OrderEvent>, DataError> {
+    sqlx::query_as!(
+        OrderEvent,
+        r#"
+        SELECT
+            e.from_status as "from_status: PaymentStatus",
+            e.to_status as "to_status: PaymentStatus",
+            e.source as "source: OrderEventSource",
+            e.payment_key,
+            u.email::text as "actor_email?",
+            e.note,
+            e.created_at
+        FROM order_events e
+        LEFT JOIN users u ON u.user_id = e.actor_id
+        WHERE e.order_id = $1
+        ORDER BY e.created_at, e.event_id
+        "#,
+        order_id
+    )
+    .fetch_all(db)
+    .await
+    .map_err(DataError::from)
+}
@@ -0,0 +1,32 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{
+    data::errors::DataError,
+    models::payment_attempt::{PaymentAttempt, PaymentAttemptStatus},
+    money::Money,
+};
+
+pub async fn get_attempts_for_order(db: &PgPool, order_id: Uuid) -> Result<Vec<PaymentAttempt>, DataError> {
+    sqlx::query_as!(
+        PaymentAttempt,
+        r#"
+        SELECT
+            payment_key,
+            order_id,
+            amount as "amount: Money",
+            status as "status: PaymentAttemptStatus",
+            gateway_code,
+            gateway_message,
+            created_at,
+            completed_at
+        FROM payment_attempts
+        WHERE order_id = $1
+        ORDER BY created_at
+        "#,
+        order_id
+    )
+    .fetch_all(db)
+    .await
+    .map_err(DataError::from)
+}
@@ -0,0 +1,90 @@
+use sqlx::PgPool;
+
+use crate::{
+    constants::errors,
+    data::errors::DataError,
+    models::pricing::{PricingRule, PricingTier},
+    money::Money,
+};
+
+/// Every rule version, newest effective date first.
+pub async fn get_pricing_rules(db: &PgPool) -> Result<Vec<PricingRule>, DataError> {
+    let rules = sqlx::query!(
+        r#"
+        SELECT pricing_rule_id, price_per_thousand, minimum_amount as "minimum_amount: Money", effective_from, note, created_at
+        FROM pricing_rules
+        ORDER BY effective_from DESC, pricing_rule_id DESC
+        "#
+    )
+    .fetch_all(db)
+    .await?;
+
+    let tiers = sqlx::query!(
+        r#"
+        SELECT pricing_rule_id, starts_at, price_per_thousand
+        FROM pricing_rule_tiers
+        ORDER BY starts_at
+        "#
+    )
+    .fetch_all(db)
+    .await?;
+
+    Ok(rules
+        .into_iter()
+        .map(|rule| PricingRule {
+            pricing_rule_id: rule.pricing_rule_id,
+            price_per_thousand: rule.price_per_thousand,
+            minimum_amount: rule.minimum_amount,
+            effective_from: rule.effective_from,
+            note: rule.note,
+            created_at: rule.created_at,
+            tiers: tiers
+                .iter()
+                .filter(|tier| tier.pricing_rule_id == rule.pricing_rule_id)
+                .map(|tier| PricingTier {
+                    starts_at: tier.starts_at,
+                    price_per_thousand: tier.price_per_thousand,
+                })
+                .collect(),
+        })
+        .collect())
+}
+
+/// The rule new orders are priced with: the latest one whose effective date has passed.
+pub async fn get_active_rule(db: &PgPool) -> Result<PricingRule, DataError> {
+    let rule = sqlx::query!(
+        r#"
+        SELECT pricing_rule_id, price_per_thousand, minimum_amount as "minimum_amount: Money", effective_from, note, created_at
+        FROM pricing_rules
+        WHERE effective_from <= NOW()
+        ORDER BY effective_from DESC, pricing_rule_id DESC
+        LIMIT 1
+        "#
+    )
+    .fetch_optional(db)
+    .await?
+    .ok_or(DataError::NotFound(errors::PRICING_RULE_NOT_FOUND))?;
+
+    let tiers = sqlx::query_as!(
+        PricingTier,
+        r#"
+        SELECT starts_at, price_per_thousand
+        FROM pricing_rule_tiers
+        WHERE pricing_rule_id = $1
+        ORDER BY starts_at
+        "#,
+        rule.pricing_rule_id
+    )
+    .fetch_all(db)
+    .await?;
+
+    Ok(PricingRule {
+        pricing_rule_id: rule.pricing_rule_id,
+        price_per_thousand: rule.price_per_thousand,
+        minimum_amount: rule.minimum_amount,
+        effective_from: rule.effective_from,
+        note: rule.note,
+        created_at: rule.created_at,
+        tiers,
+    })
+}
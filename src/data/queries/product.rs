@@ -0,0 +1,18 @@
+use sqlx::PgPool;
+
+use crate::{constants::errors, data::errors::DataError};
+
+/// The id of an active product, looked up by its code.
+pub async fn get_active_product_id(db: &PgPool, code: &str) -> Result<i32, DataError> {
+    sqlx::query_scalar!(
+        r#"
+        SELECT product_id
+        FROM products
+        WHERE code = $1 AND active
+        "#,
+        code
+    )
+    .fetch_optional(db)
+    .await?
+    .ok_or(DataError::NotFound(errors::PRODUCT_NOT_AVAILABLE))
+}
@@ -0,0 +1,122 @@
+use sqlx::PgPool;
+use time::OffsetDateTime;
+
+use crate::{
+    data::errors::DataError,
+    models::{
+        order::PaymentStatus,
+        reconciliation::{FindingKind, ReconciliationCandidate, ReconciliationFinding, ReconciliationRun},
+    },
+    money::Money,
+};
+
+/// Orders created in the window, with the total refunded so far.
+pub async fn get_candidates(
+    db: &PgPool,
+    window_start: OffsetDateTime,
+    window_end: OffsetDateTime,
+) -> Result<Vec<ReconciliationCandidate>, DataError> {
+    sqlx::query_as!(
+        ReconciliationCandidate,
+        r#"
+        SELECT
+            o.order_id,
+            o.order_number,
+            o.payment_status as "payment_status: PaymentStatus",
+            o.price_amount as "price_amount: Money",
+            o.payment_key,
+            COALESCE((SELECT SUM(r.amount) FROM refunds r WHERE r.order_id = o.order_id), 0)::bigint as "refunded_amount!: Money"
+        FROM orders o
+        WHERE o.created_at >= $1 AND o.created_at < $2
+            -- Paid from the credit wallet; the gateway never saw these
+            AND NOT EXISTS (
+                SELECT 1 FROM credit_transactions ct WHERE ct.order_id = o.order_id AND ct.kind = 'spend'
+            )
+            -- Included in a subscription; the subscription's charges are what the gateway saw
+            AND o.subscription_id IS NULL
+        ORDER BY o.created_at
+        "#,
+        window_start,
+        window_end
+    )
+    .fetch_all(db)
+    .await
+    .map_err(DataError::from)
+}
+
+pub async fn get_recent_runs(db: &PgPool, limit: i64) -> Result<Vec<ReconciliationRun>, DataError> {
+    sqlx::query_as!(
+        ReconciliationRun,
+        r#"
+        SELECT
+            run_id,
+            window_start,
+            window_end,
+            checked_count,
+            finding_count,
+            error_count,
+            started_at,
+            finished_at
+        FROM reconciliation_runs
+        ORDER BY started_at DESC
+        LIMIT $1
+        "#,
+        limit
+    )
+    .fetch_all(db)
+    .await
+    .map_err(DataError::from)
+}
+
+pub async fn get_open_findings(db: &PgPool) -> Result<Vec<ReconciliationFinding>, DataError> {
+    sqlx::query_as!(
+        ReconciliationFinding,
+        r#"
+        SELECT
+            f.finding_id,
+            f.order_id,
+            o.order_number,
+            f.kind as "kind: FindingKind",
+            f.local_status as "local_status: PaymentStatus",
+            f.local_amount as "local_amount: Money",
+            f.gateway_status,
+            f.gateway_amount as "gateway_amount: Money",
+            f.gateway_payment_key,
+            f.created_at
+        FROM reconciliation_findings f
+        JOIN orders o ON o.order_id = f.order_id
+        WHERE f.resolved_at IS NULL
+        ORDER BY f.created_at, f.finding_id
+        "#
+    )
+    .fetch_all(db)
+    .await
+    .map_err(DataError::from)
+}
+
+pub async fn get_open_finding(db: &PgPool, finding_id: i32) -> Result<Option<ReconciliationFinding>, DataError> {
+    let finding = sqlx::query_as!(
+        ReconciliationFinding,
+        r#"
+        SELECT
+            f.finding_id,
+            f.order_id,
+            o.order_number,
+            f.kind as "kind: FindingKind",
+            f.local_status as "local_status: PaymentStatus",
+            f.local_amount as "local_amount: Money",
+            f.gateway_status,
+            f.gateway_amount as "gateway_amount: Money",
+            f.gateway_payment_key,
+            f.created_at
+        FROM reconciliation_findings f
+        JOIN orders o ON o.order_id = f.order_id
+        WHERE f.finding_id = $1 AND f.resolved_at IS NULL
+        "#,
+        finding_id
+    )
+    .fetch_optional(db)
+    .await?;
+
+    Ok(finding)
+}
@@ -0,0 +1,36 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{data::errors::DataError, models::refund::Refund, money::Money};
+
+pub async fn get_refunds_for_order(db: &PgPool, order_id: Uuid) -> Result<Vec<Refund>, DataError> {
+    sqlx::query_as!(
+        Refund,
+        r#"
+        SELECT
+            r.amount as "amount: Money",
+            r.reason,
+            u.email::text as "refunded_by_email?",
+            r.created_at
+        FROM refunds r
+        LEFT JOIN users u ON u.user_id = r.refunded_by
+        WHERE r.order_id = $1
+        ORDER BY r.created_at, r.refund_id
+        "#,
+        order_id
+    )
+    .fetch_all(db)
+    .await
+    .map_err(DataError::from)
+}
+
+pub async fn get_refunded_total(db: &PgPool, order_id: Uuid) -> Result<Money, DataError> {
+    let total = sqlx::query_scalar!(
+        r#"SELECT COALESCE(SUM(amount), 0)::bigint as "total!: Money" FROM refunds WHERE order_id = $1"#,
+        order_id
+    )
+    .fetch_one(db)
+    .await?;
+
+    Ok(total)
+}
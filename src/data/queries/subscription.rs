@@ -0,0 +1,202 @@
+use sqlx::PgPool;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::{
+    constants::errors,
+    data::{errors::DataError, map_row_not_found},
+    models::{
+        order::PaymentStatus,
+        subscription::{Subscription, SubscriptionCharge, SubscriptionPlan, SubscriptionStatus},
+    },
+    money::Money,
+};
+
+pub async fn get_active_plans(db: &PgPool) -> Result<Vec<SubscriptionPlan>, DataError> {
+    sqlx::query_as!(
+        SubscriptionPlan,
+        r#"
+        SELECT plan_id, name, price_amount as "price_amount: Money", included_characters
+        FROM subscription_plans
+        WHERE is_active
+        ORDER BY price_amount
+        "#
+    )
+    .fetch_all(db)
+    .await
+    .map_err(DataError::from)
+}
+
+pub async fn get_active_plan(db: &PgPool, plan_id: i32) -> Result<SubscriptionPlan, DataError> {
+    sqlx::query_as!(
+        SubscriptionPlan,
+        r#"
+        SELECT plan_id, name, price_amount as "price_amount: Money", included_characters
+        FROM subscription_plans
+        WHERE plan_id = $1 AND is_active
+        "#,
+        plan_id
+    )
+    .fetch_one(db)
+    .await
+    .map_err(|e| map_row_not_found(e, errors::SUBSCRIPTION_PLAN_NOT_FOUND))
+}
+
+pub async fn get_subscription_for_user(
+    db: &PgPool,
+    subscription_id: Uuid,
+    user_id: i32,
+) -> Result<Subscription, DataError> {
+    sqlx::query_as!(
+        Subscription,
+        r#"
+        SELECT
+            s.subscription_id,
+            s.user_id,
+            u.email as user_email,
+            p.name as plan_name,
+            p.price_amount as "price_amount: Money",
+            p.included_characters,
+            s.status as "status: SubscriptionStatus",
+            s.customer_key,
+            s.billing_key,
+            s.card_label,
+            s.current_period_end,
+            s.characters_used,
+            s.cancel_at_period_end,
+            s.failed_attempts,
+            s.next_retry_at
+        FROM subscriptions s
+        JOIN subscription_plans p ON p.plan_id = s.plan_id
+        JOIN users u ON u.user_id = s.user_id
+        WHERE s.subscription_id = $1 AND s.user_id = $2
+        "#,
+        subscription_id,
+        user_id
+    )
+    .fetch_one(db)
+    .await
+    .map_err(|e| map_row_not_found(e, errors::SUBSCRIPTION_NOT_FOUND))
+}
+
+/// The user's active or past-due subscription, if any.
+pub async fn get_live_subscription(db: &PgPool, user_id: i32) -> Result<Option<Subscription>, DataError> {
+    let subscription = sqlx::query_as!(
+        Subscription,
+        r#"
+        SELECT
+            s.subscription_id,
+            s.user_id,
+            u.email as user_email,
+            p.name as plan_name,
+            p.price_amount as "price_amount: Money",
+            p.included_characters,
+            s.status as "status: SubscriptionStatus",
+            s.customer_key,
+            s.billing_key,
+            s.card_label,
+            s.current_period_end,
+            s.characters_used,
+            s.cancel_at_period_end,
+            s.failed_attempts,
+            s.next_retry_at
+        FROM subscriptions s
+        JOIN subscription_plans p ON p.plan_id = s.plan_id
+        JOIN users u ON u.user_id = s.user_id
+        WHERE s.user_id = $1 AND s.status IN ('active', 'past_due')
+        "#,
+        user_id
+    )
+    .fetch_optional(db)
+    .await?;
+
+    Ok(subscription)
+}
+
+/// Subscriptions to renew or end: active ones past their period end and
+/// past-due ones whose retry is due.
+pub async fn get_due_subscriptions(db: &PgPool, now: OffsetDateTime) -> Result<Vec<Subscription>, DataError> {
+    sqlx::query_as!(
+        Subscription,
+        r#"
+        SELECT
+            s.subscription_id,
+            s.user_id,
+            u.email as user_email,
+            p.name as plan_name,
+            p.price_amount as "price_amount: Money",
+            p.included_characters,
+            s.status as "status: SubscriptionStatus",
+            s.customer_key,
+            s.billing_key,
+            s.card_label,
+            s.current_period_end,
+            s.characters_used,
+            s.cancel_at_period_end,
+            s.failed_attempts,
+            s.next_retry_at
+        FROM subscriptions s
+        JOIN subscription_plans p ON p.plan_id = s.plan_id
+        JOIN users u ON u.user_id = s.user_id
+        WHERE (s.status = 'active' AND s.current_period_end <= $1)
+            OR (s.status = 'past_due' AND s.next_retry_at <= $1)
+        ORDER BY s.current_period_end
+        "#,
+        now
+    )
+    .fetch_all(db)
+    .await
+    .map_err(DataError::from)
+}
+
+/// The subscription's most recent charge attempts, newest first.
+pub async fn get_charges(db: &PgPool, subscription_id: Uuid, limit: i64) -> Result<Vec<SubscriptionCharge>, DataError> {
+    sqlx::query_as!(
+        SubscriptionCharge,
+        r#"
+        SELECT
+            charge_id,
+            subscription_id,
+            order_number,
+            amount as "amount: Money",
+            status as "status: PaymentStatus",
+            failure_message,
+            created_at
+        FROM subscription_charges
+        WHERE subscription_id = $1
+        ORDER BY created_at DESC, charge_id DESC
+        LIMIT $2
+        "#,
+        subscription_id,
+        limit
+    )
+    .fetch_all(db)
+    .await
+    .map_err(DataError::from)
+}
+
+pub async fn get_charge_by_order_number(
+    db: &PgPool,
+    order_number: &str,
+) -> Result<Option<SubscriptionCharge>, DataError> {
+    let charge = sqlx::query_as!(
+        SubscriptionCharge,
+        r#"
+        SELECT
+            charge_id,
+            subscription_id,
+            order_number,
+            amount as "amount: Money",
+            status as "status: PaymentStatus",
+            failure_message,
+            created_at
+        FROM subscription_charges
+        WHERE order_number = $1
+        "#,
+        order_number
+    )
+    .fetch_optional(db)
+    .await?;
+
+    Ok(charge)
+}
@@ -33,3 +33,14 @@ pub async fn get_user_email(db: &PgPool, user_id: i32) -> Result<Option<String>,
 
     Ok(result.map(|row| row.email))
 }
+
+pub async fn get_payment_emails_enabled(db: &PgPool, user_id: i32) -> Result<bool, DataError> {
+    let result = sqlx::query!(
+        "SELECT payment_emails_enabled FROM users WHERE user_id = $1",
+        user_id
+    )
+    .fetch_optional(db)
+    .await?;
+
+    Ok(result.is_some_and(|row| row.payment_emails_enabled))
+}
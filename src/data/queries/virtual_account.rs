@@ -0,0 +1,43 @@
+use sqlx::PgPool;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::{
+    constants::errors,
+    data::{errors::DataError, map_row_not_found},
+    models::virtual_account::VirtualAccount,
+};
+
+pub async fn get_virtual_account(db: &PgPool, order_id: Uuid) -> Result<VirtualAccount, DataError> {
+    sqlx::query_as!(
+        VirtualAccount,
+        r#"
+        SELECT v.order_id, o.order_number, v.bank_code, v.account_number, v.due_at
+        FROM virtual_accounts v
+        JOIN orders o ON o.order_id = v.order_id
+        WHERE v.order_id = $1
+        "#,
+        order_id
+    )
+    .fetch_one(db)
+    .await
+    .map_err(|e| map_row_not_found(e, errors::VIRTUAL_ACCOUNT_NOT_FOUND))
+}
+
+/// Accounts still waiting for a deposit that were due before `due_before`.
+pub async fn get_overdue(db: &PgPool, due_before: OffsetDateTime) -> Result<Vec<VirtualAccount>, DataError> {
+    sqlx::query_as!(
+        VirtualAccount,
+        r#"
+        SELECT v.order_id, o.order_number, v.bank_code, v.account_number, v.due_at
+        FROM virtual_accounts v
+        JOIN orders o ON o.order_id = v.order_id
+        WHERE o.payment_status = 'awaiting_deposit' AND v.due_at < $1
+        ORDER BY v.due_at
+        "#,
+        due_before
+    )
+    .fetch_all(db)
+    .await
+    .map_err(DataError::from)
+}
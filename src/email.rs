@@ -1,10 +1,27 @@
+use std::path::PathBuf;
+
 use lettre::{
-    message::{header::ContentType, Mailbox},
+    message::{
+        header::{ContentType, Header, HeaderName, HeaderValue},
+        Attachment, Mailbox, MultiPart, SinglePart,
+    },
     transport::smtp::authentication::Credentials,
     Message, SmtpTransport, Transport,
 };
+use sqlx::PgPool;
+use time::OffsetDateTime;
+
+use crate::{
+    data::{errors::DataError, queries},
+    email_capture::{self, CapturedEmail, MemoryMailbox},
+    models::{order::Order, subscription::Subscription},
+    paths, receipt,
+    signing::Signer,
+    views::emails::{self, EmailContent},
+};
 
-use crate::{email_templates, paths};
+/// Signing purpose for unsubscribe tokens.
+pub const UNSUBSCRIBE_TOKEN_PURPOSE: &str = "unsubscribe";
 
 #[derive(Debug, thiserror::Error)]
 pub enum EmailError {
@@ -16,6 +33,12 @@ pub enum EmailError {
     InvalidAddress(#[from] lettre::address::AddressError),
     #[error("Configuration error: {0}")]
     Config(String),
+    #[error("Email capture error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Suppression lookup error: {0}")]
+    Data(#[from] DataError),
+    #[error("Recipient {0} is on the suppression list")]
+    Suppressed(String),
 }
 
 #[derive(Clone)]
@@ -24,11 +47,17 @@ pub struct EmailConfig {
     from_address: String,
     from_name: String,
     base_url: String,
+    site_name: String,
+    signer: Signer,
 }
 
 #[derive(Clone)]
 pub enum EmailMode {
     Console,
+    /// Writes each message as an `.eml` file into `dir` (development)
+    File { dir: PathBuf },
+    /// Keeps messages in memory (tests)
+    Memory(MemoryMailbox),
     Smtp {
         host: String,
         port: u16,
@@ -38,12 +67,19 @@ pub enum EmailMode {
 }
 
 impl EmailConfig {
-    pub fn from_env() -> Result<Self, EmailError> {
+    pub fn from_env(site_name: &str, signer: Signer) -> Result<Self, EmailError> {
         let mode_str = dotenvy::var("EMAIL_MODE")
             .map_err(|_| EmailError::Config("EMAIL_MODE must be set".to_string()))?;
 
         let mode = match mode_str.as_str() {
             "console" => EmailMode::Console,
+            "file" => {
+                let dir = dotenvy::var("EMAIL_FILE_DIR")
+                    .map_err(|_| EmailError::Config("EMAIL_FILE_DIR must be set when EMAIL_MODE=file".to_string()))?;
+
+                EmailMode::File { dir: dir.into() }
+            }
+            "memory" => EmailMode::Memory(MemoryMailbox::default()),
             "smtp" => {
                 let host = dotenvy::var("SMTP_HOST")
                     .map_err(|_| EmailError::Config("SMTP_HOST must be set when EMAIL_MODE=smtp".to_string()))?;
@@ -63,7 +99,7 @@ impl EmailConfig {
                     password,
                 }
             }
-            _ => return Err(EmailError::Config(format!("EMAIL_MODE must be either 'console' or 'smtp', got '{}'", mode_str))),
+            _ => return Err(EmailError::Config(format!("EMAIL_MODE must be one of 'console', 'file', 'memory' or 'smtp', got '{}'", mode_str))),
         };
 
         let from_address = dotenvy::var("EMAIL_FROM_ADDRESS")
@@ -78,13 +114,112 @@ impl EmailConfig {
             from_address,
             from_name,
             base_url,
+            site_name: site_name.to_string(),
+            signer,
         })
     }
 
+    #[cfg(test)]
+    pub fn in_memory(mailbox: MemoryMailbox) -> Self {
+        Self {
+            mode: EmailMode::Memory(mailbox),
+            from_address: "test@example.com".to_string(),
+            from_name: "Test".to_string(),
+            base_url: "http://localhost".to_string(),
+            site_name: "Test".to_string(),
+            signer: Signer::new("test-secret-key-that-is-long-enough"),
+        }
+    }
+
     pub fn base_url(&self) -> &str {
         &self.base_url
     }
 
+    /// Whether outgoing email is captured locally instead of being delivered.
+    pub fn captures_messages(&self) -> bool {
+        matches!(self.mode, EmailMode::File { .. } | EmailMode::Memory(_))
+    }
+
+    /// Returns captured messages, newest first. Empty unless capturing.
+    pub async fn captured_messages(&self) -> Result<Vec<CapturedEmail>, EmailError> {
+        match &self.mode {
+            EmailMode::File { dir } => Ok(email_capture::read_from_dir(dir).await?),
+            EmailMode::Memory(mailbox) => Ok(mailbox.messages()),
+            EmailMode::Console | EmailMode::Smtp { .. } => Ok(Vec::new()),
+        }
+    }
+
+    async fn capture(&self, email: &Message) -> Result<(), EmailError> {
+        let id = email_capture::generate_message_id();
+        let raw = email.formatted();
+
+        match &self.mode {
+            EmailMode::File { dir } => email_capture::write_to_dir(dir, &id, &raw).await?,
+            EmailMode::Memory(mailbox) => mailbox.push(id, raw),
+            EmailMode::Console | EmailMode::Smtp { .. } => {
+                unreachable!("Only file and memory modes capture messages")
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Page where the recipient confirms unsubscribing (linked from the email body).
+    fn unsubscribe_page_url(&self, email: &str) -> String {
+        format!(
+            "{}{}?token={}",
+            self.base_url,
+            paths::pages::UNSUBSCRIBE,
+            self.signer.sign(UNSUBSCRIBE_TOKEN_PURPOSE, &email.to_lowercase())
+        )
+    }
+
+    /// RFC 8058 one-click endpoint (used in the `List-Unsubscribe` header).
+    fn unsubscribe_action_url(&self, email: &str) -> String {
+        format!(
+            "{}{}?token={}",
+            self.base_url,
+            paths::actions::UNSUBSCRIBE,
+            self.signer.sign(UNSUBSCRIBE_TOKEN_PURPOSE, &email.to_lowercase())
+        )
+    }
+
+    fn build_message(&self, to: Mailbox, content: EmailContent) -> Result<Message, EmailError> {
+        let from_mailbox: Mailbox = format!("{} <{}>", self.from_name, self.from_address).parse()?;
+
+        Ok(Message::builder()
+            .from(from_mailbox)
+            .to(to)
+            .subject(content.subject)
+            .multipart(MultiPart::alternative_plain_html(content.text, content.html))?)
+    }
+
+    /// Builds an optional notification, adding one-click unsubscribe headers.
+    ///
+    /// With an attachment the message becomes multipart/mixed around the usual
+    /// plain/HTML alternative.
+    fn build_notification(
+        &self,
+        to_email: &str,
+        content: EmailContent,
+        attachment: Option<SinglePart>,
+    ) -> Result<Message, EmailError> {
+        let from_mailbox: Mailbox = format!("{} <{}>", self.from_name, self.from_address).parse()?;
+        let body = MultiPart::alternative_plain_html(content.text, content.html);
+        let body = match attachment {
+            Some(attachment) => MultiPart::mixed().multipart(body).singlepart(attachment),
+            None => body,
+        };
+
+        Ok(Message::builder()
+            .from(from_mailbox)
+            .to(to_email.parse()?)
+            .subject(content.subject)
+            .header(ListUnsubscribe(format!("<{}>", self.unsubscribe_action_url(to_email))))
+            .header(ListUnsubscribePost)
+            .multipart(body)?)
+    }
+
     fn create_smtp_transport(&self) -> Result<SmtpTransport, EmailError> {
         match &self.mode {
             EmailMode::Smtp { host, port, username, password } => {
@@ -94,11 +229,48 @@ impl EmailConfig {
                     .credentials(creds)
                     .build())
             }
-            EmailMode::Console => unreachable!("Console mode doesn't need SMTP transport"),
+            EmailMode::Console | EmailMode::File { .. } | EmailMode::Memory(_) => {
+                unreachable!("Only SMTP mode needs an SMTP transport")
+            }
         }
     }
 }
 
+#[derive(Clone)]
+struct ListUnsubscribe(String);
+
+impl Header for ListUnsubscribe {
+    fn name() -> HeaderName {
+        HeaderName::new_from_ascii_str("List-Unsubscribe")
+    }
+
+    fn parse(s: &str) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(Self(s.to_string()))
+    }
+
+    fn display(&self) -> HeaderValue {
+        HeaderValue::new(Self::name(), self.0.clone())
+    }
+}
+
+#[derive(Clone)]
+struct ListUnsubscribePost;
+
+impl Header for ListUnsubscribePost {
+    fn name() -> HeaderName {
+        HeaderName::new_from_ascii_str("List-Unsubscribe-Post")
+    }
+
+    fn parse(_: &str) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(Self)
+    }
+
+    fn display(&self) -> HeaderValue {
+        HeaderValue::new(Self::name(), "List-Unsubscribe=One-Click".to_string())
+    }
+}
+
+/// Sign-in mail is security-critical and deliberately bypasses the suppression list.
 pub async fn send_magic_link(
     config: &EmailConfig,
     to_email: &str,
@@ -106,15 +278,10 @@ pub async fn send_magic_link(
 ) -> Result<(), EmailError> {
     let magic_link = format!("{}{}?token={}", config.base_url, paths::actions::VERIFY_MAGIC_LINK, token);
 
-    let from_mailbox: Mailbox = format!("{} <{}>", config.from_name, config.from_address).parse()?;
-    let to_mailbox: Mailbox = to_email.parse()?;
-
-    let email = Message::builder()
-        .from(from_mailbox)
-        .to(to_mailbox)
-        .subject("Sign in to your account")
-        .header(ContentType::TEXT_HTML)
-        .body(email_templates::magic_link_signin(&magic_link))?;
+    let email = config.build_message(
+        to_email.parse()?,
+        emails::magic_link_signin(&config.site_name, &magic_link),
+    )?;
 
     match &config.mode {
         EmailMode::Console => {
@@ -124,6 +291,11 @@ pub async fn send_magic_link(
             tracing::info!("======================================\n");
             Ok(())
         }
+        EmailMode::File { .. } | EmailMode::Memory(_) => {
+            config.capture(&email).await?;
+            tracing::info!("Magic link email captured for {}", to_email);
+            Ok(())
+        }
         EmailMode::Smtp { .. } => {
             let mailer = config.create_smtp_transport()?;
             mailer.send(&email)?;
@@ -135,18 +307,25 @@ pub async fn send_magic_link(
 
 pub async fn send_contact_inquiry(
     config: &EmailConfig,
+    db: &PgPool,
+    inquiry_id: i32,
     from_email: &str,
     message: &str,
 ) -> Result<(), EmailError> {
-    let from_mailbox: Mailbox = format!("{} <{}>", config.from_name, config.from_address).parse()?;
-    let to_mailbox: Mailbox = config.from_address.parse()?;
+    if queries::email_suppression::is_suppressed(db, &config.from_address).await? {
+        tracing::info!("Skipping contact inquiry email to suppressed address {}", config.from_address);
+        return Ok(());
+    }
 
-    let email = Message::builder()
-        .from(from_mailbox)
-        .to(to_mailbox)
-        .subject("New Contact Inquiry")
-        .header(ContentType::TEXT_HTML)
-        .body(email_templates::contact_inquiry(from_email, message))?;
+    let email = config.build_message(
+        config.from_address.parse()?,
+        emails::contact_inquiry(
+            &config.site_name,
+            from_email,
+            message,
+            &format!("{}{}", config.base_url, paths::helpers::inquiry_detail_path(inquiry_id)),
+        ),
+    )?;
 
     match &config.mode {
         EmailMode::Console => {
@@ -156,6 +335,11 @@ pub async fn send_contact_inquiry(
             tracing::info!("===========================================\n");
             Ok(())
         }
+        EmailMode::File { .. } | EmailMode::Memory(_) => {
+            config.capture(&email).await?;
+            tracing::info!("Contact inquiry email captured from {}", from_email);
+            Ok(())
+        }
         EmailMode::Smtp { .. } => {
             let mailer = config.create_smtp_transport()?;
             mailer.send(&email)?;
@@ -164,3 +348,254 @@ pub async fn send_contact_inquiry(
         }
     }
 }
+
+/// Sends a staff reply to the author of a contact inquiry.
+///
+/// Unlike notifications, a reply to a suppressed address fails with
+/// [`EmailError::Suppressed`] so it isn't recorded as sent.
+pub async fn send_inquiry_reply(
+    config: &EmailConfig,
+    db: &PgPool,
+    to_email: &str,
+    reply: &str,
+    original_message: &str,
+) -> Result<(), EmailError> {
+    if queries::email_suppression::is_suppressed(db, to_email).await? {
+        return Err(EmailError::Suppressed(to_email.to_string()));
+    }
+
+    let email = config.build_message(
+        to_email.parse()?,
+        emails::inquiry_reply(&config.site_name, reply, original_message),
+    )?;
+
+    match &config.mode {
+        EmailMode::Console => {
+            tracing::info!("\n\n========== INQUIRY REPLY EMAIL ==========");
+            tracing::info!("To: {}", to_email);
+            tracing::info!("Reply: {}", reply);
+            tracing::info!("=========================================\n");
+            Ok(())
+        }
+        EmailMode::File { .. } | EmailMode::Memory(_) => {
+            config.capture(&email).await?;
+            tracing::info!("Inquiry reply email captured for {}", to_email);
+            Ok(())
+        }
+        EmailMode::Smtp { .. } => {
+            let mailer = config.create_smtp_transport()?;
+            mailer.send(&email)?;
+            tracing::info!("Inquiry reply email sent to {}", to_email);
+            Ok(())
+        }
+    }
+}
+
+pub async fn send_payment_receipt(
+    config: &EmailConfig,
+    db: &PgPool,
+    order: &Order,
+) -> Result<(), EmailError> {
+    if queries::email_suppression::is_suppressed(db, &order.user_email).await? {
+        tracing::info!("Skipping payment receipt email to suppressed address {}", order.user_email);
+        return Ok(());
+    }
+
+    let result_url = format!("{}{}", config.base_url, paths::helpers::result_path(&order.order_id));
+    let unsubscribe_url = config.unsubscribe_page_url(&order.user_email);
+
+    let email = config.build_notification(
+        &order.user_email,
+        emails::payment_receipt(&config.site_name, order, &result_url, &unsubscribe_url),
+        receipt_attachment(&config.site_name, order),
+    )?;
+
+    match &config.mode {
+        EmailMode::Console => {
+            tracing::info!("\n\n========== PAYMENT RECEIPT EMAIL ==========");
+            tracing::info!("To: {}", order.user_email);
+            tracing::info!("Order: {}", order.order_number);
+            tracing::info!("Result: {}", result_url);
+            tracing::info!("===========================================\n");
+            Ok(())
+        }
+        EmailMode::File { .. } | EmailMode::Memory(_) => {
+            config.capture(&email).await?;
+            tracing::info!("Payment receipt email captured for {}", order.user_email);
+            Ok(())
+        }
+        EmailMode::Smtp { .. } => {
+            let mailer = config.create_smtp_transport()?;
+            mailer.send(&email)?;
+            tracing::info!("Payment receipt email sent to {}", order.user_email);
+            Ok(())
+        }
+    }
+}
+
+/// The PDF receipt, or `None` if it can't be rendered; the email still goes out
+/// and the receipt stays downloadable from the dashboard. It is sent as the
+/// payment succeeds, so there are no refunds to list yet.
+fn receipt_attachment(site_name: &str, order: &Order) -> Option<SinglePart> {
+    match receipt::render(site_name, order, &[]) {
+        Ok(pdf) => Some(
+            Attachment::new(receipt::file_name(order))
+                .body(pdf, ContentType::parse("application/pdf").expect("PDF content type should be valid")),
+        ),
+        Err(e) => {
+            tracing::error!("Failed to render receipt for order {}: {}", order.order_number, e);
+            None
+        }
+    }
+}
+
+pub async fn send_payment_failed(
+    config: &EmailConfig,
+    db: &PgPool,
+    order: &Order,
+) -> Result<(), EmailError> {
+    if queries::email_suppression::is_suppressed(db, &order.user_email).await? {
+        tracing::info!("Skipping payment failure email to suppressed address {}", order.user_email);
+        return Ok(());
+    }
+
+    let retry_url = format!("{}{}", config.base_url, paths::helpers::quote_path(&order.order_id));
+    let unsubscribe_url = config.unsubscribe_page_url(&order.user_email);
+
+    let email = config.build_notification(
+        &order.user_email,
+        emails::payment_failed(&config.site_name, order, &retry_url, &unsubscribe_url),
+        None,
+    )?;
+
+    match &config.mode {
+        EmailMode::Console => {
+            tracing::info!("\n\n========== PAYMENT FAILED EMAIL ==========");
+            tracing::info!("To: {}", order.user_email);
+            tracing::info!("Order: {}", order.order_number);
+            tracing::info!("Retry: {}", retry_url);
+            tracing::info!("==========================================\n");
+            Ok(())
+        }
+        EmailMode::File { .. } | EmailMode::Memory(_) => {
+            config.capture(&email).await?;
+            tracing::info!("Payment failure email captured for {}", order.user_email);
+            Ok(())
+        }
+        EmailMode::Smtp { .. } => {
+            let mailer = config.create_smtp_transport()?;
+            mailer.send(&email)?;
+            tracing::info!("Payment failure email sent to {}", order.user_email);
+            Ok(())
+        }
+    }
+}
+
+/// Dunning notice after a failed renewal, with the date of the next attempt.
+pub async fn send_subscription_payment_failed(
+    config: &EmailConfig,
+    db: &PgPool,
+    subscription: &Subscription,
+    next_retry_at: OffsetDateTime,
+) -> Result<(), EmailError> {
+    let to_email = &subscription.user_email;
+    if queries::email_suppression::is_suppressed(db, to_email).await? {
+        tracing::info!("Skipping subscription payment failure email to suppressed address {}", to_email);
+        return Ok(());
+    }
+
+    let manage_url = format!("{}{}", config.base_url, paths::pages::SUBSCRIPTION);
+    let unsubscribe_url = config.unsubscribe_page_url(to_email);
+
+    let email = config.build_notification(
+        to_email,
+        emails::subscription_payment_failed(&config.site_name, subscription, next_retry_at, &manage_url, &unsubscribe_url),
+        None,
+    )?;
+
+    match &config.mode {
+        EmailMode::Console => {
+            tracing::info!("\n\n========== SUBSCRIPTION PAYMENT FAILED EMAIL ==========");
+            tracing::info!("To: {}", to_email);
+            tracing::info!("Plan: {}", subscription.plan_name);
+            tracing::info!("Next retry: {}", next_retry_at);
+            tracing::info!("======================================================\n");
+            Ok(())
+        }
+        EmailMode::File { .. } | EmailMode::Memory(_) => {
+            config.capture(&email).await?;
+            tracing::info!("Subscription payment failure email captured for {}", to_email);
+            Ok(())
+        }
+        EmailMode::Smtp { .. } => {
+            let mailer = config.create_smtp_transport()?;
+            mailer.send(&email)?;
+            tracing::info!("Subscription payment failure email sent to {}", to_email);
+            Ok(())
+        }
+    }
+}
+
+pub async fn send_subscription_ended(
+    config: &EmailConfig,
+    db: &PgPool,
+    subscription: &Subscription,
+) -> Result<(), EmailError> {
+    let to_email = &subscription.user_email;
+    if queries::email_suppression::is_suppressed(db, to_email).await? {
+        tracing::info!("Skipping subscription ended email to suppressed address {}", to_email);
+        return Ok(());
+    }
+
+    let subscribe_url = format!("{}{}", config.base_url, paths::pages::SUBSCRIPTION);
+    let unsubscribe_url = config.unsubscribe_page_url(to_email);
+
+    let email = config.build_notification(
+        to_email,
+        emails::subscription_ended(&config.site_name, subscription, &subscribe_url, &unsubscribe_url),
+        None,
+    )?;
+
+    match &config.mode {
+        EmailMode::Console => {
+            tracing::info!("\n\n========== SUBSCRIPTION ENDED EMAIL ==========");
+            tracing::info!("To: {}", to_email);
+            tracing::info!("Plan: {}", subscription.plan_name);
+            tracing::info!("==============================================\n");
+            Ok(())
+        }
+        EmailMode::File { .. } | EmailMode::Memory(_) => {
+            config.capture(&email).await?;
+            tracing::info!("Subscription ended email captured for {}", to_email);
+            Ok(())
+        }
+        EmailMode::Smtp { .. } => {
+            let mailer = config.create_smtp_transport()?;
+            mailer.send(&email)?;
+            tracing::info!("Subscription ended email sent to {}", to_email);
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_magic_link_is_captured_in_memory() {
+        let mailbox = MemoryMailbox::default();
+        let config = EmailConfig::in_memory(mailbox.clone());
+
+        send_magic_link(&config, "user@example.com", "abc123").await.unwrap();
+
+        let email = mailbox.last().expect("Magic link email should be captured");
+        assert_eq!(email.to, "user@example.com");
+
+        let link_prefix = format!("{}{}", config.base_url(), paths::actions::VERIFY_MAGIC_LINK);
+        assert_eq!(
+            email.find_link(&link_prefix),
+            Some(format!("{}?token=abc123", link_prefix).as_str())
+        );
+    }
+}
@@ -0,0 +1,142 @@
+//! Local capture of outgoing email for development and tests.
+//!
+//! Captured messages are kept in their raw RFC 5322 form, either as `.eml` files
+//! in a directory (`EMAIL_MODE=file`) or in a shared in-memory mailbox
+//! (`EMAIL_MODE=memory`), and parsed back when read.
+
+use std::{
+    io,
+    path::Path,
+    sync::{Arc, Mutex},
+};
+
+use mail_parser::{MessageParser, MimeHeaders};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+const EML_EXTENSION: &str = "eml";
+
+/// In-memory mailbox shared by every clone of the email configuration.
+#[derive(Clone, Default)]
+pub struct MemoryMailbox {
+    messages: Arc<Mutex<Vec<RawMessage>>>,
+}
+
+struct RawMessage {
+    id: String,
+    raw: Vec<u8>,
+}
+
+impl MemoryMailbox {
+    pub fn push(&self, id: String, raw: Vec<u8>) {
+        self.messages
+            .lock()
+            .expect("Mailbox lock poisoned")
+            .push(RawMessage { id, raw });
+    }
+
+    /// Returns all captured messages, newest first.
+    pub fn messages(&self) -> Vec<CapturedEmail> {
+        let messages = self.messages.lock().expect("Mailbox lock poisoned");
+        messages
+            .iter()
+            .rev()
+            .filter_map(|message| CapturedEmail::parse(message.id.clone(), &message.raw))
+            .collect()
+    }
+
+    #[cfg(test)]
+    pub fn last(&self) -> Option<CapturedEmail> {
+        self.messages().into_iter().next()
+    }
+}
+
+/// A captured message with the parts needed to inspect it.
+pub struct CapturedEmail {
+    pub id: String,
+    pub to: String,
+    pub subject: String,
+    pub date: Option<String>,
+    pub html_body: Option<String>,
+    pub text_body: Option<String>,
+    /// File names of attached parts
+    pub attachments: Vec<String>,
+    pub raw: String,
+}
+
+impl CapturedEmail {
+    pub fn parse(id: String, raw: &[u8]) -> Option<Self> {
+        let message = MessageParser::default().parse(raw)?;
+
+        Some(Self {
+            to: message
+                .to()
+                .and_then(|to| to.first())
+                .and_then(|addr| addr.address())
+                .unwrap_or_default()
+                .to_string(),
+            subject: message.subject().unwrap_or_default().to_string(),
+            date: message.date().map(|date| date.to_rfc3339()),
+            html_body: message.body_html(0).map(|body| body.into_owned()),
+            text_body: message.body_text(0).map(|body| body.into_owned()),
+            attachments: message
+                .attachments()
+                .map(|part| part.attachment_name().unwrap_or("attachment").to_string())
+                .collect(),
+            raw: String::from_utf8_lossy(raw).into_owned(),
+            id,
+        })
+    }
+
+    /// Finds the first link in the message body starting with `prefix`.
+    #[cfg(test)]
+    pub fn find_link(&self, prefix: &str) -> Option<&str> {
+        let body = self.text_body.as_deref().or(self.html_body.as_deref())?;
+        let rest = &body[body.find(prefix)?..];
+        let end = rest
+            .find(|c: char| c.is_whitespace() || matches!(c, '"' | '<' | ')'))
+            .unwrap_or(rest.len());
+        Some(&rest[..end])
+    }
+}
+
+/// Generates a message ID that sorts chronologically.
+pub fn generate_message_id() -> String {
+    format!(
+        "{}-{}",
+        OffsetDateTime::now_utc().unix_timestamp_nanos(),
+        Uuid::new_v4().simple()
+    )
+}
+
+pub async fn write_to_dir(dir: &Path, id: &str, raw: &[u8]) -> io::Result<()> {
+    tokio::fs::create_dir_all(dir).await?;
+    tokio::fs::write(dir.join(format!("{}.{}", id, EML_EXTENSION)), raw).await
+}
+
+/// Reads all `.eml` files in `dir`, newest first.
+pub async fn read_from_dir(dir: &Path) -> io::Result<Vec<CapturedEmail>> {
+    let mut entries = match tokio::fs::read_dir(dir).await {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+
+    let mut messages = Vec::new();
+    while let Some(entry) = entries.next_entry().await? {
+        let path = entry.path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some(EML_EXTENSION) {
+            continue;
+        }
+        let Some(id) = path.file_stem().and_then(|stem| stem.to_str()) else {
+            continue;
+        };
+        let raw = tokio::fs::read(&path).await?;
+        if let Some(message) = CapturedEmail::parse(id.to_string(), &raw) {
+            messages.push(message);
+        }
+    }
+
+    messages.sort_by(|a, b| b.id.cmp(&a.id));
+    Ok(messages)
+}
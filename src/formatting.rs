@@ -1,15 +1,32 @@
-use time::{OffsetDateTime, format_description::well_known::Rfc3339};
+use time::{Date, Month, OffsetDateTime, format_description::well_known::Rfc3339};
 
-pub fn format_price(amount: i32) -> String {
-    amount
+/// A count with thousands separators, e.g. characters. Amounts of money format
+/// themselves through [`Money`](crate::money::Money).
+pub fn format_number(value: impl Into<i64>) -> String {
+    let value = value.into();
+    let grouped = value
+        .unsigned_abs()
         .to_string()
         .as_bytes()
         .rchunks(3)
         .rev()
         .map(std::str::from_utf8)
         .collect::<Result<Vec<&str>, _>>()
-        .expect("Price formatting should always produce valid UTF-8")
-        .join(",")
+        .expect("Number formatting should always produce valid UTF-8")
+        .join(",");
+    if value < 0 { format!("-{}", grouped) } else { grouped }
+}
+
+/// A byte count in B, KB or MB.
+pub fn format_file_size(bytes: i32) -> String {
+    let bytes = f64::from(bytes);
+    if bytes < 1024.0 {
+        format!("{} B", bytes)
+    } else if bytes < 1024.0 * 1024.0 {
+        format!("{:.2} KB", bytes / 1024.0)
+    } else {
+        format!("{:.2} MB", bytes / (1024.0 * 1024.0))
+    }
 }
 
 pub fn format_datetime(dt: OffsetDateTime) -> String {
@@ -23,3 +40,13 @@ pub fn format_datetime(dt: OffsetDateTime) -> String {
         date_part.to_string()
     }
 }
+
+/// Parses a `YYYY-MM-DD` form value, as sent by `<input type="date">`.
+pub fn parse_date(value: &str) -> Option<Date> {
+    let mut parts = value.trim().splitn(3, '-').map(str::parse::<i32>);
+    let (Some(Ok(year)), Some(Ok(month)), Some(Ok(day))) = (parts.next(), parts.next(), parts.next()) else {
+        return None;
+    };
+    let month = Month::try_from(u8::try_from(month).ok()?).ok()?;
+    Date::from_calendar_date(year, month, u8::try_from(day).ok()?).ok()
+}
@@ -0,0 +1,17 @@
+use axum::{extract::{Path, State}, response::Response};
+use sqlx::PgPool;
+
+use crate::{
+    data::commands::contact_inquiry,
+    handlers::{errors::HandlerError, htmx},
+};
+
+/// Deletes a quarantined inquiry from the quarantine list.
+pub async fn delete_inquiry(
+    State(db): State<PgPool>,
+    Path(inquiry_id): Path<i32>,
+) -> Result<Response, HandlerError> {
+    contact_inquiry::delete_quarantined_inquiry(&db, inquiry_id).await?;
+
+    Ok(htmx::empty_ok())
+}
@@ -0,0 +1,16 @@
+use axum::{extract::{Path, State}, response::Response};
+use sqlx::PgPool;
+
+use crate::{
+    data::commands::email_suppression,
+    handlers::{errors::HandlerError, htmx},
+};
+
+pub async fn delete_suppression(
+    State(db): State<PgPool>,
+    Path(suppression_id): Path<i32>,
+) -> Result<Response, HandlerError> {
+    email_suppression::remove_suppression(&db, suppression_id).await?;
+
+    Ok(htmx::empty_ok())
+}
@@ -1,3 +1,7 @@
+mod delete_inquiry;
+mod delete_suppression;
 mod revoke_role;
 
+pub use delete_inquiry::delete_inquiry;
+pub use delete_suppression::delete_suppression;
 pub use revoke_role::delete_revoke_role;
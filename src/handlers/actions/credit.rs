@@ -0,0 +1,153 @@
+use axum::{Extension, Form, extract::{Query, State}};
+use sqlx::PgPool;
+use tower_sessions::Session;
+
+use crate::{
+    auth::CurrentUser,
+    config::AppConfig,
+    constants::{errors, logging, messages},
+    data::{amount_in_range, commands::{self, credit::CreditPayment, discount::Redemption}, errors::DataError, queries},
+    flash::FlashMessage,
+    handlers::errors::HandlerResult,
+    models::{credit::PayWithCreditsForm, order::PaymentStatus},
+    notifications,
+    paths,
+    payment::{ConfirmRequest, PaymentError, SharedGateway},
+};
+
+use super::payment::PaymentVerifyQuery;
+
+/// Pays a quote from the user's credit balance instead of the gateway.
+pub async fn post_actions_pay_with_credits(
+    State(config): State<AppConfig>,
+    State(db): State<PgPool>,
+    Extension(current_user): Extension<CurrentUser>,
+    session: Session,
+    Form(form): Form<PayWithCreditsForm>,
+) -> HandlerResult {
+    let user_id = current_user.require_authenticated();
+
+    let order = queries::order::get_order_for_user(&db, form.order_id, user_id).await?;
+    let quote_path = paths::helpers::quote_path(&order.order_id);
+
+    if order.is_quote_expired(config.quotes().validity()) {
+        return Ok(FlashMessage::error(messages::QUOTE_EXPIRED)
+            .set_and_redirect(&session, paths::pages::TEXT_ANALYZER)
+            .await?);
+    }
+
+    if !order.payment_status.is_payable() {
+        return Ok(FlashMessage::error(messages::ORDER_ALREADY_PROCESSED)
+            .set_and_redirect(&session, &quote_path)
+            .await?);
+    }
+
+    if let Redemption::Unavailable(_) = commands::discount::reserve_redemption(&db, &order).await? {
+        let tax = amount_in_range(config.tax().apply(order.list_price_amount))?;
+        commands::discount::remove_from_order(&db, order.order_id, user_id, tax).await?;
+
+        return Ok(FlashMessage::error(messages::DISCOUNT_WITHDRAWN)
+            .set_and_redirect(&session, &quote_path)
+            .await?);
+    }
+
+    let payment = match commands::credit::pay_order_with_credits(&db, order.order_id, user_id).await {
+        Ok(payment) => payment,
+        // Paid or cancelled elsewhere since the check above; a gateway payment records its redemption again
+        Err(DataError::InvalidTransition { .. }) => {
+            commands::discount::release_redemption(&db, order.order_id).await?;
+
+            return Ok(FlashMessage::error(messages::ORDER_ALREADY_PROCESSED)
+                .set_and_redirect(&session, &quote_path)
+                .await?);
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    match payment {
+        CreditPayment::Paid(order) => {
+            notifications::notify_payment_succeeded(&db, config.email(), &order).await;
+
+            Ok(FlashMessage::success(messages::PAYMENT_SUCCESS)
+                .set_and_redirect(&session, &paths::helpers::result_path(&order.order_id))
+                .await?)
+        }
+        CreditPayment::InsufficientBalance => {
+            commands::discount::release_redemption(&db, order.order_id).await?;
+
+            Ok(FlashMessage::error(errors::INSUFFICIENT_CREDITS)
+                .set_and_redirect(&session, &quote_path)
+                .await?)
+        }
+        // The redemption is left to the gateway payment, which releases it if it fails
+        CreditPayment::PaymentInProgress => Ok(FlashMessage::error(messages::PAYMENT_IN_PROGRESS)
+            .set_and_redirect(&session, &quote_path)
+            .await?),
+    }
+}
+
+/// Confirms a top-up payment the gateway redirected back with.
+///
+/// A refreshed redirect finds the top-up already paid and reports success again.
+pub async fn get_actions_credit_top_up_verify(
+    State(db): State<PgPool>,
+    State(gateway): State<SharedGateway>,
+    Extension(current_user): Extension<CurrentUser>,
+    session: Session,
+    Query(query): Query<PaymentVerifyQuery>,
+) -> HandlerResult {
+    let user_id = current_user.require_authenticated();
+
+    let topup = queries::credit::get_topup_by_order_number(&db, &query.order_id)
+        .await?
+        .filter(|topup| topup.user_id == user_id)
+        .ok_or(DataError::NotFound(errors::TOP_UP_NOT_FOUND))?;
+
+    if topup.status == PaymentStatus::Paid {
+        return Ok(FlashMessage::success(messages::CREDITS_TOPPED_UP)
+            .set_and_redirect(&session, paths::pages::DASHBOARD)
+            .await?);
+    }
+
+    if query.amount != topup.amount {
+        tracing::warn!(
+            target: logging::SECURITY_TARGET,
+            user_id,
+            order_number = %topup.order_number,
+            payment_key = %query.payment_key,
+            expected = %topup.amount,
+            received = %query.amount,
+            "Payment amount mismatch on top-up return"
+        );
+        return Ok(FlashMessage::error(messages::PAYMENT_FAILED)
+            .set_and_redirect(&session, paths::pages::DASHBOARD)
+            .await?);
+    }
+
+    let confirmation = gateway
+        .confirm(ConfirmRequest {
+            payment_key: &query.payment_key,
+            order_number: &topup.order_number,
+            amount: topup.amount,
+        })
+        .await;
+
+    let flash = match confirmation {
+        Ok(_) => {
+            commands::credit::complete_topup(&db, topup.topup_id, &query.payment_key).await?;
+            FlashMessage::success(messages::CREDITS_TOPPED_UP)
+        }
+        Err(PaymentError::Rejected { code, message }) => {
+            tracing::error!("Top-up confirmation rejected ({}): {}", code, message);
+            commands::credit::fail_topup(&db, topup.topup_id).await?;
+            FlashMessage::error(messages::PAYMENT_FAILED)
+        }
+        Err(e) => {
+            // Left pending; the webhook or reconciliation settles it
+            tracing::error!("Failed to call payment gateway: {}", e);
+            FlashMessage::error(messages::PAYMENT_FAILED)
+        }
+    };
+
+    Ok(flash.set_and_redirect(&session, paths::pages::DASHBOARD).await?)
+}
@@ -1,11 +1,19 @@
 pub mod admin;
 mod auth;
+mod credit;
 mod payment;
+mod payment_webhook;
 mod sign_out;
+mod subscription;
 mod todo;
+mod unsubscribe;
 
 pub use auth::get_actions_auth_verify;
+pub use credit::{get_actions_credit_top_up_verify, post_actions_pay_with_credits};
 pub use payment::{get_actions_payment_verify, post_actions_payment_initiate};
+pub use payment_webhook::post_actions_payment_webhook;
 pub use sign_out::post_actions_sign_out;
+pub use subscription::get_actions_subscription_card_registered;
 pub use todo::delete_actions_todos_todo_id;
 pub use todo::patch_actions_todos_todo_id_toggle;
+pub use unsubscribe::post_actions_unsubscribe;
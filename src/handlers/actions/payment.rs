@@ -1,16 +1,20 @@
 use axum::{Extension, Form, extract::{Query, State}, response::{IntoResponse, Redirect, Response}};
-use serde::{Deserialize, Serialize};
+use serde::Deserialize;
 use sqlx::PgPool;
 use uuid::Uuid;
 
 use crate::{
     auth::CurrentUser,
     config::AppConfig,
-    constants::messages,
-    data::{commands, queries},
+    constants::{logging, messages, payment},
+    data::{amount_in_range, commands::{self, discount::Redemption, payment_attempt::AttemptStart}, errors::DataError, queries},
     flash::FlashMessage,
-    models::order::PaymentStatus,
+    handlers::errors::HandlerResult,
+    models::{order::{Order, OrderEventSource, PaymentStatus}, payment_attempt::PaymentAttemptStatus},
+    money::Money,
+    notifications,
     paths,
+    payment::{ConfirmRequest, GatewayPayment, GatewayPaymentStatus, PaymentError, PaymentGateway, SharedGateway, update_cash_receipt},
 };
 use tower_sessions::Session;
 
@@ -20,6 +24,7 @@ pub struct PaymentInitiateForm {
 }
 
 pub async fn post_actions_payment_initiate(
+    State(config): State<AppConfig>,
     State(db): State<PgPool>,
     Extension(current_user): Extension<CurrentUser>,
     session: Session,
@@ -29,7 +34,13 @@ pub async fn post_actions_payment_initiate(
 
     let order = queries::order::get_order_for_user(&db, form.order_id, user_id).await?;
 
-    if !matches!(order.payment_status, PaymentStatus::Pending) {
+    if order.is_quote_expired(config.quotes().validity()) {
+        return Ok(FlashMessage::error(messages::QUOTE_EXPIRED)
+            .set_and_redirect(&session, paths::pages::TEXT_ANALYZER)
+            .await?);
+    }
+
+    if !order.payment_status.is_payable() {
         return Ok(FlashMessage::error(messages::ORDER_ALREADY_PROCESSED)
             .set_and_redirect(&session, &paths::helpers::quote_path(&order.order_id))
             .await?);
@@ -42,24 +53,20 @@ pub async fn post_actions_payment_initiate(
 #[derive(Deserialize)]
 pub struct PaymentVerifyQuery {
     #[serde(rename = "orderId")]
-    order_id: String,
+    pub(super) order_id: String,
     #[serde(rename = "paymentKey")]
-    payment_key: String,
-    amount: i32,
-}
-
-#[derive(Serialize)]
-struct TossConfirmRequest {
-    #[serde(rename = "paymentKey")]
-    payment_key: String,
-    #[serde(rename = "orderId")]
-    order_id: String,
-    amount: i32,
+    pub(super) payment_key: String,
+    pub(super) amount: Money,
 }
 
+/// Confirms the payment the gateway redirected back with.
+///
+/// Each payment key is confirmed at most once: a refreshed or double-clicked
+/// redirect finds the recorded attempt and reuses its outcome.
 pub async fn get_actions_payment_verify(
     State(config): State<AppConfig>,
     State(db): State<PgPool>,
+    State(gateway): State<SharedGateway>,
     Extension(current_user): Extension<CurrentUser>,
     session: Session,
     Query(query): Query<PaymentVerifyQuery>,
@@ -67,63 +74,328 @@ pub async fn get_actions_payment_verify(
     let user_id = current_user.require_authenticated();
 
     let order = queries::order::get_order_by_order_number_for_user(&db, &query.order_id, user_id).await?;
+    let quote_path = paths::helpers::quote_path(&order.order_id);
 
     if query.amount != order.price_amount {
-        tracing::error!("Payment amount mismatch: expected {}, got {}", order.price_amount, query.amount);
+        tracing::warn!(
+            target: logging::SECURITY_TARGET,
+            user_id,
+            order_number = %order.order_number,
+            payment_key = %query.payment_key,
+            expected = %order.price_amount,
+            received = %query.amount,
+            "Payment amount mismatch on checkout return"
+        );
         return Ok(FlashMessage::error(messages::PAYMENT_FAILED)
-            .set_and_redirect(&session, &paths::helpers::quote_path(&order.order_id))
+            .set_and_redirect(&session, &quote_path)
             .await?);
     }
 
-    let secret_key = config.payment().toss_secret_key();
+    let start = commands::payment_attempt::begin_attempt(&db, &query.payment_key, order.order_id, order.price_amount).await?;
+    if let AttemptStart::Existing(attempt) = start {
+        if attempt.order_id != order.order_id {
+            tracing::warn!(
+                target: logging::SECURITY_TARGET,
+                user_id,
+                order_number = %order.order_number,
+                payment_key = %query.payment_key,
+                "Payment key reused for a different order"
+            );
+            return Ok(FlashMessage::error(messages::PAYMENT_FAILED)
+                .set_and_redirect(&session, &quote_path)
+                .await?);
+        }
 
-    let confirm_request = TossConfirmRequest {
-        payment_key: query.payment_key.clone(),
-        order_id: query.order_id.clone(),
-        amount: query.amount,
-    };
+        let flash = match attempt.status {
+            PaymentAttemptStatus::Succeeded if order.payment_status == PaymentStatus::AwaitingDeposit => {
+                return Ok(FlashMessage::success(messages::VIRTUAL_ACCOUNT_ISSUED)
+                    .set_and_redirect(&session, &paths::helpers::deposit_path(&order.order_id))
+                    .await?);
+            }
+            PaymentAttemptStatus::Succeeded => {
+                return Ok(FlashMessage::success(messages::PAYMENT_SUCCESS)
+                    .set_and_redirect(&session, &paths::helpers::result_path(&order.order_id))
+                    .await?);
+            }
+            PaymentAttemptStatus::Confirming => FlashMessage::error(messages::PAYMENT_IN_PROGRESS),
+            PaymentAttemptStatus::Failed | PaymentAttemptStatus::Errored => FlashMessage::error(messages::PAYMENT_FAILED),
+        };
+        return Ok(flash.set_and_redirect(&session, &quote_path).await?);
+    }
+
+    // The quote can expire while the customer is in the payment window. Checked
+    // after the replay lookup so a refreshed redirect still shows its outcome.
+    if order.is_quote_expired(config.quotes().validity()) {
+        commands::payment_attempt::complete_attempt(
+            &db,
+            &query.payment_key,
+            PaymentAttemptStatus::Failed,
+            Some(payment::QUOTE_EXPIRED_CODE),
+            Some(messages::QUOTE_EXPIRED),
+        ).await?;
+
+        return Ok(FlashMessage::error(messages::QUOTE_EXPIRED)
+            .set_and_redirect(&session, paths::pages::TEXT_ANALYZER)
+            .await?);
+    }
+
+    // Counted before confirming so concurrent checkouts cannot overshoot the
+    // code's limits; given back below if the payment does not go through.
+    if let Redemption::Unavailable(reason) = commands::discount::reserve_redemption(&db, &order).await? {
+        commands::payment_attempt::complete_attempt(
+            &db,
+            &query.payment_key,
+            PaymentAttemptStatus::Failed,
+            Some(payment::DISCOUNT_UNAVAILABLE_CODE),
+            Some(reason),
+        ).await?;
+        let tax = amount_in_range(config.tax().apply(order.list_price_amount))?;
+        commands::discount::remove_from_order(&db, order.order_id, user_id, tax).await?;
 
-    let client = reqwest::Client::new();
-    let response = client
-        .post(crate::constants::payment::TOSS_API_CONFIRM_URL)
-        .basic_auth(secret_key, Some(""))
-        .json(&confirm_request)
-        .send()
+        return Ok(FlashMessage::error(messages::DISCOUNT_WITHDRAWN)
+            .set_and_redirect(&session, &quote_path)
+            .await?);
+    }
+
+    let confirmation = gateway
+        .confirm(ConfirmRequest {
+            payment_key: &query.payment_key,
+            order_number: &order.order_number,
+            amount: order.price_amount,
+        })
         .await;
 
-    match response {
-        Ok(resp) if resp.status().is_success() => {
-            commands::order::update_order_payment(
+    // The method is taken from the gateway rather than the return URL, so only
+    // what the customer actually paid with is stored, and only if it is offered.
+    if let Ok(payment) = &confirmation
+        && !payment.payment_method().is_some_and(|method| config.payment().methods().contains(&method))
+    {
+        return cancel_unavailable_method(&db, gateway.as_ref(), &session, &order, payment).await;
+    }
+
+    match confirmation {
+        // A virtual account was issued; the order completes when the deposit arrives
+        Ok(ref payment @ GatewayPayment {
+            status: GatewayPaymentStatus::WaitingForDeposit,
+            virtual_account: Some(ref account),
+            ..
+        }) => {
+            commands::payment_attempt::complete_attempt(
+                &db,
+                &query.payment_key,
+                PaymentAttemptStatus::Succeeded,
+                Some(GatewayPaymentStatus::WaitingForDeposit.as_str()),
+                None,
+            ).await?;
+
+            match commands::virtual_account::record_issued(
                 &db,
                 order.order_id,
                 &query.payment_key,
-                PaymentStatus::Paid,
+                account,
+                OrderEventSource::Checkout,
+            ).await {
+                Ok(_) => {}
+                // The webhook or reconciliation already recorded this account
+                Err(DataError::InvalidTransition { .. })
+                    if already_settled(&db, order.order_id, &query.payment_key, PaymentStatus::AwaitingDeposit).await? => {}
+                Err(e @ DataError::InvalidTransition { .. }) => {
+                    let Some(current) = settled_by_other_payment(&db, order.order_id, &query.payment_key).await? else {
+                        return Err(e.into());
+                    };
+                    return cancel_duplicate_payment(&db, gateway.as_ref(), &session, &current, payment).await;
+                }
+                Err(e) => return Err(e.into()),
+            }
+
+            Ok(FlashMessage::success(messages::VIRTUAL_ACCOUNT_ISSUED)
+                .set_and_redirect(&session, &paths::helpers::deposit_path(&order.order_id))
+                .await?)
+        }
+        Ok(payment) => {
+            commands::payment_attempt::complete_attempt(
+                &db,
+                &query.payment_key,
+                PaymentAttemptStatus::Succeeded,
+                Some(payment.status.as_str()),
+                None,
             ).await?;
 
+            let order = match commands::order::update_order_payment(
+                &db,
+                order.order_id,
+                &query.payment_key,
+                payment.payment_method(),
+                PaymentStatus::Paid,
+                OrderEventSource::Checkout,
+            ).await {
+                Ok(order) => order,
+                // The webhook or reconciliation got there first and has already notified the user
+                Err(DataError::InvalidTransition { .. })
+                    if already_settled(&db, order.order_id, &query.payment_key, PaymentStatus::Paid).await? =>
+                {
+                    return Ok(FlashMessage::success(messages::PAYMENT_SUCCESS)
+                        .set_and_redirect(&session, &paths::helpers::result_path(&order.order_id))
+                        .await?);
+                }
+                Err(e @ DataError::InvalidTransition { .. }) => {
+                    let Some(current) = settled_by_other_payment(&db, order.order_id, &query.payment_key).await? else {
+                        return Err(e.into());
+                    };
+                    return cancel_duplicate_payment(&db, gateway.as_ref(), &session, &current, &payment).await;
+                }
+                Err(e) => return Err(e.into()),
+            };
+
+            notifications::notify_payment_succeeded(&db, config.email(), &order).await;
+            update_cash_receipt(&db, gateway.as_ref(), order.order_id).await;
+
             Ok(FlashMessage::success(messages::PAYMENT_SUCCESS)
                 .set_and_redirect(&session, &paths::helpers::result_path(&order.order_id))
                 .await?)
         }
-        Ok(resp) => {
-            let error_body = resp.text().await.unwrap_or("Unknown error".to_string());
-            tracing::error!("Toss payment confirmation failed: {}", error_body);
+        Err(PaymentError::Rejected { code, message }) => {
+            tracing::error!("Payment confirmation rejected ({}): {}", code, message);
+            commands::payment_attempt::complete_attempt(
+                &db,
+                &query.payment_key,
+                PaymentAttemptStatus::Failed,
+                Some(&code),
+                Some(&message),
+            ).await?;
+            commands::discount::release_redemption(&db, order.order_id).await?;
 
-            commands::order::update_order_payment(
+            let order = commands::order::update_order_payment(
                 &db,
                 order.order_id,
                 &query.payment_key,
+                None,
                 PaymentStatus::Failed,
+                OrderEventSource::Checkout,
             ).await?;
 
+            notifications::notify_payment_failed(&db, config.email(), &order).await;
+
             Ok(FlashMessage::error(messages::PAYMENT_FAILED)
-                .set_and_redirect(&session, &paths::helpers::quote_path(&order.order_id))
+                .set_and_redirect(&session, &quote_path)
                 .await?)
         }
         Err(e) => {
-            tracing::error!("Failed to call Toss API: {}", e);
+            tracing::error!("Failed to call payment gateway: {}", e);
+            commands::payment_attempt::complete_attempt(
+                &db,
+                &query.payment_key,
+                PaymentAttemptStatus::Errored,
+                None,
+                Some(&e.to_string()),
+            ).await?;
+            // If the payment did go through, the webhook records the redemption again
+            commands::discount::release_redemption(&db, order.order_id).await?;
+
             Ok(FlashMessage::error(messages::PAYMENT_FAILED)
-                .set_and_redirect(&session, &paths::helpers::quote_path(&order.order_id))
+                .set_and_redirect(&session, &quote_path)
                 .await?)
         }
     }
 }
+
+/// Whether the order already reached `status` for this payment through another path.
+async fn already_settled(
+    db: &PgPool,
+    order_id: Uuid,
+    payment_key: &str,
+    status: PaymentStatus,
+) -> Result<bool, DataError> {
+    Ok(queries::order::get_order(db, order_id)
+        .await?
+        .is_some_and(|order| order.payment_status == status && order.payment_key.as_deref() == Some(payment_key)))
+}
+
+/// The order, if a different payment settled it while this one was being
+/// confirmed, e.g. credits spent in another tab.
+async fn settled_by_other_payment(db: &PgPool, order_id: Uuid, payment_key: &str) -> Result<Option<Order>, DataError> {
+    Ok(queries::order::get_order(db, order_id)
+        .await?
+        .filter(|order| !order.payment_status.is_payable() && order.payment_key.as_deref() != Some(payment_key)))
+}
+
+/// Cancels `payment` in full because `order` was settled another way, so the
+/// customer is not charged twice. A payment the gateway will not cancel is left
+/// for reconciliation, which reports it as unknown to the order.
+async fn cancel_duplicate_payment(
+    db: &PgPool,
+    gateway: &dyn PaymentGateway,
+    session: &Session,
+    order: &Order,
+    payment: &GatewayPayment,
+) -> HandlerResult {
+    commands::payment_attempt::complete_attempt(
+        db,
+        &payment.payment_key,
+        PaymentAttemptStatus::Failed,
+        Some(payment::ALREADY_PAID_CODE),
+        Some(payment::ALREADY_PAID_CANCEL_REASON),
+    ).await?;
+
+    let flash = match gateway.cancel(&payment.payment_key, payment::ALREADY_PAID_CANCEL_REASON, None, payment.total_amount).await {
+        Ok(_) => {
+            tracing::warn!("Cancelled payment {} for order {}, which was already paid", payment.payment_key, order.order_number);
+            FlashMessage::info(messages::DUPLICATE_PAYMENT_CANCELLED)
+        }
+        Err(e) => {
+            tracing::error!("Duplicate payment {} for order {} not cancelled: {}", payment.payment_key, order.order_number, e);
+            FlashMessage::error(messages::DUPLICATE_PAYMENT_NOT_CANCELLED)
+        }
+    };
+    let path = if order.payment_status == PaymentStatus::Paid {
+        paths::helpers::result_path(&order.order_id)
+    } else {
+        paths::helpers::quote_path(&order.order_id)
+    };
+
+    Ok(flash.set_and_redirect(session, &path).await?)
+}
+
+/// Cancels `payment` in full because it was made with a method the shop does not
+/// offer, e.g. one turned off while the customer was in the payment window. The
+/// order stays payable so the customer can pay another way.
+async fn cancel_unavailable_method(
+    db: &PgPool,
+    gateway: &dyn PaymentGateway,
+    session: &Session,
+    order: &Order,
+    payment: &GatewayPayment,
+) -> HandlerResult {
+    commands::payment_attempt::complete_attempt(
+        db,
+        &payment.payment_key,
+        PaymentAttemptStatus::Failed,
+        Some(payment::METHOD_UNAVAILABLE_CODE),
+        Some(payment::METHOD_UNAVAILABLE_CANCEL_REASON),
+    ).await?;
+    commands::discount::release_redemption(db, order.order_id).await?;
+
+    let flash = match gateway.cancel(&payment.payment_key, payment::METHOD_UNAVAILABLE_CANCEL_REASON, None, payment.total_amount).await {
+        Ok(_) => {
+            tracing::warn!(
+                "Cancelled payment {} for order {} made with unavailable method {:?}",
+                payment.payment_key,
+                order.order_number,
+                payment.method
+            );
+            FlashMessage::error(messages::PAYMENT_METHOD_UNAVAILABLE)
+        }
+        Err(e) => {
+            tracing::error!(
+                "Payment {} for order {} with unavailable method {:?} not cancelled: {}",
+                payment.payment_key,
+                order.order_number,
+                payment.method,
+                e
+            );
+            FlashMessage::error(messages::PAYMENT_METHOD_UNAVAILABLE_NOT_CANCELLED)
+        }
+    };
+
+    Ok(flash.set_and_redirect(session, &paths::helpers::quote_path(&order.order_id)).await?)
+}
@@ -0,0 +1,79 @@
+use axum::{extract::State, http::StatusCode};
+use sqlx::PgPool;
+
+use crate::{
+    config::AppConfig,
+    constants::payment,
+    data::{commands, queries},
+    handlers::errors::HandlerResult,
+    models::order::OrderEventSource,
+    payment::{PaymentError, SharedGateway, SyncOutcome, WebhookEvent, sync_order, update_cash_receipt},
+};
+
+/// Receives payment status events from the gateway.
+///
+/// Every delivery is stored as received. The event body is not trusted: the payment
+/// is looked up through the gateway and the order synced to that. Virtual account
+/// deposits arrive as `DEPOSIT_CALLBACK` events and settle the same way. Transport errors
+/// answer 500 so the gateway retries; everything else answers 200.
+pub async fn post_actions_payment_webhook(
+    State(config): State<AppConfig>,
+    State(db): State<PgPool>,
+    State(gateway): State<SharedGateway>,
+    body: String,
+) -> HandlerResult<StatusCode> {
+    let event = serde_json::from_str::<WebhookEvent>(&body).ok();
+    let event_id = commands::payment_webhook::record_event(
+        &db,
+        event.as_ref().map(|event| event.event_type.as_str()),
+        event.as_ref().and_then(|event| event.data.payment_key.as_deref()),
+        &body,
+    )
+    .await?;
+
+    let Some(event) = event else {
+        commands::payment_webhook::mark_event_processed(&db, event_id, "ignored: unreadable payload").await?;
+        return Ok(StatusCode::BAD_REQUEST);
+    };
+    if ![payment::WEBHOOK_PAYMENT_STATUS_CHANGED, payment::WEBHOOK_DEPOSIT_CALLBACK].contains(&event.event_type.as_str()) {
+        commands::payment_webhook::mark_event_processed(&db, event_id, "ignored: unsupported event type").await?;
+        return Ok(StatusCode::OK);
+    }
+
+    // Deposit callbacks identify the payment by order ID only
+    let lookup = match (&event.data.payment_key, &event.data.order_id) {
+        (Some(payment_key), _) => gateway.query(payment_key).await,
+        (None, Some(order_id)) => gateway.query_by_order(order_id).await,
+        (None, None) => {
+            commands::payment_webhook::mark_event_processed(&db, event_id, "ignored: no payment reference").await?;
+            return Ok(StatusCode::OK);
+        }
+    };
+
+    let payment = match lookup {
+        Ok(payment) => payment,
+        Err(e @ PaymentError::Rejected { .. }) => {
+            tracing::warn!("Webhook event {} refers to a payment the gateway rejected: {}", event_id, e);
+            commands::payment_webhook::mark_event_processed(&db, event_id, &format!("ignored: {}", e)).await?;
+            return Ok(StatusCode::OK);
+        }
+        Err(e) => {
+            tracing::error!("Failed to query payment for webhook event {}: {}", event_id, e);
+            commands::payment_webhook::mark_event_processed(&db, event_id, &format!("error: {}", e)).await?;
+            return Ok(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+
+    let outcome = sync_order(&db, config.email(), &payment, OrderEventSource::Webhook).await?;
+    tracing::info!("Webhook event {} for order {}: {}", event_id, payment.order_id, outcome);
+    commands::payment_webhook::mark_event_processed(&db, event_id, &outcome.to_string()).await?;
+
+    // A deposit or refund may leave the order's cash receipt behind
+    if matches!(outcome, SyncOutcome::Updated { .. })
+        && let Some(order) = queries::order::get_order_by_order_number(&db, &payment.order_id).await?
+    {
+        update_cash_receipt(&db, gateway.as_ref(), order.order_id).await;
+    }
+
+    Ok(StatusCode::OK)
+}
@@ -0,0 +1,96 @@
+use axum::{Extension, extract::{Query, State}};
+use serde::Deserialize;
+use sqlx::PgPool;
+use tower_sessions::Session;
+use uuid::Uuid;
+
+use crate::{
+    auth::CurrentUser,
+    constants::{errors, logging, messages},
+    data::{commands::{self, subscription::ChargeFailure}, queries},
+    flash::FlashMessage,
+    handlers::errors::HandlerResult,
+    models::subscription::SubscriptionStatus,
+    paths,
+    payment::{self, ChargeOutcome, PaymentError, SharedGateway},
+};
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CardRegisteredQuery {
+    pub subscription_id: Uuid,
+    pub customer_key: String,
+    pub auth_key: String,
+}
+
+/// Exchanges a registered card for a billing key and charges the first (or
+/// overdue) period on it.
+pub async fn get_actions_subscription_card_registered(
+    State(db): State<PgPool>,
+    State(gateway): State<SharedGateway>,
+    Extension(current_user): Extension<CurrentUser>,
+    session: Session,
+    Query(query): Query<CardRegisteredQuery>,
+) -> HandlerResult {
+    let user_id = current_user.require_authenticated();
+
+    let subscription = queries::subscription::get_subscription_for_user(&db, query.subscription_id, user_id).await?;
+
+    if subscription.customer_key != query.customer_key {
+        tracing::warn!(
+            target: logging::SECURITY_TARGET,
+            user_id,
+            subscription_id = %subscription.subscription_id,
+            "Customer key mismatch on card registration return"
+        );
+        return Ok(FlashMessage::error(errors::SUBSCRIPTION_CARD_REJECTED)
+            .set_and_redirect(&session, paths::pages::SUBSCRIPTION)
+            .await?);
+    }
+
+    if !subscription.status.accepts_card() {
+        return Ok(FlashMessage::error(errors::SUBSCRIPTION_CARD_NOT_NEEDED)
+            .set_and_redirect(&session, paths::pages::SUBSCRIPTION)
+            .await?);
+    }
+
+    let billing_key = match gateway.issue_billing_key(&query.auth_key, &subscription.customer_key).await {
+        Ok(billing_key) => billing_key,
+        Err(e) => {
+            if !matches!(e, PaymentError::Rejected { .. }) {
+                tracing::error!("Failed to call payment gateway: {}", e);
+            }
+            return Ok(FlashMessage::error(errors::SUBSCRIPTION_CARD_REJECTED)
+                .set_and_redirect(&session, &paths::helpers::subscription_card_path(&subscription.subscription_id))
+                .await?);
+        }
+    };
+    commands::subscription::save_card(
+        &db,
+        subscription.subscription_id,
+        &billing_key.billing_key,
+        billing_key.card_number.as_deref(),
+    )
+    .await?;
+
+    let subscription = queries::subscription::get_subscription_for_user(&db, query.subscription_id, user_id).await?;
+    let card_path = paths::helpers::subscription_card_path(&subscription.subscription_id);
+    let (flash, redirect) = match payment::charge_subscription(&db, gateway.as_ref(), &subscription).await? {
+        ChargeOutcome::Paid if subscription.status == SubscriptionStatus::PastDue => {
+            (FlashMessage::success(messages::SUBSCRIPTION_CARD_UPDATED), paths::pages::SUBSCRIPTION)
+        }
+        ChargeOutcome::Paid => (FlashMessage::success(messages::SUBSCRIPTION_STARTED), paths::pages::SUBSCRIPTION),
+        // A new subscription stays waiting for a card, so offer to try another one
+        ChargeOutcome::Declined { message, failure: ChargeFailure::NotStarted } => (
+            FlashMessage::error(format!("{}: {}", errors::SUBSCRIPTION_CHARGE_FAILED, message)),
+            card_path.as_str(),
+        ),
+        ChargeOutcome::Declined { message, .. } => (
+            FlashMessage::error(format!("{}: {}", errors::SUBSCRIPTION_CHARGE_FAILED, message)),
+            paths::pages::SUBSCRIPTION,
+        ),
+        ChargeOutcome::Unreachable => (FlashMessage::error(messages::PAYMENT_IN_PROGRESS), paths::pages::SUBSCRIPTION),
+    };
+
+    Ok(flash.set_and_redirect(&session, redirect).await?)
+}
@@ -0,0 +1,39 @@
+use axum::{
+    Extension,
+    extract::{Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use sqlx::PgPool;
+
+use crate::{
+    auth::CurrentUser,
+    config::AppConfig,
+    constants::errors,
+    data::commands,
+    email::UNSUBSCRIBE_TOKEN_PURPOSE,
+    handlers::{errors::HandlerError, pages::UnsubscribeQuery},
+    models::email_suppression::SuppressionReason,
+    views::pages,
+};
+
+/// Handles both the confirmation form and RFC 8058 one-click requests from mail clients.
+pub async fn post_actions_unsubscribe(
+    State(config): State<AppConfig>,
+    State(db): State<PgPool>,
+    Extension(current_user): Extension<CurrentUser>,
+    Query(query): Query<UnsubscribeQuery>,
+) -> Result<Response, HandlerError> {
+    let Some(email) = config.signer().verify(UNSUBSCRIBE_TOKEN_PURPOSE, &query.token) else {
+        return Ok((
+            StatusCode::BAD_REQUEST,
+            pages::unsubscribe_invalid(&current_user, config.site_name(), errors::UNSUBSCRIBE_LINK_INVALID),
+        )
+            .into_response());
+    };
+
+    commands::email_suppression::add_suppression(&db, &email, SuppressionReason::Unsubscribe, None).await?;
+    tracing::info!("Suppressed {} via unsubscribe link", email);
+
+    Ok(pages::unsubscribed(&current_user, config.site_name(), &email).into_response())
+}
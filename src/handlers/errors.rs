@@ -4,7 +4,7 @@ use axum::{
 };
 use thiserror::Error;
 
-use crate::{auth::CurrentUser, constants::error_pages, data::errors::DataError, views::pages};
+use crate::{auth::CurrentUser, constants::{error_pages, errors}, data::errors::DataError, email::EmailError, receipt::ReceiptError, views::pages};
 
 /// Type alias for handler results, defaulting to Response.
 pub type HandlerResult<T = Response> = Result<T, HandlerError>;
@@ -19,6 +19,12 @@ pub enum HandlerError {
 
     #[error("{0}")]
     Session(#[from] tower_sessions::session::Error),
+
+    #[error("{0}")]
+    Email(#[from] EmailError),
+
+    #[error("{0}")]
+    Receipt(#[from] ReceiptError),
 }
 
 impl IntoResponse for HandlerError {
@@ -27,6 +33,7 @@ impl IntoResponse for HandlerError {
             Self::Data(DataError::NotFound(msg)) => (StatusCode::NOT_FOUND, *msg),
             Self::Data(DataError::Unauthorized(msg)) => (StatusCode::UNAUTHORIZED, *msg),
             Self::Data(DataError::InvalidInput(msg)) => (StatusCode::BAD_REQUEST, msg.as_str()),
+            Self::Data(DataError::InvalidTransition { .. }) => (StatusCode::CONFLICT, errors::ORDER_STATUS_CONFLICT),
             Self::Data(DataError::Database(e)) => {
                 tracing::error!(error = %e, "Database error in handler");
                 (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")
@@ -35,6 +42,14 @@ impl IntoResponse for HandlerError {
                 tracing::error!(error = %e, "Session error in handler");
                 (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")
             }
+            Self::Email(e) => {
+                tracing::error!(error = %e, "Email error in handler");
+                (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")
+            }
+            Self::Receipt(e) => {
+                tracing::error!(error = %e, "Receipt error in handler");
+                (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")
+            }
         };
 
         (status, pages::server_error(&CurrentUser::Guest, None, error_pages::FALLBACK_SITE_NAME, message)).into_response()
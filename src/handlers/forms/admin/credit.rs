@@ -0,0 +1,38 @@
+use axum::{Extension, Form, extract::{Path, State}};
+use sqlx::PgPool;
+use tower_sessions::Session;
+
+use crate::{
+    auth::CurrentUser,
+    constants::{errors, messages},
+    data::commands,
+    flash::FlashMessage,
+    handlers::errors::HandlerResult,
+    models::credit::CreditAdjustmentForm,
+    paths::helpers,
+};
+
+/// Adds or removes credits by hand, recorded in the ledger with the admin's note.
+pub async fn post_adjust_credits(
+    State(db): State<PgPool>,
+    Path(user_id): Path<i32>,
+    Extension(current_user): Extension<CurrentUser>,
+    session: Session,
+    Form(form): Form<CreditAdjustmentForm>,
+) -> HandlerResult {
+    let admin_user_id = current_user.require_authenticated();
+    let detail_path = helpers::user_detail_path(user_id);
+
+    let note = form.note.trim();
+    let adjusted = if form.amount.is_zero() || note.is_empty() {
+        None
+    } else {
+        commands::credit::adjust_balance(&db, user_id, form.amount, note, admin_user_id).await?
+    };
+
+    let flash = match adjusted {
+        Some(_) => FlashMessage::success(messages::CREDITS_ADJUSTED),
+        None => FlashMessage::error(errors::CREDIT_ADJUSTMENT_INVALID),
+    };
+    Ok(flash.set_and_redirect(&session, &detail_path).await?)
+}
@@ -0,0 +1,51 @@
+use axum::{Extension, Form, extract::{Path, State}};
+use sqlx::PgPool;
+use tower_sessions::Session;
+
+use crate::{
+    auth::CurrentUser,
+    constants::{errors, messages},
+    data::commands,
+    flash::FlashMessage,
+    handlers::errors::HandlerResult,
+    models::discount::DiscountCodeForm,
+    paths,
+};
+
+pub async fn post_create_discount(
+    State(db): State<PgPool>,
+    Extension(current_user): Extension<CurrentUser>,
+    session: Session,
+    Form(form): Form<DiscountCodeForm>,
+) -> HandlerResult {
+    let admin_user_id = current_user.require_authenticated();
+
+    let code = match form.validate() {
+        Ok(code) => code,
+        Err(message) => {
+            return Ok(FlashMessage::error(message)
+                .set_and_redirect(&session, paths::pages::admin::DISCOUNTS)
+                .await?);
+        }
+    };
+
+    let flash = match commands::discount::create_discount_code(&db, &code, admin_user_id).await? {
+        Some(_) => FlashMessage::success(messages::DISCOUNT_CODE_CREATED),
+        None => FlashMessage::error(errors::DISCOUNT_CODE_EXISTS),
+    };
+
+    Ok(flash.set_and_redirect(&session, paths::pages::admin::DISCOUNTS).await?)
+}
+
+/// Activates or deactivates a code.
+pub async fn post_toggle_discount(
+    State(db): State<PgPool>,
+    Path(discount_code_id): Path<i32>,
+    session: Session,
+) -> HandlerResult {
+    commands::discount::toggle_discount_code(&db, discount_code_id).await?;
+
+    Ok(FlashMessage::success(messages::DISCOUNT_CODE_UPDATED)
+        .set_and_redirect(&session, paths::pages::admin::DISCOUNTS)
+        .await?)
+}
@@ -0,0 +1,114 @@
+use axum::extract::{Multipart, State};
+use sqlx::PgPool;
+use tower_sessions::Session;
+
+use crate::{
+    constants::{errors, file_upload},
+    data::{commands::email_suppression, errors::DataError},
+    flash::FlashMessage,
+    handlers::errors::HandlerResult,
+    models::email_suppression::SuppressionReason,
+    paths,
+    validation::EMAIL_RX,
+};
+
+/// Imports a bounce/complaint export from the email provider.
+///
+/// The CSV needs an `email` column; an optional `reason` column is mapped with
+/// [`SuppressionReason::from_report`]. Rows without a valid address are skipped.
+pub async fn post_import_suppressions(
+    State(db): State<PgPool>,
+    session: Session,
+    mut multipart: Multipart,
+) -> HandlerResult {
+    let mut data = None;
+
+    while let Some(field) = multipart.next_field().await.map_err(|e| {
+        DataError::InvalidInput(format!("Failed to process multipart data: {}", e))
+    })? {
+        if field.name() == Some("file") {
+            data = Some(field.bytes().await.map_err(|e| {
+                DataError::InvalidInput(format!("Failed to read file: {}", e))
+            })?);
+        }
+    }
+
+    let data = data.ok_or(DataError::NotFound(errors::NO_FILE_PROVIDED))?;
+    if data.len() > file_upload::MAX_FILE_SIZE {
+        return Ok(FlashMessage::error(format!("File too large. Maximum size is {} MB.", file_upload::MAX_FILE_SIZE / 1024 / 1024))
+            .set_and_redirect(&session, paths::pages::admin::SUPPRESSIONS)
+            .await?);
+    }
+
+    let Some((entries, skipped)) = parse_suppression_csv(&data) else {
+        return Ok(FlashMessage::error(errors::NO_EMAIL_COLUMN)
+            .set_and_redirect(&session, paths::pages::admin::SUPPRESSIONS)
+            .await?);
+    };
+
+    let imported = email_suppression::import_suppressions(&db, &entries, "CSV import").await?;
+    let already_suppressed = entries.len() as u64 - imported;
+
+    Ok(FlashMessage::success(format!(
+        "Imported {} suppressions ({} already suppressed, {} skipped)",
+        imported, already_suppressed, skipped
+    ))
+    .set_and_redirect(&session, paths::pages::admin::SUPPRESSIONS)
+    .await?)
+}
+
+/// Returns the valid entries and the number of skipped rows, or `None` without an `email` column.
+fn parse_suppression_csv(data: &[u8]) -> Option<(Vec<(String, SuppressionReason)>, usize)> {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .flexible(true)
+        .from_reader(data);
+
+    let headers = reader.headers().ok()?.clone();
+    let column = |name: &str| headers.iter().position(|h| h.eq_ignore_ascii_case(name));
+    let email_column = column("email")?;
+    let reason_column = column("reason");
+
+    let mut entries = Vec::new();
+    let mut skipped = 0;
+    for record in reader.records() {
+        let Ok(record) = record else {
+            skipped += 1;
+            continue;
+        };
+        let email = record.get(email_column).unwrap_or_default().to_lowercase();
+        if !EMAIL_RX.is_match(&email) {
+            skipped += 1;
+            continue;
+        }
+        let reason = reason_column
+            .and_then(|i| record.get(i))
+            .map(SuppressionReason::from_report)
+            .unwrap_or(SuppressionReason::Bounce);
+        entries.push((email, reason));
+    }
+
+    Some((entries, skipped))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_suppression_csv_maps_reasons_and_skips_invalid_rows() {
+        let csv = b"Email , Reason\nA@Example.com,complaint\nnot-an-email,bounce\nb@example.com,\n";
+        let (entries, skipped) = parse_suppression_csv(csv).unwrap();
+
+        assert_eq!(skipped, 1);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].0, "a@example.com");
+        assert!(matches!(entries[0].1, SuppressionReason::Complaint));
+        assert!(matches!(entries[1].1, SuppressionReason::Bounce));
+    }
+
+    #[test]
+    fn test_parse_suppression_csv_requires_email_column() {
+        assert!(parse_suppression_csv(b"address\na@example.com\n").is_none());
+    }
+}
@@ -0,0 +1,141 @@
+use axum::{Extension, Form, extract::{Path, State}};
+use sqlx::PgPool;
+use tower_sessions::Session;
+
+use crate::{
+    auth::CurrentUser,
+    config::AppConfig,
+    constants::{errors, messages},
+    data::{commands::contact_inquiry as commands, queries::contact_inquiry as queries},
+    email::{self, EmailError},
+    flash::FlashMessage,
+    handlers::errors::HandlerResult,
+    models::contact::{AssignInquiryForm, InquiryEntryForm, InquiryStatusForm},
+    paths::helpers,
+};
+
+pub async fn post_inquiry_status(
+    State(db): State<PgPool>,
+    Path(inquiry_id): Path<i32>,
+    session: Session,
+    Form(form): Form<InquiryStatusForm>,
+) -> HandlerResult {
+    commands::update_inquiry_status(&db, inquiry_id, form.status).await?;
+
+    Ok(FlashMessage::success(messages::INQUIRY_STATUS_UPDATED)
+        .set_and_redirect(&session, &helpers::inquiry_detail_path(inquiry_id))
+        .await?)
+}
+
+pub async fn post_inquiry_assign(
+    State(db): State<PgPool>,
+    Path(inquiry_id): Path<i32>,
+    session: Session,
+    Form(form): Form<AssignInquiryForm>,
+) -> HandlerResult {
+    let detail_path = helpers::inquiry_detail_path(inquiry_id);
+
+    let assignee = match form.assignee() {
+        Ok(assignee) => assignee,
+        Err(message) => {
+            return Ok(FlashMessage::error(message).set_and_redirect(&session, &detail_path).await?);
+        }
+    };
+    commands::assign_inquiry(&db, inquiry_id, assignee).await?;
+
+    Ok(FlashMessage::success(messages::INQUIRY_ASSIGNED)
+        .set_and_redirect(&session, &detail_path)
+        .await?)
+}
+
+pub async fn post_inquiry_note(
+    State(db): State<PgPool>,
+    Path(inquiry_id): Path<i32>,
+    Extension(current_user): Extension<CurrentUser>,
+    session: Session,
+    Form(form): Form<InquiryEntryForm>,
+) -> HandlerResult {
+    let admin_user_id = current_user.require_authenticated();
+    let detail_path = helpers::inquiry_detail_path(inquiry_id);
+
+    let body = form.body.trim();
+    if body.is_empty() {
+        return Ok(FlashMessage::error(errors::INQUIRY_ENTRY_EMPTY)
+            .set_and_redirect(&session, &detail_path)
+            .await?);
+    }
+
+    commands::add_note(&db, inquiry_id, admin_user_id, body).await?;
+
+    Ok(FlashMessage::success(messages::INQUIRY_NOTE_ADDED)
+        .set_and_redirect(&session, &detail_path)
+        .await?)
+}
+
+/// Emails the reply to the inquiry's author and records it in the thread once sent.
+pub async fn post_inquiry_reply(
+    State(db): State<PgPool>,
+    State(config): State<AppConfig>,
+    Path(inquiry_id): Path<i32>,
+    Extension(current_user): Extension<CurrentUser>,
+    session: Session,
+    Form(form): Form<InquiryEntryForm>,
+) -> HandlerResult {
+    let admin_user_id = current_user.require_authenticated();
+    let detail_path = helpers::inquiry_detail_path(inquiry_id);
+
+    let body = form.body.trim();
+    if body.is_empty() {
+        return Ok(FlashMessage::error(errors::INQUIRY_ENTRY_EMPTY)
+            .set_and_redirect(&session, &detail_path)
+            .await?);
+    }
+
+    let inquiry = queries::get_inquiry(&db, inquiry_id).await?;
+
+    match email::send_inquiry_reply(config.email(), &db, &inquiry.email, body, &inquiry.message).await {
+        Ok(()) => {}
+        Err(EmailError::Suppressed(_)) => {
+            return Ok(FlashMessage::error(errors::INQUIRY_RECIPIENT_SUPPRESSED)
+                .set_and_redirect(&session, &detail_path)
+                .await?);
+        }
+        Err(e) => {
+            tracing::error!("Failed to send reply for inquiry {}: {}", inquiry_id, e);
+            return Ok(FlashMessage::error(messages::EMAIL_SEND_FAILED)
+                .set_and_redirect(&session, &detail_path)
+                .await?);
+        }
+    }
+
+    commands::record_reply(&db, inquiry_id, admin_user_id, body).await?;
+
+    Ok(FlashMessage::success(messages::INQUIRY_REPLY_SENT)
+        .set_and_redirect(&session, &detail_path)
+        .await?)
+}
+
+/// Moves a quarantined inquiry into the inbox and sends the notification it skipped.
+pub async fn post_inquiry_release(
+    State(db): State<PgPool>,
+    State(config): State<AppConfig>,
+    Path(inquiry_id): Path<i32>,
+    session: Session,
+) -> HandlerResult {
+    let detail_path = helpers::inquiry_detail_path(inquiry_id);
+
+    if !commands::release_inquiry(&db, inquiry_id).await? {
+        return Ok(FlashMessage::error(errors::INQUIRY_NOT_QUARANTINED)
+            .set_and_redirect(&session, &detail_path)
+            .await?);
+    }
+
+    let inquiry = queries::get_inquiry(&db, inquiry_id).await?;
+    if let Err(e) = email::send_contact_inquiry(config.email(), &db, inquiry_id, &inquiry.email, &inquiry.message).await {
+        tracing::error!("Failed to send contact inquiry email for released inquiry {}: {}", inquiry_id, e);
+    }
+
+    Ok(FlashMessage::success(messages::INQUIRY_RELEASED)
+        .set_and_redirect(&session, &detail_path)
+        .await?)
+}
@@ -1,3 +1,17 @@
+mod credit;
+mod discount;
 mod grant_role;
+mod import_suppressions;
+mod inquiry;
+mod pricing;
+mod reconciliation;
+mod refund;
 
+pub use credit::post_adjust_credits;
+pub use discount::{post_create_discount, post_toggle_discount};
 pub use grant_role::post_grant_role;
+pub use import_suppressions::post_import_suppressions;
+pub use inquiry::{post_inquiry_assign, post_inquiry_note, post_inquiry_release, post_inquiry_reply, post_inquiry_status};
+pub use pricing::post_create_pricing_rule;
+pub use reconciliation::{post_dismiss_finding, post_fix_finding, post_run_reconciliation};
+pub use refund::post_refund_order;
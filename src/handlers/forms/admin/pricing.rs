@@ -0,0 +1,39 @@
+use axum::{Extension, Form, extract::State};
+use sqlx::PgPool;
+use time::OffsetDateTime;
+use tower_sessions::Session;
+
+use crate::{
+    auth::CurrentUser,
+    constants::messages,
+    data::commands,
+    flash::FlashMessage,
+    handlers::errors::HandlerResult,
+    models::pricing::PricingRuleForm,
+    paths,
+};
+
+/// Saves a new price list version; it takes over from its effective date.
+pub async fn post_create_pricing_rule(
+    State(db): State<PgPool>,
+    Extension(current_user): Extension<CurrentUser>,
+    session: Session,
+    Form(form): Form<PricingRuleForm>,
+) -> HandlerResult {
+    let admin_user_id = current_user.require_authenticated();
+
+    let rule = match form.validate(OffsetDateTime::now_utc()) {
+        Ok(rule) => rule,
+        Err(message) => {
+            return Ok(FlashMessage::error(message)
+                .set_and_redirect(&session, paths::pages::admin::PRICING)
+                .await?);
+        }
+    };
+
+    commands::pricing::create_pricing_rule(&db, &rule, admin_user_id).await?;
+
+    Ok(FlashMessage::success(messages::PRICING_RULE_CREATED)
+        .set_and_redirect(&session, paths::pages::admin::PRICING)
+        .await?)
+}
@@ -0,0 +1,104 @@
+use axum::{Extension, extract::{Path, State}};
+use sqlx::PgPool;
+use time::OffsetDateTime;
+use tower_sessions::Session;
+
+use crate::{
+    auth::CurrentUser,
+    config::AppConfig,
+    constants::{errors, messages, reconciliation::DISMISSED_RESOLUTION},
+    data::{commands, errors::DataError, queries},
+    flash::FlashMessage,
+    handlers::errors::HandlerResult,
+    models::order::OrderEventSource,
+    paths,
+    payment::{self, SharedGateway, SyncOutcome},
+};
+
+/// Runs a reconciliation now, over the same window as the nightly job.
+pub async fn post_run_reconciliation(
+    State(db): State<PgPool>,
+    State(config): State<AppConfig>,
+    State(gateway): State<SharedGateway>,
+    session: Session,
+) -> HandlerResult {
+    let window_end = OffsetDateTime::now_utc();
+    let window_start = window_end - config.reconciliation().window();
+
+    let flash = match payment::reconcile(&db, gateway.as_ref(), window_start, window_end).await {
+        Ok(summary) => FlashMessage::success(format!(
+            "Checked {} orders: {} new findings, {} gateway errors",
+            summary.checked, summary.findings, summary.errors
+        )),
+        Err(e) => {
+            tracing::error!("Reconciliation failed: {}", e);
+            FlashMessage::error(errors::RECONCILIATION_RUN_FAILED)
+        }
+    };
+
+    Ok(flash.set_and_redirect(&session, paths::pages::admin::RECONCILIATION).await?)
+}
+
+/// Applies the gateway's current record of the payment to the order.
+///
+/// The payment is queried again rather than taken from the finding, and the change
+/// goes through the order state machine like a webhook would.
+pub async fn post_fix_finding(
+    State(db): State<PgPool>,
+    State(config): State<AppConfig>,
+    State(gateway): State<SharedGateway>,
+    Path(finding_id): Path<i32>,
+    Extension(current_user): Extension<CurrentUser>,
+    session: Session,
+) -> HandlerResult {
+    let admin_user_id = current_user.require_authenticated();
+    let page = paths::pages::admin::RECONCILIATION;
+
+    let finding = queries::reconciliation::get_open_finding(&db, finding_id)
+        .await?
+        .ok_or(DataError::NotFound(errors::RECONCILIATION_FINDING_NOT_FOUND))?;
+    if !finding.kind.is_fixable() {
+        return Ok(FlashMessage::error(errors::RECONCILIATION_FIX_NOT_AVAILABLE)
+            .set_and_redirect(&session, page)
+            .await?);
+    }
+
+    let payment = match gateway.query_by_order(&finding.order_number).await {
+        Ok(payment) => payment,
+        Err(e) => {
+            tracing::error!("Reconciliation fix for order {} failed: {}", finding.order_number, e);
+            return Ok(FlashMessage::error(format!("{}: {}", errors::RECONCILIATION_FIX_FAILED, e))
+                .set_and_redirect(&session, page)
+                .await?);
+        }
+    };
+
+    let outcome = payment::sync_order(&db, config.email(), &payment, OrderEventSource::Reconciliation).await?;
+    if !matches!(outcome, SyncOutcome::Updated { .. } | SyncOutcome::Unchanged(_)) {
+        return Ok(FlashMessage::error(format!("{}: {}", errors::RECONCILIATION_FIX_FAILED, outcome))
+            .set_and_redirect(&session, page)
+            .await?);
+    }
+
+    commands::reconciliation::resolve_finding(&db, finding_id, admin_user_id, &outcome.to_string()).await?;
+
+    Ok(FlashMessage::success(messages::RECONCILIATION_FIX_APPLIED)
+        .set_and_redirect(&session, page)
+        .await?)
+}
+
+/// Closes a finding without changing the order, once someone has looked into it.
+pub async fn post_dismiss_finding(
+    State(db): State<PgPool>,
+    Path(finding_id): Path<i32>,
+    Extension(current_user): Extension<CurrentUser>,
+    session: Session,
+) -> HandlerResult {
+    let admin_user_id = current_user.require_authenticated();
+
+    commands::reconciliation::resolve_finding(&db, finding_id, admin_user_id, DISMISSED_RESOLUTION).await?;
+
+    Ok(FlashMessage::success(messages::RECONCILIATION_FINDING_DISMISSED)
+        .set_and_redirect(&session, paths::pages::admin::RECONCILIATION)
+        .await?)
+}
@@ -0,0 +1,99 @@
+use axum::{Extension, Form, extract::{Path, State}};
+use sqlx::PgPool;
+use tower_sessions::Session;
+use uuid::Uuid;
+
+use crate::{
+    auth::CurrentUser,
+    constants::{errors, messages},
+    data::{amount_in_range, commands::{self, refund::RecordRefundParams}, errors::DataError, queries},
+    flash::FlashMessage,
+    handlers::errors::HandlerResult,
+    models::{order::OrderEventSource, refund::RefundForm},
+    money::Money,
+    paths::helpers,
+    payment::{SharedGateway, update_cash_receipt},
+};
+
+/// Refunds all or part of a paid order through the gateway, then records it.
+/// Orders paid with credits are refunded to the customer's credit balance.
+pub async fn post_refund_order(
+    State(db): State<PgPool>,
+    State(gateway): State<SharedGateway>,
+    Path(order_id): Path<Uuid>,
+    Extension(current_user): Extension<CurrentUser>,
+    session: Session,
+    Form(form): Form<RefundForm>,
+) -> HandlerResult {
+    let admin_user_id = current_user.require_authenticated();
+    let detail_path = helpers::order_detail_path(order_id);
+
+    let order = queries::order::get_order(&db, order_id)
+        .await?
+        .ok_or(DataError::NotFound(errors::ORDER_NOT_FOUND))?;
+
+    let reason = form.reason.trim();
+    if reason.is_empty() {
+        return Ok(FlashMessage::error(errors::REFUND_REASON_REQUIRED)
+            .set_and_redirect(&session, &detail_path)
+            .await?);
+    }
+
+    let refunded = queries::refund::get_refunded_total(&db, order_id).await?;
+    let refundable = if order.payment_status.is_refundable() {
+        amount_in_range(order.price_amount.checked_sub(refunded))?
+    } else {
+        Money::zero(order.price_amount.currency)
+    };
+    if !form.amount.is_positive() || form.amount > refundable {
+        return Ok(FlashMessage::error(errors::REFUND_AMOUNT_INVALID)
+            .set_and_redirect(&session, &detail_path)
+            .await?);
+    }
+
+    let params = RecordRefundParams {
+        order_id,
+        amount: form.amount,
+        reason,
+        refunded_by: Some(admin_user_id),
+        source: OrderEventSource::Admin,
+    };
+
+    let Some(payment_key) = order.payment_key.as_deref() else {
+        if queries::credit::get_order_spend(&db, order_id).await?.is_none() {
+            return Ok(FlashMessage::error(errors::REFUND_AMOUNT_INVALID)
+                .set_and_redirect(&session, &detail_path)
+                .await?);
+        }
+
+        commands::credit::refund_order_to_credits(&db, params).await?;
+        return Ok(FlashMessage::success(messages::REFUNDED_TO_CREDITS)
+            .set_and_redirect(&session, &detail_path)
+            .await?);
+    };
+
+    // Held across the gateway call so its cancellation webhook cannot record
+    // this refund first
+    let pending = commands::refund::begin_refund(&db, order_id).await?;
+    if form.amount > pending.refundable {
+        return Ok(FlashMessage::error(errors::REFUND_AMOUNT_INVALID)
+            .set_and_redirect(&session, &detail_path)
+            .await?);
+    }
+
+    // Keyed by the refunded total, so retrying after a lost response does not refund twice
+    let refunded_total = amount_in_range(pending.refunded.checked_add(form.amount))?;
+    if let Err(e) = gateway.cancel(payment_key, reason, Some(form.amount), refunded_total).await {
+        tracing::error!("Refund of order {} failed: {}", order.order_number, e);
+        return Ok(FlashMessage::error(format!("{}: {}", errors::REFUND_FAILED, e))
+            .set_and_redirect(&session, &detail_path)
+            .await?);
+    }
+
+    pending.record(params).await?;
+    update_cash_receipt(&db, gateway.as_ref(), order_id).await;
+
+    Ok(FlashMessage::success(messages::REFUND_ISSUED)
+        .set_and_redirect(&session, &detail_path)
+        .await?)
+}
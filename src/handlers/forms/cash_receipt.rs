@@ -0,0 +1,79 @@
+use axum::{Extension, Form, extract::{Path, State}};
+use sqlx::PgPool;
+use tower_sessions::Session;
+use uuid::Uuid;
+
+use crate::{
+    auth::CurrentUser,
+    config::AppConfig,
+    constants::{errors, messages},
+    data::{commands, queries},
+    flash::FlashMessage,
+    handlers::errors::HandlerResult,
+    models::{cash_receipt::CashReceiptForm, order::Order},
+    paths,
+};
+
+/// Saves the cash receipt details the customer entered at checkout. The receipt
+/// is issued only if the order is then paid by account transfer or virtual account.
+pub async fn post_forms_checkout_cash_receipt(
+    State(db): State<PgPool>,
+    State(config): State<AppConfig>,
+    Path(order_id): Path<Uuid>,
+    Extension(current_user): Extension<CurrentUser>,
+    session: Session,
+    Form(form): Form<CashReceiptForm>,
+) -> HandlerResult {
+    let user_id = current_user.require_authenticated();
+    let order = queries::order::get_order_for_user(&db, order_id, user_id).await?;
+    let checkout_path = paths::helpers::checkout_path(&order_id);
+
+    if let Some(flash) = check_changeable(&order, &config) {
+        return Ok(flash.set_and_redirect(&session, &checkout_path).await?);
+    }
+
+    let identifier = match form.receipt_type.normalize_identifier(&form.identifier) {
+        Ok(identifier) => identifier,
+        Err(message) => return Ok(FlashMessage::error(message).set_and_redirect(&session, &checkout_path).await?),
+    };
+
+    let flash = if commands::cash_receipt::save_request(&db, order_id, form.receipt_type, &identifier).await? {
+        FlashMessage::success(messages::CASH_RECEIPT_SAVED)
+    } else {
+        FlashMessage::error(errors::CASH_RECEIPT_NOT_APPLICABLE)
+    };
+    Ok(flash.set_and_redirect(&session, &checkout_path).await?)
+}
+
+pub async fn post_forms_checkout_cash_receipt_remove(
+    State(db): State<PgPool>,
+    State(config): State<AppConfig>,
+    Path(order_id): Path<Uuid>,
+    Extension(current_user): Extension<CurrentUser>,
+    session: Session,
+) -> HandlerResult {
+    let user_id = current_user.require_authenticated();
+    let order = queries::order::get_order_for_user(&db, order_id, user_id).await?;
+    let checkout_path = paths::helpers::checkout_path(&order_id);
+
+    if let Some(flash) = check_changeable(&order, &config) {
+        return Ok(flash.set_and_redirect(&session, &checkout_path).await?);
+    }
+
+    commands::cash_receipt::remove_request(&db, order_id).await?;
+
+    Ok(FlashMessage::success(messages::CASH_RECEIPT_REMOVED)
+        .set_and_redirect(&session, &checkout_path)
+        .await?)
+}
+
+/// The message to show when the order is past the point where its receipt details can change.
+fn check_changeable(order: &Order, config: &AppConfig) -> Option<FlashMessage> {
+    if order.is_quote_expired(config.quotes().validity()) {
+        Some(FlashMessage::error(messages::QUOTE_EXPIRED))
+    } else if !order.payment_status.is_payable() {
+        Some(FlashMessage::error(errors::CASH_RECEIPT_NOT_APPLICABLE))
+    } else {
+        None
+    }
+}
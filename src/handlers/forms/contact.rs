@@ -1,17 +1,21 @@
-use axum::{Extension, Form, extract::State, http::StatusCode, response::{IntoResponse, Response}};
+use std::net::SocketAddr;
+
+use axum::{Extension, Form, extract::{ConnectInfo, State}, http::StatusCode, response::{IntoResponse, Response}};
 use sqlx::PgPool;
+use time::OffsetDateTime;
 use tower_sessions::Session;
 use validator::Validate;
 
 use crate::{
     auth::CurrentUser,
     config::AppConfig,
-    constants::messages,
-    data::queries,
+    constants::{errors, messages, spam::DUPLICATE_WINDOW_HOURS},
+    data::{commands, queries},
     email,
     flash::FlashMessage,
     models::contact::{ContactForm, FIELD_EMAIL, FIELD_MESSAGE},
     paths,
+    spam::{self, FormStamp},
     views::pages,
 };
 
@@ -20,10 +24,17 @@ use super::parse_validation_errors;
 pub async fn post_forms_contact(
     State(config): State<AppConfig>,
     State(db): State<PgPool>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
     Extension(current_user): Extension<CurrentUser>,
     session: Session,
     Form(form): Form<ContactForm>,
 ) -> Result<Response, crate::handlers::errors::HandlerError> {
+    if let Err(e) = config.proof_of_work().verify(addr.ip(), &form.pow_challenge, &form.pow_solution) {
+        tracing::warn!("Rejected contact form from {}: {}", addr.ip(), e);
+        let flash = FlashMessage::error(errors::POW_FAILED);
+        return Ok(render_form(&current_user, &config, addr, Some(&flash), &form, None, None));
+    }
+
     let email_to_use = match &current_user {
         CurrentUser::Authenticated { user_id, email, .. } => {
             queries::user::get_user_email(&db, *user_id)
@@ -32,17 +43,59 @@ pub async fn post_forms_contact(
         }
         CurrentUser::Guest => {
             if let Err(validation_errors) = form.validate() {
-                return Ok(render_validation_errors(&current_user, config.site_name(), &form, &validation_errors, None));
+                let errors = parse_validation_errors(&validation_errors);
+                return Ok(render_form(
+                    &current_user,
+                    &config,
+                    addr,
+                    None,
+                    &form,
+                    errors.get(FIELD_EMAIL).map(String::as_str),
+                    errors.get(FIELD_MESSAGE).map(String::as_str),
+                ));
             }
             form.email.clone()
         }
     };
 
-    if let Err(e) = email::send_contact_inquiry(config.email(), &email_to_use, &form.message).await {
-        tracing::error!("Failed to send contact inquiry email: {}", e);
-        return Ok(FlashMessage::error(messages::EMAIL_SEND_FAILED)
-            .set_and_redirect(&session, paths::pages::ROOT)
-            .await?);
+    let stamp = spam::check_form_stamp(config.signer(), &form.form_stamp, OffsetDateTime::now_utc());
+    if stamp == FormStamp::Stale {
+        return Ok(render_form(&current_user, &config, addr, None, &form, None, Some(errors::CONTACT_FORM_EXPIRED)));
+    }
+
+    let content_hash = spam::content_hash(&form.message);
+    let recent_duplicates =
+        queries::contact_inquiry::count_recent_by_content_hash(&db, &content_hash, DUPLICATE_WINDOW_HOURS).await?;
+    let assessment = spam::assess_submission(&form.website, &stamp, &form.message, recent_duplicates);
+
+    let user_id = match &current_user {
+        CurrentUser::Authenticated { user_id, .. } => Some(*user_id),
+        CurrentUser::Guest => None,
+    };
+
+    // Stored first so the inquiry reaches the admin inbox even if the notification fails
+    let inquiry_id = commands::contact_inquiry::create_inquiry(
+        &db,
+        commands::contact_inquiry::CreateInquiryParams {
+            user_id,
+            email: &email_to_use,
+            message: &form.message,
+            content_hash: &content_hash,
+            assessment: &assessment,
+        },
+    )
+    .await?;
+
+    // Quarantined submissions get the same response so bots can't tell they were caught
+    if assessment.is_spam() {
+        tracing::warn!(
+            "Quarantined contact inquiry {} (score {}): {}",
+            inquiry_id,
+            assessment.score,
+            assessment.reasons.join(", ")
+        );
+    } else if let Err(e) = email::send_contact_inquiry(config.email(), &db, inquiry_id, &email_to_use, &form.message).await {
+        tracing::error!("Failed to send contact inquiry email for inquiry {}: {}", inquiry_id, e);
     }
 
     Ok(FlashMessage::success(messages::CONTACT_SENT)
@@ -50,25 +103,32 @@ pub async fn post_forms_contact(
         .await?)
 }
 
-fn render_validation_errors(
+/// Re-renders the contact form with a fresh form stamp and proof-of-work challenge.
+fn render_form(
     current_user: &CurrentUser,
-    site_name: &str,
+    config: &AppConfig,
+    addr: SocketAddr,
+    flash: Option<&FlashMessage>,
     form: &ContactForm,
-    validation_errors: &validator::ValidationErrors,
-    user_email: Option<String>,
+    email_error: Option<&str>,
+    message_error: Option<&str>,
 ) -> Response {
-    let errors = parse_validation_errors(validation_errors);
-    let email_to_show = user_email.as_deref().or(Some(&form.email));
+    let form_stamp = spam::issue_form_stamp(config.signer(), OffsetDateTime::now_utc());
+    let pow = config.proof_of_work().issue(addr.ip());
     (
         StatusCode::BAD_REQUEST,
         pages::root(
             current_user,
-            None,
-            site_name,
-            email_to_show,
-            Some(&form.message),
-            errors.get(FIELD_EMAIL).map(String::as_str),
-            errors.get(FIELD_MESSAGE).map(String::as_str),
+            flash,
+            config.site_name(),
+            pages::ContactFormView {
+                email: Some(&form.email),
+                message: Some(&form.message),
+                email_error,
+                message_error,
+                form_stamp: &form_stamp,
+                pow: pow.as_ref(),
+            },
         ),
     )
         .into_response()
@@ -0,0 +1,32 @@
+use axum::{Extension, Form, extract::State, response::{IntoResponse, Redirect}};
+use sqlx::PgPool;
+use tower_sessions::Session;
+
+use crate::{
+    auth::CurrentUser,
+    constants::errors,
+    data::commands,
+    flash::FlashMessage,
+    handlers::errors::HandlerResult,
+    models::credit::TopUpForm,
+    paths,
+};
+
+/// Starts a credit purchase and sends the user to pay for it.
+pub async fn post_forms_credit_top_up(
+    State(db): State<PgPool>,
+    Extension(current_user): Extension<CurrentUser>,
+    session: Session,
+    Form(form): Form<TopUpForm>,
+) -> HandlerResult {
+    let user_id = current_user.require_authenticated();
+
+    if !form.is_valid() {
+        return Ok(FlashMessage::error(errors::TOP_UP_AMOUNT_INVALID)
+            .set_and_redirect(&session, paths::pages::DASHBOARD)
+            .await?);
+    }
+
+    let topup = commands::credit::create_topup(&db, user_id, form.amount).await?;
+    Ok(Redirect::to(&paths::helpers::credit_top_up_path(&topup.topup_id)).into_response())
+}
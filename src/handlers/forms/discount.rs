@@ -0,0 +1,91 @@
+use axum::{Extension, Form, extract::{Path, State}};
+use sqlx::PgPool;
+use time::OffsetDateTime;
+use tower_sessions::Session;
+use uuid::Uuid;
+
+use crate::{
+    auth::CurrentUser,
+    config::AppConfig,
+    constants::{errors, messages},
+    data::{amount_in_range, commands, queries},
+    flash::FlashMessage,
+    handlers::errors::HandlerResult,
+    models::{discount::ApplyDiscountForm, order::Order},
+    paths,
+};
+
+/// Applies a discount code to an unpaid quote.
+///
+/// Limits are checked here for a friendly message, and again atomically when the
+/// payment is confirmed.
+pub async fn post_forms_quote_discount(
+    State(db): State<PgPool>,
+    State(config): State<AppConfig>,
+    Path(order_id): Path<Uuid>,
+    Extension(current_user): Extension<CurrentUser>,
+    session: Session,
+    Form(form): Form<ApplyDiscountForm>,
+) -> HandlerResult {
+    let user_id = current_user.require_authenticated();
+    let order = queries::order::get_order_for_user(&db, order_id, user_id).await?;
+    let quote_path = paths::helpers::quote_path(&order_id);
+
+    if let Some(flash) = check_changeable(&order, &config) {
+        return Ok(flash.set_and_redirect(&session, &quote_path).await?);
+    }
+
+    let Some(code) = queries::discount::get_discount_code_by_code(&db, &form.code).await? else {
+        return Ok(FlashMessage::error(errors::DISCOUNT_CODE_INVALID)
+            .set_and_redirect(&session, &quote_path)
+            .await?);
+    };
+
+    let user_redemptions = queries::discount::get_user_redemption_count(&db, code.discount_code_id, user_id).await?;
+    if let Err(reason) = code.check_usable(order.list_price_amount, user_redemptions, OffsetDateTime::now_utc()) {
+        return Ok(FlashMessage::error(reason).set_and_redirect(&session, &quote_path).await?);
+    }
+
+    let discount_amount = amount_in_range(code.discount_for(order.list_price_amount))?;
+    let price_amount = amount_in_range(order.list_price_amount.checked_sub(discount_amount))?;
+    let tax = amount_in_range(config.tax().apply(price_amount))?;
+    commands::discount::apply_to_order(&db, order_id, user_id, code.discount_code_id, discount_amount, tax).await?;
+
+    Ok(FlashMessage::success(messages::DISCOUNT_APPLIED)
+        .set_and_redirect(&session, &quote_path)
+        .await?)
+}
+
+pub async fn post_forms_quote_discount_remove(
+    State(db): State<PgPool>,
+    State(config): State<AppConfig>,
+    Path(order_id): Path<Uuid>,
+    Extension(current_user): Extension<CurrentUser>,
+    session: Session,
+) -> HandlerResult {
+    let user_id = current_user.require_authenticated();
+    let order = queries::order::get_order_for_user(&db, order_id, user_id).await?;
+    let quote_path = paths::helpers::quote_path(&order_id);
+
+    if let Some(flash) = check_changeable(&order, &config) {
+        return Ok(flash.set_and_redirect(&session, &quote_path).await?);
+    }
+
+    let tax = amount_in_range(config.tax().apply(order.list_price_amount))?;
+    commands::discount::remove_from_order(&db, order_id, user_id, tax).await?;
+
+    Ok(FlashMessage::success(messages::DISCOUNT_REMOVED)
+        .set_and_redirect(&session, &quote_path)
+        .await?)
+}
+
+/// The message to show when the order's price can no longer change.
+fn check_changeable(order: &Order, config: &AppConfig) -> Option<FlashMessage> {
+    if order.is_quote_expired(config.quotes().validity()) {
+        Some(FlashMessage::error(messages::QUOTE_EXPIRED))
+    } else if !order.payment_status.is_payable() {
+        Some(FlashMessage::error(errors::DISCOUNT_NOT_APPLICABLE))
+    } else {
+        None
+    }
+}
@@ -1,11 +1,21 @@
 pub mod admin;
+mod cash_receipt;
 mod contact;
+mod credit;
+mod discount;
+mod notification_preferences;
 mod sign_in;
+mod subscription;
 mod text_analyzer;
 mod todo;
 
+pub use cash_receipt::{post_forms_checkout_cash_receipt, post_forms_checkout_cash_receipt_remove};
 pub use contact::post_forms_contact;
+pub use credit::post_forms_credit_top_up;
+pub use discount::{post_forms_quote_discount, post_forms_quote_discount_remove};
+pub use notification_preferences::post_forms_notification_preferences;
 pub use sign_in::post_forms_sign_in;
+pub use subscription::{post_forms_subscribe, post_forms_subscription_cancel, post_forms_subscription_resume};
 pub use text_analyzer::post_forms_text_analyzer;
 pub use todo::post_forms_todos;
 
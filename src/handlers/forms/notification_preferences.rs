@@ -0,0 +1,28 @@
+use axum::{Extension, Form, extract::State};
+use sqlx::PgPool;
+use tower_sessions::Session;
+
+use crate::{
+    auth::CurrentUser,
+    constants::messages,
+    data::commands,
+    flash::FlashMessage,
+    handlers::errors::HandlerResult,
+    models::user::NotificationPreferencesForm,
+    paths,
+};
+
+pub async fn post_forms_notification_preferences(
+    State(db): State<PgPool>,
+    Extension(current_user): Extension<CurrentUser>,
+    session: Session,
+    Form(form): Form<NotificationPreferencesForm>,
+) -> HandlerResult {
+    let user_id = current_user.require_authenticated();
+
+    commands::user::set_payment_emails_enabled(&db, user_id, form.payment_emails.is_some()).await?;
+
+    Ok(FlashMessage::success(messages::NOTIFICATION_PREFERENCES_UPDATED)
+        .set_and_redirect(&session, paths::pages::DASHBOARD)
+        .await?)
+}
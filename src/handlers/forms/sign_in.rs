@@ -1,11 +1,13 @@
-use axum::{Extension, Form, extract::State, http::StatusCode, response::{IntoResponse, Response}};
+use std::net::SocketAddr;
+
+use axum::{Extension, Form, extract::{ConnectInfo, State}, http::StatusCode, response::{IntoResponse, Response}};
 use sqlx::PgPool;
 use validator::Validate;
 
 use crate::{
     auth::CurrentUser,
     config::AppConfig,
-    constants::messages,
+    constants::{errors, messages},
     data::commands,
     email,
     flash::FlashMessage,
@@ -21,12 +23,20 @@ use super::parse_validation_errors;
 pub async fn post_forms_sign_in(
     State(config): State<AppConfig>,
     State(db): State<PgPool>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
     Extension(current_user): Extension<CurrentUser>,
     session: Session,
     Form(form): Form<MagicLinkRequestForm>,
 ) -> Result<Response, crate::handlers::errors::HandlerError> {
+    if let Err(e) = config.proof_of_work().verify(addr.ip(), &form.pow_challenge, &form.pow_solution) {
+        tracing::warn!("Rejected sign-in from {}: {}", addr.ip(), e);
+        let flash = FlashMessage::error(errors::POW_FAILED);
+        return Ok(render_form(&current_user, &config, addr, Some(&flash), &form, None));
+    }
+
     if let Err(validation_errors) = form.validate() {
-        return Ok(render_validation_errors(&current_user, config.site_name(), &form, &validation_errors));
+        let errors = parse_validation_errors(&validation_errors);
+        return Ok(render_form(&current_user, &config, addr, None, &form, errors.get(FIELD_EMAIL).map(String::as_str)));
     }
 
     let token = magic_link::generate_token();
@@ -44,21 +54,25 @@ pub async fn post_forms_sign_in(
         .await?)
 }
 
-fn render_validation_errors(
+/// Re-renders the sign-in form with a fresh proof-of-work challenge.
+fn render_form(
     current_user: &CurrentUser,
-    site_name: &str,
+    config: &AppConfig,
+    addr: SocketAddr,
+    flash: Option<&FlashMessage>,
     form: &MagicLinkRequestForm,
-    validation_errors: &validator::ValidationErrors,
+    email_error: Option<&str>,
 ) -> Response {
-    let errors = parse_validation_errors(validation_errors);
+    let pow = config.proof_of_work().issue(addr.ip());
     (
         StatusCode::BAD_REQUEST,
         pages::sign_in(
             current_user,
-            None,
-            site_name,
+            flash,
+            config.site_name(),
             Some(&form.email),
-            errors.get(FIELD_EMAIL).map(String::as_str),
+            email_error,
+            pow.as_ref(),
         ),
     )
         .into_response()
@@ -0,0 +1,68 @@
+use axum::{Extension, Form, extract::State, response::{IntoResponse, Redirect}};
+use sqlx::PgPool;
+use tower_sessions::Session;
+
+use crate::{
+    auth::CurrentUser,
+    constants::{errors, messages},
+    data::{commands, errors::DataError, queries},
+    flash::FlashMessage,
+    handlers::errors::HandlerResult,
+    models::subscription::SubscribeForm,
+    paths,
+};
+
+/// Starts a subscription to a plan and sends the user to register a card for it.
+pub async fn post_forms_subscribe(
+    State(db): State<PgPool>,
+    Extension(current_user): Extension<CurrentUser>,
+    session: Session,
+    Form(form): Form<SubscribeForm>,
+) -> HandlerResult {
+    let user_id = current_user.require_authenticated();
+
+    if queries::subscription::get_live_subscription(&db, user_id).await?.is_some() {
+        return Ok(FlashMessage::error(errors::SUBSCRIPTION_ALREADY_ACTIVE)
+            .set_and_redirect(&session, paths::pages::SUBSCRIPTION)
+            .await?);
+    }
+
+    let plan = queries::subscription::get_active_plan(&db, form.plan_id).await?;
+    let subscription_id = commands::subscription::create_subscription(&db, user_id, plan.plan_id).await?;
+
+    Ok(Redirect::to(&paths::helpers::subscription_card_path(&subscription_id)).into_response())
+}
+
+pub async fn post_forms_subscription_cancel(
+    State(db): State<PgPool>,
+    Extension(current_user): Extension<CurrentUser>,
+    session: Session,
+) -> HandlerResult {
+    let user_id = current_user.require_authenticated();
+
+    let subscription = queries::subscription::get_live_subscription(&db, user_id)
+        .await?
+        .ok_or(DataError::NotFound(errors::SUBSCRIPTION_NOT_FOUND))?;
+    commands::subscription::cancel_subscription(&db, subscription.subscription_id, user_id).await?;
+
+    Ok(FlashMessage::success(messages::SUBSCRIPTION_CANCELLED)
+        .set_and_redirect(&session, paths::pages::SUBSCRIPTION)
+        .await?)
+}
+
+pub async fn post_forms_subscription_resume(
+    State(db): State<PgPool>,
+    Extension(current_user): Extension<CurrentUser>,
+    session: Session,
+) -> HandlerResult {
+    let user_id = current_user.require_authenticated();
+
+    let subscription = queries::subscription::get_live_subscription(&db, user_id)
+        .await?
+        .ok_or(DataError::NotFound(errors::SUBSCRIPTION_NOT_FOUND))?;
+    commands::subscription::resume_subscription(&db, subscription.subscription_id, user_id).await?;
+
+    Ok(FlashMessage::success(messages::SUBSCRIPTION_RESUMED)
+        .set_and_redirect(&session, paths::pages::SUBSCRIPTION)
+        .await?)
+}
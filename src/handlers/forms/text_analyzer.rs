@@ -1,18 +1,26 @@
 use axum::{Extension, extract::{Multipart, State}, response::{IntoResponse, Redirect, Response}};
 use sqlx::PgPool;
+use time::OffsetDateTime;
 
 use crate::{
     auth::CurrentUser,
-    constants::{errors, file_upload, pricing},
-    data::{commands, errors::DataError},
+    config::AppConfig,
+    constants::{errors, file_upload, messages, products},
+    data::{amount_in_range, commands, errors::DataError, queries},
     flash::FlashMessage,
-    models::order::Order,
+    models::{
+        order::Order,
+        order_item::{OrderItemMetadata, TextAnalysis},
+        subscription::SubscriptionStatus,
+    },
+    money::Money,
     paths,
 };
 use tower_sessions::Session;
 
 pub async fn post_forms_text_analyzer(
     State(db): State<PgPool>,
+    State(config): State<AppConfig>,
     Extension(current_user): Extension<CurrentUser>,
     session: Session,
     mut multipart: Multipart,
@@ -59,24 +67,56 @@ pub async fn post_forms_text_analyzer(
     let text_content = text_content.ok_or(DataError::NotFound(errors::NO_FILE_CONTENT))?;
 
     let text_length = text_content.chars().count() as i32;
-    let calculated_price = text_length * pricing::PRICE_PER_CHARACTER;
-    let price_amount = calculated_price.max(pricing::MINIMUM_ORDER_AMOUNT);
+    let pricing_rule = queries::pricing::get_active_rule(&db).await?;
+    let list_price_amount = pricing_rule.price_for(text_length);
+    let product_id = queries::product::get_active_product_id(&db, products::TEXT_ANALYSIS).await?;
 
-    let order_number = Order::generate_order_number(user_id);
-
-    let order = commands::order::create_order(
-        &db,
-        commands::order::CreateOrderParams {
+    let params = |price_amount| -> Result<commands::order::CreateOrderParams, DataError> {
+        Ok(commands::order::CreateOrderParams {
             user_id,
-            user_email,
-            filename,
-            file_size,
-            text_content,
-            text_length,
-            price_amount,
-            order_number,
-        },
-    ).await?;
+            user_email: user_email.clone(),
+            items: vec![commands::order::NewOrderItem {
+                product_id,
+                quantity: 1,
+                unit_price_amount: list_price_amount,
+                metadata: OrderItemMetadata::TextAnalysis(TextAnalysis {
+                    filename: filename.clone(),
+                    file_size,
+                    text_content: text_content.clone(),
+                    text_length,
+                }),
+            }],
+            tax: amount_in_range(config.tax().apply(price_amount))?,
+            pricing_rule_id: pricing_rule.pricing_rule_id,
+            order_number: Order::generate_order_number(user_id),
+        })
+    };
+
+    // Files that fit in the subscription's remaining characters skip checkout
+    let subscription = queries::subscription::get_live_subscription(&db, user_id).await?;
+    if let Some(subscription) = &subscription
+        && subscription.covers(text_length, OffsetDateTime::now_utc())
+    {
+        match commands::subscription::create_covered_order(&db, subscription.subscription_id, params(Money::zero(list_price_amount.currency))?).await {
+            Ok(order) => {
+                return Ok(FlashMessage::success(messages::SUBSCRIPTION_COVERED)
+                    .set_and_redirect(&session, &paths::helpers::result_path(&order.order_id))
+                    .await?);
+            }
+            // Another upload used up the allowance first; charge this one as usual
+            Err(DataError::InvalidInput(_)) => {}
+            Err(e) => return Err(e.into()),
+        }
+    }
+
+    let order = commands::order::create_order(&db, params(list_price_amount)?).await?;
+    let quote_path = paths::helpers::quote_path(&order.order_id);
+
+    if subscription.is_some_and(|subscription| subscription.status == SubscriptionStatus::Active) {
+        return Ok(FlashMessage::info(messages::SUBSCRIPTION_QUOTA_EXCEEDED)
+            .set_and_redirect(&session, &quote_path)
+            .await?);
+    }
 
-    Ok(Redirect::to(&paths::helpers::quote_path(&order.order_id)).into_response())
+    Ok(Redirect::to(&quote_path).into_response())
 }
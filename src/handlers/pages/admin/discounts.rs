@@ -0,0 +1,23 @@
+use axum::{Extension, extract::State};
+use maud::Markup;
+use sqlx::PgPool;
+
+use crate::{
+    auth::CurrentUser,
+    config::AppConfig,
+    data::queries::discount,
+    flash::FlashMessage,
+    handlers::errors::HandlerError,
+    views::pages::admin as admin_views,
+};
+
+pub async fn get_admin_discounts(
+    State(db): State<PgPool>,
+    State(config): State<AppConfig>,
+    Extension(current_user): Extension<CurrentUser>,
+    Extension(flash): Extension<Option<FlashMessage>>,
+) -> Result<Markup, HandlerError> {
+    let codes = discount::get_discount_codes(&db).await?;
+
+    Ok(admin_views::discounts(&current_user, flash.as_ref(), config.site_name(), codes))
+}
@@ -0,0 +1,50 @@
+use axum::{Extension, extract::{Query, State}};
+use maud::Markup;
+use serde::Deserialize;
+use sqlx::PgPool;
+
+use crate::{
+    auth::CurrentUser,
+    config::AppConfig,
+    constants::admin::ITEMS_PER_PAGE,
+    data::queries::contact_inquiry,
+    flash::FlashMessage,
+    handlers::{errors::HandlerError, pagination::default_page},
+    models::{admin::PaginatedResult, contact::InquiryStatus},
+    views::pages::admin as admin_views,
+};
+
+#[derive(Deserialize)]
+pub struct InquiriesQuery {
+    #[serde(default = "default_page")]
+    pub page: i64,
+    pub status: Option<InquiryStatus>,
+}
+
+pub async fn get_admin_inquiries(
+    State(db): State<PgPool>,
+    State(config): State<AppConfig>,
+    Query(query): Query<InquiriesQuery>,
+    Extension(current_user): Extension<CurrentUser>,
+    Extension(flash): Extension<Option<FlashMessage>>,
+) -> Result<Markup, HandlerError> {
+    let page = query.page.max(1);
+    let status_filter = query.status;
+
+    let inquiries = contact_inquiry::get_inquiries_paginated(&db, status_filter, page, ITEMS_PER_PAGE).await?;
+
+    let total_count = contact_inquiry::get_total_inquiry_count(&db, status_filter).await?;
+
+    let paginated = PaginatedResult::new(inquiries, total_count, page, ITEMS_PER_PAGE);
+
+    let quarantined_count = contact_inquiry::get_total_quarantined_count(&db).await?;
+
+    Ok(admin_views::inquiries(
+        &current_user,
+        flash.as_ref(),
+        config.site_name(),
+        paginated,
+        status_filter,
+        quarantined_count,
+    ))
+}
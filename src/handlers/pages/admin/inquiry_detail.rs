@@ -0,0 +1,35 @@
+use axum::{Extension, extract::{Path, State}};
+use maud::Markup;
+use sqlx::PgPool;
+
+use crate::{
+    auth::CurrentUser,
+    config::AppConfig,
+    data::queries::{admin, contact_inquiry},
+    flash::FlashMessage,
+    handlers::errors::HandlerError,
+    views::pages::admin as admin_views,
+};
+
+pub async fn get_admin_inquiry_detail(
+    State(db): State<PgPool>,
+    State(config): State<AppConfig>,
+    Path(inquiry_id): Path<i32>,
+    Extension(current_user): Extension<CurrentUser>,
+    Extension(flash): Extension<Option<FlashMessage>>,
+) -> Result<Markup, HandlerError> {
+    let inquiry = contact_inquiry::get_inquiry(&db, inquiry_id).await?;
+
+    let entries = contact_inquiry::get_inquiry_entries(&db, inquiry_id).await?;
+
+    let admins = admin::get_admins(&db).await?;
+
+    Ok(admin_views::inquiry_detail(
+        &current_user,
+        flash.as_ref(),
+        config.site_name(),
+        inquiry,
+        entries,
+        admins,
+    ))
+}
@@ -1,11 +1,25 @@
+mod discounts;
 mod home;
+mod inquiries;
+mod inquiry_detail;
 mod orders;
+mod quarantine;
 mod order_detail;
+mod pricing;
+mod reconciliation;
+mod suppressions;
 mod users;
 mod user_detail;
 
+pub use discounts::get_admin_discounts;
 pub use home::get_admin_home;
+pub use inquiries::get_admin_inquiries;
+pub use inquiry_detail::get_admin_inquiry_detail;
 pub use orders::get_admin_orders;
+pub use quarantine::get_admin_quarantine;
 pub use order_detail::get_admin_order_detail;
+pub use pricing::get_admin_pricing;
+pub use reconciliation::get_admin_reconciliation;
+pub use suppressions::get_admin_suppressions;
 pub use users::get_admin_users;
 pub use user_detail::get_admin_user_detail;
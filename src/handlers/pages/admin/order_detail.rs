@@ -5,9 +5,11 @@ use sqlx::PgPool;
 use crate::{
     auth::CurrentUser,
     config::AppConfig,
-    data::queries::admin,
+    data::{amount_in_range, errors::DataError, queries::{admin, cash_receipt, order as order_queries, payment_attempt, refund}},
     flash::FlashMessage,
     handlers::errors::HandlerError,
+    models::admin::{OrderPaymentActivity, OrderRefunds},
+    money::{Currency, Money},
     views::pages::admin as admin_views,
 };
 
@@ -19,11 +21,27 @@ pub async fn get_admin_order_detail(
     Extension(flash): Extension<Option<FlashMessage>>,
 ) -> Result<Markup, HandlerError> {
     let order = admin::get_order_detail(&db, &order_id).await?;
+    let order_uuid = order_id.parse().map_err(|_| DataError::NotFound("Invalid order ID format"))?;
+    let refunds = refund::get_refunds_for_order(&db, order_uuid).await?;
+    let refunded = amount_in_range(Money::checked_sum(Currency::Krw, refunds.iter().map(|refund| refund.amount)))?;
+    let refundable = if order.payment_status.is_refundable() {
+        amount_in_range(order.price_amount.checked_sub(refunded))?
+    } else {
+        Money::zero(order.price_amount.currency)
+    };
+    let events = order_queries::get_order_events(&db, order_uuid).await?;
+    let payment = OrderPaymentActivity {
+        attempts: payment_attempt::get_attempts_for_order(&db, order_uuid).await?,
+        cash_receipt: cash_receipt::get_cash_receipt(&db, order_uuid).await?,
+    };
 
     Ok(admin_views::order_detail(
         &current_user,
         flash.as_ref(),
         config.site_name(),
         order,
+        OrderRefunds { refunds, refundable },
+        events,
+        payment,
     ))
 }
@@ -10,7 +10,7 @@ use crate::{
     data::queries::admin,
     flash::FlashMessage,
     handlers::{errors::HandlerError, pagination::default_page},
-    models::{admin::PaginatedResult, order::PaymentStatus},
+    models::{admin::PaginatedResult, order::PaymentStatus, payment_method::PaymentMethod},
     views::pages::admin as admin_views,
 };
 
@@ -19,6 +19,7 @@ pub struct OrdersQuery {
     #[serde(default = "default_page")]
     pub page: i64,
     pub status: Option<PaymentStatus>,
+    pub method: Option<PaymentMethod>,
 }
 
 pub async fn get_admin_orders(
@@ -30,10 +31,11 @@ pub async fn get_admin_orders(
 ) -> Result<Markup, HandlerError> {
     let page = query.page.max(1);
     let status_filter = query.status;
+    let method_filter = query.method;
 
-    let orders = admin::get_orders_paginated(&db, status_filter, page, ITEMS_PER_PAGE).await?;
+    let orders = admin::get_orders_paginated(&db, status_filter, method_filter, page, ITEMS_PER_PAGE).await?;
 
-    let total_count = admin::get_total_order_count(&db, status_filter).await?;
+    let total_count = admin::get_total_order_count(&db, status_filter, method_filter).await?;
 
     let paginated = PaginatedResult::new(orders, total_count, page, ITEMS_PER_PAGE);
 
@@ -43,5 +45,6 @@ pub async fn get_admin_orders(
         config.site_name(),
         paginated,
         status_filter,
+        method_filter,
     ))
 }
@@ -0,0 +1,42 @@
+use axum::{Extension, extract::{Query, State}};
+use maud::Markup;
+use serde::Deserialize;
+use sqlx::PgPool;
+
+use crate::{
+    auth::CurrentUser,
+    config::AppConfig,
+    constants::pricing::DEFAULT_PREVIEW_CHARACTERS,
+    data::queries::pricing,
+    flash::FlashMessage,
+    handlers::errors::HandlerError,
+    views::pages::admin as admin_views,
+};
+
+#[derive(Deserialize)]
+pub struct PricingPreviewQuery {
+    #[serde(default = "default_preview_characters")]
+    characters: i32,
+}
+
+fn default_preview_characters() -> i32 {
+    DEFAULT_PREVIEW_CHARACTERS
+}
+
+pub async fn get_admin_pricing(
+    State(db): State<PgPool>,
+    State(config): State<AppConfig>,
+    Query(query): Query<PricingPreviewQuery>,
+    Extension(current_user): Extension<CurrentUser>,
+    Extension(flash): Extension<Option<FlashMessage>>,
+) -> Result<Markup, HandlerError> {
+    let rules = pricing::get_pricing_rules(&db).await?;
+
+    Ok(admin_views::pricing(
+        &current_user,
+        flash.as_ref(),
+        config.site_name(),
+        rules,
+        query.characters.max(0),
+    ))
+}
@@ -0,0 +1,31 @@
+use axum::{Extension, extract::State};
+use maud::Markup;
+use sqlx::PgPool;
+
+use crate::{
+    auth::CurrentUser,
+    config::AppConfig,
+    constants::reconciliation::RECENT_RUNS_LIMIT,
+    data::queries::reconciliation,
+    flash::FlashMessage,
+    handlers::errors::HandlerError,
+    views::pages::admin as admin_views,
+};
+
+pub async fn get_admin_reconciliation(
+    State(db): State<PgPool>,
+    State(config): State<AppConfig>,
+    Extension(current_user): Extension<CurrentUser>,
+    Extension(flash): Extension<Option<FlashMessage>>,
+) -> Result<Markup, HandlerError> {
+    let runs = reconciliation::get_recent_runs(&db, RECENT_RUNS_LIMIT).await?;
+    let findings = reconciliation::get_open_findings(&db).await?;
+
+    Ok(admin_views::reconciliation(
+        &current_user,
+        flash.as_ref(),
+        config.site_name(),
+        runs,
+        findings,
+    ))
+}
@@ -0,0 +1,37 @@
+use axum::{Extension, extract::{Query, State}};
+use maud::Markup;
+use sqlx::PgPool;
+
+use crate::{
+    auth::CurrentUser,
+    config::AppConfig,
+    constants::admin::ITEMS_PER_PAGE,
+    data::queries::email_suppression,
+    flash::FlashMessage,
+    handlers::{errors::HandlerError, pagination::PaginationQuery},
+    models::admin::PaginatedResult,
+    views::pages::admin as admin_views,
+};
+
+pub async fn get_admin_suppressions(
+    State(db): State<PgPool>,
+    State(config): State<AppConfig>,
+    Query(query): Query<PaginationQuery>,
+    Extension(current_user): Extension<CurrentUser>,
+    Extension(flash): Extension<Option<FlashMessage>>,
+) -> Result<Markup, HandlerError> {
+    let page = query.page.max(1);
+
+    let suppressions = email_suppression::get_suppressions_paginated(&db, page, ITEMS_PER_PAGE).await?;
+
+    let total_count = email_suppression::get_total_suppression_count(&db).await?;
+
+    let paginated = PaginatedResult::new(suppressions, total_count, page, ITEMS_PER_PAGE);
+
+    Ok(admin_views::suppressions(
+        &current_user,
+        flash.as_ref(),
+        config.site_name(),
+        paginated,
+    ))
+}
@@ -1,26 +1,47 @@
-use axum::{Extension, extract::{Path, State}};
+use axum::{Extension, extract::{Path, State}, response::IntoResponse};
 use sqlx::PgPool;
+use tower_sessions::Session;
 use uuid::Uuid;
 
-use crate::{auth::CurrentUser, config::AppConfig, data::queries, flash::FlashMessage, handlers::errors::HandlerError, views::pages};
-use maud::Markup;
+use crate::{
+    auth::CurrentUser,
+    config::AppConfig,
+    constants::messages,
+    data::queries,
+    flash::FlashMessage,
+    handlers::errors::HandlerResult,
+    paths,
+    views::pages,
+};
 
 pub async fn get_checkout(
     State(config): State<AppConfig>,
     State(db): State<PgPool>,
     Extension(current_user): Extension<CurrentUser>,
     Extension(flash): Extension<Option<FlashMessage>>,
+    session: Session,
     Path(order_id): Path<Uuid>,
-) -> Result<Markup, HandlerError> {
+) -> HandlerResult {
     let user_id = current_user.require_authenticated();
 
     let order = queries::order::get_order_for_user(&db, order_id, user_id).await?;
 
+    if order.is_quote_expired(config.quotes().validity()) {
+        return Ok(FlashMessage::error(messages::QUOTE_EXPIRED)
+            .set_and_redirect(&session, paths::pages::TEXT_ANALYZER)
+            .await?);
+    }
+
+    let cash_receipt = queries::cash_receipt::get_cash_receipt(&db, order.order_id).await?;
+
     Ok(pages::checkout(
         &current_user,
         flash.as_ref(),
         config.site_name(),
         &order,
-        config.payment().toss_client_key(),
-    ))
+        config.payment().provider(),
+        config.payment().methods(),
+        cash_receipt.as_ref(),
+    )
+    .into_response())
 }
@@ -0,0 +1,43 @@
+use axum::{Extension, extract::{Path, State}, response::IntoResponse};
+use sqlx::PgPool;
+use tower_sessions::Session;
+use uuid::Uuid;
+
+use crate::{
+    auth::CurrentUser,
+    config::AppConfig,
+    constants::messages,
+    data::queries,
+    flash::FlashMessage,
+    handlers::errors::HandlerResult,
+    paths,
+    views::pages,
+};
+
+pub async fn get_credit_top_up(
+    State(config): State<AppConfig>,
+    State(db): State<PgPool>,
+    Extension(current_user): Extension<CurrentUser>,
+    Extension(flash): Extension<Option<FlashMessage>>,
+    session: Session,
+    Path(topup_id): Path<Uuid>,
+) -> HandlerResult {
+    let user_id = current_user.require_authenticated();
+
+    let topup = queries::credit::get_topup_for_user(&db, topup_id, user_id).await?;
+
+    if !topup.status.is_payable() {
+        return Ok(FlashMessage::error(messages::ORDER_ALREADY_PROCESSED)
+            .set_and_redirect(&session, paths::pages::DASHBOARD)
+            .await?);
+    }
+
+    Ok(pages::credit_top_up(
+        &current_user,
+        flash.as_ref(),
+        config.site_name(),
+        &topup,
+        config.payment().provider(),
+    )
+    .into_response())
+}
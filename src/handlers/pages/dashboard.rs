@@ -4,6 +4,7 @@ use sqlx::PgPool;
 use crate::{
     auth::CurrentUser,
     config::AppConfig,
+    constants::credits,
     data::queries,
     flash::FlashMessage,
     handlers::errors::HandlerError,
@@ -20,11 +21,17 @@ pub async fn get_dashboard(
     let user_id = current_user.require_authenticated();
 
     let recent_orders = queries::order::get_orders_for_user(&db, user_id, 10).await?;
+    let payment_emails_enabled = queries::user::get_payment_emails_enabled(&db, user_id).await?;
+    let credit_balance = queries::credit::get_balance(&db, user_id).await?;
+    let credit_history = queries::credit::get_transactions(&db, user_id, credits::HISTORY_LIMIT).await?;
 
     Ok(pages::dashboard(
         &current_user,
         flash.as_ref(),
         config.site_name(),
         recent_orders,
+        payment_emails_enabled,
+        credit_balance,
+        credit_history,
     ))
 }
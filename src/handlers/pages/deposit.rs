@@ -0,0 +1,48 @@
+use axum::{Extension, extract::{Path, State}, response::{IntoResponse, Redirect}};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{
+    auth::CurrentUser,
+    config::AppConfig,
+    data::queries,
+    flash::FlashMessage,
+    handlers::errors::HandlerResult,
+    models::order::PaymentStatus,
+    paths,
+    views::pages,
+};
+
+/// Deposit instructions while the order waits for a bank transfer. Once it has
+/// settled either way, the result or the quote page says what happened.
+pub async fn get_deposit(
+    State(config): State<AppConfig>,
+    State(db): State<PgPool>,
+    Extension(current_user): Extension<CurrentUser>,
+    Extension(flash): Extension<Option<FlashMessage>>,
+    Path(order_id): Path<Uuid>,
+) -> HandlerResult {
+    let user_id = current_user.require_authenticated();
+
+    let order = queries::order::get_order_for_user(&db, order_id, user_id).await?;
+
+    match order.payment_status {
+        PaymentStatus::AwaitingDeposit => {}
+        PaymentStatus::Paid => return Ok(Redirect::to(&paths::helpers::result_path(&order.order_id)).into_response()),
+        _ => return Ok(Redirect::to(&paths::helpers::quote_path(&order.order_id)).into_response()),
+    }
+
+    let account = queries::virtual_account::get_virtual_account(&db, order.order_id).await?;
+    let cash_receipt = queries::cash_receipt::get_cash_receipt(&db, order.order_id).await?;
+
+    Ok(pages::deposit(
+        &current_user,
+        flash.as_ref(),
+        config.site_name(),
+        &order,
+        &account,
+        cash_receipt.as_ref(),
+        config.payment().provider(),
+    )
+    .into_response())
+}
@@ -0,0 +1,34 @@
+use axum::{Extension, extract::{Path, State}};
+use maud::Markup;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{
+    auth::CurrentUser,
+    config::AppConfig,
+    data::queries,
+    flash::FlashMessage,
+    handlers::errors::HandlerError,
+    paths,
+    views::pages::dev as dev_views,
+};
+
+pub async fn get_fake_card_registration(
+    State(config): State<AppConfig>,
+    State(db): State<PgPool>,
+    Extension(current_user): Extension<CurrentUser>,
+    Extension(flash): Extension<Option<FlashMessage>>,
+    Path(subscription_id): Path<Uuid>,
+) -> Result<Markup, HandlerError> {
+    let user_id = current_user.require_authenticated();
+
+    let subscription = queries::subscription::get_subscription_for_user(&db, subscription_id, user_id).await?;
+
+    Ok(dev_views::fake_card_registration(
+        &current_user,
+        flash.as_ref(),
+        config.site_name(),
+        &paths::helpers::card_registered_path(&subscription.subscription_id),
+        &subscription.customer_key,
+    ))
+}
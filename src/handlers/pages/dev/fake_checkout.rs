@@ -0,0 +1,78 @@
+use axum::{Extension, extract::{Path, Query, State}};
+use maud::Markup;
+use serde::Deserialize;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{
+    auth::CurrentUser,
+    config::AppConfig,
+    data::queries,
+    flash::FlashMessage,
+    handlers::errors::HandlerError,
+    models::payment_method::PaymentMethod,
+    paths,
+    views::pages::dev as dev_views,
+};
+
+#[derive(Deserialize)]
+pub struct FakeCheckoutQuery {
+    method: Option<PaymentMethod>,
+}
+
+/// Opens the stand-in window for the method picked on the checkout page.
+pub async fn get_fake_checkout(
+    State(config): State<AppConfig>,
+    State(db): State<PgPool>,
+    Extension(current_user): Extension<CurrentUser>,
+    Extension(flash): Extension<Option<FlashMessage>>,
+    Path(order_id): Path<Uuid>,
+    Query(query): Query<FakeCheckoutQuery>,
+) -> Result<Markup, HandlerError> {
+    let user_id = current_user.require_authenticated();
+
+    let order = queries::order::get_order_for_user(&db, order_id, user_id).await?;
+    let method = query.method.unwrap_or(PaymentMethod::Card);
+
+    if method == PaymentMethod::VirtualAccount {
+        return Ok(dev_views::fake_virtual_account(&current_user, flash.as_ref(), config.site_name(), &order));
+    }
+
+    Ok(dev_views::fake_checkout(
+        &current_user,
+        flash.as_ref(),
+        config.site_name(),
+        dev_views::FakeCheckoutView {
+            order_number: &order.order_number,
+            amount: order.price_amount,
+            method,
+            verify_path: paths::actions::PAYMENT_VERIFY,
+            cancel_path: &paths::helpers::quote_path(&order.order_id),
+        },
+    ))
+}
+
+pub async fn get_fake_top_up_checkout(
+    State(config): State<AppConfig>,
+    State(db): State<PgPool>,
+    Extension(current_user): Extension<CurrentUser>,
+    Extension(flash): Extension<Option<FlashMessage>>,
+    Path(topup_id): Path<Uuid>,
+) -> Result<Markup, HandlerError> {
+    let user_id = current_user.require_authenticated();
+
+    let topup = queries::credit::get_topup_for_user(&db, topup_id, user_id).await?;
+
+    Ok(dev_views::fake_checkout(
+        &current_user,
+        flash.as_ref(),
+        config.site_name(),
+        dev_views::FakeCheckoutView {
+            order_number: &topup.order_number,
+            amount: topup.amount,
+            method: PaymentMethod::Card,
+            verify_path: paths::actions::CREDIT_TOP_UP_VERIFY,
+            cancel_path: paths::pages::DASHBOARD,
+        },
+    ))
+}
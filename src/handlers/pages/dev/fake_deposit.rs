@@ -0,0 +1,57 @@
+use std::any::Any;
+
+use axum::{Extension, extract::{Path, State}};
+use sqlx::PgPool;
+use tower_sessions::Session;
+use uuid::Uuid;
+
+use crate::{
+    auth::CurrentUser,
+    config::AppConfig,
+    constants::messages,
+    data::queries,
+    flash::FlashMessage,
+    handlers::errors::HandlerResult,
+    models::order::OrderEventSource,
+    paths,
+    payment::{FakeGateway, SharedGateway, SyncOutcome, sync_order, update_cash_receipt},
+};
+
+/// Transfers the money into the order's fake virtual account and settles the
+/// order the way the gateway's deposit webhook would.
+pub async fn get_fake_deposit(
+    State(config): State<AppConfig>,
+    State(db): State<PgPool>,
+    State(gateway): State<SharedGateway>,
+    Extension(current_user): Extension<CurrentUser>,
+    session: Session,
+    Path(order_id): Path<Uuid>,
+) -> HandlerResult {
+    let user_id = current_user.require_authenticated();
+
+    let order = queries::order::get_order_for_user(&db, order_id, user_id).await?;
+    let deposit_path = paths::helpers::deposit_path(&order.order_id);
+
+    // Only registered with `PAYMENT_GATEWAY=fake`
+    let gateway: &dyn Any = gateway.as_ref();
+    let Some(fake) = gateway.downcast_ref::<FakeGateway>() else {
+        return Ok(FlashMessage::error(messages::PAYMENT_FAILED).set_and_redirect(&session, &deposit_path).await?);
+    };
+
+    let payment = match fake.deposit(&order.order_number) {
+        Ok(payment) => payment,
+        Err(e) => {
+            return Ok(FlashMessage::error(e.to_string()).set_and_redirect(&session, &deposit_path).await?);
+        }
+    };
+
+    match sync_order(&db, config.email(), &payment, OrderEventSource::Webhook).await? {
+        SyncOutcome::Updated { .. } | SyncOutcome::Unchanged(_) => {
+            update_cash_receipt(&db, fake, order.order_id).await;
+            Ok(FlashMessage::success(messages::DEPOSIT_RECEIVED)
+                .set_and_redirect(&session, &paths::helpers::result_path(&order.order_id))
+                .await?)
+        }
+        outcome => Ok(FlashMessage::error(outcome.to_string()).set_and_redirect(&session, &deposit_path).await?),
+    }
+}
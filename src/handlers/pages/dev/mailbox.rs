@@ -0,0 +1,49 @@
+use axum::{Extension, extract::{Path, State}};
+use maud::Markup;
+
+use crate::{
+    auth::CurrentUser,
+    config::AppConfig,
+    constants::errors,
+    data::errors::DataError,
+    flash::FlashMessage,
+    handlers::errors::HandlerError,
+    views::pages::dev as dev_views,
+};
+
+pub async fn get_dev_mailbox(
+    State(config): State<AppConfig>,
+    Extension(current_user): Extension<CurrentUser>,
+    Extension(flash): Extension<Option<FlashMessage>>,
+) -> Result<Markup, HandlerError> {
+    let messages = config.email().captured_messages().await?;
+
+    Ok(dev_views::mailbox(
+        &current_user,
+        flash.as_ref(),
+        config.site_name(),
+        messages,
+    ))
+}
+
+pub async fn get_dev_mailbox_message(
+    State(config): State<AppConfig>,
+    Path(message_id): Path<String>,
+    Extension(current_user): Extension<CurrentUser>,
+    Extension(flash): Extension<Option<FlashMessage>>,
+) -> Result<Markup, HandlerError> {
+    let message = config
+        .email()
+        .captured_messages()
+        .await?
+        .into_iter()
+        .find(|message| message.id == message_id)
+        .ok_or(DataError::NotFound(errors::EMAIL_NOT_FOUND))?;
+
+    Ok(dev_views::mailbox_message(
+        &current_user,
+        flash.as_ref(),
+        config.site_name(),
+        message,
+    ))
+}
@@ -0,0 +1,9 @@
+mod fake_card_registration;
+mod fake_checkout;
+mod fake_deposit;
+mod mailbox;
+
+pub use fake_card_registration::get_fake_card_registration;
+pub use fake_checkout::{get_fake_checkout, get_fake_top_up_checkout};
+pub use fake_deposit::get_fake_deposit;
+pub use mailbox::{get_dev_mailbox, get_dev_mailbox_message};
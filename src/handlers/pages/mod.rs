@@ -1,18 +1,29 @@
 pub mod admin;
+pub mod dev;
 mod checkout;
+mod credit_top_up;
 mod dashboard;
+mod deposit;
 mod quote;
+mod receipt;
 mod result;
 mod root;
 mod sign_in;
+mod subscription;
 mod text_analyzer;
 mod todos;
+mod unsubscribe;
 
 pub use checkout::get_checkout;
+pub use credit_top_up::get_credit_top_up;
 pub use dashboard::get_dashboard;
+pub use deposit::get_deposit;
 pub use quote::get_quote;
+pub use receipt::get_receipt;
 pub use result::get_result;
 pub use root::get_root;
 pub use sign_in::get_sign_in;
+pub use subscription::{get_subscription, get_subscription_card};
 pub use text_analyzer::get_text_analyzer;
 pub use todos::get_todos;
+pub use unsubscribe::{UnsubscribeQuery, get_unsubscribe};
@@ -1,9 +1,8 @@
-use axum::{Extension, extract::{Path, State}};
+use axum::{Extension, extract::{Path, State}, response::{IntoResponse, Redirect}};
 use sqlx::PgPool;
 use uuid::Uuid;
 
-use crate::{auth::CurrentUser, config::AppConfig, data::queries, flash::FlashMessage, handlers::errors::HandlerError, views::pages};
-use maud::Markup;
+use crate::{auth::CurrentUser, config::AppConfig, data::queries, flash::FlashMessage, handlers::errors::HandlerResult, models::order::PaymentStatus, paths, views::pages};
 
 pub async fn get_quote(
     State(config): State<AppConfig>,
@@ -11,10 +10,30 @@ pub async fn get_quote(
     Extension(current_user): Extension<CurrentUser>,
     Extension(flash): Extension<Option<FlashMessage>>,
     Path(order_id): Path<Uuid>,
-) -> Result<Markup, HandlerError> {
+) -> HandlerResult {
     let user_id = current_user.require_authenticated();
 
     let order = queries::order::get_order_for_user(&db, order_id, user_id).await?;
 
-    Ok(pages::quote(&current_user, flash.as_ref(), config.site_name(), &order))
+    if order.payment_status == PaymentStatus::AwaitingDeposit {
+        return Ok(Redirect::to(&paths::helpers::deposit_path(&order.order_id)).into_response());
+    }
+
+    let discount = match order.discount_code_id {
+        Some(discount_code_id) => queries::discount::get_discount_code(&db, discount_code_id).await?,
+        None => None,
+    };
+
+    let credit_balance = queries::credit::get_balance(&db, user_id).await?;
+
+    Ok(pages::quote(
+        &current_user,
+        flash.as_ref(),
+        config.site_name(),
+        &order,
+        discount.as_ref(),
+        config.quotes().validity(),
+        credit_balance,
+    )
+    .into_response())
 }
@@ -0,0 +1,44 @@
+use axum::{
+    Extension,
+    extract::{Path, State},
+    http::header,
+    response::IntoResponse,
+};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{
+    auth::CurrentUser,
+    config::AppConfig,
+    constants::errors,
+    data::{errors::DataError, queries},
+    handlers::errors::HandlerResult,
+    receipt,
+};
+
+pub async fn get_receipt(
+    State(config): State<AppConfig>,
+    State(db): State<PgPool>,
+    Extension(current_user): Extension<CurrentUser>,
+    Path(order_id): Path<Uuid>,
+) -> HandlerResult {
+    let user_id = current_user.require_authenticated();
+
+    let order = queries::order::get_order_for_user(&db, order_id, user_id).await?;
+
+    if !order.payment_status.has_receipt() {
+        return Err(DataError::Unauthorized(errors::RECEIPT_NOT_AVAILABLE).into());
+    }
+
+    let refunds = queries::refund::get_refunds_for_order(&db, order.order_id).await?;
+    let pdf = receipt::render(config.site_name(), &order, &refunds)?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/pdf".to_string()),
+            (header::CONTENT_DISPOSITION, format!("attachment; filename=\"{}\"", receipt::file_name(&order))),
+        ],
+        pdf,
+    )
+        .into_response())
+}
@@ -20,5 +20,7 @@ pub async fn get_result(
         return Err(DataError::Unauthorized(errors::PAYMENT_NOT_COMPLETED).into());
     }
 
-    Ok(pages::result(&current_user, flash.as_ref(), config.site_name(), &order))
+    let cash_receipt = queries::cash_receipt::get_cash_receipt(&db, order.order_id).await?;
+
+    Ok(pages::result(&current_user, flash.as_ref(), config.site_name(), &order, cash_receipt.as_ref()))
 }
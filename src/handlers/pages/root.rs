@@ -1,11 +1,15 @@
-use axum::{Extension, extract::State};
+use std::net::SocketAddr;
+
+use axum::{Extension, extract::{ConnectInfo, State}};
 use sqlx::PgPool;
-use crate::{auth::CurrentUser, config::AppConfig, data::queries, flash::FlashMessage, handlers::errors::HandlerError, views::pages};
+use time::OffsetDateTime;
+use crate::{auth::CurrentUser, config::AppConfig, data::queries, flash::FlashMessage, handlers::errors::HandlerError, spam, views::pages};
 use maud::Markup;
 
 pub async fn get_root(
     State(config): State<AppConfig>,
     State(db): State<PgPool>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
     Extension(current_user): Extension<CurrentUser>,
     Extension(flash): Extension<Option<FlashMessage>>,
 ) -> Result<Markup, HandlerError> {
@@ -16,5 +20,20 @@ pub async fn get_root(
         CurrentUser::Guest => None,
     };
 
-    Ok(pages::root(&current_user, flash.as_ref(), config.site_name(), user_email.as_deref(), None, None, None))
+    let form_stamp = spam::issue_form_stamp(config.signer(), OffsetDateTime::now_utc());
+    let pow = config.proof_of_work().issue(addr.ip());
+
+    Ok(pages::root(
+        &current_user,
+        flash.as_ref(),
+        config.site_name(),
+        pages::ContactFormView {
+            email: user_email.as_deref(),
+            message: None,
+            email_error: None,
+            message_error: None,
+            form_stamp: &form_stamp,
+            pow: pow.as_ref(),
+        },
+    ))
 }
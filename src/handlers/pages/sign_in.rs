@@ -1,11 +1,16 @@
-use axum::{Extension, extract::State};
+use std::net::SocketAddr;
+
+use axum::{Extension, extract::{ConnectInfo, State}};
 use crate::{auth::CurrentUser, config::AppConfig, flash::FlashMessage, handlers::errors::HandlerError, views::pages};
 use maud::Markup;
 
 pub async fn get_sign_in(
     State(config): State<AppConfig>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
     Extension(current_user): Extension<CurrentUser>,
     Extension(flash): Extension<Option<FlashMessage>>,
 ) -> Result<Markup, HandlerError> {
-    Ok(pages::sign_in(&current_user, flash.as_ref(), config.site_name(), None, None))
+    let pow = config.proof_of_work().issue(addr.ip());
+
+    Ok(pages::sign_in(&current_user, flash.as_ref(), config.site_name(), None, None, pow.as_ref()))
 }
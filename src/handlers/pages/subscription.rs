@@ -0,0 +1,72 @@
+use axum::{Extension, extract::{Path, State}, response::IntoResponse};
+use sqlx::PgPool;
+use tower_sessions::Session;
+use uuid::Uuid;
+
+use crate::{
+    auth::CurrentUser,
+    config::AppConfig,
+    constants::{errors, subscriptions},
+    data::queries,
+    flash::FlashMessage,
+    handlers::errors::HandlerResult,
+    paths,
+    views::pages,
+};
+
+pub async fn get_subscription(
+    State(config): State<AppConfig>,
+    State(db): State<PgPool>,
+    Extension(current_user): Extension<CurrentUser>,
+    Extension(flash): Extension<Option<FlashMessage>>,
+) -> HandlerResult {
+    let user_id = current_user.require_authenticated();
+
+    let subscription = queries::subscription::get_live_subscription(&db, user_id).await?;
+    let (plans, charges) = match &subscription {
+        Some(subscription) => (
+            Vec::new(),
+            queries::subscription::get_charges(&db, subscription.subscription_id, subscriptions::CHARGE_HISTORY_LIMIT)
+                .await?,
+        ),
+        None => (queries::subscription::get_active_plans(&db).await?, Vec::new()),
+    };
+
+    Ok(pages::subscription(
+        &current_user,
+        flash.as_ref(),
+        config.site_name(),
+        &plans,
+        subscription.as_ref(),
+        &charges,
+    )
+    .into_response())
+}
+
+pub async fn get_subscription_card(
+    State(config): State<AppConfig>,
+    State(db): State<PgPool>,
+    Extension(current_user): Extension<CurrentUser>,
+    Extension(flash): Extension<Option<FlashMessage>>,
+    session: Session,
+    Path(subscription_id): Path<Uuid>,
+) -> HandlerResult {
+    let user_id = current_user.require_authenticated();
+
+    let subscription = queries::subscription::get_subscription_for_user(&db, subscription_id, user_id).await?;
+
+    if !subscription.status.accepts_card() {
+        return Ok(FlashMessage::error(errors::SUBSCRIPTION_CARD_NOT_NEEDED)
+            .set_and_redirect(&session, paths::pages::SUBSCRIPTION)
+            .await?);
+    }
+
+    Ok(pages::subscription_card(
+        &current_user,
+        flash.as_ref(),
+        config.site_name(),
+        &subscription,
+        config.payment().provider(),
+    )
+    .into_response())
+}
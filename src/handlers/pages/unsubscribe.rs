@@ -0,0 +1,39 @@
+use axum::{
+    Extension,
+    extract::{Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use serde::Deserialize;
+
+use crate::{
+    auth::CurrentUser,
+    config::AppConfig,
+    constants::errors,
+    email::UNSUBSCRIBE_TOKEN_PURPOSE,
+    flash::FlashMessage,
+    views::pages,
+};
+
+#[derive(Deserialize)]
+pub struct UnsubscribeQuery {
+    #[serde(default)]
+    pub token: String,
+}
+
+pub async fn get_unsubscribe(
+    State(config): State<AppConfig>,
+    Extension(current_user): Extension<CurrentUser>,
+    Extension(flash): Extension<Option<FlashMessage>>,
+    Query(query): Query<UnsubscribeQuery>,
+) -> Response {
+    let Some(email) = config.signer().verify(UNSUBSCRIBE_TOKEN_PURPOSE, &query.token) else {
+        return (
+            StatusCode::BAD_REQUEST,
+            pages::unsubscribe_invalid(&current_user, config.site_name(), errors::UNSUBSCRIBE_LINK_INVALID),
+        )
+            .into_response();
+    };
+
+    pages::unsubscribe(&current_user, flash.as_ref(), config.site_name(), &email, &query.token).into_response()
+}
@@ -0,0 +1,32 @@
+use std::time::Duration;
+
+use sqlx::PgPool;
+use tokio::time::MissedTickBehavior;
+
+use crate::{
+    constants::cash_receipts,
+    payment::{self, SharedGateway},
+};
+
+/// Issues cash receipts for transfers that settled without the customer present,
+/// such as virtual account deposits, and retries those the gateway did not take.
+pub async fn run(db: PgPool, gateway: SharedGateway) {
+    let mut interval = tokio::time::interval(Duration::from_secs(cash_receipts::SWEEP_INTERVAL_SECONDS));
+    interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    loop {
+        interval.tick().await;
+
+        match payment::sync_pending_cash_receipts(&db, gateway.as_ref()).await {
+            Ok(summary) if summary.issued + summary.cancelled + summary.refused + summary.errors == 0 => {}
+            Ok(summary) => tracing::info!(
+                "Cash receipts: {} issued, {} cancelled, {} refused, {} errors",
+                summary.issued,
+                summary.cancelled,
+                summary.refused,
+                summary.errors
+            ),
+            Err(e) => tracing::error!("Cash receipt sweep failed: {}", e),
+        }
+    }
+}
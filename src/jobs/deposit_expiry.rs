@@ -0,0 +1,32 @@
+use std::time::Duration;
+
+use sqlx::PgPool;
+use time::OffsetDateTime;
+use tokio::time::MissedTickBehavior;
+
+use crate::{
+    constants::deposits,
+    email::EmailConfig,
+    payment::{self, SharedGateway},
+};
+
+/// Cancels orders whose virtual account expired without a deposit.
+pub async fn run(db: PgPool, gateway: SharedGateway, email: EmailConfig) {
+    let mut interval = tokio::time::interval(Duration::from_secs(deposits::EXPIRY_SWEEP_INTERVAL_SECONDS));
+    interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    loop {
+        interval.tick().await;
+
+        match payment::expire_overdue_deposits(&db, gateway.as_ref(), &email, OffsetDateTime::now_utc()).await {
+            Ok(summary) if summary.expired + summary.settled + summary.errors == 0 => {}
+            Ok(summary) => tracing::info!(
+                "Deposit expiry: {} expired, {} settled, {} errors",
+                summary.expired,
+                summary.settled,
+                summary.errors
+            ),
+            Err(e) => tracing::error!("Deposit expiry failed: {}", e),
+        }
+    }
+}
@@ -0,0 +1,31 @@
+//! Background jobs started alongside the server.
+//!
+//! Each job runs on its own Tokio task for the lifetime of the process. Failures
+//! are logged and retried on the next run; they never stop the server.
+
+mod cash_receipts;
+mod deposit_expiry;
+mod order_expiry;
+mod reconciliation;
+mod subscription_renewal;
+
+use axum::extract::FromRef;
+use sqlx::PgPool;
+
+use crate::{
+    config::{AppConfig, AppState},
+    payment::SharedGateway,
+};
+
+pub fn spawn_all(state: &AppState) {
+    let db = PgPool::from_ref(state);
+    let config = AppConfig::from_ref(state);
+
+    let gateway = SharedGateway::from_ref(state);
+
+    tokio::spawn(order_expiry::run(db.clone(), config.quotes().clone()));
+    tokio::spawn(cash_receipts::run(db.clone(), gateway.clone()));
+    tokio::spawn(deposit_expiry::run(db.clone(), gateway.clone(), config.email().clone()));
+    tokio::spawn(subscription_renewal::run(db.clone(), gateway.clone(), config.email().clone()));
+    tokio::spawn(reconciliation::run(db, gateway, config.reconciliation().clone()));
+}
@@ -0,0 +1,24 @@
+use std::time::Duration;
+
+use sqlx::PgPool;
+use time::OffsetDateTime;
+use tokio::time::MissedTickBehavior;
+
+use crate::{config::QuoteConfig, constants::quotes, data::commands};
+
+/// Cancels orders whose quote expired without payment.
+pub async fn run(db: PgPool, config: QuoteConfig) {
+    let mut interval = tokio::time::interval(Duration::from_secs(quotes::EXPIRY_SWEEP_INTERVAL_SECONDS));
+    interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    loop {
+        interval.tick().await;
+
+        let cutoff = OffsetDateTime::now_utc() - config.validity() - time::Duration::minutes(quotes::EXPIRY_GRACE_MINUTES);
+        match commands::order::expire_stale_orders(&db, cutoff, config.purge_expired_content()).await {
+            Ok(0) => {}
+            Ok(expired) => tracing::info!("Cancelled {} expired orders", expired),
+            Err(e) => tracing::error!("Failed to expire stale orders: {}", e),
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use sqlx::PgPool;
+use time::{OffsetDateTime, Time};
+
+use crate::{config::ReconciliationConfig, payment::{self, SharedGateway}};
+
+/// Compares recent orders with the gateway once a night.
+pub async fn run(db: PgPool, gateway: SharedGateway, config: ReconciliationConfig) {
+    loop {
+        let now = OffsetDateTime::now_utc();
+        let next_run = next_run_after(now, config.hour_utc());
+        tokio::time::sleep((next_run - now).unsigned_abs()).await;
+
+        let window_end = OffsetDateTime::now_utc();
+        match payment::reconcile(&db, gateway.as_ref(), window_end - config.window(), window_end).await {
+            Ok(summary) => tracing::info!(
+                "Reconciliation checked {} orders: {} new findings, {} errors",
+                summary.checked,
+                summary.findings,
+                summary.errors
+            ),
+            Err(e) => tracing::error!("Reconciliation failed: {}", e),
+        }
+    }
+}
+
+/// The next time it is `hour_utc` o'clock, strictly after `now`.
+fn next_run_after(now: OffsetDateTime, hour_utc: u8) -> OffsetDateTime {
+    let today = now.replace_time(Time::from_hms(hour_utc, 0, 0).unwrap_or(Time::MIDNIGHT));
+    if today > now { today } else { today + time::Duration::days(1) }
+}
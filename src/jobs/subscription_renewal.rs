@@ -0,0 +1,33 @@
+use std::time::Duration;
+
+use sqlx::PgPool;
+use time::OffsetDateTime;
+use tokio::time::MissedTickBehavior;
+
+use crate::{
+    constants::subscriptions,
+    email::EmailConfig,
+    payment::{self, SharedGateway},
+};
+
+/// Charges subscriptions as their periods end and retries overdue ones.
+pub async fn run(db: PgPool, gateway: SharedGateway, email: EmailConfig) {
+    let mut interval = tokio::time::interval(Duration::from_secs(subscriptions::RENEWAL_SWEEP_INTERVAL_SECONDS));
+    interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    loop {
+        interval.tick().await;
+
+        match payment::renew_due(&db, gateway.as_ref(), &email, OffsetDateTime::now_utc()).await {
+            Ok(summary) if summary.renewed + summary.failed + summary.ended + summary.errors == 0 => {}
+            Ok(summary) => tracing::info!(
+                "Subscription renewals: {} renewed, {} failed, {} ended, {} errors",
+                summary.renewed,
+                summary.failed,
+                summary.ended,
+                summary.errors
+            ),
+            Err(e) => tracing::error!("Subscription renewal failed: {}", e),
+        }
+    }
+}
@@ -8,16 +8,25 @@ mod config;
 mod constants;
 mod data;
 mod email;
-mod email_templates;
+mod email_capture;
 mod flash;
 mod formatting;
 mod handlers;
 mod init;
+mod jobs;
 mod magic_link;
 mod middlewares;
 mod models;
+mod money;
+mod notifications;
 mod paths;
+mod payment;
+mod proof_of_work;
+mod receipt;
 mod routes;
+mod signing;
+mod spam;
+mod tax;
 mod validation;
 mod views;
 
@@ -32,7 +41,12 @@ async fn main() {
     let config = AppConfig::from_env().unwrap_or_else(|e| {
         eprintln!("Configuration error: {}", e);
         eprintln!("\nPlease check your .env file and ensure all required variables are set.");
-        eprintln!("Required: DATABASE_URL, SERVER_ADDR, SITE_NAME");
+        eprintln!("Required: DATABASE_URL, SERVER_ADDR, SITE_NAME, SECRET_KEY");
+        std::process::exit(1);
+    });
+
+    let payment_gateway = payment::from_config(config.payment()).unwrap_or_else(|e| {
+        eprintln!("Payment gateway error: {}", e);
         std::process::exit(1);
     });
 
@@ -40,7 +54,8 @@ async fn main() {
     let session_layer = init::init_session(db.clone()).await;
 
     let server_addr = config.server_addr().to_string();
-    let state = AppState::new(db, config);
+    let state = AppState::new(db, config, payment_gateway);
+    jobs::spawn_all(&state);
 
     let listener = tokio::net::TcpListener::bind(&server_addr)
         .await
@@ -1,13 +1,38 @@
+use sqlx::types::Json;
 use time::OffsetDateTime;
-use crate::models::order::PaymentStatus;
+use crate::{
+    models::{
+        cash_receipt::CashReceipt, order::PaymentStatus, order_item::OrderItem, payment_attempt::PaymentAttempt,
+        payment_method::PaymentMethod, refund::Refund,
+    },
+    money::Money,
+};
 
 pub use crate::models::pagination::PaginatedResult;
 
 pub struct AdminStats {
     pub total_users: i64,
     pub total_orders: i64,
-    pub total_revenue: i32,
+    /// Amount charged less refunds, VAT included
+    pub total_revenue: Money,
+    /// Revenue excluding VAT; refunds are split in each order's net/tax proportion
+    pub net_revenue: Money,
     pub orders_last_7_days: i64,
+    pub products: Vec<ProductRevenue>,
+}
+
+/// Sales of one product, from the order items of paid orders.
+pub struct ProductRevenue {
+    pub product_name: String,
+    pub quantity: i64,
+    /// Item amounts with each order's discount and refunds spread over its items
+    /// in proportion to their list price, VAT included
+    pub revenue: Money,
+}
+
+pub struct AdminUser {
+    pub user_id: i32,
+    pub email: String,
 }
 
 pub struct UserListItem {
@@ -16,7 +41,7 @@ pub struct UserListItem {
     pub is_admin: bool,
     pub created_at: OffsetDateTime,
     pub order_count: i64,
-    pub total_spent: i32,
+    pub total_spent: Money,
 }
 
 pub struct UserDetail {
@@ -25,15 +50,17 @@ pub struct UserDetail {
     pub is_admin: bool,
     pub created_at: OffsetDateTime,
     pub order_count: i64,
-    pub total_spent: i32,
+    pub total_spent: Money,
+    pub credit_balance: Money,
 }
 
 pub struct OrderListItem {
     pub order_id: String,
     pub order_number: String,
     pub user_email: String,
-    pub price_amount: i32,
+    pub price_amount: Money,
     pub payment_status: PaymentStatus,
+    pub payment_method: Option<PaymentMethod>,
     pub created_at: OffsetDateTime,
 }
 
@@ -42,11 +69,31 @@ pub struct OrderDetail {
     pub order_number: String,
     pub user_id: i32,
     pub user_email: String,
-    pub price_amount: i32,
+    pub list_price_amount: Money,
+    pub discount_amount: Money,
+    pub discount_code: Option<String>,
+    pub price_amount: Money,
+    pub net_amount: Money,
+    pub tax_amount: Money,
+    pub tax_rate_percent: i32,
+    pub pricing_rule_id: i32,
     pub payment_status: PaymentStatus,
     pub created_at: OffsetDateTime,
     pub paid_at: Option<OffsetDateTime>,
     pub payment_key: Option<String>,
-    pub filename: String,
-    pub text_length: i32,
+    pub payment_method: Option<PaymentMethod>,
+    pub items: Json<Vec<OrderItem>>,
+}
+
+/// What happened at the gateway for an order, shown under its payment information.
+pub struct OrderPaymentActivity {
+    pub attempts: Vec<PaymentAttempt>,
+    pub cash_receipt: Option<CashReceipt>,
+}
+
+/// An order's refunds and what is left to refund.
+pub struct OrderRefunds {
+    pub refunds: Vec<Refund>,
+    /// Paid amount less the refunds; zero if the order is not refundable
+    pub refundable: Money,
 }
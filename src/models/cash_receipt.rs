@@ -0,0 +1,130 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{constants::errors, money::Money};
+
+/// What a cash receipt (현금영수증) is for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum CashReceiptType {
+    /// 소득공제: an individual's income tax deduction
+    IncomeDeduction,
+    /// 지출증빙: proof of a business expense
+    ExpenseProof,
+}
+
+impl CashReceiptType {
+    pub const ALL: [Self; 2] = [Self::IncomeDeduction, Self::ExpenseProof];
+
+    pub fn display_text(&self) -> &'static str {
+        match self {
+            Self::IncomeDeduction => "Income deduction (소득공제)",
+            Self::ExpenseProof => "Expense proof (지출증빙)",
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::IncomeDeduction => "income_deduction",
+            Self::ExpenseProof => "expense_proof",
+        }
+    }
+
+    /// Strips separators from the identifier and checks it suits the receipt type:
+    /// a mobile phone or cash receipt card number for income deduction, a business
+    /// registration number or mobile phone number for expense proof.
+    pub fn normalize_identifier(&self, identifier: &str) -> Result<String, &'static str> {
+        let digits: String = identifier.chars().filter(|c| !matches!(c, '-' | ' ')).collect();
+        let is_digits = !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit());
+        let is_phone = is_digits && digits.starts_with("01") && (10..=11).contains(&digits.len());
+
+        match self {
+            Self::IncomeDeduction if is_phone || (is_digits && (13..=19).contains(&digits.len())) => Ok(digits),
+            Self::IncomeDeduction => Err(errors::CASH_RECEIPT_INCOME_DEDUCTION_INVALID),
+            Self::ExpenseProof if is_phone || (is_digits && digits.len() == 10) => Ok(digits),
+            Self::ExpenseProof => Err(errors::CASH_RECEIPT_EXPENSE_PROOF_INVALID),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "text", rename_all = "lowercase")]
+pub enum CashReceiptStatus {
+    /// Waiting for the order to be paid
+    Requested,
+    Issued,
+    /// The gateway refused to issue it; see the failure message
+    Failed,
+    /// Cancelled in full after a refund
+    Cancelled,
+}
+
+impl CashReceiptStatus {
+    pub fn display_text(&self) -> &'static str {
+        match self {
+            Self::Requested => "Requested",
+            Self::Issued => "Issued",
+            Self::Failed => "Failed",
+            Self::Cancelled => "Cancelled",
+        }
+    }
+
+    pub fn css_class(&self) -> &'static str {
+        match self {
+            Self::Requested => "text-yellow-600",
+            Self::Issued => "text-green-600",
+            Self::Failed => "text-red-600",
+            Self::Cancelled => "text-gray-600",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CashReceipt {
+    pub receipt_type: CashReceiptType,
+    /// Digits only
+    pub identifier: String,
+    pub status: CashReceiptStatus,
+    pub receipt_key: Option<String>,
+    pub issue_number: Option<String>,
+    /// The gateway's printable receipt
+    pub receipt_url: Option<String>,
+    pub issued_amount: Option<Money>,
+    pub cancelled_amount: Money,
+    pub failure_message: Option<String>,
+}
+
+impl CashReceipt {
+    /// The identifier with all but its last four digits hidden.
+    pub fn masked_identifier(&self) -> String {
+        let visible = self.identifier.len().saturating_sub(4);
+        format!("{}{}", "*".repeat(visible), &self.identifier[visible..])
+    }
+}
+
+/// Cash receipt details entered on the checkout page.
+#[derive(Deserialize)]
+pub struct CashReceiptForm {
+    pub receipt_type: CashReceiptType,
+    pub identifier: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_identifier_must_suit_the_receipt_type() {
+        let income = CashReceiptType::IncomeDeduction;
+        let expense = CashReceiptType::ExpenseProof;
+
+        assert_eq!(income.normalize_identifier("010-1234-5678"), Ok("01012345678".to_string()));
+        assert_eq!(income.normalize_identifier("1234 5678 9012 3456"), Ok("1234567890123456".to_string()));
+        assert!(income.normalize_identifier("123-45-67890").is_err());
+
+        assert_eq!(expense.normalize_identifier("123-45-67890"), Ok("1234567890".to_string()));
+        assert_eq!(expense.normalize_identifier("01012345678"), Ok("01012345678".to_string()));
+        assert!(expense.normalize_identifier("12345").is_err());
+        assert!(expense.normalize_identifier("abc-de-fghij").is_err());
+    }
+}
@@ -1,7 +1,8 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
 use validator::Validate;
 
-use crate::validation::EMAIL_RX;
+use crate::{constants::errors, validation::EMAIL_RX};
 
 pub const FIELD_EMAIL: &str = "email";
 pub const FIELD_MESSAGE: &str = "message";
@@ -12,4 +13,143 @@ pub struct ContactForm {
     pub email: String,
     #[validate(length(min = 1, message = "Message cannot be empty"))]
     pub message: String,
+    /// Honeypot, see [`crate::constants::spam::HONEYPOT_FIELD`].
+    #[serde(default)]
+    pub website: String,
+    /// Signed render timestamp from [`crate::spam::issue_form_stamp`].
+    #[serde(default)]
+    pub form_stamp: String,
+    #[serde(default)]
+    pub pow_challenge: String,
+    #[serde(default)]
+    pub pow_solution: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum InquiryStatus {
+    New,
+    InProgress,
+    Resolved,
+}
+
+impl InquiryStatus {
+    pub const ALL: [Self; 3] = [Self::New, Self::InProgress, Self::Resolved];
+
+    pub fn display_text(&self) -> &'static str {
+        match self {
+            Self::New => "New",
+            Self::InProgress => "In Progress",
+            Self::Resolved => "Resolved",
+        }
+    }
+
+    pub fn css_class(&self) -> &'static str {
+        match self {
+            Self::New => "text-indigo-600",
+            Self::InProgress => "text-yellow-600",
+            Self::Resolved => "text-green-600",
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::New => "new",
+            Self::InProgress => "in_progress",
+            Self::Resolved => "resolved",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "text", rename_all = "lowercase")]
+pub enum InquiryEntryKind {
+    Note,
+    Reply,
+}
+
+pub struct ContactInquiry {
+    pub inquiry_id: i32,
+    pub user_id: Option<i32>,
+    pub email: String,
+    pub message: String,
+    pub status: InquiryStatus,
+    pub assigned_to: Option<i32>,
+    pub assignee_email: Option<String>,
+    pub spam_score: i32,
+    pub spam_reasons: Vec<String>,
+    pub quarantined_at: Option<OffsetDateTime>,
+    pub created_at: OffsetDateTime,
+    pub updated_at: OffsetDateTime,
+}
+
+pub struct InquiryListItem {
+    pub inquiry_id: i32,
+    pub email: String,
+    pub message: String,
+    pub status: InquiryStatus,
+    pub assignee_email: Option<String>,
+    pub created_at: OffsetDateTime,
+}
+
+pub struct QuarantinedInquiry {
+    pub inquiry_id: i32,
+    pub email: String,
+    pub message: String,
+    pub spam_score: i32,
+    pub spam_reasons: Vec<String>,
+    pub created_at: OffsetDateTime,
+}
+
+/// A note or reply in an inquiry's thread.
+pub struct InquiryEntry {
+    pub kind: InquiryEntryKind,
+    pub body: String,
+    pub author_email: Option<String>,
+    pub created_at: OffsetDateTime,
+}
+
+#[derive(Deserialize)]
+pub struct InquiryStatusForm {
+    pub status: InquiryStatus,
+}
+
+#[derive(Deserialize)]
+pub struct AssignInquiryForm {
+    /// Empty to unassign.
+    #[serde(default)]
+    pub assigned_to: String,
+}
+
+impl AssignInquiryForm {
+    /// The chosen admin's user ID, `None` to unassign.
+    pub fn assignee(&self) -> Result<Option<i32>, &'static str> {
+        match self.assigned_to.trim() {
+            "" => Ok(None),
+            value => value.parse().map(Some).map_err(|_| errors::INQUIRY_ASSIGNEE_INVALID),
+        }
+    }
+}
+
+/// Shared by the internal note and email reply forms.
+#[derive(Deserialize)]
+pub struct InquiryEntryForm {
+    pub body: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn form(assigned_to: &str) -> AssignInquiryForm {
+        AssignInquiryForm { assigned_to: assigned_to.to_string() }
+    }
+
+    #[test]
+    fn test_assignee_rejects_invalid_values() {
+        assert_eq!(form("").assignee(), Ok(None));
+        assert_eq!(form("7").assignee(), Ok(Some(7)));
+        assert_eq!(form("admin").assignee(), Err(errors::INQUIRY_ASSIGNEE_INVALID));
+    }
 }
@@ -0,0 +1,107 @@
+use serde::Deserialize;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use super::order::PaymentStatus;
+use crate::{constants::credits, money::Money};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+pub enum CreditTransactionKind {
+    TopUp,
+    /// An order paid with credits
+    Spend,
+    /// A credits-paid order refunded to the wallet
+    Refund,
+    /// A manual change by an admin
+    Adjustment,
+}
+
+impl CreditTransactionKind {
+    pub fn display_text(&self) -> &'static str {
+        match self {
+            Self::TopUp => "Top-up",
+            Self::Spend => "Order payment",
+            Self::Refund => "Refund",
+            Self::Adjustment => "Adjustment",
+        }
+    }
+}
+
+/// One entry of a user's credit ledger.
+#[derive(Debug, Clone)]
+pub struct CreditTransaction {
+    pub kind: CreditTransactionKind,
+    /// Positive for credits added, negative for credits used.
+    pub amount: Money,
+    pub balance_after: Money,
+    pub order_id: Option<Uuid>,
+    pub note: Option<String>,
+    pub created_at: OffsetDateTime,
+}
+
+/// A credit purchase, paid through the gateway like an order.
+///
+/// `status` is only ever pending, paid or failed.
+#[derive(Debug, Clone)]
+pub struct CreditTopUp {
+    pub topup_id: Uuid,
+    pub user_id: i32,
+    pub order_number: String,
+    pub amount: Money,
+    pub status: PaymentStatus,
+}
+
+impl CreditTopUp {
+    pub fn generate_order_number(user_id: i32) -> String {
+        let uuid_string = Uuid::new_v4().simple().to_string();
+        format!("{}{}-{}", credits::TOP_UP_ORDER_PREFIX, user_id, &uuid_string[..8])
+    }
+
+    pub fn is_top_up_order_number(order_number: &str) -> bool {
+        order_number.starts_with(credits::TOP_UP_ORDER_PREFIX)
+    }
+}
+
+#[derive(Deserialize)]
+pub struct TopUpForm {
+    pub amount: Money,
+}
+
+impl TopUpForm {
+    /// Only the offered amounts can be bought.
+    pub fn is_valid(&self) -> bool {
+        credits::TOP_UP_AMOUNTS.contains(&self.amount)
+    }
+}
+
+#[derive(Deserialize)]
+pub struct PayWithCreditsForm {
+    pub order_id: Uuid,
+}
+
+#[derive(Deserialize)]
+pub struct CreditAdjustmentForm {
+    /// Negative to remove credits.
+    pub amount: Money,
+    pub note: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::order::Order;
+
+    #[test]
+    fn test_top_up_order_numbers_are_distinct_from_orders() {
+        let order_number = CreditTopUp::generate_order_number(7);
+        assert!(CreditTopUp::is_top_up_order_number(&order_number));
+        assert!(!CreditTopUp::is_top_up_order_number(&Order::generate_order_number(7)));
+    }
+
+    #[test]
+    fn test_only_offered_amounts_can_be_bought() {
+        assert!(TopUpForm { amount: credits::TOP_UP_AMOUNTS[0] }.is_valid());
+        assert!(!TopUpForm { amount: Money::krw(1) }.is_valid());
+    }
+}
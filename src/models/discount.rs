@@ -0,0 +1,262 @@
+use serde::Deserialize;
+use time::{OffsetDateTime, Time};
+
+use crate::{constants::{errors, pricing}, formatting, money::Money};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "text", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum DiscountKind {
+    /// `percent_off` percent off the list price
+    Percentage,
+    /// `amount_off` off the list price
+    Fixed,
+}
+
+#[derive(Debug, Clone)]
+pub struct DiscountCode {
+    pub discount_code_id: i32,
+    pub code: String,
+    pub kind: DiscountKind,
+    /// Set for percentage codes only
+    pub percent_off: Option<i32>,
+    /// Set for fixed codes only
+    pub amount_off: Option<Money>,
+    pub min_order_amount: Money,
+    pub max_redemptions: Option<i32>,
+    pub per_user_limit: Option<i32>,
+    pub expires_at: Option<OffsetDateTime>,
+    pub is_active: bool,
+    pub redemption_count: i32,
+    pub created_at: OffsetDateTime,
+}
+
+impl DiscountCode {
+    /// Codes are matched case-insensitively and stored upper-case.
+    pub fn normalize(code: &str) -> String {
+        code.trim().to_uppercase()
+    }
+
+    /// The amount taken off `list_price`, or `None` if it overflows.
+    ///
+    /// Never brings the total below the minimum order amount, which is what the
+    /// gateway can still charge.
+    pub fn discount_for(&self, list_price: Money) -> Option<Money> {
+        let discount = match (self.kind, self.percent_off, self.amount_off) {
+            (DiscountKind::Percentage, Some(percent), _) => {
+                let scaled = list_price.checked_mul(i64::from(percent))?;
+                Money::new(scaled.amount / 100, scaled.currency)
+            }
+            (DiscountKind::Fixed, _, Some(amount)) => amount,
+            _ => Money::zero(list_price.currency),
+        };
+        let floor = pricing::MINIMUM_ORDER_AMOUNT.min(list_price);
+        Some(discount.min(list_price.checked_sub(floor)?).max(Money::zero(list_price.currency)))
+    }
+
+    /// Checks whether a user with `user_redemptions` earlier uses of this code can
+    /// apply it to an order of `list_price`, returning the reason if not.
+    pub fn check_usable(&self, list_price: Money, user_redemptions: i64, now: OffsetDateTime) -> Result<(), &'static str> {
+        if !self.is_active {
+            return Err(errors::DISCOUNT_CODE_INVALID);
+        }
+        if self.expires_at.is_some_and(|expires_at| expires_at <= now) {
+            return Err(errors::DISCOUNT_CODE_EXPIRED);
+        }
+        if self.max_redemptions.is_some_and(|max| self.redemption_count >= max) {
+            return Err(errors::DISCOUNT_CODE_EXHAUSTED);
+        }
+        if self.per_user_limit.is_some_and(|limit| user_redemptions >= i64::from(limit)) {
+            return Err(errors::DISCOUNT_CODE_USER_LIMIT);
+        }
+        if list_price < self.min_order_amount {
+            return Err(errors::DISCOUNT_CODE_MINIMUM_NOT_MET);
+        }
+        Ok(())
+    }
+
+    /// Short description such as "10% off" or "₩1,000 off".
+    pub fn describe(&self) -> String {
+        match (self.percent_off, self.amount_off) {
+            (Some(percent), _) => format!("{}% off", percent),
+            (None, Some(amount)) => format!("{} off", amount),
+            (None, None) => "No discount".to_string(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct ApplyDiscountForm {
+    pub code: String,
+}
+
+/// New discount code from the admin page. Optional limits arrive as empty strings.
+#[derive(Deserialize)]
+pub struct DiscountCodeForm {
+    pub code: String,
+    pub kind: DiscountKind,
+    /// Percent off, or won off for a fixed code
+    pub value: i64,
+    #[serde(default)]
+    pub min_order_amount: String,
+    #[serde(default)]
+    pub max_redemptions: String,
+    #[serde(default)]
+    pub per_user_limit: String,
+    /// Last valid day (`YYYY-MM-DD`, UTC); the code works until the end of it.
+    #[serde(default)]
+    pub expires_on: String,
+}
+
+/// Validated [`DiscountCodeForm`].
+pub struct NewDiscountCode {
+    pub code: String,
+    pub kind: DiscountKind,
+    pub percent_off: Option<i32>,
+    pub amount_off: Option<Money>,
+    pub min_order_amount: Money,
+    pub max_redemptions: Option<i32>,
+    pub per_user_limit: Option<i32>,
+    pub expires_at: Option<OffsetDateTime>,
+}
+
+impl DiscountCodeForm {
+    pub fn validate(&self) -> Result<NewDiscountCode, &'static str> {
+        let code = DiscountCode::normalize(&self.code);
+        if code.is_empty() || !code.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+            return Err(errors::DISCOUNT_CODE_FORMAT);
+        }
+
+        let (percent_off, amount_off) = match self.kind {
+            DiscountKind::Percentage if (1..=100).contains(&self.value) => (i32::try_from(self.value).ok(), None),
+            DiscountKind::Fixed if self.value > 0 => (None, Some(Money::krw(self.value))),
+            _ => return Err(errors::DISCOUNT_VALUE_INVALID),
+        };
+
+        Ok(NewDiscountCode {
+            code,
+            kind: self.kind,
+            percent_off,
+            amount_off,
+            min_order_amount: parse_minimum_amount(&self.min_order_amount)?,
+            max_redemptions: parse_optional_count(&self.max_redemptions, 1)?,
+            per_user_limit: parse_optional_count(&self.per_user_limit, 1)?,
+            expires_at: parse_expiry_date(&self.expires_on)?,
+        })
+    }
+}
+
+/// Parses the minimum order amount in won; empty means no minimum.
+fn parse_minimum_amount(value: &str) -> Result<Money, &'static str> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Ok(Money::krw(0));
+    }
+    value
+        .parse::<i64>()
+        .ok()
+        .filter(|amount| *amount >= 0)
+        .map(Money::krw)
+        .ok_or(errors::DISCOUNT_MINIMUM_INVALID)
+}
+
+fn parse_optional_count(value: &str, min: i32) -> Result<Option<i32>, &'static str> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Ok(None);
+    }
+    value
+        .parse::<i32>()
+        .ok()
+        .filter(|count| *count >= min)
+        .map(Some)
+        .ok_or(errors::DISCOUNT_LIMIT_INVALID)
+}
+
+/// Parses `YYYY-MM-DD` into the end of that day (the start of the next), UTC.
+fn parse_expiry_date(value: &str) -> Result<Option<OffsetDateTime>, &'static str> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Ok(None);
+    }
+
+    let date = formatting::parse_date(value)
+        .and_then(|date| date.next_day())
+        .ok_or(errors::DISCOUNT_EXPIRY_INVALID)?;
+
+    Ok(Some(date.with_time(Time::MIDNIGHT).assume_utc()))
+}
+
+#[cfg(test)]
+mod tests {
+    use time::{Date, Month};
+
+    use super::*;
+
+    fn code(kind: DiscountKind, value: i64) -> DiscountCode {
+        DiscountCode {
+            discount_code_id: 1,
+            code: "SPRING".to_string(),
+            kind,
+            percent_off: (kind == DiscountKind::Percentage).then_some(value as i32),
+            amount_off: (kind == DiscountKind::Fixed).then_some(Money::krw(value)),
+            min_order_amount: Money::krw(0),
+            max_redemptions: None,
+            per_user_limit: None,
+            expires_at: None,
+            is_active: true,
+            redemption_count: 0,
+            created_at: OffsetDateTime::UNIX_EPOCH,
+        }
+    }
+
+    #[test]
+    fn test_discount_never_goes_below_minimum_order() {
+        let won = Money::krw;
+        assert_eq!(code(DiscountKind::Percentage, 10).discount_for(won(5000)), Some(won(500)));
+        assert_eq!(code(DiscountKind::Fixed, 1000).discount_for(won(5000)), Some(won(1000)));
+        assert_eq!(
+            code(DiscountKind::Percentage, 100).discount_for(won(5000)),
+            won(5000).checked_sub(pricing::MINIMUM_ORDER_AMOUNT)
+        );
+        assert_eq!(code(DiscountKind::Fixed, 1000).discount_for(pricing::MINIMUM_ORDER_AMOUNT), Some(won(0)));
+        assert_eq!(code(DiscountKind::Fixed, 3_000_000_000).discount_for(won(5_000_000_000)), Some(won(3_000_000_000)));
+        assert_eq!(code(DiscountKind::Percentage, 10).discount_for(won(i64::MAX)), None);
+    }
+
+    #[test]
+    fn test_limits_are_checked() {
+        let now = OffsetDateTime::now_utc();
+        let mut discount = code(DiscountKind::Fixed, 100);
+        assert!(discount.check_usable(Money::krw(1000), 0, now).is_ok());
+
+        discount.min_order_amount = Money::krw(2000);
+        assert_eq!(discount.check_usable(Money::krw(1000), 0, now), Err(errors::DISCOUNT_CODE_MINIMUM_NOT_MET));
+
+        discount.per_user_limit = Some(1);
+        assert_eq!(discount.check_usable(Money::krw(5000), 1, now), Err(errors::DISCOUNT_CODE_USER_LIMIT));
+
+        discount.max_redemptions = Some(3);
+        discount.redemption_count = 3;
+        assert_eq!(discount.check_usable(Money::krw(5000), 0, now), Err(errors::DISCOUNT_CODE_EXHAUSTED));
+
+        discount.expires_at = Some(now);
+        assert_eq!(discount.check_usable(Money::krw(5000), 0, now), Err(errors::DISCOUNT_CODE_EXPIRED));
+    }
+
+    #[test]
+    fn test_minimum_amount_is_parsed_as_won() {
+        assert_eq!(parse_minimum_amount(""), Ok(Money::krw(0)));
+        assert_eq!(parse_minimum_amount("3000000000"), Ok(Money::krw(3_000_000_000)));
+        assert_eq!(parse_minimum_amount("-1"), Err(errors::DISCOUNT_MINIMUM_INVALID));
+        assert_eq!(parse_minimum_amount("1.5"), Err(errors::DISCOUNT_MINIMUM_INVALID));
+    }
+
+    #[test]
+    fn test_expiry_date_covers_the_whole_day() {
+        let expires_at = parse_expiry_date("2026-12-31").unwrap().unwrap();
+        assert_eq!(expires_at.date(), Date::from_calendar_date(2027, Month::January, 1).unwrap());
+        assert!(parse_expiry_date("2026-13-01").is_err());
+        assert_eq!(parse_expiry_date(" "), Ok(None));
+    }
+}
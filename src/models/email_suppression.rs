@@ -0,0 +1,46 @@
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "text", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum SuppressionReason {
+    Bounce,
+    Complaint,
+    Unsubscribe,
+}
+
+impl SuppressionReason {
+    pub fn display_text(&self) -> &'static str {
+        match self {
+            Self::Bounce => "Bounce",
+            Self::Complaint => "Complaint",
+            Self::Unsubscribe => "Unsubscribe",
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Bounce => "bounce",
+            Self::Complaint => "complaint",
+            Self::Unsubscribe => "unsubscribe",
+        }
+    }
+
+    /// Maps the reason column of a bounce report. Anything unrecognized counts as a bounce.
+    pub fn from_report(value: &str) -> Self {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "complaint" | "spam" | "abuse" => Self::Complaint,
+            "unsubscribe" | "unsubscribed" => Self::Unsubscribe,
+            _ => Self::Bounce,
+        }
+    }
+}
+
+pub struct EmailSuppression {
+    pub suppression_id: i32,
+    pub email: String,
+    pub reason: SuppressionReason,
+    pub details: Option<String>,
+    pub created_at: OffsetDateTime,
+}
@@ -1,6 +1,18 @@
 pub mod admin;
+pub mod cash_receipt;
 pub mod contact;
+pub mod credit;
+pub mod discount;
+pub mod email_suppression;
 pub mod order;
+pub mod order_item;
 pub mod pagination;
+pub mod payment_attempt;
+pub mod payment_method;
+pub mod pricing;
+pub mod reconciliation;
+pub mod refund;
+pub mod subscription;
 pub mod todo;
 pub mod user;
+pub mod virtual_account;
@@ -1,17 +1,30 @@
 use serde::{Deserialize, Serialize};
+use sqlx::types::Json;
 use time::OffsetDateTime;
 use uuid::Uuid;
 
-use crate::{constants::errors, data::errors::DataError};
+use crate::{
+    constants::errors,
+    data::errors::DataError,
+    models::{
+        order_item::{self, OrderItem, TextAnalysis},
+        payment_method::PaymentMethod,
+    },
+    money::Money,
+};
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type)]
-#[sqlx(type_name = "text", rename_all = "lowercase")]
-#[serde(rename_all = "lowercase")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
 pub enum PaymentStatus {
     Pending,
+    /// A virtual account was issued and the customer has yet to transfer the money
+    AwaitingDeposit,
     Paid,
     Failed,
     Cancelled,
+    Refunded,
+    PartiallyRefunded,
 }
 
 impl PaymentStatus {
@@ -19,8 +32,11 @@ impl PaymentStatus {
         match self {
             Self::Paid => "Paid",
             Self::Pending => "Pending",
+            Self::AwaitingDeposit => "Awaiting deposit",
             Self::Failed => "Failed",
             Self::Cancelled => "Cancelled",
+            Self::Refunded => "Refunded",
+            Self::PartiallyRefunded => "Partially refunded",
         }
     }
 
@@ -28,17 +44,72 @@ impl PaymentStatus {
         match self {
             Self::Paid => "text-green-600",
             Self::Pending => "text-yellow-600",
+            Self::AwaitingDeposit => "text-blue-600",
             Self::Failed => "text-red-600",
             Self::Cancelled => "text-gray-600",
+            Self::Refunded => "text-gray-600",
+            Self::PartiallyRefunded => "text-orange-600",
+        }
+    }
+
+    /// Whether a checkout can be started for an order in this status.
+    ///
+    /// Failed payments can be retried; Toss only rejects order IDs that were approved or cancelled.
+    pub fn is_payable(&self) -> bool {
+        matches!(self, Self::Pending | Self::Failed)
+    }
+
+    /// The order lifecycle:
+    ///
+    /// ```text
+    /// pending ─┬─> paid ─┬─> partially_refunded ─> refunded
+    ///          │    ^    └────────────────────────> refunded
+    ///          ├─> awaiting_deposit (-> paid | failed | cancelled)
+    ///          ├─> failed (retryable: -> paid | awaiting_deposit | failed | cancelled)
+    ///          └─> cancelled
+    /// ```
+    ///
+    /// `failed -> failed` and `partially_refunded -> partially_refunded` are allowed
+    /// for a further failed attempt and a further partial refund.
+    pub fn can_transition_to(&self, next: Self) -> bool {
+        match self {
+            Self::Pending | Self::Failed => {
+                matches!(next, Self::Paid | Self::AwaitingDeposit | Self::Failed | Self::Cancelled)
+            }
+            Self::AwaitingDeposit => matches!(next, Self::Paid | Self::Failed | Self::Cancelled),
+            Self::Paid => matches!(next, Self::PartiallyRefunded | Self::Refunded),
+            Self::PartiallyRefunded => matches!(next, Self::PartiallyRefunded | Self::Refunded),
+            Self::Refunded | Self::Cancelled => false,
+        }
+    }
+
+    pub fn ensure_transition(&self, next: Self) -> Result<(), DataError> {
+        if self.can_transition_to(next) {
+            Ok(())
+        } else {
+            Err(DataError::InvalidTransition { from: *self, to: next })
         }
     }
 
+    /// Whether money was captured and some of it can still be refunded.
+    pub fn is_refundable(&self) -> bool {
+        matches!(self, Self::Paid | Self::PartiallyRefunded)
+    }
+
+    /// Whether the order was paid at some point, so a receipt can be issued for it.
+    pub fn has_receipt(&self) -> bool {
+        matches!(self, Self::Paid | Self::PartiallyRefunded | Self::Refunded)
+    }
+
     pub fn as_str(&self) -> &'static str {
         match self {
             Self::Paid => "paid",
             Self::Pending => "pending",
+            Self::AwaitingDeposit => "awaiting_deposit",
             Self::Failed => "failed",
             Self::Cancelled => "cancelled",
+            Self::Refunded => "refunded",
+            Self::PartiallyRefunded => "partially_refunded",
         }
     }
 }
@@ -48,16 +119,24 @@ pub struct Order {
     pub order_id: Uuid,
     pub user_id: i32,
     pub user_email: String,
-    pub filename: String,
-    pub file_size: i32,
-    pub text_content: String,
-    pub text_length: i32,
-    pub price_amount: i32,
+    /// Price before any discount: the sum of the item amounts.
+    pub list_price_amount: Money,
+    pub discount_amount: Money,
+    pub discount_code_id: Option<i32>,
+    /// The amount charged: list price less discount, plus VAT when prices exclude it.
+    pub price_amount: Money,
+    /// Supply amount; `net_amount + tax_amount == price_amount`
+    pub net_amount: Money,
+    pub tax_amount: Money,
+    pub tax_rate_percent: i32,
     pub payment_status: PaymentStatus,
     pub payment_key: Option<String>,
+    /// Chosen at checkout; `None` until the customer returns from the payment window
+    pub payment_method: Option<PaymentMethod>,
     pub order_number: String,
     pub created_at: OffsetDateTime,
     pub paid_at: Option<OffsetDateTime>,
+    pub items: Json<Vec<OrderItem>>,
 }
 
 impl Order {
@@ -69,6 +148,32 @@ impl Order {
         }
     }
 
+    /// What the customer sees as the purchase in the payment window and on cash receipts.
+    pub fn order_name(&self) -> String {
+        order_item::order_name(&self.items)
+    }
+
+    /// The file to analyze, for orders that include a text analysis.
+    pub fn text_analysis(&self) -> Option<&TextAnalysis> {
+        self.items.iter().find_map(|item| item.metadata.text_analysis())
+    }
+
+    /// Whether the order was paid in a way that can carry a cash receipt.
+    pub fn allows_cash_receipt(&self) -> bool {
+        self.payment_method.is_some_and(|method| method.allows_cash_receipt())
+    }
+
+    pub fn quote_expires_at(&self, validity: time::Duration) -> OffsetDateTime {
+        self.created_at + validity
+    }
+
+    /// Whether the quote can no longer be paid: cancelled by the expiry job, or
+    /// still payable but past its validity window.
+    pub fn is_quote_expired(&self, validity: time::Duration) -> bool {
+        self.payment_status == PaymentStatus::Cancelled
+            || (self.payment_status.is_payable() && OffsetDateTime::now_utc() > self.quote_expires_at(validity))
+    }
+
     pub fn generate_order_number(user_id: i32) -> String {
         let uuid_string = Uuid::new_v4().to_string();
         let uuid_prefix = uuid_string
@@ -79,14 +184,88 @@ impl Order {
     }
 }
 
+/// What caused an order status change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "text", rename_all = "lowercase")]
+pub enum OrderEventSource {
+    /// The customer's return from the payment window
+    Checkout,
+    /// A payment gateway webhook
+    Webhook,
+    /// An admin action, such as a refund
+    Admin,
+    /// The application itself, such as order creation
+    System,
+    /// A fix applied from the payment reconciliation page
+    Reconciliation,
+}
+
+impl OrderEventSource {
+    pub fn display_text(&self) -> &'static str {
+        match self {
+            Self::Checkout => "Checkout",
+            Self::Webhook => "Webhook",
+            Self::Admin => "Admin",
+            Self::System => "System",
+            Self::Reconciliation => "Reconciliation",
+        }
+    }
+}
+
+/// One entry of an order's status history.
+#[derive(Debug, Clone)]
+pub struct OrderEvent {
+    /// `None` for the event that created the order.
+    pub from_status: Option<PaymentStatus>,
+    pub to_status: PaymentStatus,
+    pub source: OrderEventSource,
+    pub payment_key: Option<String>,
+    pub actor_email: Option<String>,
+    pub note: Option<String>,
+    pub created_at: OffsetDateTime,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OrderSummary {
     pub order_id: Uuid,
-    pub filename: String,
-    pub file_size: i32,
-    pub text_length: i32,
-    pub price_amount: i32,
+    pub price_amount: Money,
     pub payment_status: PaymentStatus,
     pub order_number: String,
     pub created_at: OffsetDateTime,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_paid_order_cannot_fail_or_reopen() {
+        assert!(PaymentStatus::Pending.can_transition_to(PaymentStatus::Paid));
+        assert!(PaymentStatus::Failed.can_transition_to(PaymentStatus::Paid));
+        assert!(!PaymentStatus::Paid.can_transition_to(PaymentStatus::Failed));
+        assert!(!PaymentStatus::Paid.can_transition_to(PaymentStatus::Pending));
+        assert!(!PaymentStatus::Paid.can_transition_to(PaymentStatus::Paid));
+    }
+
+    #[test]
+    fn test_awaiting_deposit_settles_once() {
+        assert!(PaymentStatus::Pending.can_transition_to(PaymentStatus::AwaitingDeposit));
+        assert!(PaymentStatus::AwaitingDeposit.can_transition_to(PaymentStatus::Paid));
+        assert!(PaymentStatus::AwaitingDeposit.can_transition_to(PaymentStatus::Cancelled));
+        assert!(!PaymentStatus::AwaitingDeposit.can_transition_to(PaymentStatus::AwaitingDeposit));
+        assert!(!PaymentStatus::AwaitingDeposit.is_payable());
+        assert!(!PaymentStatus::Paid.can_transition_to(PaymentStatus::AwaitingDeposit));
+    }
+
+    #[test]
+    fn test_refund_transitions() {
+        assert!(PaymentStatus::Paid.can_transition_to(PaymentStatus::PartiallyRefunded));
+        assert!(PaymentStatus::PartiallyRefunded.can_transition_to(PaymentStatus::PartiallyRefunded));
+        assert!(PaymentStatus::PartiallyRefunded.can_transition_to(PaymentStatus::Refunded));
+        assert!(!PaymentStatus::Pending.can_transition_to(PaymentStatus::Refunded));
+        assert!(matches!(
+            PaymentStatus::Refunded.ensure_transition(PaymentStatus::Paid),
+            Err(DataError::InvalidTransition { from: PaymentStatus::Refunded, to: PaymentStatus::Paid })
+        ));
+    }
+}
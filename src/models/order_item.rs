@@ -0,0 +1,107 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::money::Money;
+
+/// One line of an order: a product, how many of it, and what it was sold for.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrderItem {
+    pub order_item_id: Uuid,
+    pub product_code: String,
+    pub product_name: String,
+    pub quantity: i32,
+    pub unit_price_amount: Money,
+    pub metadata: OrderItemMetadata,
+}
+
+impl OrderItem {
+    /// The line total before any order discount, or `None` if it overflows.
+    pub fn amount(&self) -> Option<Money> {
+        self.unit_price_amount.checked_mul(i64::from(self.quantity))
+    }
+
+    /// What the line is, as shown on the payment window, receipts and emails.
+    pub fn description(&self) -> String {
+        match &self.metadata {
+            OrderItemMetadata::TextAnalysis(analysis) => format!("{} - {}", self.product_name, analysis.filename),
+        }
+    }
+}
+
+/// Product-specific details of an order item, tagged with the product kind.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum OrderItemMetadata {
+    TextAnalysis(TextAnalysis),
+}
+
+impl OrderItemMetadata {
+    pub fn text_analysis(&self) -> Option<&TextAnalysis> {
+        match self {
+            Self::TextAnalysis(analysis) => Some(analysis),
+        }
+    }
+}
+
+/// An uploaded file to analyze.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TextAnalysis {
+    pub filename: String,
+    pub file_size: i32,
+    /// Emptied when the quote expires unpaid and content purging is on
+    pub text_content: String,
+    pub text_length: i32,
+}
+
+impl TextAnalysis {
+    pub fn word_count(&self) -> usize {
+        self.text_content.split_whitespace().count()
+    }
+}
+
+/// Name of the whole order for the payment window and cash receipts: the first
+/// item's description, followed by how many other items there are.
+pub fn order_name(items: &[OrderItem]) -> String {
+    match items {
+        [] => String::new(),
+        [item] => item.description(),
+        [first, rest @ ..] => format!("{} and {} more", first.description(), rest.len()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text_item(filename: &str) -> OrderItem {
+        OrderItem {
+            order_item_id: Uuid::nil(),
+            product_code: "text_analysis".to_string(),
+            product_name: "Text Analysis".to_string(),
+            quantity: 1,
+            unit_price_amount: Money::krw(1200),
+            metadata: OrderItemMetadata::TextAnalysis(TextAnalysis {
+                filename: filename.to_string(),
+                file_size: 10,
+                text_content: "a b".to_string(),
+                text_length: 3,
+            }),
+        }
+    }
+
+    #[test]
+    fn test_order_name_counts_the_other_items() {
+        assert_eq!(order_name(&[text_item("a.txt")]), "Text Analysis - a.txt");
+        assert_eq!(
+            order_name(&[text_item("a.txt"), text_item("b.txt"), text_item("c.txt")]),
+            "Text Analysis - a.txt and 2 more"
+        );
+    }
+
+    #[test]
+    fn test_metadata_is_tagged_with_the_product_kind() {
+        let json = serde_json::to_value(&text_item("a.txt").metadata).unwrap();
+        assert_eq!(json["kind"], "text_analysis");
+        assert_eq!(json["filename"], "a.txt");
+    }
+}
@@ -0,0 +1,47 @@
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::money::Money;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "text", rename_all = "lowercase")]
+pub enum PaymentAttemptStatus {
+    /// Confirm request sent, no answer recorded yet
+    Confirming,
+    Succeeded,
+    /// The gateway refused the confirmation
+    Failed,
+    /// The gateway could not be reached; the attempt may be retried
+    Errored,
+}
+
+impl PaymentAttemptStatus {
+    pub fn display_text(&self) -> &'static str {
+        match self {
+            Self::Confirming => "Confirming",
+            Self::Succeeded => "Succeeded",
+            Self::Failed => "Failed",
+            Self::Errored => "Errored",
+        }
+    }
+
+    pub fn css_class(&self) -> &'static str {
+        match self {
+            Self::Confirming => "text-yellow-600",
+            Self::Succeeded => "text-green-600",
+            Self::Failed | Self::Errored => "text-red-600",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PaymentAttempt {
+    pub payment_key: String,
+    pub order_id: Uuid,
+    pub amount: Money,
+    pub status: PaymentAttemptStatus,
+    pub gateway_code: Option<String>,
+    pub gateway_message: Option<String>,
+    pub created_at: OffsetDateTime,
+    pub completed_at: Option<OffsetDateTime>,
+}
@@ -0,0 +1,76 @@
+use serde::{Deserialize, Serialize};
+
+/// How the customer pays for an order. Offered on the checkout page, but stored
+/// as the payment gateway reports it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum PaymentMethod {
+    Card,
+    /// Real-time account transfer from the customer's bank
+    Transfer,
+    /// Bank transfer to an issued account; the order waits for the deposit
+    VirtualAccount,
+    MobilePhone,
+    KakaoPay,
+    NaverPay,
+    TossPay,
+}
+
+impl PaymentMethod {
+    pub const ALL: [Self; 7] = [
+        Self::Card,
+        Self::Transfer,
+        Self::VirtualAccount,
+        Self::MobilePhone,
+        Self::KakaoPay,
+        Self::NaverPay,
+        Self::TossPay,
+    ];
+
+    pub fn display_text(&self) -> &'static str {
+        match self {
+            Self::Card => "Card",
+            Self::Transfer => "Account transfer",
+            Self::VirtualAccount => "Bank transfer (virtual account)",
+            Self::MobilePhone => "Mobile phone",
+            Self::KakaoPay => "KakaoPay",
+            Self::NaverPay => "NaverPay",
+            Self::TossPay => "Toss Pay",
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Card => "card",
+            Self::Transfer => "transfer",
+            Self::VirtualAccount => "virtual_account",
+            Self::MobilePhone => "mobile_phone",
+            Self::KakaoPay => "kakao_pay",
+            Self::NaverPay => "naver_pay",
+            Self::TossPay => "toss_pay",
+        }
+    }
+
+    /// Whether the customer can ask for a cash receipt (현금영수증) for the payment.
+    pub fn allows_cash_receipt(&self) -> bool {
+        matches!(self, Self::Transfer | Self::VirtualAccount)
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|method| method.as_str() == value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_round_trips_every_method() {
+        for method in PaymentMethod::ALL {
+            assert_eq!(PaymentMethod::parse(method.as_str()), Some(method));
+        }
+        assert_eq!(PaymentMethod::parse("cash"), None);
+    }
+}
@@ -0,0 +1,183 @@
+use serde::Deserialize;
+use time::{OffsetDateTime, Time};
+
+use crate::{
+    constants::{errors, pricing},
+    formatting,
+    money::Money,
+};
+
+/// A volume tier: characters from `starts_at` on are charged at its rate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PricingTier {
+    pub starts_at: i32,
+    pub price_per_thousand: i32,
+}
+
+/// One version of the price list. Rules are never edited; a change is a new version.
+#[derive(Debug, Clone)]
+pub struct PricingRule {
+    pub pricing_rule_id: i32,
+    /// KRW per 1,000 characters below the first tier
+    pub price_per_thousand: i32,
+    pub minimum_amount: Money,
+    pub effective_from: OffsetDateTime,
+    pub note: Option<String>,
+    pub created_at: OffsetDateTime,
+    /// Ordered by `starts_at`
+    pub tiers: Vec<PricingTier>,
+}
+
+impl PricingRule {
+    /// The price of `characters`, with each tier's rate applied to the characters
+    /// in it, rounded up to whole won and raised to the rule's minimum.
+    pub fn price_for(&self, characters: i32) -> Money {
+        let characters = i64::from(characters.max(0));
+        let mut rates = vec![(0, self.price_per_thousand)];
+        rates.extend(self.tiers.iter().map(|tier| (i64::from(tier.starts_at), tier.price_per_thousand)));
+
+        let mut milli_won = 0_i64;
+        for (i, &(start, rate)) in rates.iter().enumerate() {
+            let end = rates.get(i + 1).map_or(characters, |&(next, _)| next.min(characters));
+            if end > start {
+                milli_won += (end - start) * i64::from(rate);
+            }
+        }
+
+        Money::krw((milli_won + 999) / 1000).max(self.minimum_amount)
+    }
+
+    pub fn label(&self) -> String {
+        format!("v{}", self.pricing_rule_id)
+    }
+
+    /// Short description such as "₩1,000 / 1,000 chars, min. ₩100".
+    pub fn describe(&self) -> String {
+        format!(
+            "{} / 1,000 chars, min. {}",
+            Money::krw(i64::from(self.price_per_thousand)),
+            self.minimum_amount
+        )
+    }
+}
+
+/// A new rule version from the admin page.
+#[derive(Deserialize)]
+pub struct PricingRuleForm {
+    pub price_per_thousand: i32,
+    pub minimum_amount: Money,
+    /// One tier per line as `starts_at = price_per_thousand`, e.g. `50000 = 800`.
+    #[serde(default)]
+    pub tiers: String,
+    /// First day (`YYYY-MM-DD`, UTC) the rule applies; empty for immediately.
+    #[serde(default)]
+    pub effective_on: String,
+    #[serde(default)]
+    pub note: String,
+}
+
+/// Validated [`PricingRuleForm`].
+pub struct NewPricingRule {
+    pub price_per_thousand: i32,
+    pub minimum_amount: Money,
+    pub tiers: Vec<PricingTier>,
+    pub effective_from: OffsetDateTime,
+    pub note: Option<String>,
+}
+
+impl PricingRuleForm {
+    pub fn validate(&self, now: OffsetDateTime) -> Result<NewPricingRule, &'static str> {
+        if self.price_per_thousand < 0 {
+            return Err(errors::PRICING_RATE_INVALID);
+        }
+        if self.minimum_amount < pricing::MINIMUM_ORDER_AMOUNT {
+            return Err(errors::PRICING_MINIMUM_INVALID);
+        }
+
+        let effective_from = match self.effective_on.trim() {
+            "" => now,
+            value => formatting::parse_date(value)
+                .map(|date| date.with_time(Time::MIDNIGHT).assume_utc().max(now))
+                .filter(|effective_from| effective_from.date() >= now.date())
+                .ok_or(errors::PRICING_EFFECTIVE_DATE_INVALID)?,
+        };
+
+        let note = self.note.trim();
+        Ok(NewPricingRule {
+            price_per_thousand: self.price_per_thousand,
+            minimum_amount: self.minimum_amount,
+            tiers: parse_tiers(&self.tiers)?,
+            effective_from,
+            note: (!note.is_empty()).then(|| note.to_string()),
+        })
+    }
+}
+
+fn parse_tiers(value: &str) -> Result<Vec<PricingTier>, &'static str> {
+    let mut tiers: Vec<PricingTier> = Vec::new();
+    for line in value.lines().map(str::trim).filter(|line| !line.is_empty()) {
+        let (starts_at, rate) = line.split_once('=').ok_or(errors::PRICING_TIERS_INVALID)?;
+        let tier = PricingTier {
+            starts_at: starts_at.trim().replace(',', "").parse().map_err(|_| errors::PRICING_TIERS_INVALID)?,
+            price_per_thousand: rate.trim().replace(',', "").parse().map_err(|_| errors::PRICING_TIERS_INVALID)?,
+        };
+
+        let after_previous = tiers.last().map_or(tier.starts_at > 0, |last| tier.starts_at > last.starts_at);
+        if !after_previous || tier.price_per_thousand < 0 {
+            return Err(errors::PRICING_TIERS_INVALID);
+        }
+        tiers.push(tier);
+    }
+
+    if tiers.len() > pricing::MAX_TIERS {
+        return Err(errors::PRICING_TIERS_INVALID);
+    }
+    Ok(tiers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(tiers: Vec<PricingTier>) -> PricingRule {
+        PricingRule {
+            pricing_rule_id: 1,
+            price_per_thousand: 1000,
+            minimum_amount: Money::krw(100),
+            effective_from: OffsetDateTime::UNIX_EPOCH,
+            note: None,
+            created_at: OffsetDateTime::UNIX_EPOCH,
+            tiers,
+        }
+    }
+
+    #[test]
+    fn test_flat_rate_with_minimum() {
+        let flat = rule(Vec::new());
+        assert_eq!(flat.price_for(0), Money::krw(100));
+        assert_eq!(flat.price_for(99), Money::krw(100));
+        assert_eq!(flat.price_for(2500), Money::krw(2500));
+    }
+
+    #[test]
+    fn test_tiers_apply_to_the_characters_in_them() {
+        let tiered = rule(vec![
+            PricingTier { starts_at: 1000, price_per_thousand: 500 },
+            PricingTier { starts_at: 2000, price_per_thousand: 250 },
+        ]);
+        assert_eq!(tiered.price_for(1000), Money::krw(1000));
+        assert_eq!(tiered.price_for(1500), Money::krw(1000 + 250));
+        assert_eq!(tiered.price_for(3000), Money::krw(1000 + 500 + 250));
+        // Fractions of a won are rounded up
+        assert_eq!(tiered.price_for(2001), Money::krw(1500 + 1));
+    }
+
+    #[test]
+    fn test_tiers_must_be_increasing() {
+        assert_eq!(parse_tiers("1,000 = 800\n5000=500").unwrap().len(), 2);
+        assert_eq!(parse_tiers(" "), Ok(Vec::new()));
+        assert!(parse_tiers("5000 = 500\n1000 = 800").is_err());
+        assert!(parse_tiers("0 = 800").is_err());
+        assert!(parse_tiers("1000").is_err());
+    }
+}
@@ -0,0 +1,75 @@
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use super::order::PaymentStatus;
+use crate::money::Money;
+
+/// How an order disagrees with the gateway's record of its payment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+pub enum FindingKind {
+    /// The gateway took the money but the order is still unpaid here
+    PaidNotRecorded,
+    /// The gateway charged a different amount than the order's price
+    AmountMismatch,
+    /// The order is paid here but the gateway has no such payment
+    UnknownPayment,
+    /// The gateway settled the payment to a different status (e.g. cancelled there)
+    StatusMismatch,
+}
+
+impl FindingKind {
+    pub fn display_text(&self) -> &'static str {
+        match self {
+            Self::PaidNotRecorded => "Paid at gateway, unpaid here",
+            Self::AmountMismatch => "Amount mismatch",
+            Self::UnknownPayment => "Unknown payment key",
+            Self::StatusMismatch => "Status mismatch",
+        }
+    }
+
+    /// Whether applying the gateway's status can resolve it. Amount and key
+    /// mismatches need a person to look at the payment.
+    pub fn is_fixable(&self) -> bool {
+        matches!(self, Self::PaidNotRecorded | Self::StatusMismatch)
+    }
+}
+
+/// An order checked by a reconciliation run, with what we have on file.
+#[derive(Debug, Clone)]
+pub struct ReconciliationCandidate {
+    pub order_id: Uuid,
+    pub order_number: String,
+    pub payment_status: PaymentStatus,
+    pub price_amount: Money,
+    pub payment_key: Option<String>,
+    pub refunded_amount: Money,
+}
+
+#[derive(Debug, Clone)]
+pub struct ReconciliationRun {
+    pub run_id: i32,
+    pub window_start: OffsetDateTime,
+    pub window_end: OffsetDateTime,
+    pub checked_count: i32,
+    pub finding_count: i32,
+    pub error_count: i32,
+    pub started_at: OffsetDateTime,
+    /// `None` while the run is in progress, or if it was interrupted.
+    pub finished_at: Option<OffsetDateTime>,
+}
+
+/// An unresolved mismatch, as shown on the reconciliation page.
+#[derive(Debug, Clone)]
+pub struct ReconciliationFinding {
+    pub finding_id: i32,
+    pub order_id: Uuid,
+    pub order_number: String,
+    pub kind: FindingKind,
+    pub local_status: PaymentStatus,
+    pub local_amount: Money,
+    pub gateway_status: Option<String>,
+    pub gateway_amount: Option<Money>,
+    pub gateway_payment_key: Option<String>,
+    pub created_at: OffsetDateTime,
+}
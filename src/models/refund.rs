@@ -0,0 +1,19 @@
+use serde::Deserialize;
+use time::OffsetDateTime;
+
+use crate::money::Money;
+
+#[derive(Debug, Clone)]
+pub struct Refund {
+    pub amount: Money,
+    pub reason: String,
+    /// Admin who issued the refund; `None` for refunds made at the gateway.
+    pub refunded_by_email: Option<String>,
+    pub created_at: OffsetDateTime,
+}
+
+#[derive(Deserialize)]
+pub struct RefundForm {
+    pub amount: Money,
+    pub reason: String,
+}
@@ -0,0 +1,197 @@
+use serde::Deserialize;
+use time::{Duration, OffsetDateTime};
+use uuid::Uuid;
+
+use super::order::PaymentStatus;
+use crate::{constants::subscriptions, formatting, money::Money};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+pub enum SubscriptionStatus {
+    /// Created, waiting for the customer to register a card
+    Incomplete,
+    Active,
+    /// A renewal charge failed and is being retried
+    PastDue,
+    Ended,
+}
+
+impl SubscriptionStatus {
+    pub fn display_text(&self) -> &'static str {
+        match self {
+            Self::Incomplete => "Waiting for card",
+            Self::Active => "Active",
+            Self::PastDue => "Payment overdue",
+            Self::Ended => "Ended",
+        }
+    }
+
+    pub fn css_class(&self) -> &'static str {
+        match self {
+            Self::Incomplete => "text-yellow-600",
+            Self::Active => "text-green-600",
+            Self::PastDue => "text-red-600",
+            Self::Ended => "text-gray-600",
+        }
+    }
+
+    /// Whether a card can be (re-)registered: to start the subscription, or to
+    /// replace a card whose renewal failed.
+    pub fn accepts_card(&self) -> bool {
+        matches!(self, Self::Incomplete | Self::PastDue)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SubscriptionPlan {
+    pub plan_id: i32,
+    pub name: String,
+    /// Charged every period, VAT included
+    pub price_amount: Money,
+    pub included_characters: i32,
+}
+
+impl SubscriptionPlan {
+    /// Short description such as "₩9,900 / month, 100,000 characters".
+    pub fn describe(&self) -> String {
+        format!(
+            "{} / month, {} characters",
+            self.price_amount,
+            formatting::format_number(self.included_characters)
+        )
+    }
+}
+
+/// A user's subscription with its plan's terms.
+#[derive(Debug, Clone)]
+pub struct Subscription {
+    pub subscription_id: Uuid,
+    pub user_id: i32,
+    pub user_email: String,
+    pub plan_name: String,
+    pub price_amount: Money,
+    pub included_characters: i32,
+    pub status: SubscriptionStatus,
+    pub customer_key: String,
+    pub billing_key: Option<String>,
+    pub card_label: Option<String>,
+    pub current_period_end: Option<OffsetDateTime>,
+    pub characters_used: i32,
+    pub cancel_at_period_end: bool,
+    pub failed_attempts: i32,
+    pub next_retry_at: Option<OffsetDateTime>,
+}
+
+impl Subscription {
+    pub fn generate_customer_key() -> String {
+        format!("{}{}", subscriptions::CUSTOMER_KEY_PREFIX, Uuid::new_v4().simple())
+    }
+
+    pub fn generate_order_number(&self) -> String {
+        let uuid_string = Uuid::new_v4().simple().to_string();
+        format!("{}{}-{}", subscriptions::ORDER_PREFIX, self.user_id, &uuid_string[..8])
+    }
+
+    pub fn is_charge_order_number(order_number: &str) -> bool {
+        order_number.starts_with(subscriptions::ORDER_PREFIX)
+    }
+
+    pub fn remaining_characters(&self) -> i32 {
+        (self.included_characters - self.characters_used).max(0)
+    }
+
+    /// Whether the current period's included characters cover an order of `characters`.
+    pub fn covers(&self, characters: i32, now: OffsetDateTime) -> bool {
+        self.status == SubscriptionStatus::Active
+            && self.current_period_end.is_some_and(|end| end > now)
+            && characters <= self.remaining_characters()
+    }
+
+    /// Gateway order name, e.g. "Basic Plan".
+    pub fn order_name(&self) -> String {
+        format!("{} Plan", self.plan_name)
+    }
+}
+
+/// One attempt to charge a subscription. `status` is only ever pending, paid or failed.
+#[derive(Debug, Clone)]
+pub struct SubscriptionCharge {
+    pub charge_id: i32,
+    pub subscription_id: Uuid,
+    pub order_number: String,
+    pub amount: Money,
+    pub status: PaymentStatus,
+    pub failure_message: Option<String>,
+    pub created_at: OffsetDateTime,
+}
+
+/// When to retry a renewal after `failed_attempts` failures, or `None` once the
+/// dunning schedule is used up and the subscription should end.
+pub fn next_retry_at(failed_attempts: i32, now: OffsetDateTime) -> Option<OffsetDateTime> {
+    let index = usize::try_from(failed_attempts).ok()?.checked_sub(1)?;
+    subscriptions::RETRY_DELAYS_DAYS
+        .get(index)
+        .map(|&days| now + Duration::days(days))
+}
+
+#[derive(Deserialize)]
+pub struct SubscribeForm {
+    pub plan_id: i32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{credit::CreditTopUp, order::Order};
+
+    fn subscription(now: OffsetDateTime) -> Subscription {
+        Subscription {
+            subscription_id: Uuid::nil(),
+            user_id: 1,
+            user_email: "customer@example.com".to_string(),
+            plan_name: "Basic".to_string(),
+            price_amount: Money::krw(9900),
+            included_characters: 1000,
+            status: SubscriptionStatus::Active,
+            customer_key: "cus_1".to_string(),
+            billing_key: Some("billing".to_string()),
+            card_label: None,
+            current_period_end: Some(now + Duration::days(1)),
+            characters_used: 400,
+            cancel_at_period_end: false,
+            failed_attempts: 0,
+            next_retry_at: None,
+        }
+    }
+
+    #[test]
+    fn test_covers_only_within_an_active_period() {
+        let now = OffsetDateTime::UNIX_EPOCH;
+        let mut subscription = subscription(now);
+        assert!(subscription.covers(600, now));
+        assert!(!subscription.covers(601, now));
+        assert!(!subscription.covers(1, now + Duration::days(1)));
+
+        subscription.status = SubscriptionStatus::PastDue;
+        assert!(!subscription.covers(1, now));
+    }
+
+    #[test]
+    fn test_charge_order_numbers_are_distinct_from_orders_and_top_ups() {
+        let order_number = subscription(OffsetDateTime::UNIX_EPOCH).generate_order_number();
+        assert!(Subscription::is_charge_order_number(&order_number));
+        assert!(!Subscription::is_charge_order_number(&Order::generate_order_number(1)));
+        assert!(!Subscription::is_charge_order_number(&CreditTopUp::generate_order_number(1)));
+    }
+
+    #[test]
+    fn test_dunning_schedule_ends_after_the_last_retry() {
+        let now = OffsetDateTime::UNIX_EPOCH;
+        assert_eq!(next_retry_at(0, now), None);
+        assert_eq!(next_retry_at(1, now), Some(now + Duration::days(subscriptions::RETRY_DELAYS_DAYS[0])));
+
+        let attempts = subscriptions::RETRY_DELAYS_DAYS.len() as i32;
+        assert!(next_retry_at(attempts, now).is_some());
+        assert_eq!(next_retry_at(attempts + 1, now), None);
+    }
+}
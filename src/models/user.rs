@@ -10,4 +10,14 @@ pub const FIELD_EMAIL: &str = "email";
 pub struct MagicLinkRequestForm {
     #[validate(regex(path = "*EMAIL_RX", message = "Invalid email format"))]
     pub email: String,
+    #[serde(default)]
+    pub pow_challenge: String,
+    #[serde(default)]
+    pub pow_solution: String,
+}
+
+/// Checkbox fields are only submitted when checked.
+#[derive(Deserialize)]
+pub struct NotificationPreferencesForm {
+    pub payment_emails: Option<String>,
 }
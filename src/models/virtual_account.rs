@@ -0,0 +1,63 @@
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+/// Bank transfer instructions for an order paid by virtual account.
+#[derive(Debug, Clone)]
+pub struct VirtualAccount {
+    pub order_id: Uuid,
+    pub order_number: String,
+    pub bank_code: String,
+    pub account_number: String,
+    pub due_at: OffsetDateTime,
+}
+
+impl VirtualAccount {
+    /// The bank's name, falling back to the code for banks not listed.
+    pub fn bank_name(&self) -> &str {
+        bank_name(&self.bank_code).unwrap_or(&self.bank_code)
+    }
+}
+
+/// Korean bank names for the two-digit codes Toss reports.
+fn bank_name(code: &str) -> Option<&'static str> {
+    Some(match code {
+        "02" => "KDB산업은행",
+        "03" => "IBK기업은행",
+        "04" | "06" => "KB국민은행",
+        "07" => "Sh수협은행",
+        "11" => "NH농협은행",
+        "12" => "단위농협",
+        "20" => "우리은행",
+        "23" => "SC제일은행",
+        "31" => "iM뱅크",
+        "32" => "부산은행",
+        "34" => "광주은행",
+        "37" => "전북은행",
+        "39" => "경남은행",
+        "45" => "새마을금고",
+        "71" => "우체국예금보험",
+        "81" => "하나은행",
+        "88" => "신한은행",
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bank_name_falls_back_to_code() {
+        let mut account = VirtualAccount {
+            order_id: Uuid::nil(),
+            order_number: "ORD-1-abcd1234".to_string(),
+            bank_code: "20".to_string(),
+            account_number: "12345678901234".to_string(),
+            due_at: OffsetDateTime::UNIX_EPOCH,
+        };
+        assert_eq!(account.bank_name(), "우리은행");
+
+        account.bank_code = "99".to_string();
+        assert_eq!(account.bank_name(), "99");
+    }
+}
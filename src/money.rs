@@ -0,0 +1,266 @@
+//! Amounts of money in a currency's minor unit.
+//!
+//! Database columns hold the amount as `BIGINT` in minor units of the shop
+//! currency, KRW; the currency itself is not stored, so decoding yields won.
+
+use std::{cmp::Ordering, fmt};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use sqlx::{
+    Decode, Encode, Postgres, Type,
+    encode::IsNull,
+    error::BoxDynError,
+    postgres::{PgArgumentBuffer, PgTypeInfo, PgValueRef},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Currency {
+    /// South Korean won, which has no minor unit in use
+    #[default]
+    Krw,
+}
+
+impl Currency {
+    /// ISO 4217 code
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Krw => "KRW",
+        }
+    }
+
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Self::Krw => "₩",
+        }
+    }
+
+    /// Digits after the decimal point; amounts are counted in this minor unit.
+    pub fn decimals(&self) -> u32 {
+        match self {
+            Self::Krw => 0,
+        }
+    }
+}
+
+/// Conventions for writing amounts for readers of a language.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Locale {
+    /// Symbol first: "₩1,200". The site's language.
+    #[default]
+    English,
+    /// Unit after the number: "1,200원"
+    Korean,
+}
+
+impl Locale {
+    pub fn group_separator(&self) -> &'static str {
+        match self {
+            Self::English | Self::Korean => ",",
+        }
+    }
+
+    pub fn decimal_separator(&self) -> &'static str {
+        match self {
+            Self::English | Self::Korean => ".",
+        }
+    }
+
+    /// The sign written for `currency`, and whether it follows the number.
+    fn currency_sign(&self, currency: Currency) -> (&'static str, bool) {
+        match (self, currency) {
+            (Self::English, _) => (currency.symbol(), false),
+            (Self::Korean, Currency::Krw) => ("원", true),
+        }
+    }
+}
+
+/// An amount in minor units of its currency.
+///
+/// There are no arithmetic operators: the `checked_*` methods return `None` on
+/// overflow or when the currencies differ, and callers decide what that means.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Money {
+    pub amount: i64,
+    pub currency: Currency,
+}
+
+impl Money {
+    pub const fn new(amount: i64, currency: Currency) -> Self {
+        Self { amount, currency }
+    }
+
+    pub const fn krw(amount: i64) -> Self {
+        Self::new(amount, Currency::Krw)
+    }
+
+    pub fn zero(currency: Currency) -> Self {
+        Self::new(0, currency)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.amount == 0
+    }
+
+    pub fn is_positive(&self) -> bool {
+        self.amount > 0
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.amount < 0
+    }
+
+    pub fn abs(self) -> Self {
+        Self::new(self.amount.abs(), self.currency)
+    }
+
+    /// The smaller amount; `self` when the currencies differ.
+    pub fn min(self, other: Self) -> Self {
+        if other < self { other } else { self }
+    }
+
+    /// The larger amount; `self` when the currencies differ.
+    pub fn max(self, other: Self) -> Self {
+        if other > self { other } else { self }
+    }
+
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        self.same_currency(other)?;
+        Some(Self::new(self.amount.checked_add(other.amount)?, self.currency))
+    }
+
+    pub fn checked_sub(self, other: Self) -> Option<Self> {
+        self.same_currency(other)?;
+        Some(Self::new(self.amount.checked_sub(other.amount)?, self.currency))
+    }
+
+    pub fn checked_mul(self, factor: i64) -> Option<Self> {
+        Some(Self::new(self.amount.checked_mul(factor)?, self.currency))
+    }
+
+    pub fn checked_neg(self) -> Option<Self> {
+        Some(Self::new(self.amount.checked_neg()?, self.currency))
+    }
+
+    /// Sums amounts of one currency; `None` on overflow or mixed currencies.
+    pub fn checked_sum(currency: Currency, amounts: impl IntoIterator<Item = Self>) -> Option<Self> {
+        amounts.into_iter().try_fold(Self::zero(currency), Self::checked_add)
+    }
+
+    /// The amount with its currency sign, written the way `locale` does.
+    pub fn format(&self, locale: Locale) -> String {
+        let number = self.grouped_digits(locale);
+        match locale.currency_sign(self.currency) {
+            (sign, false) => format!("{}{}{}", self.sign(), sign, number),
+            (sign, true) => format!("{}{}{}", self.sign(), number, sign),
+        }
+    }
+
+    /// The amount with its ISO code instead of the symbol, e.g. "KRW 1,200", for
+    /// output that cannot render currency symbols.
+    pub fn format_code(&self, locale: Locale) -> String {
+        format!("{}{} {}", self.sign(), self.currency.code(), self.grouped_digits(locale))
+    }
+
+    fn sign(&self) -> &'static str {
+        if self.is_negative() { "-" } else { "" }
+    }
+
+    fn grouped_digits(&self, locale: Locale) -> String {
+        let decimals = self.currency.decimals() as usize;
+        let digits = format!("{:0>width$}", self.amount.unsigned_abs(), width = decimals + 1);
+        let (major, minor) = digits.split_at(digits.len() - decimals);
+        let grouped = major
+            .as_bytes()
+            .rchunks(3)
+            .rev()
+            .map(|chunk| std::str::from_utf8(chunk).expect("Digits should be valid UTF-8"))
+            .collect::<Vec<_>>()
+            .join(locale.group_separator());
+        if minor.is_empty() {
+            grouped
+        } else {
+            format!("{}{}{}", grouped, locale.decimal_separator(), minor)
+        }
+    }
+
+    fn same_currency(self, other: Self) -> Option<()> {
+        (self.currency == other.currency).then_some(())
+    }
+}
+
+/// In the site's locale, [`Locale::English`]: "₩1,200", "-₩500".
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.format(Locale::default()))
+    }
+}
+
+/// Amounts of different currencies are not ordered.
+impl PartialOrd for Money {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        (self.currency == other.currency).then(|| self.amount.cmp(&other.amount))
+    }
+}
+
+impl Type<Postgres> for Money {
+    fn type_info() -> PgTypeInfo {
+        <i64 as Type<Postgres>>::type_info()
+    }
+}
+
+impl Encode<'_, Postgres> for Money {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        <i64 as Encode<Postgres>>::encode_by_ref(&self.amount, buf)
+    }
+}
+
+impl<'r> Decode<'r, Postgres> for Money {
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        Ok(Self::krw(<i64 as Decode<Postgres>>::decode(value)?))
+    }
+}
+
+/// Serialized as the bare amount, as the payment gateway and stored JSON expect.
+impl Serialize for Money {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.amount.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Money {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        i64::deserialize(deserializer).map(Self::krw)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_formats_won_with_grouping() {
+        assert_eq!(Money::krw(0).to_string(), "₩0");
+        assert_eq!(Money::krw(1200).to_string(), "₩1,200");
+        assert_eq!(Money::krw(-1_234_567).to_string(), "-₩1,234,567");
+        assert_eq!(Money::krw(3_000_000_000).format_code(Locale::English), "KRW 3,000,000,000");
+        assert_eq!(Money::krw(-100).format_code(Locale::English), "-KRW 100");
+    }
+
+    #[test]
+    fn test_korean_puts_the_unit_after_the_number() {
+        assert_eq!(Money::krw(1200).format(Locale::Korean), "1,200원");
+        assert_eq!(Money::krw(-500).format(Locale::Korean), "-500원");
+    }
+
+    #[test]
+    fn test_checked_arithmetic_reports_overflow() {
+        assert_eq!(Money::krw(700).checked_sub(Money::krw(1000)), Some(Money::krw(-300)));
+        assert_eq!(Money::krw(i64::MAX).checked_add(Money::krw(1)), None);
+        assert_eq!(Money::krw(i64::MAX).checked_mul(2), None);
+        assert_eq!(Money::krw(i64::MIN).checked_neg(), None);
+        assert_eq!(
+            Money::checked_sum(Currency::Krw, [Money::krw(2_000_000_000), Money::krw(2_000_000_000)]),
+            Some(Money::krw(4_000_000_000))
+        );
+    }
+}
@@ -0,0 +1,68 @@
+//! Transactional notifications sent to users.
+//!
+//! Each notification checks the recipient's preferences first. Delivery failures are
+//! logged rather than returned so they never interrupt the flow that triggered them.
+
+use sqlx::PgPool;
+use time::OffsetDateTime;
+
+use crate::{
+    data::queries,
+    email::{self, EmailConfig},
+    models::{order::Order, subscription::Subscription},
+};
+
+pub async fn notify_payment_succeeded(db: &PgPool, config: &EmailConfig, order: &Order) {
+    if !payment_emails_enabled(db, order.user_id).await {
+        return;
+    }
+
+    if let Err(e) = email::send_payment_receipt(config, db, order).await {
+        tracing::error!("Failed to send payment receipt for order {}: {}", order.order_number, e);
+    }
+}
+
+pub async fn notify_payment_failed(db: &PgPool, config: &EmailConfig, order: &Order) {
+    if !payment_emails_enabled(db, order.user_id).await {
+        return;
+    }
+
+    if let Err(e) = email::send_payment_failed(config, db, order).await {
+        tracing::error!("Failed to send payment failure email for order {}: {}", order.order_number, e);
+    }
+}
+
+pub async fn notify_subscription_payment_failed(
+    db: &PgPool,
+    config: &EmailConfig,
+    subscription: &Subscription,
+    next_retry_at: OffsetDateTime,
+) {
+    if !payment_emails_enabled(db, subscription.user_id).await {
+        return;
+    }
+
+    if let Err(e) = email::send_subscription_payment_failed(config, db, subscription, next_retry_at).await {
+        tracing::error!("Failed to send dunning email for subscription {}: {}", subscription.subscription_id, e);
+    }
+}
+
+pub async fn notify_subscription_ended(db: &PgPool, config: &EmailConfig, subscription: &Subscription) {
+    if !payment_emails_enabled(db, subscription.user_id).await {
+        return;
+    }
+
+    if let Err(e) = email::send_subscription_ended(config, db, subscription).await {
+        tracing::error!("Failed to send subscription ended email for {}: {}", subscription.subscription_id, e);
+    }
+}
+
+async fn payment_emails_enabled(db: &PgPool, user_id: i32) -> bool {
+    match queries::user::get_payment_emails_enabled(db, user_id).await {
+        Ok(enabled) => enabled,
+        Err(e) => {
+            tracing::error!("Failed to read notification preferences for user {}: {}", user_id, e);
+            false
+        }
+    }
+}
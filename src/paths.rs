@@ -31,6 +31,15 @@ pub mod pages {
     pub const QUOTE: &str = "/quote/{order_id}";
     pub const CHECKOUT: &str = "/checkout/{order_id}";
     pub const RESULT: &str = "/result/{order_id}";
+    pub const CREDIT_TOP_UP: &str = "/credits/top_up/{topup_id}";
+    /// Bank transfer instructions for an order awaiting a deposit
+    pub const DEPOSIT: &str = "/orders/{order_id}/deposit";
+    pub const SUBSCRIPTION: &str = "/subscription";
+    /// Card registration for a new or overdue subscription
+    pub const SUBSCRIPTION_CARD: &str = "/subscription/{subscription_id}/card";
+    /// PDF receipt download
+    pub const RECEIPT: &str = "/receipts/{order_id}";
+    pub const UNSUBSCRIBE: &str = "/unsubscribe";
 
     pub mod admin {
         pub const HOME: &str = "/admin";
@@ -38,6 +47,26 @@ pub mod pages {
         pub const USER_DETAIL: &str = "/admin/users/{user_id}";
         pub const ORDERS: &str = "/admin/orders";
         pub const ORDER_DETAIL: &str = "/admin/orders/{order_id}";
+        pub const SUPPRESSIONS: &str = "/admin/suppressions";
+        pub const INQUIRIES: &str = "/admin/inquiries";
+        pub const INQUIRY_DETAIL: &str = "/admin/inquiries/{inquiry_id}";
+        pub const QUARANTINE: &str = "/admin/inquiries/quarantine";
+        pub const RECONCILIATION: &str = "/admin/reconciliation";
+        pub const DISCOUNTS: &str = "/admin/discounts";
+        pub const PRICING: &str = "/admin/pricing";
+    }
+
+    /// Development-only pages, registered only when email is captured locally
+    /// or the fake payment gateway is selected
+    pub mod dev {
+        pub const MAILBOX: &str = "/dev/mailbox";
+        pub const MAILBOX_MESSAGE: &str = "/dev/mailbox/{message_id}";
+        /// Mock checkout, registered only with `PAYMENT_GATEWAY=fake`
+        pub const FAKE_CHECKOUT: &str = "/dev/checkout/{order_id}";
+        pub const FAKE_TOP_UP_CHECKOUT: &str = "/dev/checkout/top_up/{topup_id}";
+        pub const FAKE_CARD_REGISTRATION: &str = "/dev/billing/{subscription_id}";
+        /// Simulates the customer's bank transfer into an issued virtual account
+        pub const FAKE_DEPOSIT: &str = "/dev/deposits/{order_id}";
     }
 }
 
@@ -47,10 +76,33 @@ pub mod forms {
         TODOS => "/todos",
         CONTACT => "/contact",
         TEXT_ANALYZER => "/text_analyzer",
+        NOTIFICATION_PREFERENCES => "/notification_preferences",
+        QUOTE_DISCOUNT => "/quote/{order_id}/discount",
+        QUOTE_DISCOUNT_REMOVE => "/quote/{order_id}/discount/remove",
+        CHECKOUT_CASH_RECEIPT => "/checkout/{order_id}/cash_receipt",
+        CHECKOUT_CASH_RECEIPT_REMOVE => "/checkout/{order_id}/cash_receipt/remove",
+        CREDIT_TOP_UP => "/credits/top_up",
+        SUBSCRIBE => "/subscription",
+        SUBSCRIPTION_CANCEL => "/subscription/cancel",
+        SUBSCRIPTION_RESUME => "/subscription/resume",
     });
 
     pub mod admin {
         pub const GRANT_ROLE: &str = "/forms/admin/users/{user_id}/grant-role";
+        pub const IMPORT_SUPPRESSIONS: &str = "/forms/admin/suppressions/import";
+        pub const INQUIRY_STATUS: &str = "/forms/admin/inquiries/{inquiry_id}/status";
+        pub const INQUIRY_ASSIGN: &str = "/forms/admin/inquiries/{inquiry_id}/assign";
+        pub const INQUIRY_NOTE: &str = "/forms/admin/inquiries/{inquiry_id}/notes";
+        pub const INQUIRY_REPLY: &str = "/forms/admin/inquiries/{inquiry_id}/reply";
+        pub const INQUIRY_RELEASE: &str = "/forms/admin/inquiries/{inquiry_id}/release";
+        pub const REFUND_ORDER: &str = "/forms/admin/orders/{order_id}/refund";
+        pub const ADJUST_CREDITS: &str = "/forms/admin/users/{user_id}/credits";
+        pub const CREATE_PRICING_RULE: &str = "/forms/admin/pricing";
+        pub const CREATE_DISCOUNT: &str = "/forms/admin/discounts";
+        pub const TOGGLE_DISCOUNT: &str = "/forms/admin/discounts/{discount_code_id}/toggle";
+        pub const RUN_RECONCILIATION: &str = "/forms/admin/reconciliation/run";
+        pub const FIX_FINDING: &str = "/forms/admin/reconciliation/findings/{finding_id}/fix";
+        pub const DISMISS_FINDING: &str = "/forms/admin/reconciliation/findings/{finding_id}/dismiss";
     }
 }
 
@@ -62,16 +114,24 @@ pub mod actions {
         TODOS_TODO_ID_TOGGLE => "/todos/{todo_id}/toggle",
         PAYMENT_INITIATE => "/payment/initiate",
         PAYMENT_VERIFY => "/payment/verify",
+        PAYMENT_WEBHOOK => "/payment/webhook",
+        PAY_WITH_CREDITS => "/credits/pay",
+        CREDIT_TOP_UP_VERIFY => "/credits/verify",
+        SUBSCRIPTION_CARD_REGISTERED => "/subscription/card",
+        UNSUBSCRIBE => "/unsubscribe",
     });
 
     pub mod admin {
         pub const REVOKE_ROLE: &str = "/actions/admin/users/{user_id}/revoke-role";
+        pub const DELETE_SUPPRESSION: &str = "/actions/admin/suppressions/{suppression_id}";
+        pub const DELETE_INQUIRY: &str = "/actions/admin/inquiries/{inquiry_id}";
     }
 }
 
 pub mod static_files {
     define_nested_routes!("/static", {
         FAVICON => "/img/favicon.svg",
+        POW_SCRIPT => "/js/pow.js",
     });
 }
 
@@ -80,7 +140,8 @@ pub fn with_param(path: &str, param_name: &str, value: &impl ToString) -> String
 }
 
 pub fn with_query_param(base: &str, key: &str, value: &str) -> String {
-    format!("{}?{}={}", base, key, value)
+    let separator = if base.contains('?') { '&' } else { '?' };
+    format!("{}{}{}={}", base, separator, key, value)
 }
 
 pub fn with_page(base: &str, page: i64) -> String {
@@ -90,6 +151,7 @@ pub fn with_page(base: &str, page: i64) -> String {
 /// Common path builders for frequently used routes
 pub mod helpers {
     use super::*;
+    use crate::money::Money;
     use uuid::Uuid;
 
     pub fn user_detail_path(user_id: i32) -> String {
@@ -111,4 +173,72 @@ pub mod helpers {
     pub fn result_path(order_id: &Uuid) -> String {
         with_param(pages::RESULT, "order_id", order_id)
     }
+
+    pub fn receipt_path(order_id: &Uuid) -> String {
+        with_param(pages::RECEIPT, "order_id", order_id)
+    }
+
+    pub fn fake_checkout_path(order_id: &Uuid) -> String {
+        with_param(pages::dev::FAKE_CHECKOUT, "order_id", order_id)
+    }
+
+    pub fn credit_top_up_path(topup_id: &Uuid) -> String {
+        with_param(pages::CREDIT_TOP_UP, "topup_id", topup_id)
+    }
+
+    pub fn deposit_path(order_id: &Uuid) -> String {
+        with_param(pages::DEPOSIT, "order_id", order_id)
+    }
+
+    pub fn fake_deposit_path(order_id: &Uuid) -> String {
+        with_param(pages::dev::FAKE_DEPOSIT, "order_id", order_id)
+    }
+
+    pub fn subscription_card_path(subscription_id: &Uuid) -> String {
+        with_param(pages::SUBSCRIPTION_CARD, "subscription_id", subscription_id)
+    }
+
+    pub fn fake_card_registration_path(subscription_id: &Uuid) -> String {
+        with_param(pages::dev::FAKE_CARD_REGISTRATION, "subscription_id", subscription_id)
+    }
+
+    /// The gateway redirect after a card registration; Toss appends `customerKey` and `authKey`
+    pub fn card_registered_path(subscription_id: &Uuid) -> String {
+        with_query_param(actions::SUBSCRIPTION_CARD_REGISTERED, "subscriptionId", &subscription_id.to_string())
+    }
+
+    pub fn fake_top_up_checkout_path(topup_id: &Uuid) -> String {
+        with_param(pages::dev::FAKE_TOP_UP_CHECKOUT, "topup_id", topup_id)
+    }
+
+    /// The gateway success redirect to `verify_path`, carrying the parameters Toss appends
+    pub fn payment_verify_url(verify_path: &str, order_number: &str, payment_key: &str, amount: Money) -> String {
+        let url = with_query_param(verify_path, "orderId", &urlencoding::encode(order_number));
+        let url = with_query_param(&url, "paymentKey", &urlencoding::encode(payment_key));
+        with_query_param(&url, "amount", &amount.amount.to_string())
+    }
+
+    pub fn inquiry_detail_path(inquiry_id: i32) -> String {
+        with_param(pages::admin::INQUIRY_DETAIL, "inquiry_id", &inquiry_id)
+    }
+
+    pub fn toggle_discount_path(discount_code_id: i32) -> String {
+        with_param(forms::admin::TOGGLE_DISCOUNT, "discount_code_id", &discount_code_id)
+    }
+
+    pub fn fix_finding_path(finding_id: i32) -> String {
+        with_param(forms::admin::FIX_FINDING, "finding_id", &finding_id)
+    }
+
+    pub fn dismiss_finding_path(finding_id: i32) -> String {
+        with_param(forms::admin::DISMISS_FINDING, "finding_id", &finding_id)
+    }
+
+    pub fn delete_suppression_path(suppression_id: i32) -> String {
+        with_param(actions::admin::DELETE_SUPPRESSION, "suppression_id", &suppression_id)
+    }
+
+    pub fn mailbox_message_path(message_id: &str) -> String {
+        with_param(pages::dev::MAILBOX_MESSAGE, "message_id", &message_id)
+    }
 }
@@ -0,0 +1,133 @@
+//! Charges subscriptions through their billing keys and runs the renewal schedule.
+
+use sqlx::PgPool;
+use time::OffsetDateTime;
+
+use super::{BillingChargeRequest, GatewayPayment, GatewayPaymentStatus, PaymentError, PaymentGateway};
+use crate::{
+    data::{
+        commands::{self, subscription::{ChargeFailure, PendingCharge}},
+        errors::DataError,
+        queries,
+    },
+    email::EmailConfig,
+    models::subscription::{Subscription, SubscriptionStatus},
+    notifications,
+};
+
+pub enum ChargeOutcome {
+    Paid,
+    Declined { message: String, failure: ChargeFailure },
+    /// The gateway could not be reached; the charge stays pending and is retried.
+    Unreachable,
+}
+
+#[derive(Debug, Default)]
+pub struct RenewalSummary {
+    pub renewed: i32,
+    pub failed: i32,
+    pub ended: i32,
+    pub errors: i32,
+}
+
+/// Charges one period of `subscription` on its registered card.
+pub async fn charge_subscription(
+    db: &PgPool,
+    gateway: &dyn PaymentGateway,
+    subscription: &Subscription,
+) -> Result<ChargeOutcome, DataError> {
+    let Some(billing_key) = subscription.billing_key.as_deref() else {
+        return Ok(ChargeOutcome::Unreachable);
+    };
+
+    let charge = commands::subscription::start_charge(db, subscription).await?;
+    let result = if charge.resumed {
+        // The earlier attempt may have been charged after all; asking first avoids charging twice
+        match gateway.query_by_order(&charge.order_number).await {
+            Ok(payment) if payment.status == GatewayPaymentStatus::Done => Ok(payment),
+            Ok(_) | Err(PaymentError::Rejected { .. }) => send_charge(gateway, subscription, billing_key, &charge).await,
+            Err(e) => Err(e),
+        }
+    } else {
+        send_charge(gateway, subscription, billing_key, &charge).await
+    };
+
+    let message = match result {
+        Ok(payment) if payment.status == GatewayPaymentStatus::Done && payment.total_amount == charge.amount => {
+            commands::subscription::record_charge_paid(db, subscription.subscription_id, charge.charge_id, &payment.payment_key)
+                .await?;
+            return Ok(ChargeOutcome::Paid);
+        }
+        Ok(payment) => format!("Unexpected gateway result: {} for {}", payment.status.as_str(), payment.total_amount),
+        Err(PaymentError::Rejected { message, .. }) => message,
+        Err(e @ (PaymentError::Request(_) | PaymentError::Unavailable { .. })) => {
+            tracing::error!("Charge {} could not reach the gateway: {}", charge.order_number, e);
+            return Ok(ChargeOutcome::Unreachable);
+        }
+    };
+
+    let failure =
+        commands::subscription::record_charge_failed(db, subscription.subscription_id, charge.charge_id, &message).await?;
+    Ok(ChargeOutcome::Declined { message, failure })
+}
+
+async fn send_charge(
+    gateway: &dyn PaymentGateway,
+    subscription: &Subscription,
+    billing_key: &str,
+    charge: &PendingCharge,
+) -> Result<GatewayPayment, PaymentError> {
+    gateway
+        .charge_billing_key(BillingChargeRequest {
+            billing_key,
+            customer_key: &subscription.customer_key,
+            order_number: &charge.order_number,
+            order_name: &subscription.order_name(),
+            amount: charge.amount,
+            customer_email: &subscription.user_email,
+        })
+        .await
+}
+
+/// Renews every subscription whose period or retry is due, ending those that
+/// were cancelled, and emails the customer when a renewal fails.
+pub async fn renew_due(
+    db: &PgPool,
+    gateway: &dyn PaymentGateway,
+    email: &EmailConfig,
+    now: OffsetDateTime,
+) -> Result<RenewalSummary, DataError> {
+    let mut summary = RenewalSummary::default();
+
+    for subscription in queries::subscription::get_due_subscriptions(db, now).await? {
+        if subscription.status == SubscriptionStatus::Active && subscription.cancel_at_period_end {
+            commands::subscription::end_subscription(db, subscription.subscription_id).await?;
+            summary.ended += 1;
+            continue;
+        }
+
+        match charge_subscription(db, gateway, &subscription).await {
+            Ok(ChargeOutcome::Paid) => summary.renewed += 1,
+            Ok(ChargeOutcome::Declined { failure, .. }) => {
+                summary.failed += 1;
+                match failure {
+                    ChargeFailure::Retrying { next_retry_at } => {
+                        notifications::notify_subscription_payment_failed(db, email, &subscription, next_retry_at).await
+                    }
+                    ChargeFailure::Ended => {
+                        summary.ended += 1;
+                        notifications::notify_subscription_ended(db, email, &subscription).await
+                    }
+                    ChargeFailure::NotStarted => {}
+                }
+            }
+            Ok(ChargeOutcome::Unreachable) => summary.errors += 1,
+            Err(e) => {
+                tracing::error!("Renewal of subscription {} failed: {}", subscription.subscription_id, e);
+                summary.errors += 1;
+            }
+        }
+    }
+
+    Ok(summary)
+}
@@ -0,0 +1,166 @@
+//! Issues cash receipts (현금영수증) for paid transfers and cancels them as the
+//! orders are refunded.
+
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use super::{CashReceiptRequest, PaymentError, PaymentGateway};
+use crate::{
+    constants::{cash_receipts, errors},
+    data::{amount_in_range, commands::{self, cash_receipt::LockedCashReceipt}, errors::DataError, queries},
+    models::{
+        cash_receipt::CashReceiptStatus,
+        order::{Order, PaymentStatus},
+    },
+    money::Money,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CashReceiptOutcome {
+    Unchanged,
+    Issued,
+    Cancelled,
+    /// The gateway refused to issue the receipt
+    Refused,
+    /// The gateway could not be reached; the next sweep tries again
+    Unreachable,
+}
+
+#[derive(Debug, Default)]
+pub struct CashReceiptSummary {
+    pub issued: i32,
+    pub cancelled: i32,
+    pub refused: i32,
+    pub errors: i32,
+}
+
+/// Brings an order's cash receipt in line with the order: issues a requested
+/// receipt once a transfer is paid, and cancels as much of it as was refunded.
+///
+/// A receipt another sync is working on is skipped; that sync brings it up to date.
+pub async fn sync_cash_receipt(
+    db: &PgPool,
+    gateway: &dyn PaymentGateway,
+    order_id: Uuid,
+) -> Result<CashReceiptOutcome, DataError> {
+    let Some(locked) = commands::cash_receipt::lock_receipt(db, order_id).await? else {
+        return Ok(CashReceiptOutcome::Unchanged);
+    };
+    let order = queries::order::get_order(db, order_id)
+        .await?
+        .ok_or(DataError::NotFound(errors::ORDER_NOT_FOUND))?;
+    if !order.allows_cash_receipt() {
+        return Ok(CashReceiptOutcome::Unchanged);
+    }
+
+    match locked.receipt.status {
+        CashReceiptStatus::Requested if !order.payment_status.has_receipt() => Ok(CashReceiptOutcome::Unchanged),
+        // Refunded before it was issued: there is nothing left to issue it for
+        CashReceiptStatus::Requested if order.payment_status == PaymentStatus::Refunded => {
+            let zero = Money::zero(order.price_amount.currency);
+            locked.record_cancellation(zero, zero, true).await?;
+            Ok(CashReceiptOutcome::Cancelled)
+        }
+        CashReceiptStatus::Requested => {
+            let outcome = issue(gateway, &order, locked).await?;
+            // Issued for the full price; cancel whatever was refunded in the meantime
+            if outcome == CashReceiptOutcome::Issued
+                && let Some(locked) = commands::cash_receipt::lock_receipt(db, order_id).await?
+            {
+                cancel_refunded(db, gateway, &order, locked).await?;
+            }
+            Ok(outcome)
+        }
+        CashReceiptStatus::Issued => cancel_refunded(db, gateway, &order, locked).await,
+        CashReceiptStatus::Failed | CashReceiptStatus::Cancelled => Ok(CashReceiptOutcome::Unchanged),
+    }
+}
+
+async fn issue(
+    gateway: &dyn PaymentGateway,
+    order: &Order,
+    locked: LockedCashReceipt,
+) -> Result<CashReceiptOutcome, DataError> {
+    let order_name = order.order_name();
+    let request = CashReceiptRequest {
+        order_number: &order.order_number,
+        order_name: &order_name,
+        amount: order.price_amount,
+        receipt_type: locked.receipt.receipt_type,
+        identifier: &locked.receipt.identifier,
+    };
+    match gateway.issue_cash_receipt(request).await {
+        Ok(issued) => {
+            locked.record_issued(&issued, order.price_amount).await?;
+            Ok(CashReceiptOutcome::Issued)
+        }
+        Err(PaymentError::Rejected { code, message }) => {
+            tracing::warn!("Cash receipt for order {} refused ({}): {}", order.order_number, code, message);
+            locked.record_failed(&message).await?;
+            Ok(CashReceiptOutcome::Refused)
+        }
+        Err(e) => {
+            tracing::warn!("Cash receipt for order {} not issued: {}", order.order_number, e);
+            Ok(CashReceiptOutcome::Unreachable)
+        }
+    }
+}
+
+/// Cancels the part of an issued receipt that has since been refunded.
+async fn cancel_refunded(
+    db: &PgPool,
+    gateway: &dyn PaymentGateway,
+    order: &Order,
+    locked: LockedCashReceipt,
+) -> Result<CashReceiptOutcome, DataError> {
+    let Some(receipt_key) = locked.receipt.receipt_key.as_deref() else {
+        return Ok(CashReceiptOutcome::Unchanged);
+    };
+    let issued_amount = locked.receipt.issued_amount.unwrap_or(order.price_amount);
+    let cancelled = locked.receipt.cancelled_amount;
+
+    let refunded = queries::refund::get_refunded_total(db, order.order_id).await?.min(issued_amount);
+    if refunded <= cancelled {
+        return Ok(CashReceiptOutcome::Unchanged);
+    }
+
+    let cancel_amount = amount_in_range(refunded.checked_sub(cancelled))?;
+    match gateway.cancel_cash_receipt(receipt_key, cancel_amount, refunded).await {
+        Ok(()) => {
+            locked.record_cancellation(cancelled, refunded, refunded >= issued_amount).await?;
+            Ok(CashReceiptOutcome::Cancelled)
+        }
+        Err(e) => {
+            tracing::warn!("Cash receipt for order {} not cancelled: {}", order.order_number, e);
+            Ok(CashReceiptOutcome::Unreachable)
+        }
+    }
+}
+
+/// Runs [`sync_cash_receipt`] right after a payment or refund. Errors are only
+/// logged; the background sweep picks the receipt up again.
+pub async fn update_cash_receipt(db: &PgPool, gateway: &dyn PaymentGateway, order_id: Uuid) {
+    if let Err(e) = sync_cash_receipt(db, gateway, order_id).await {
+        tracing::error!("Failed to update cash receipt for order {}: {}", order_id, e);
+    }
+}
+
+/// Issues and cancels the cash receipts that are behind their orders.
+pub async fn sync_pending_cash_receipts(
+    db: &PgPool,
+    gateway: &dyn PaymentGateway,
+) -> Result<CashReceiptSummary, DataError> {
+    let mut summary = CashReceiptSummary::default();
+
+    for order_id in queries::cash_receipt::get_pending_order_ids(db, cash_receipts::SWEEP_BATCH_SIZE).await? {
+        match sync_cash_receipt(db, gateway, order_id).await? {
+            CashReceiptOutcome::Unchanged => {}
+            CashReceiptOutcome::Issued => summary.issued += 1,
+            CashReceiptOutcome::Cancelled => summary.cancelled += 1,
+            CashReceiptOutcome::Refused => summary.refused += 1,
+            CashReceiptOutcome::Unreachable => summary.errors += 1,
+        }
+    }
+
+    Ok(summary)
+}
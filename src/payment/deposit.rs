@@ -0,0 +1,59 @@
+//! Closes out virtual accounts whose due date passed.
+
+use sqlx::PgPool;
+use time::{Duration, OffsetDateTime};
+
+use super::{GatewayPaymentStatus, PaymentGateway, sync_order};
+use crate::{
+    constants::deposits,
+    data::{commands, errors::DataError, queries},
+    email::EmailConfig,
+    models::order::OrderEventSource,
+};
+
+#[derive(Debug, Default)]
+pub struct DepositExpirySummary {
+    pub expired: i32,
+    /// Deposits the gateway received but no webhook delivered
+    pub settled: i32,
+    pub errors: i32,
+}
+
+/// Cancels orders still awaiting a deposit past their account's due date.
+///
+/// Each one is checked with the gateway first, so a transfer whose webhook was
+/// lost completes the order instead of cancelling it.
+pub async fn expire_overdue_deposits(
+    db: &PgPool,
+    gateway: &dyn PaymentGateway,
+    email: &EmailConfig,
+    now: OffsetDateTime,
+) -> Result<DepositExpirySummary, DataError> {
+    let mut summary = DepositExpirySummary::default();
+    let cutoff = now - Duration::minutes(deposits::EXPIRY_GRACE_MINUTES);
+
+    for account in queries::virtual_account::get_overdue(db, cutoff).await? {
+        match gateway.query_by_order(&account.order_number).await {
+            Ok(payment) if payment.status == GatewayPaymentStatus::Done => {
+                let outcome = sync_order(db, email, &payment, OrderEventSource::System).await?;
+                tracing::info!("Overdue deposit for order {}: {}", account.order_number, outcome);
+                summary.settled += 1;
+                continue;
+            }
+            Ok(_) => {}
+            Err(e) if e.is_not_found() => {}
+            Err(e) => {
+                tracing::warn!("Deposit expiry could not query order {}: {}", account.order_number, e);
+                summary.errors += 1;
+                continue;
+            }
+        }
+
+        if commands::virtual_account::expire(db, account.order_id).await? {
+            commands::discount::release_redemption(db, account.order_id).await?;
+            summary.expired += 1;
+        }
+    }
+
+    Ok(summary)
+}
@@ -0,0 +1,372 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use async_trait::async_trait;
+use time::{Duration, OffsetDateTime};
+use uuid::Uuid;
+
+use super::{
+    BillingChargeRequest, BillingKey, CashReceiptRequest, ConfirmRequest, GatewayCashReceipt, GatewayEasyPay,
+    GatewayPayment, GatewayPaymentStatus, GatewayVirtualAccount, PaymentError, PaymentGateway, TOSS_METHODS,
+};
+use crate::{constants::deposits, models::payment_method::PaymentMethod, money::Money};
+
+const KEY_PREFIX: &str = "fake_";
+const DECLINE_KEY_PREFIX: &str = "fake_decline_";
+/// 우리은행, as a plausible bank for issued accounts
+const VIRTUAL_ACCOUNT_BANK_CODE: &str = "20";
+const AUTH_KEY_PREFIX: &str = "fake_auth_";
+const DECLINE_AUTH_KEY_PREFIX: &str = "fake_auth_decline_";
+const BILLING_KEY_PREFIX: &str = "fake_billing_";
+const DECLINE_BILLING_KEY_PREFIX: &str = "fake_billing_decline_";
+const CASH_RECEIPT_KEY_PREFIX: &str = "fake_receipt_";
+
+/// In-memory gateway for local development and tests.
+///
+/// The mock checkout page hands out payment keys from [`FakeGateway::payment_key`];
+/// confirming a "decline" key fails the way a refused card does at Toss, and a
+/// virtual account key issues an account that waits for [`FakeGateway::deposit`].
+/// Approved keys carry their payment method, reported back the way Toss reports it.
+/// Payments live only as long as the process.
+///
+/// Billing keys carry their customer key and whether the card declines, so they
+/// keep working across restarts.
+#[derive(Clone, Default)]
+pub struct FakeGateway {
+    payments: Arc<Mutex<HashMap<String, GatewayPayment>>>,
+}
+
+impl FakeGateway {
+    /// Generates a payment key as the mock checkout would for `method`, approving
+    /// or declining on confirm.
+    pub fn payment_key(method: PaymentMethod, approve: bool) -> String {
+        if approve {
+            format!("{}{}_{}", KEY_PREFIX, method.as_str(), Uuid::new_v4().simple())
+        } else {
+            format!("{}{}", DECLINE_KEY_PREFIX, Uuid::new_v4().simple())
+        }
+    }
+
+    /// Marks the virtual account issued for `order_number` as paid, the way a
+    /// customer's bank transfer would.
+    pub fn deposit(&self, order_number: &str) -> Result<GatewayPayment, PaymentError> {
+        let mut payments = self.payments();
+        let payment = payments
+            .values_mut()
+            .find(|payment| payment.order_id == order_number && payment.virtual_account.is_some())
+            .ok_or_else(|| rejected("NOT_FOUND_PAYMENT", "No virtual account for this order"))?;
+
+        let expired = payment.virtual_account.as_ref().is_some_and(|account| account.due_date < OffsetDateTime::now_utc());
+        if payment.status != GatewayPaymentStatus::WaitingForDeposit || expired {
+            return Err(rejected("NOT_ALLOWED_DEPOSIT", "The virtual account no longer accepts deposits"));
+        }
+
+        payment.status = GatewayPaymentStatus::Done;
+        Ok(payment.clone())
+    }
+
+    /// Generates an auth key as the mock card registration would. A declining
+    /// card registers fine but every charge on it fails.
+    pub fn billing_auth_key(approve: bool) -> String {
+        let prefix = if approve { AUTH_KEY_PREFIX } else { DECLINE_AUTH_KEY_PREFIX };
+        format!("{}{}", prefix, Uuid::new_v4().simple())
+    }
+
+    fn payments(&self) -> std::sync::MutexGuard<'_, HashMap<String, GatewayPayment>> {
+        self.payments.lock().expect("Fake gateway lock poisoned")
+    }
+}
+
+/// The method an approved key from [`FakeGateway::payment_key`] was generated for.
+fn key_method(payment_key: &str) -> Option<PaymentMethod> {
+    let rest = payment_key.strip_prefix(KEY_PREFIX)?;
+    PaymentMethod::ALL
+        .into_iter()
+        .find(|method| rest.strip_prefix(method.as_str()).is_some_and(|id| id.starts_with('_')))
+}
+
+/// A payment in `method` as Toss would describe it.
+fn gateway_payment(payment_key: String, order_number: &str, amount: Money, method: PaymentMethod) -> GatewayPayment {
+    let (name, provider) = TOSS_METHODS
+        .iter()
+        .find(|(candidate, _, _)| *candidate == method)
+        .map(|(_, name, provider)| (*name, *provider))
+        .expect("Every payment method has a Toss name");
+
+    GatewayPayment {
+        payment_key,
+        order_id: order_number.to_string(),
+        status: GatewayPaymentStatus::Done,
+        total_amount: amount,
+        balance_amount: amount,
+        virtual_account: None,
+        method: Some(name.to_string()),
+        easy_pay: provider.map(|provider| GatewayEasyPay { provider: provider.to_string() }),
+    }
+}
+
+fn rejected(code: &str, message: &str) -> PaymentError {
+    PaymentError::Rejected {
+        code: code.to_string(),
+        message: message.to_string(),
+    }
+}
+
+#[async_trait]
+impl PaymentGateway for FakeGateway {
+    async fn confirm(&self, request: ConfirmRequest<'_>) -> Result<GatewayPayment, PaymentError> {
+        if request.payment_key.starts_with(DECLINE_KEY_PREFIX) {
+            return Err(rejected("REJECT_CARD_PAYMENT", "Declined by the fake gateway"));
+        }
+        let method = key_method(request.payment_key).filter(|_| request.amount.is_positive());
+        let Some(method) = method else {
+            return Err(rejected("INVALID_REQUEST", "Unknown payment session"));
+        };
+
+        let mut payments = self.payments();
+        if payments.contains_key(request.payment_key) {
+            return Err(rejected("ALREADY_PROCESSED_PAYMENT", "Payment already confirmed"));
+        }
+
+        let mut payment = gateway_payment(request.payment_key.to_string(), request.order_number, request.amount, method);
+        if method == PaymentMethod::VirtualAccount {
+            payment.status = GatewayPaymentStatus::WaitingForDeposit;
+            payment.virtual_account = Some(GatewayVirtualAccount {
+                account_number: format!("{:014}", Uuid::new_v4().as_u128() % 100_000_000_000_000),
+                bank_code: VIRTUAL_ACCOUNT_BANK_CODE.to_string(),
+                due_date: OffsetDateTime::now_utc() + Duration::hours(deposits::VALID_HOURS),
+            });
+        }
+        payments.insert(payment.payment_key.clone(), payment.clone());
+        Ok(payment)
+    }
+
+    async fn cancel(
+        &self,
+        payment_key: &str,
+        _reason: &str,
+        amount: Option<Money>,
+        refunded_total: Money,
+    ) -> Result<GatewayPayment, PaymentError> {
+        let mut payments = self.payments();
+        let payment = payments
+            .get_mut(payment_key)
+            .ok_or_else(|| rejected("NOT_FOUND_PAYMENT", "Payment not found"))?;
+
+        // The cancellation that brought the total here was already applied; replay it
+        if payment.total_amount.checked_sub(payment.balance_amount) == Some(refunded_total) {
+            return Ok(payment.clone());
+        }
+
+        if payment.status == GatewayPaymentStatus::Canceled {
+            return Err(rejected("ALREADY_CANCELED_PAYMENT", "Payment already cancelled"));
+        }
+
+        let amount = amount.unwrap_or(payment.balance_amount);
+        let balance_amount = payment.balance_amount.checked_sub(amount);
+        let Some(balance_amount) = balance_amount.filter(|balance| amount.is_positive() && !balance.is_negative()) else {
+            return Err(rejected("NOT_CANCELABLE_AMOUNT", "Cancel amount exceeds the remaining balance"));
+        };
+
+        payment.balance_amount = balance_amount;
+        payment.status = if payment.balance_amount.is_zero() {
+            GatewayPaymentStatus::Canceled
+        } else {
+            GatewayPaymentStatus::PartialCanceled
+        };
+        Ok(payment.clone())
+    }
+
+    async fn query(&self, payment_key: &str) -> Result<GatewayPayment, PaymentError> {
+        self.payments()
+            .get(payment_key)
+            .cloned()
+            .ok_or_else(|| rejected("NOT_FOUND_PAYMENT", "Payment not found"))
+    }
+
+    async fn query_by_order(&self, order_number: &str) -> Result<GatewayPayment, PaymentError> {
+        self.payments()
+            .values()
+            .find(|payment| payment.order_id == order_number)
+            .cloned()
+            .ok_or_else(|| rejected("NOT_FOUND_PAYMENT", "Payment not found"))
+    }
+
+    async fn issue_billing_key(&self, auth_key: &str, customer_key: &str) -> Result<BillingKey, PaymentError> {
+        let (prefix, card_number) = if auth_key.starts_with(DECLINE_AUTH_KEY_PREFIX) {
+            (DECLINE_BILLING_KEY_PREFIX, "4000-00**-****-0002")
+        } else if auth_key.starts_with(AUTH_KEY_PREFIX) {
+            (BILLING_KEY_PREFIX, "4242-42**-****-4242")
+        } else {
+            return Err(rejected("INVALID_AUTH_KEY", "Unknown card registration"));
+        };
+
+        Ok(BillingKey {
+            billing_key: format!("{}{}", prefix, customer_key),
+            card_number: Some(card_number.to_string()),
+        })
+    }
+
+    async fn charge_billing_key(&self, request: BillingChargeRequest<'_>) -> Result<GatewayPayment, PaymentError> {
+        let mut payments = self.payments();
+        if let Some(payment) = payments.values().find(|payment| payment.order_id == request.order_number) {
+            return Ok(payment.clone());
+        }
+
+        if request.billing_key.starts_with(DECLINE_BILLING_KEY_PREFIX) {
+            return Err(rejected("REJECT_CARD_PAYMENT", "Declined by the fake gateway"));
+        }
+        let registered_for = request.billing_key.strip_prefix(BILLING_KEY_PREFIX);
+        if registered_for != Some(request.customer_key) || !request.amount.is_positive() {
+            return Err(rejected("NOT_MATCHES_CUSTOMER_KEY", "Billing key does not belong to this customer"));
+        }
+
+        let payment = gateway_payment(
+            Self::payment_key(PaymentMethod::Card, true),
+            request.order_number,
+            request.amount,
+            PaymentMethod::Card,
+        );
+        payments.insert(payment.payment_key.clone(), payment.clone());
+        Ok(payment)
+    }
+
+    async fn issue_cash_receipt(&self, request: CashReceiptRequest<'_>) -> Result<GatewayCashReceipt, PaymentError> {
+        if !request.amount.is_positive() {
+            return Err(rejected("INVALID_REQUEST", "Cash receipt amount must be positive"));
+        }
+
+        let id = Uuid::new_v4();
+        Ok(GatewayCashReceipt {
+            receipt_key: format!("{}{}", CASH_RECEIPT_KEY_PREFIX, id.simple()),
+            issue_number: Some(format!("{:09}", id.as_u128() % 1_000_000_000)),
+            receipt_url: None,
+        })
+    }
+
+    async fn cancel_cash_receipt(&self, receipt_key: &str, _amount: Money, _cancelled_total: Money) -> Result<(), PaymentError> {
+        if !receipt_key.starts_with(CASH_RECEIPT_KEY_PREFIX) {
+            return Err(rejected("NOT_FOUND_CASH_RECEIPT", "Unknown cash receipt"));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn confirm_request(payment_key: &str) -> ConfirmRequest<'_> {
+        ConfirmRequest {
+            payment_key,
+            order_number: "ORDER-1",
+            amount: Money::krw(1000),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_confirm_query_and_cancel() {
+        let gateway = FakeGateway::default();
+        let key = FakeGateway::payment_key(PaymentMethod::Card, true);
+
+        let payment = gateway.confirm(confirm_request(&key)).await.unwrap();
+        assert_eq!(payment.status, GatewayPaymentStatus::Done);
+        assert!(gateway.confirm(confirm_request(&key)).await.is_err());
+        assert_eq!(gateway.query(&key).await.unwrap().order_id, "ORDER-1");
+        assert_eq!(gateway.query_by_order("ORDER-1").await.unwrap().payment_key, key);
+
+        let partial = gateway.cancel(&key, "test", Some(Money::krw(400)), Money::krw(400)).await.unwrap();
+        assert_eq!(partial.status, GatewayPaymentStatus::PartialCanceled);
+        assert_eq!(partial.balance_amount, Money::krw(600));
+        assert!(gateway.cancel(&key, "test", Some(Money::krw(700)), Money::krw(1100)).await.is_err());
+
+        let cancelled = gateway.cancel(&key, "test", None, Money::krw(1000)).await.unwrap();
+        assert_eq!(cancelled.status, GatewayPaymentStatus::Canceled);
+        assert_eq!(cancelled.balance_amount, Money::krw(0));
+    }
+
+    #[tokio::test]
+    async fn test_retried_cancel_is_applied_once() {
+        let gateway = FakeGateway::default();
+        let key = FakeGateway::payment_key(PaymentMethod::Card, true);
+        gateway.confirm(confirm_request(&key)).await.unwrap();
+
+        let first = gateway.cancel(&key, "test", Some(Money::krw(300)), Money::krw(300)).await.unwrap();
+        let retried = gateway.cancel(&key, "test", Some(Money::krw(300)), Money::krw(300)).await.unwrap();
+        assert_eq!(retried.balance_amount, first.balance_amount);
+        assert_eq!(gateway.query(&key).await.unwrap().balance_amount, Money::krw(700));
+
+        let second = gateway.cancel(&key, "test", Some(Money::krw(300)), Money::krw(600)).await.unwrap();
+        assert_eq!(second.balance_amount, Money::krw(400));
+    }
+
+    #[tokio::test]
+    async fn test_confirm_reports_the_method_in_the_key() {
+        let gateway = FakeGateway::default();
+        for method in [PaymentMethod::Card, PaymentMethod::Transfer, PaymentMethod::KakaoPay] {
+            let key = FakeGateway::payment_key(method, true);
+            assert_eq!(gateway.confirm(confirm_request(&key)).await.unwrap().payment_method(), Some(method));
+        }
+
+        let unknown = gateway.confirm(confirm_request("fake_cash_1")).await;
+        assert!(matches!(unknown, Err(PaymentError::Rejected { code, .. }) if code == "INVALID_REQUEST"));
+    }
+
+    #[tokio::test]
+    async fn test_decline_key_is_rejected() {
+        let gateway = FakeGateway::default();
+        let key = FakeGateway::payment_key(PaymentMethod::Card, false);
+
+        let result = gateway.confirm(confirm_request(&key)).await;
+        assert!(matches!(result, Err(PaymentError::Rejected { code, .. }) if code == "REJECT_CARD_PAYMENT"));
+        assert!(gateway.query(&key).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_virtual_account_waits_for_deposit() {
+        let gateway = FakeGateway::default();
+        assert!(gateway.deposit("ORDER-1").is_err());
+
+        let key = FakeGateway::payment_key(PaymentMethod::VirtualAccount, true);
+        let issued = gateway.confirm(confirm_request(&key)).await.unwrap();
+        assert_eq!(issued.status, GatewayPaymentStatus::WaitingForDeposit);
+        assert!(issued.virtual_account.is_some());
+        assert_eq!(issued.payment_method(), Some(PaymentMethod::VirtualAccount));
+
+        let deposited = gateway.deposit("ORDER-1").unwrap();
+        assert_eq!(deposited.status, GatewayPaymentStatus::Done);
+        assert_eq!(gateway.query(&issued.payment_key).await.unwrap().status, GatewayPaymentStatus::Done);
+        assert!(gateway.deposit("ORDER-1").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_billing_key_charges_once_per_order() {
+        let gateway = FakeGateway::default();
+        let card = gateway.issue_billing_key(&FakeGateway::billing_auth_key(true), "cus_1").await.unwrap();
+        let charge = |order_number| BillingChargeRequest {
+            billing_key: &card.billing_key,
+            customer_key: "cus_1",
+            order_number,
+            order_name: "Plan",
+            amount: Money::krw(9900),
+            customer_email: "customer@example.com",
+        };
+
+        let first = gateway.charge_billing_key(charge("SUB-1")).await.unwrap();
+        assert_eq!(first.status, GatewayPaymentStatus::Done);
+        let replayed = gateway.charge_billing_key(charge("SUB-1")).await.unwrap();
+        assert_eq!(replayed.payment_key, first.payment_key);
+        assert_ne!(gateway.charge_billing_key(charge("SUB-2")).await.unwrap().payment_key, first.payment_key);
+
+        let mut other_customer = charge("SUB-3");
+        other_customer.customer_key = "cus_2";
+        assert!(gateway.charge_billing_key(other_customer).await.is_err());
+
+        let declining = gateway.issue_billing_key(&FakeGateway::billing_auth_key(false), "cus_1").await.unwrap();
+        let mut declined = charge("SUB-4");
+        declined.billing_key = &declining.billing_key;
+        assert!(gateway.charge_billing_key(declined).await.is_err());
+    }
+}
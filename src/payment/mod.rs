@@ -0,0 +1,268 @@
+//! Payment gateway abstraction.
+//!
+//! Handlers talk to the payment provider only through [`PaymentGateway`], held in
+//! `AppState` as a [`SharedGateway`]. `PAYMENT_GATEWAY=toss` (the default) calls the
+//! Toss Payments API; `PAYMENT_GATEWAY=fake` approves payments in memory behind a
+//! mock checkout page, so the whole purchase flow runs without network access.
+
+mod billing;
+mod cash_receipt;
+mod deposit;
+mod fake;
+mod reconcile;
+mod sync;
+mod toss;
+
+use std::{any::Any, sync::Arc};
+
+use async_trait::async_trait;
+use serde::Deserialize;
+use time::OffsetDateTime;
+
+use crate::{
+    config::{PaymentConfig, PaymentProvider},
+    models::{cash_receipt::CashReceiptType, payment_method::PaymentMethod},
+    money::Money,
+};
+
+pub use billing::{ChargeOutcome, charge_subscription, renew_due};
+pub use cash_receipt::{sync_pending_cash_receipts, update_cash_receipt};
+pub use deposit::expire_overdue_deposits;
+pub use fake::FakeGateway;
+pub use reconcile::reconcile;
+pub use sync::{SyncOutcome, sync_order};
+pub use toss::TossGateway;
+
+pub type SharedGateway = Arc<dyn PaymentGateway>;
+
+#[derive(Debug, thiserror::Error)]
+pub enum PaymentError {
+    /// The gateway processed the request and refused it.
+    #[error("Payment rejected ({code}): {message}")]
+    Rejected { code: String, message: String },
+
+    /// The gateway could not be reached or answered with something unexpected.
+    #[error("Payment gateway request failed: {0}")]
+    Request(#[from] reqwest::Error),
+
+    /// The gateway answered with a server error, a rate limit or a body that is
+    /// not a Toss error, so whether the request took effect is unknown. Handle it
+    /// like [`PaymentError::Request`].
+    #[error("Payment gateway unavailable ({status}): {message}")]
+    Unavailable { status: u16, message: String },
+}
+
+impl PaymentError {
+    /// Whether the gateway has no such payment (Toss `NOT_FOUND_PAYMENT*` codes).
+    pub fn is_not_found(&self) -> bool {
+        matches!(self, Self::Rejected { code, .. } if code.starts_with("NOT_FOUND_PAYMENT"))
+    }
+}
+
+/// Payment status as reported by the gateway (Toss status names).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum GatewayPaymentStatus {
+    Ready,
+    InProgress,
+    WaitingForDeposit,
+    Done,
+    Canceled,
+    PartialCanceled,
+    Aborted,
+    Expired,
+}
+
+impl GatewayPaymentStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Ready => "READY",
+            Self::InProgress => "IN_PROGRESS",
+            Self::WaitingForDeposit => "WAITING_FOR_DEPOSIT",
+            Self::Done => "DONE",
+            Self::Canceled => "CANCELED",
+            Self::PartialCanceled => "PARTIAL_CANCELED",
+            Self::Aborted => "ABORTED",
+            Self::Expired => "EXPIRED",
+        }
+    }
+}
+
+/// A payment as the gateway sees it.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GatewayPayment {
+    pub payment_key: String,
+    /// Our `order_number`, which is the order ID sent to the gateway.
+    pub order_id: String,
+    pub status: GatewayPaymentStatus,
+    pub total_amount: Money,
+    /// Amount left after cancellations.
+    pub balance_amount: Money,
+    /// Transfer details, for virtual account payments only.
+    #[serde(default)]
+    pub virtual_account: Option<GatewayVirtualAccount>,
+    /// How the customer paid, in Toss's words (e.g. `카드`, `간편결제`).
+    #[serde(default)]
+    pub method: Option<String>,
+    /// The easy pay service used, if any.
+    #[serde(default)]
+    pub easy_pay: Option<GatewayEasyPay>,
+}
+
+/// Toss's `method` and `easyPay.provider` for each payment method we offer.
+const TOSS_METHODS: [(PaymentMethod, &str, Option<&str>); 7] = [
+    (PaymentMethod::Card, "카드", None),
+    (PaymentMethod::Transfer, "계좌이체", None),
+    (PaymentMethod::VirtualAccount, "가상계좌", None),
+    (PaymentMethod::MobilePhone, "휴대폰", None),
+    (PaymentMethod::KakaoPay, "간편결제", Some("카카오페이")),
+    (PaymentMethod::NaverPay, "간편결제", Some("네이버페이")),
+    (PaymentMethod::TossPay, "간편결제", Some("토스페이")),
+];
+
+impl GatewayPayment {
+    /// The method the customer actually paid with, or `None` for one we do not offer.
+    ///
+    /// An easy pay provider wins over `method`, since Toss may report an easy
+    /// payment funded by a card as `카드`.
+    pub fn payment_method(&self) -> Option<PaymentMethod> {
+        let found = match &self.easy_pay {
+            Some(easy_pay) => TOSS_METHODS.iter().find(|(_, _, provider)| *provider == Some(easy_pay.provider.as_str())),
+            None => {
+                let method = self.method.as_deref()?;
+                TOSS_METHODS.iter().find(|(_, name, provider)| provider.is_none() && *name == method)
+            }
+        };
+        found.map(|(method, _, _)| *method)
+    }
+}
+
+/// The easy pay service behind a payment (Toss `easyPay`).
+#[derive(Debug, Clone, Deserialize)]
+pub struct GatewayEasyPay {
+    /// e.g. `카카오페이`, `토스페이`
+    pub provider: String,
+}
+
+/// The account a virtual account payment is transferred to.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GatewayVirtualAccount {
+    pub account_number: String,
+    pub bank_code: String,
+    #[serde(with = "time::serde::rfc3339")]
+    pub due_date: OffsetDateTime,
+}
+
+/// Webhook delivery body (Toss format). Only the payment key or order ID is used;
+/// the payment itself is re-queried from the gateway before anything changes.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookEvent {
+    pub event_type: String,
+    pub data: WebhookEventData,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookEventData {
+    /// Sent with payment status changes
+    pub payment_key: Option<String>,
+    /// Sent with deposit callbacks, which carry no payment key
+    pub order_id: Option<String>,
+}
+
+pub struct ConfirmRequest<'a> {
+    pub payment_key: &'a str,
+    pub order_number: &'a str,
+    pub amount: Money,
+}
+
+/// A card registered for recurring charges.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BillingKey {
+    pub billing_key: String,
+    /// Masked card number
+    pub card_number: Option<String>,
+}
+
+/// A charge made on a billing key, without the customer present.
+pub struct BillingChargeRequest<'a> {
+    pub billing_key: &'a str,
+    /// The customer key the billing key was issued for
+    pub customer_key: &'a str,
+    pub order_number: &'a str,
+    pub order_name: &'a str,
+    pub amount: Money,
+    pub customer_email: &'a str,
+}
+
+/// A cash receipt (현금영수증) to issue for a paid order.
+pub struct CashReceiptRequest<'a> {
+    pub order_number: &'a str,
+    pub order_name: &'a str,
+    pub amount: Money,
+    pub receipt_type: CashReceiptType,
+    /// Phone, cash receipt card or business registration number, digits only
+    pub identifier: &'a str,
+}
+
+/// A cash receipt as issued by the gateway.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GatewayCashReceipt {
+    pub receipt_key: String,
+    /// The tax authority's approval number
+    pub issue_number: Option<String>,
+    pub receipt_url: Option<String>,
+}
+
+/// `Any` lets development routes reach the [`FakeGateway`] behind a [`SharedGateway`].
+#[async_trait]
+pub trait PaymentGateway: Any + Send + Sync {
+    /// Approves a payment the customer authorized in the checkout widget. For a
+    /// virtual account this issues the account and returns `WAITING_FOR_DEPOSIT`.
+    async fn confirm(&self, request: ConfirmRequest<'_>) -> Result<GatewayPayment, PaymentError>;
+
+    /// Cancels a payment in full, or partially when `amount` is given.
+    /// `refunded_total` is the payment's cancelled amount afterwards; repeating a
+    /// request for the same total does not cancel again.
+    async fn cancel(
+        &self,
+        payment_key: &str,
+        reason: &str,
+        amount: Option<Money>,
+        refunded_total: Money,
+    ) -> Result<GatewayPayment, PaymentError>;
+
+    /// Looks up the current state of a payment.
+    async fn query(&self, payment_key: &str) -> Result<GatewayPayment, PaymentError>;
+
+    /// Looks up the payment made for one of our orders, by `order_number`.
+    async fn query_by_order(&self, order_number: &str) -> Result<GatewayPayment, PaymentError>;
+
+    /// Exchanges the `authKey` from a card registration for a billing key.
+    async fn issue_billing_key(&self, auth_key: &str, customer_key: &str) -> Result<BillingKey, PaymentError>;
+
+    /// Charges a registered card. Repeating a request with the same order number
+    /// returns the original result instead of charging again.
+    async fn charge_billing_key(&self, request: BillingChargeRequest<'_>) -> Result<GatewayPayment, PaymentError>;
+
+    /// Issues a cash receipt for a payment made by transfer.
+    async fn issue_cash_receipt(&self, request: CashReceiptRequest<'_>) -> Result<GatewayCashReceipt, PaymentError>;
+
+    /// Cancels `amount` of an issued cash receipt. `cancelled_total` is the receipt's
+    /// cancelled amount afterwards; repeating a request for the same total does not
+    /// cancel again.
+    async fn cancel_cash_receipt(&self, receipt_key: &str, amount: Money, cancelled_total: Money) -> Result<(), PaymentError>;
+}
+
+/// Builds the gateway selected by `PAYMENT_GATEWAY`.
+pub fn from_config(config: &PaymentConfig) -> Result<SharedGateway, PaymentError> {
+    Ok(match config.provider() {
+        PaymentProvider::Toss(toss) => Arc::new(TossGateway::new(toss)?),
+        PaymentProvider::Fake => Arc::new(FakeGateway::default()),
+    })
+}
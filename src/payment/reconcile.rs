@@ -0,0 +1,184 @@
+//! Compares recent orders with the gateway's records and files the differences.
+
+use sqlx::PgPool;
+use time::OffsetDateTime;
+
+use super::{GatewayPayment, PaymentGateway};
+use crate::{
+    data::{commands, errors::DataError, queries},
+    models::{
+        order::PaymentStatus,
+        reconciliation::{FindingKind, ReconciliationCandidate},
+    },
+};
+
+#[derive(Debug)]
+pub struct ReconciliationSummary {
+    pub checked: i32,
+    /// New findings; mismatches already open from earlier runs are not counted again.
+    pub findings: i32,
+    pub errors: i32,
+}
+
+/// Whether money was captured for an order in this status.
+fn is_captured(status: PaymentStatus) -> bool {
+    matches!(status, PaymentStatus::Paid | PaymentStatus::PartiallyRefunded | PaymentStatus::Refunded)
+}
+
+/// Decides how an order disagrees with the gateway, if at all.
+///
+/// `payment` is `None` when the gateway has no payment for the order. Orders that
+/// were never paid and attempts the gateway aborted are not mismatches.
+pub fn classify(candidate: &ReconciliationCandidate, payment: Option<&GatewayPayment>) -> Option<FindingKind> {
+    let captured = is_captured(candidate.payment_status);
+    let Some(payment) = payment else {
+        return captured.then_some(FindingKind::UnknownPayment);
+    };
+
+    if captured && candidate.payment_key.as_deref().is_some_and(|key| key != payment.payment_key) {
+        return Some(FindingKind::UnknownPayment);
+    }
+
+    let target = payment.status.order_status()?;
+    if target != PaymentStatus::Failed && payment.total_amount != candidate.price_amount {
+        return Some(FindingKind::AmountMismatch);
+    }
+
+    let matches = match target {
+        PaymentStatus::Paid if candidate.payment_status.is_payable() || candidate.payment_status == PaymentStatus::AwaitingDeposit => {
+            return Some(FindingKind::PaidNotRecorded);
+        }
+        PaymentStatus::Paid => candidate.payment_status == PaymentStatus::Paid,
+        PaymentStatus::Failed => !captured,
+        _ => {
+            candidate.payment_status == target
+                && payment.total_amount.checked_sub(payment.balance_amount) == Some(candidate.refunded_amount)
+        }
+    };
+
+    (!matches).then_some(FindingKind::StatusMismatch)
+}
+
+/// Checks every order created in the window against the gateway.
+///
+/// Orders the gateway cannot be asked about are counted as errors and picked up
+/// again by the next run that covers them.
+pub async fn reconcile(
+    db: &PgPool,
+    gateway: &dyn PaymentGateway,
+    window_start: OffsetDateTime,
+    window_end: OffsetDateTime,
+) -> Result<ReconciliationSummary, DataError> {
+    let run_id = commands::reconciliation::start_run(db, window_start, window_end).await?;
+    let candidates = queries::reconciliation::get_candidates(db, window_start, window_end).await?;
+    let mut summary = ReconciliationSummary {
+        checked: 0,
+        findings: 0,
+        errors: 0,
+    };
+
+    for candidate in &candidates {
+        let payment = match gateway.query_by_order(&candidate.order_number).await {
+            Ok(payment) => Some(payment),
+            Err(e) if e.is_not_found() => None,
+            Err(e) => {
+                tracing::warn!("Reconciliation could not query order {}: {}", candidate.order_number, e);
+                summary.errors += 1;
+                continue;
+            }
+        };
+        summary.checked += 1;
+
+        if let Some(kind) = classify(candidate, payment.as_ref()) {
+            let recorded =
+                commands::reconciliation::record_finding(db, run_id, candidate, kind, payment.as_ref()).await?;
+            if recorded {
+                tracing::warn!("Reconciliation mismatch on order {}: {:?}", candidate.order_number, kind);
+                summary.findings += 1;
+            }
+        }
+    }
+
+    commands::reconciliation::finish_run(db, run_id, summary.checked, summary.findings, summary.errors).await?;
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::{money::Money, payment::GatewayPaymentStatus};
+
+    fn candidate(payment_status: PaymentStatus, payment_key: Option<&str>) -> ReconciliationCandidate {
+        ReconciliationCandidate {
+            order_id: Uuid::nil(),
+            order_number: "ORDER-1".to_string(),
+            payment_status,
+            price_amount: Money::krw(1000),
+            payment_key: payment_key.map(str::to_string),
+            refunded_amount: Money::krw(0),
+        }
+    }
+
+    fn payment(status: GatewayPaymentStatus, total_amount: i64, balance_amount: i64) -> GatewayPayment {
+        GatewayPayment {
+            payment_key: "key-1".to_string(),
+            order_id: "ORDER-1".to_string(),
+            status,
+            total_amount: Money::krw(total_amount),
+            balance_amount: Money::krw(balance_amount),
+            virtual_account: None,
+            method: None,
+            easy_pay: None,
+        }
+    }
+
+    #[test]
+    fn test_matching_orders_are_not_flagged() {
+        let done = payment(GatewayPaymentStatus::Done, 1000, 1000);
+        assert_eq!(classify(&candidate(PaymentStatus::Paid, Some("key-1")), Some(&done)), None);
+        assert_eq!(classify(&candidate(PaymentStatus::Pending, None), None), None);
+
+        let aborted = payment(GatewayPaymentStatus::Aborted, 1000, 1000);
+        assert_eq!(classify(&candidate(PaymentStatus::Pending, None), Some(&aborted)), None);
+
+        let mut refunded = candidate(PaymentStatus::PartiallyRefunded, Some("key-1"));
+        refunded.refunded_amount = Money::krw(400);
+        let partial = payment(GatewayPaymentStatus::PartialCanceled, 1000, 600);
+        assert_eq!(classify(&refunded, Some(&partial)), None);
+    }
+
+    #[test]
+    fn test_mismatches_are_classified() {
+        let done = payment(GatewayPaymentStatus::Done, 1000, 1000);
+        assert_eq!(
+            classify(&candidate(PaymentStatus::Pending, None), Some(&done)),
+            Some(FindingKind::PaidNotRecorded)
+        );
+        assert_eq!(
+            classify(&candidate(PaymentStatus::Cancelled, None), Some(&done)),
+            Some(FindingKind::StatusMismatch)
+        );
+        assert_eq!(
+            classify(&candidate(PaymentStatus::Paid, Some("key-1")), None),
+            Some(FindingKind::UnknownPayment)
+        );
+        assert_eq!(
+            classify(&candidate(PaymentStatus::Paid, Some("other-key")), Some(&done)),
+            Some(FindingKind::UnknownPayment)
+        );
+
+        let short = payment(GatewayPaymentStatus::Done, 900, 900);
+        assert_eq!(
+            classify(&candidate(PaymentStatus::Pending, None), Some(&short)),
+            Some(FindingKind::AmountMismatch)
+        );
+
+        let cancelled = payment(GatewayPaymentStatus::Canceled, 1000, 0);
+        assert_eq!(
+            classify(&candidate(PaymentStatus::Paid, Some("key-1")), Some(&cancelled)),
+            Some(FindingKind::StatusMismatch)
+        );
+    }
+}
@@ -0,0 +1,243 @@
+//! Applies the gateway's view of a payment to the matching order.
+
+use std::fmt;
+
+use sqlx::PgPool;
+
+use super::{GatewayPayment, GatewayPaymentStatus};
+use crate::{
+    data::{amount_in_range, commands, errors::DataError, queries},
+    email::EmailConfig,
+    models::{
+        credit::CreditTopUp,
+        order::{Order, OrderEventSource, PaymentStatus},
+        subscription::Subscription,
+    },
+    money::Money,
+    notifications,
+};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum SyncOutcome {
+    UnknownOrder,
+    /// The gateway status does not settle the order (e.g. still in progress).
+    NotSettled(GatewayPaymentStatus),
+    Unchanged(PaymentStatus),
+    AmountMismatch { expected: Money, actual: Money },
+    /// The order's current status does not allow the change (e.g. paid -> failed).
+    Rejected { from: PaymentStatus, to: PaymentStatus },
+    Updated { from: PaymentStatus, to: PaymentStatus },
+}
+
+impl fmt::Display for SyncOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownOrder => write!(f, "ignored: unknown order"),
+            Self::NotSettled(status) => write!(f, "no change: gateway status {}", status.as_str()),
+            Self::Unchanged(status) => write!(f, "no change: already {}", status.as_str()),
+            Self::AmountMismatch { expected, actual } => {
+                write!(f, "ignored: amount mismatch (expected {}, gateway {})", expected, actual)
+            }
+            Self::Rejected { from, to } => {
+                write!(f, "ignored: order cannot move from {} to {}", from.as_str(), to.as_str())
+            }
+            Self::Updated { from, to } => write!(f, "updated: {} -> {}", from.as_str(), to.as_str()),
+        }
+    }
+}
+
+impl GatewayPaymentStatus {
+    /// The order status this gateway status settles to, if any.
+    pub fn order_status(&self) -> Option<PaymentStatus> {
+        match self {
+            Self::Done => Some(PaymentStatus::Paid),
+            Self::WaitingForDeposit => Some(PaymentStatus::AwaitingDeposit),
+            Self::Canceled => Some(PaymentStatus::Refunded),
+            Self::PartialCanceled => Some(PaymentStatus::PartiallyRefunded),
+            Self::Aborted | Self::Expired => Some(PaymentStatus::Failed),
+            Self::Ready | Self::InProgress => None,
+        }
+    }
+}
+
+/// Brings the order for `payment` in line with the gateway, notifying the user on change.
+///
+/// Safe to call repeatedly: an order already in the target status with the same
+/// payment key is left alone and no notification is sent. Gateway cancellations
+/// become refunds, and an issued virtual account records its transfer details.
+/// Paid orders take their payment method from the gateway.
+/// Credit top-ups and subscription charges, which share the gateway, are settled too.
+pub async fn sync_order(
+    db: &PgPool,
+    email: &EmailConfig,
+    payment: &GatewayPayment,
+    source: OrderEventSource,
+) -> Result<SyncOutcome, DataError> {
+    if CreditTopUp::is_top_up_order_number(&payment.order_id) {
+        return sync_topup(db, payment).await;
+    }
+    if Subscription::is_charge_order_number(&payment.order_id) {
+        return sync_subscription_charge(db, payment).await;
+    }
+
+    let Some(order) = queries::order::get_order_by_order_number(db, &payment.order_id).await? else {
+        return Ok(SyncOutcome::UnknownOrder);
+    };
+    let Some(target) = payment.status.order_status() else {
+        return Ok(SyncOutcome::NotSettled(payment.status));
+    };
+
+    if matches!(target, PaymentStatus::Refunded | PaymentStatus::PartiallyRefunded) {
+        return sync_refunds(db, &order, payment, source).await;
+    }
+
+    if order.payment_status == target && order.payment_key.as_deref() == Some(payment.payment_key.as_str()) {
+        return Ok(SyncOutcome::Unchanged(target));
+    }
+
+    if matches!(target, PaymentStatus::Paid | PaymentStatus::AwaitingDeposit) && payment.total_amount != order.price_amount {
+        tracing::warn!(
+            "Gateway amount {} does not match order {} amount {}",
+            payment.total_amount,
+            order.order_number,
+            order.price_amount
+        );
+        return Ok(SyncOutcome::AmountMismatch {
+            expected: order.price_amount,
+            actual: payment.total_amount,
+        });
+    }
+
+    let from = order.payment_status;
+    let result = match (target, &payment.virtual_account) {
+        (PaymentStatus::AwaitingDeposit, Some(account)) => {
+            commands::virtual_account::record_issued(db, order.order_id, &payment.payment_key, account, source).await
+        }
+        (PaymentStatus::AwaitingDeposit, None) => return Ok(SyncOutcome::NotSettled(payment.status)),
+        _ => {
+            // Only a settled payment says how the order was paid
+            let method = if target == PaymentStatus::Paid { payment.payment_method() } else { None };
+            commands::order::update_order_payment(db, order.order_id, &payment.payment_key, method, target, source).await
+        }
+    };
+    let order = match result {
+        Ok(order) => order,
+        Err(DataError::InvalidTransition { from, to }) => return Ok(SyncOutcome::Rejected { from, to }),
+        Err(e) => return Err(e),
+    };
+
+    match target {
+        PaymentStatus::Paid => {
+            commands::discount::record_redemption(db, &order).await?;
+            notifications::notify_payment_succeeded(db, email, &order).await
+        }
+        PaymentStatus::Failed => {
+            // A virtual account that expired unpaid still holds its discount reservation
+            commands::discount::release_redemption(db, order.order_id).await?;
+            notifications::notify_payment_failed(db, email, &order).await
+        }
+        _ => {}
+    }
+
+    Ok(SyncOutcome::Updated { from, to: target })
+}
+
+/// Records whatever the gateway refunded beyond the refunds already on file,
+/// e.g. a cancellation made in the gateway's dashboard.
+async fn sync_refunds(
+    db: &PgPool,
+    order: &Order,
+    payment: &GatewayPayment,
+    source: OrderEventSource,
+) -> Result<SyncOutcome, DataError> {
+    let gateway_refunded = amount_in_range(payment.total_amount.checked_sub(payment.balance_amount))?;
+    let result = commands::refund::record_gateway_refunds(db, order.order_id, gateway_refunded, source).await;
+    let to = match result {
+        Ok(Some(to)) => to,
+        Ok(None) => return Ok(SyncOutcome::Unchanged(order.payment_status)),
+        Err(DataError::InvalidTransition { from, to }) => return Ok(SyncOutcome::Rejected { from, to }),
+        Err(e) => return Err(e),
+    };
+
+    Ok(SyncOutcome::Updated {
+        from: order.payment_status,
+        to,
+    })
+}
+
+/// Credits a top-up the gateway completed, or fails one it gave up on.
+///
+/// Cancellations of paid top-ups are left for an admin to adjust by hand, since
+/// the credits may already be spent.
+async fn sync_topup(db: &PgPool, payment: &GatewayPayment) -> Result<SyncOutcome, DataError> {
+    let Some(topup) = queries::credit::get_topup_by_order_number(db, &payment.order_id).await? else {
+        return Ok(SyncOutcome::UnknownOrder);
+    };
+    let Some(target) = payment.status.order_status() else {
+        return Ok(SyncOutcome::NotSettled(payment.status));
+    };
+
+    match target {
+        PaymentStatus::Paid if payment.total_amount != topup.amount => Ok(SyncOutcome::AmountMismatch {
+            expected: topup.amount,
+            actual: payment.total_amount,
+        }),
+        PaymentStatus::Paid => {
+            if commands::credit::complete_topup(db, topup.topup_id, &payment.payment_key).await? {
+                Ok(SyncOutcome::Updated { from: topup.status, to: target })
+            } else {
+                Ok(SyncOutcome::Unchanged(target))
+            }
+        }
+        PaymentStatus::Failed if topup.status == PaymentStatus::Pending => {
+            commands::credit::fail_topup(db, topup.topup_id).await?;
+            Ok(SyncOutcome::Updated { from: topup.status, to: target })
+        }
+        _ if topup.status == target => Ok(SyncOutcome::Unchanged(target)),
+        _ => {
+            tracing::warn!("Top-up {} is {} at the gateway", topup.order_number, payment.status.as_str());
+            Ok(SyncOutcome::Rejected { from: topup.status, to: target })
+        }
+    }
+}
+
+/// Renews the subscription for a charge the gateway completed, e.g. one whose
+/// answer was lost to a timeout.
+///
+/// A charge the gateway did not complete stays pending; the renewal sweep retries
+/// it and records the failure.
+async fn sync_subscription_charge(db: &PgPool, payment: &GatewayPayment) -> Result<SyncOutcome, DataError> {
+    let Some(charge) = queries::subscription::get_charge_by_order_number(db, &payment.order_id).await? else {
+        return Ok(SyncOutcome::UnknownOrder);
+    };
+    let Some(target) = payment.status.order_status() else {
+        return Ok(SyncOutcome::NotSettled(payment.status));
+    };
+
+    match target {
+        PaymentStatus::Paid if payment.total_amount != charge.amount => Ok(SyncOutcome::AmountMismatch {
+            expected: charge.amount,
+            actual: payment.total_amount,
+        }),
+        PaymentStatus::Paid if charge.status == PaymentStatus::Pending => {
+            let settled = commands::subscription::record_charge_paid(
+                db,
+                charge.subscription_id,
+                charge.charge_id,
+                &payment.payment_key,
+            )
+            .await?;
+            if settled {
+                Ok(SyncOutcome::Updated { from: charge.status, to: target })
+            } else {
+                Ok(SyncOutcome::Unchanged(target))
+            }
+        }
+        _ if charge.status == target => Ok(SyncOutcome::Unchanged(target)),
+        PaymentStatus::Failed if charge.status == PaymentStatus::Pending => Ok(SyncOutcome::NotSettled(payment.status)),
+        _ => {
+            tracing::warn!("Subscription charge {} is {} at the gateway", charge.order_number, payment.status.as_str());
+            Ok(SyncOutcome::Rejected { from: charge.status, to: target })
+        }
+    }
+}
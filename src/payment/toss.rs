@@ -0,0 +1,272 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize, de::{DeserializeOwned, IgnoredAny}};
+
+use super::{
+    BillingChargeRequest, BillingKey, CashReceiptRequest, ConfirmRequest, GatewayCashReceipt, GatewayPayment, PaymentError,
+    PaymentGateway,
+};
+use crate::{config::TossConfig, constants::payment, models::cash_receipt::CashReceiptType, money::Money};
+
+/// Toss Payments REST API client.
+///
+/// Holds one `reqwest::Client` so connections are pooled across requests.
+pub struct TossGateway {
+    client: reqwest::Client,
+    api_base_url: String,
+    secret_key: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TossConfirmBody<'a> {
+    payment_key: &'a str,
+    order_id: &'a str,
+    amount: Money,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TossCancelBody<'a> {
+    cancel_reason: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cancel_amount: Option<Money>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TossBillingIssueBody<'a> {
+    auth_key: &'a str,
+    customer_key: &'a str,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TossBillingChargeBody<'a> {
+    customer_key: &'a str,
+    amount: Money,
+    order_id: &'a str,
+    order_name: &'a str,
+    customer_email: &'a str,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TossCashReceiptBody<'a> {
+    amount: Money,
+    order_id: &'a str,
+    order_name: &'a str,
+    customer_identity_number: &'a str,
+    /// `소득공제` or `지출증빙`
+    r#type: &'a str,
+}
+
+#[derive(Serialize)]
+struct TossCashReceiptCancelBody {
+    amount: Money,
+}
+
+#[derive(Deserialize)]
+struct TossErrorBody {
+    code: String,
+    message: String,
+}
+
+impl TossGateway {
+    pub fn new(config: &TossConfig) -> Result<Self, PaymentError> {
+        let client = reqwest::Client::builder()
+            .connect_timeout(Duration::from_secs(payment::GATEWAY_CONNECT_TIMEOUT_SECONDS))
+            .timeout(Duration::from_secs(payment::GATEWAY_REQUEST_TIMEOUT_SECONDS))
+            .build()?;
+
+        Ok(Self {
+            client,
+            api_base_url: config.api_base_url().trim_end_matches('/').to_string(),
+            secret_key: config.secret_key().to_string(),
+        })
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}{}", self.api_base_url, path)
+    }
+
+    async fn send<T: DeserializeOwned>(&self, request: reqwest::RequestBuilder) -> Result<T, PaymentError> {
+        let response = request.basic_auth(&self.secret_key, Some("")).send().await?;
+
+        if response.status().is_success() {
+            return Ok(response.json().await?);
+        }
+
+        let status = response.status();
+        Err(error_for(status, response.json().await.ok()))
+    }
+}
+
+/// Only a client error with Toss's error body means the request was refused;
+/// anything else leaves the payment's state unknown.
+fn error_for(status: StatusCode, body: Option<TossErrorBody>) -> PaymentError {
+    match body {
+        Some(error) if status.is_client_error() && status != StatusCode::TOO_MANY_REQUESTS => PaymentError::Rejected {
+            code: error.code,
+            message: error.message,
+        },
+        Some(error) => PaymentError::Unavailable {
+            status: status.as_u16(),
+            message: error.message,
+        },
+        None => PaymentError::Unavailable {
+            status: status.as_u16(),
+            message: status.canonical_reason().unwrap_or("Unknown error").to_string(),
+        },
+    }
+}
+
+#[async_trait]
+impl PaymentGateway for TossGateway {
+    async fn confirm(&self, request: ConfirmRequest<'_>) -> Result<GatewayPayment, PaymentError> {
+        let body = TossConfirmBody {
+            payment_key: request.payment_key,
+            order_id: request.order_number,
+            amount: request.amount,
+        };
+
+        // Toss replays the original response for a repeated idempotency key
+        let request = self
+            .client
+            .post(self.url("/v1/payments/confirm"))
+            .header("Idempotency-Key", request.payment_key)
+            .json(&body);
+
+        self.send(request).await
+    }
+
+    async fn cancel(
+        &self,
+        payment_key: &str,
+        reason: &str,
+        amount: Option<Money>,
+        refunded_total: Money,
+    ) -> Result<GatewayPayment, PaymentError> {
+        let body = TossCancelBody {
+            cancel_reason: reason,
+            cancel_amount: amount,
+        };
+        let url = self.url(&format!("/v1/payments/{}/cancel", urlencoding::encode(payment_key)));
+        let request = self
+            .client
+            .post(url)
+            .header("Idempotency-Key", format!("cancel-{}-{}", payment_key, refunded_total.amount))
+            .json(&body);
+
+        self.send(request).await
+    }
+
+    async fn query(&self, payment_key: &str) -> Result<GatewayPayment, PaymentError> {
+        let url = self.url(&format!("/v1/payments/{}", urlencoding::encode(payment_key)));
+
+        self.send(self.client.get(url)).await
+    }
+
+    async fn query_by_order(&self, order_number: &str) -> Result<GatewayPayment, PaymentError> {
+        let url = self.url(&format!("/v1/payments/orders/{}", urlencoding::encode(order_number)));
+
+        self.send(self.client.get(url)).await
+    }
+
+    async fn issue_billing_key(&self, auth_key: &str, customer_key: &str) -> Result<BillingKey, PaymentError> {
+        let body = TossBillingIssueBody { auth_key, customer_key };
+
+        self.send(self.client.post(self.url("/v1/billing/authorizations/issue")).json(&body)).await
+    }
+
+    async fn charge_billing_key(&self, request: BillingChargeRequest<'_>) -> Result<GatewayPayment, PaymentError> {
+        let body = TossBillingChargeBody {
+            customer_key: request.customer_key,
+            amount: request.amount,
+            order_id: request.order_number,
+            order_name: request.order_name,
+            customer_email: request.customer_email,
+        };
+        let url = self.url(&format!("/v1/billing/{}", urlencoding::encode(request.billing_key)));
+
+        // A retried charge reuses its order number, so Toss replays instead of charging twice
+        let request = self.client.post(url).header("Idempotency-Key", request.order_number).json(&body);
+
+        self.send(request).await
+    }
+
+    async fn issue_cash_receipt(&self, request: CashReceiptRequest<'_>) -> Result<GatewayCashReceipt, PaymentError> {
+        let body = TossCashReceiptBody {
+            amount: request.amount,
+            order_id: request.order_number,
+            order_name: request.order_name,
+            customer_identity_number: request.identifier,
+            r#type: match request.receipt_type {
+                CashReceiptType::IncomeDeduction => "소득공제",
+                CashReceiptType::ExpenseProof => "지출증빙",
+            },
+        };
+
+        // One receipt per order, so a retry after a lost response is not issued twice
+        let request = self
+            .client
+            .post(self.url("/v1/cash-receipts"))
+            .header("Idempotency-Key", format!("cash-receipt-{}", request.order_number))
+            .json(&body);
+
+        self.send(request).await
+    }
+
+    async fn cancel_cash_receipt(&self, receipt_key: &str, amount: Money, cancelled_total: Money) -> Result<(), PaymentError> {
+        let url = self.url(&format!("/v1/cash-receipts/{}/cancel", urlencoding::encode(receipt_key)));
+        // Keyed by the bare running total, so each partial cancellation goes out once
+        let request = self
+            .client
+            .post(url)
+            .header("Idempotency-Key", format!("cash-receipt-cancel-{}-{}", receipt_key, cancelled_total.amount))
+            .json(&TossCashReceiptCancelBody { amount });
+
+        self.send::<IgnoredAny>(request).await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::payment_method::PaymentMethod;
+
+    fn body() -> Option<TossErrorBody> {
+        Some(TossErrorBody {
+            code: "REJECT_CARD_PAYMENT".to_string(),
+            message: "Declined".to_string(),
+        })
+    }
+
+    #[test]
+    fn test_only_client_errors_with_a_toss_body_are_rejections() {
+        assert!(matches!(error_for(StatusCode::BAD_REQUEST, body()), PaymentError::Rejected { .. }));
+        assert!(matches!(error_for(StatusCode::BAD_REQUEST, None), PaymentError::Unavailable { status: 400, .. }));
+        assert!(matches!(error_for(StatusCode::TOO_MANY_REQUESTS, body()), PaymentError::Unavailable { status: 429, .. }));
+        assert!(matches!(error_for(StatusCode::INTERNAL_SERVER_ERROR, body()), PaymentError::Unavailable { status: 500, .. }));
+    }
+    #[test]
+    fn test_payment_method_comes_from_the_toss_response() {
+        let payment = |method: &str, easy_pay: &str| {
+            let json = format!(
+                r#"{{"paymentKey":"key-1","orderId":"ORDER-1","status":"DONE","totalAmount":1000,"balanceAmount":1000,"method":"{}","easyPay":{}}}"#,
+                method, easy_pay
+            );
+            serde_json::from_str::<GatewayPayment>(&json).unwrap().payment_method()
+        };
+
+        assert_eq!(payment("카드", "null"), Some(PaymentMethod::Card));
+        assert_eq!(payment("계좌이체", "null"), Some(PaymentMethod::Transfer));
+        assert_eq!(payment("간편결제", r#"{"provider":"카카오페이","amount":1000}"#), Some(PaymentMethod::KakaoPay));
+        assert_eq!(payment("카드", r#"{"provider":"토스페이","amount":0}"#), Some(PaymentMethod::TossPay));
+        assert_eq!(payment("간편결제", r#"{"provider":"삼성페이","amount":1000}"#), None);
+        assert_eq!(payment("문화상품권", "null"), None);
+    }
+}
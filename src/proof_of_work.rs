@@ -0,0 +1,232 @@
+//! Hashcash-style proof-of-work for unauthenticated forms.
+//!
+//! The server issues a signed challenge `issued_at:difficulty:nonce`; the browser
+//! (`static/js/pow.js`) searches for a counter such that
+//! `SHA-256("{challenge}:{counter}")` starts with `difficulty` zero bits. Difficulty
+//! grows with the number of recent requests from the client's IP, so a single
+//! visitor barely notices while a flood gets progressively more expensive.
+//!
+//! Disabled unless `POW_ENABLED=true`; forms then render without a challenge and
+//! handlers skip verification.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    net::IpAddr,
+    sync::{Arc, Mutex},
+};
+
+use sha2::{Digest, Sha256};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::{config::ConfigError, constants::proof_of_work as pow, signing::Signer};
+
+const CHALLENGE_PURPOSE: &str = "proof_of_work";
+
+/// A challenge to embed in a form.
+pub struct PowChallenge {
+    pub token: String,
+    pub difficulty: u32,
+}
+
+#[derive(Debug, PartialEq, Eq, thiserror::Error)]
+pub enum PowError {
+    #[error("missing proof-of-work")]
+    Missing,
+    #[error("invalid or expired challenge")]
+    InvalidChallenge,
+    #[error("challenge already used")]
+    Replayed,
+    #[error("solution does not meet the difficulty")]
+    InsufficientWork,
+}
+
+#[derive(Clone)]
+pub struct ProofOfWork {
+    enabled: bool,
+    base_difficulty: u32,
+    max_difficulty: u32,
+    signer: Signer,
+    state: Arc<Mutex<PowState>>,
+}
+
+#[derive(Default)]
+struct PowState {
+    /// Request timestamps per IP within the rate window.
+    requests: HashMap<IpAddr, VecDeque<i64>>,
+    /// Solved challenges and when they expire, to reject replays.
+    spent: HashMap<String, i64>,
+}
+
+impl ProofOfWork {
+    pub fn from_env(signer: Signer) -> Result<Self, ConfigError> {
+        let enabled = dotenvy::var("POW_ENABLED").is_ok_and(|value| value == "true");
+        let base_difficulty = parse_difficulty("POW_BASE_DIFFICULTY", pow::DEFAULT_BASE_DIFFICULTY)?;
+        let max_difficulty = parse_difficulty("POW_MAX_DIFFICULTY", pow::DEFAULT_MAX_DIFFICULTY)?;
+
+        if max_difficulty < base_difficulty {
+            return Err(ConfigError::InvalidValue(
+                "POW_MAX_DIFFICULTY".to_string(),
+                "must not be lower than POW_BASE_DIFFICULTY".to_string(),
+            ));
+        }
+
+        Ok(Self::new(enabled, base_difficulty, max_difficulty, signer))
+    }
+
+    fn new(enabled: bool, base_difficulty: u32, max_difficulty: u32, signer: Signer) -> Self {
+        Self {
+            enabled,
+            base_difficulty,
+            max_difficulty,
+            signer,
+            state: Arc::default(),
+        }
+    }
+
+    /// Issues a challenge for `ip`, or `None` when proof-of-work is disabled.
+    pub fn issue(&self, ip: IpAddr) -> Option<PowChallenge> {
+        if !self.enabled {
+            return None;
+        }
+
+        let now = OffsetDateTime::now_utc().unix_timestamp();
+        let recent = self.record_request(ip, now);
+        let difficulty = (self.base_difficulty + recent / pow::REQUESTS_PER_DIFFICULTY_STEP).min(self.max_difficulty);
+        let payload = format!("{}:{}:{}", now, difficulty, Uuid::new_v4().simple());
+
+        Some(PowChallenge {
+            token: self.signer.sign(CHALLENGE_PURPOSE, &payload),
+            difficulty,
+        })
+    }
+
+    /// Checks a submitted solution. Always succeeds when proof-of-work is disabled.
+    pub fn verify(&self, ip: IpAddr, token: &str, solution: &str) -> Result<(), PowError> {
+        if !self.enabled {
+            return Ok(());
+        }
+        if token.is_empty() || solution.is_empty() {
+            return Err(PowError::Missing);
+        }
+
+        let now = OffsetDateTime::now_utc().unix_timestamp();
+        self.record_request(ip, now);
+
+        let payload = self.signer.verify(CHALLENGE_PURPOSE, token).ok_or(PowError::InvalidChallenge)?;
+        let mut parts = payload.split(':');
+        let (Some(issued_at), Some(difficulty)) = (
+            parts.next().and_then(|value| value.parse::<i64>().ok()),
+            parts.next().and_then(|value| value.parse::<u32>().ok()),
+        ) else {
+            return Err(PowError::InvalidChallenge);
+        };
+        let expires_at = issued_at + pow::CHALLENGE_TTL_SECONDS;
+        if now > expires_at {
+            return Err(PowError::InvalidChallenge);
+        }
+
+        if leading_zero_bits(&Sha256::digest(format!("{}:{}", token, solution))) < difficulty {
+            return Err(PowError::InsufficientWork);
+        }
+
+        let mut state = self.state.lock().expect("Proof-of-work lock poisoned");
+        state.spent.retain(|_, expiry| *expiry >= now);
+        if state.spent.insert(token.to_string(), expires_at).is_some() {
+            return Err(PowError::Replayed);
+        }
+
+        Ok(())
+    }
+
+    /// Records a request and returns how many the IP made within the rate window.
+    fn record_request(&self, ip: IpAddr, now: i64) -> u32 {
+        let mut state = self.state.lock().expect("Proof-of-work lock poisoned");
+        let window_start = now - pow::RATE_WINDOW_SECONDS;
+
+        if state.requests.len() > pow::MAX_TRACKED_IPS {
+            state.requests.retain(|_, times| times.back().is_some_and(|last| *last >= window_start));
+        }
+
+        let times = state.requests.entry(ip).or_default();
+        while times.front().is_some_and(|time| *time < window_start) {
+            times.pop_front();
+        }
+        times.push_back(now);
+        times.len() as u32
+    }
+}
+
+fn parse_difficulty(var: &str, default: u32) -> Result<u32, ConfigError> {
+    match dotenvy::var(var) {
+        Ok(value) => value
+            .parse()
+            .ok()
+            .filter(|difficulty| *difficulty <= pow::MAX_ALLOWED_DIFFICULTY)
+            .ok_or_else(|| {
+                ConfigError::InvalidValue(
+                    var.to_string(),
+                    format!("must be a number from 0 to {}", pow::MAX_ALLOWED_DIFFICULTY),
+                )
+            }),
+        Err(_) => Ok(default),
+    }
+}
+
+fn leading_zero_bits(digest: &[u8]) -> u32 {
+    let mut bits = 0;
+    for byte in digest {
+        bits += byte.leading_zeros();
+        if *byte != 0 {
+            break;
+        }
+    }
+    bits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::Ipv4Addr;
+
+    fn solve(challenge: &PowChallenge) -> String {
+        (0u64..)
+            .find(|counter| {
+                leading_zero_bits(&Sha256::digest(format!("{}:{}", challenge.token, counter))) >= challenge.difficulty
+            })
+            .unwrap()
+            .to_string()
+    }
+
+    #[test]
+    fn test_solution_is_verified_once() {
+        let pow = ProofOfWork::new(true, 8, 12, Signer::new("test-secret-key-that-is-long-enough"));
+        let ip = IpAddr::V4(Ipv4Addr::LOCALHOST);
+        let challenge = pow.issue(ip).unwrap();
+        let solution = solve(&challenge);
+
+        assert_eq!(pow.verify(ip, &challenge.token, ""), Err(PowError::Missing));
+        assert_eq!(pow.verify(ip, "forged.token", &solution), Err(PowError::InvalidChallenge));
+        assert_eq!(pow.verify(ip, &challenge.token, &solution), Ok(()));
+        assert_eq!(pow.verify(ip, &challenge.token, &solution), Err(PowError::Replayed));
+    }
+
+    #[test]
+    fn test_difficulty_scales_with_request_rate() {
+        let pow = ProofOfWork::new(true, 8, 10, Signer::new("test-secret-key-that-is-long-enough"));
+        let ip = IpAddr::V4(Ipv4Addr::LOCALHOST);
+
+        let difficulties: Vec<u32> = (0..40).map(|_| pow.issue(ip).unwrap().difficulty).collect();
+
+        assert_eq!(difficulties[0], 8);
+        assert_eq!(*difficulties.last().unwrap(), 10);
+        assert!(difficulties.windows(2).all(|pair| pair[0] <= pair[1]));
+        assert_eq!(pow.issue(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1))).unwrap().difficulty, 8);
+    }
+
+    #[test]
+    fn test_leading_zero_bits() {
+        assert_eq!(leading_zero_bits(&[0x00, 0x0f, 0xff]), 12);
+        assert_eq!(leading_zero_bits(&[0x80]), 0);
+    }
+}
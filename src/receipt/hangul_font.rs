@@ -0,0 +1,176 @@
+//! The embedded Hangul font, cut down per receipt.
+//!
+//! printpdf embeds external fonts whole and uncompressed, which would add about
+//! 2 MB to every receipt with a Korean file name. Instead the outlines the text
+//! doesn't use are emptied. Glyph ids stay as they are, so `cmap` and `hmtx` are
+//! copied unchanged and only `glyf` and `loca` are rebuilt.
+
+use std::collections::BTreeSet;
+
+/// NanumBarunGothic cut down to Hangul syllables and compatibility jamo (SIL OFL 1.1)
+const FONT: &[u8] = include_bytes!("../../assets/fonts/NanumBarunGothic.ttf");
+
+const COMPOSITE_ARGS_ARE_WORDS: u16 = 0x0001;
+const COMPOSITE_HAS_SCALE: u16 = 0x0008;
+const COMPOSITE_MORE_COMPONENTS: u16 = 0x0020;
+const COMPOSITE_HAS_XY_SCALE: u16 = 0x0040;
+const COMPOSITE_HAS_2X2: u16 = 0x0080;
+
+/// Whether the embedded font has a glyph for `c`.
+pub fn covers(c: char) -> bool {
+    ('\u{3131}'..='\u{318e}').contains(&c) || ('\u{ac00}'..='\u{d7a3}').contains(&c)
+}
+
+/// The font with outlines for the characters of `text` only. Falls back to the
+/// whole font if it can't be taken apart.
+pub fn for_text(text: &str) -> Vec<u8> {
+    subset(text).unwrap_or_else(|| FONT.to_vec())
+}
+
+fn subset(text: &str) -> Option<Vec<u8>> {
+    let face = ttf_parser::Face::parse(FONT, 0).ok()?;
+    let tables = table_records(FONT)?;
+    let (_, glyf) = *tables.iter().find(|(tag, _)| tag == b"glyf")?;
+    let (_, loca) = *tables.iter().find(|(tag, _)| tag == b"loca")?;
+    let (_, head) = *tables.iter().find(|(tag, _)| tag == b"head")?;
+    let long_offsets = read_u16(head.get(50..52)?)? == 1;
+
+    let glyph_count = usize::from(face.number_of_glyphs());
+    let offsets = (0..=glyph_count)
+        .map(|i| {
+            if long_offsets {
+                read_u32(loca.get(i * 4..i * 4 + 4)?).map(|offset| offset as usize)
+            } else {
+                read_u16(loca.get(i * 2..i * 2 + 2)?).map(|offset| usize::from(offset) * 2)
+            }
+        })
+        .collect::<Option<Vec<_>>>()?;
+    let outline = |glyph: u16| glyf.get(offsets[usize::from(glyph)]..offsets[usize::from(glyph) + 1]);
+
+    // .notdef always stays, and composites pull in their components
+    let mut keep = BTreeSet::from([0u16]);
+    let mut pending: Vec<u16> = text.chars().filter_map(|c| face.glyph_index(c)).map(|glyph| glyph.0).collect();
+    while let Some(glyph) = pending.pop() {
+        if usize::from(glyph) < glyph_count && keep.insert(glyph) {
+            pending.extend(components(outline(glyph)?)?);
+        }
+    }
+
+    let mut new_glyf = Vec::new();
+    let mut new_loca = Vec::new();
+    for glyph in 0..=glyph_count {
+        if long_offsets {
+            new_loca.extend_from_slice(&u32::try_from(new_glyf.len()).ok()?.to_be_bytes());
+        } else {
+            new_loca.extend_from_slice(&u16::try_from(new_glyf.len() / 2).ok()?.to_be_bytes());
+        }
+        if glyph < glyph_count && keep.contains(&(glyph as u16)) {
+            new_glyf.extend_from_slice(outline(glyph as u16)?);
+            new_glyf.resize(new_glyf.len().next_multiple_of(4), 0);
+        }
+    }
+
+    let tables = tables.into_iter().map(|(tag, data)| match &tag {
+        b"glyf" => (tag, new_glyf.as_slice()),
+        b"loca" => (tag, new_loca.as_slice()),
+        _ => (tag, data),
+    });
+    Some(write_font(read_u32(FONT.get(0..4)?)?, tables.collect()))
+}
+
+/// The glyphs a composite outline is built from; none for a simple or empty one.
+fn components(outline: &[u8]) -> Option<Vec<u16>> {
+    if outline.is_empty() || i16::from_be_bytes([*outline.first()?, *outline.get(1)?]) >= 0 {
+        return Some(Vec::new());
+    }
+
+    let mut glyphs = Vec::new();
+    let mut at = 10;
+    loop {
+        let flags = read_u16(outline.get(at..at + 2)?)?;
+        glyphs.push(read_u16(outline.get(at + 2..at + 4)?)?);
+        at += 4 + if flags & COMPOSITE_ARGS_ARE_WORDS != 0 { 4 } else { 2 };
+        if flags & COMPOSITE_HAS_SCALE != 0 {
+            at += 2;
+        } else if flags & COMPOSITE_HAS_XY_SCALE != 0 {
+            at += 4;
+        } else if flags & COMPOSITE_HAS_2X2 != 0 {
+            at += 8;
+        }
+        if flags & COMPOSITE_MORE_COMPONENTS == 0 {
+            return Some(glyphs);
+        }
+    }
+}
+
+fn table_records(font: &[u8]) -> Option<Vec<([u8; 4], &[u8])>> {
+    let count = usize::from(read_u16(font.get(4..6)?)?);
+    (0..count)
+        .map(|i| {
+            let record = font.get(12 + i * 16..28 + i * 16)?;
+            let offset = read_u32(&record[8..12])? as usize;
+            let length = read_u32(&record[12..16])? as usize;
+            Some((record[..4].try_into().ok()?, font.get(offset..offset + length)?))
+        })
+        .collect()
+}
+
+/// Lays `tables` out as a font file, keeping their order.
+fn write_font(version: u32, tables: Vec<([u8; 4], &[u8])>) -> Vec<u8> {
+    let count = tables.len() as u16;
+    let entry_selector = count.ilog2() as u16;
+    let search_range = (1u16 << entry_selector) * 16;
+
+    let mut font = Vec::new();
+    font.extend_from_slice(&version.to_be_bytes());
+    for value in [count, search_range, entry_selector, count * 16 - search_range] {
+        font.extend_from_slice(&value.to_be_bytes());
+    }
+
+    let mut offset = 12 + tables.len() * 16;
+    for (tag, data) in &tables {
+        font.extend_from_slice(tag);
+        for value in [checksum(data), offset as u32, data.len() as u32] {
+            font.extend_from_slice(&value.to_be_bytes());
+        }
+        offset += data.len().next_multiple_of(4);
+    }
+    for (_, data) in &tables {
+        font.extend_from_slice(data);
+        font.resize(font.len().next_multiple_of(4), 0);
+    }
+    font
+}
+
+fn checksum(data: &[u8]) -> u32 {
+    data.chunks(4).fold(0u32, |sum, chunk| {
+        let mut word = [0; 4];
+        word[..chunk.len()].copy_from_slice(chunk);
+        sum.wrapping_add(u32::from_be_bytes(word))
+    })
+}
+
+fn read_u16(bytes: &[u8]) -> Option<u16> {
+    Some(u16::from_be_bytes(bytes.try_into().ok()?))
+}
+
+fn read_u32(bytes: &[u8]) -> Option<u32> {
+    Some(u32::from_be_bytes(bytes.try_into().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_the_outlines_for_the_text_are_kept() {
+        let font = for_text("에세이");
+        assert!(font.len() < FONT.len() / 10);
+
+        let face = ttf_parser::Face::parse(&font, 0).unwrap();
+        assert_eq!(face.number_of_glyphs(), ttf_parser::Face::parse(FONT, 0).unwrap().number_of_glyphs());
+        let has_outline = |c| face.glyph_bounding_box(face.glyph_index(c).unwrap()).is_some();
+        assert!("에세이".chars().all(has_outline));
+        assert!(!has_outline('한'));
+    }
+}
@@ -0,0 +1,343 @@
+//! PDF receipts for paid orders, rendered in-process with the PDF base fonts.
+//! Hangul is drawn with an embedded font, added only to receipts that need it.
+
+mod hangul_font;
+
+use printpdf::{BuiltinFont, IndirectFontRef, Line, Mm, PdfDocument, PdfDocumentReference, PdfLayerReference, Point};
+
+use crate::{
+    constants::subscriptions,
+    formatting,
+    models::{
+        order::Order,
+        order_item::{OrderItem, OrderItemMetadata},
+        refund::Refund,
+    },
+    money::{Locale, Money},
+};
+
+const PAGE_WIDTH: f32 = 210.0;
+const PAGE_HEIGHT: f32 = 297.0;
+const MARGIN: f32 = 20.0;
+const ROW_HEIGHT: f32 = 7.0;
+/// Right edge of the amount column; amounts are right-aligned against it
+const AMOUNT_RIGHT: f32 = PAGE_WIDTH - MARGIN;
+/// Average Helvetica glyph width as a fraction of the font size, for right alignment
+const AVERAGE_GLYPH_WIDTH: f32 = 0.5;
+/// Advance of every glyph in the Hangul font as a fraction of the font size
+const HANGUL_GLYPH_WIDTH: f32 = 0.892;
+/// Characters of the payment key printed on the receipt
+const PAYMENT_KEY_SUFFIX_LENGTH: usize = 4;
+/// Receipts are written in English
+const LOCALE: Locale = Locale::English;
+
+#[derive(Debug, thiserror::Error)]
+pub enum ReceiptError {
+    #[error("Receipt rendering error: {0}")]
+    Pdf(#[from] printpdf::Error),
+
+    #[error("Receipt amounts are out of range")]
+    AmountOutOfRange,
+}
+
+/// File name offered for download and used for the email attachment.
+pub fn file_name(order: &Order) -> String {
+    format!("receipt-{}.pdf", order.order_number)
+}
+
+/// Renders the receipt for a paid order as a single A4 page, listing `refunds`
+/// below the total when some of the payment was given back.
+pub fn render(site_name: &str, order: &Order, refunds: &[Refund]) -> Result<Vec<u8>, ReceiptError> {
+    let title = format!("Receipt {}", order.order_number);
+    let (doc, page, layer) = PdfDocument::new(&title, Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Receipt");
+    let regular = doc.add_builtin_font(BuiltinFont::Helvetica)?;
+    let bold = doc.add_builtin_font(BuiltinFont::HelveticaBold)?;
+    let mut page = Page {
+        layer: doc.get_page(page).get_layer(layer),
+        regular,
+        bold,
+        texts: Vec::new(),
+        y: PAGE_HEIGHT - MARGIN,
+    };
+
+    page.text(site_name, 18.0, true);
+    page.advance(10.0);
+    page.text("Receipt", 14.0, true);
+    page.advance(12.0);
+
+    page.row("Order number", &order.order_number, false);
+    let paid_at = order.paid_at.map_or_else(|| "-".to_string(), formatting::format_datetime);
+    page.row("Payment date", &format!("{} UTC", paid_at), false);
+    page.row("Payment", &payment_reference(order), false);
+    page.row("Billed to", &order.user_email, false);
+    page.advance(6.0);
+
+    page.rule();
+    page.row("Description", "Amount", true);
+    page.rule();
+    for item in order.items.iter() {
+        page.row(&item_line(item), &amount_text(item.amount())?, false);
+    }
+    if order.discount_amount.is_positive() {
+        page.row("Discount", &amount_text(order.discount_amount.checked_neg())?, false);
+    }
+    page.rule();
+
+    page.row("Supply amount", &order.net_amount.format_code(LOCALE), false);
+    page.row(&format!("VAT ({}%)", order.tax_rate_percent), &order.tax_amount.format_code(LOCALE), false);
+    page.row("Total paid", &order.price_amount.format_code(LOCALE), true);
+
+    if !refunds.is_empty() {
+        page.advance(4.0);
+        for refund in refunds {
+            let label = format!("Refunded {}", formatting::format_datetime(refund.created_at));
+            page.row(&label, &amount_text(refund.amount.checked_neg())?, false);
+        }
+        let refunded = Money::checked_sum(order.price_amount.currency, refunds.iter().map(|refund| refund.amount));
+        page.rule();
+        page.row("Net paid", &amount_text(refunded.and_then(|refunded| order.price_amount.checked_sub(refunded)))?, true);
+    }
+
+    page.advance(10.0);
+    page.text("All amounts are in Korean won (KRW).", 9.0, false);
+    page.finish(&doc)?;
+
+    Ok(doc.save_to_bytes()?)
+}
+
+/// `amount` with its ISO code, as the base fonts have no won sign. `None` is an
+/// overflow in the caller's arithmetic and fails the receipt.
+fn amount_text(amount: Option<Money>) -> Result<String, ReceiptError> {
+    amount.map(|amount| amount.format_code(LOCALE)).ok_or(ReceiptError::AmountOutOfRange)
+}
+
+fn item_line(item: &OrderItem) -> String {
+    let mut line = match &item.metadata {
+        OrderItemMetadata::TextAnalysis(analysis) => {
+            format!("{} ({} characters)", item.description(), analysis.text_length)
+        }
+    };
+    if item.quantity > 1 {
+        line.push_str(&format!(" x {}", item.quantity));
+    }
+    line
+}
+
+/// The last characters of the gateway payment key, or how the order was paid
+/// when it never went through the gateway.
+fn payment_reference(order: &Order) -> String {
+    match &order.payment_key {
+        Some(key) => {
+            let start = key.len().saturating_sub(PAYMENT_KEY_SUFFIX_LENGTH);
+            format!("Payment key ending {}", key.get(start..).unwrap_or(key))
+        }
+        None if order.price_amount.is_zero() => subscriptions::COVERED_NOTE.to_string(),
+        None => "Prepaid credits".to_string(),
+    }
+}
+
+/// A stretch of text drawn with a single font.
+#[derive(Debug, PartialEq)]
+enum Run {
+    Hangul(String),
+    Base(String),
+}
+
+impl Run {
+    fn text(&self) -> &str {
+        match self {
+            Self::Hangul(text) | Self::Base(text) => text,
+        }
+    }
+
+    fn width(&self, size: f32) -> Mm {
+        let glyph_width = match self {
+            Self::Hangul(_) => HANGUL_GLYPH_WIDTH,
+            Self::Base(_) => AVERAGE_GLYPH_WIDTH,
+        };
+        Mm::from(printpdf::Pt(self.text().chars().count() as f32 * size * glyph_width))
+    }
+}
+
+/// Splits `text` between the Hangul font and the base fonts. The base fonts only
+/// cover WinAnsi, so anything else outside Latin-1 is replaced rather than
+/// silently dropped.
+fn runs(text: &str) -> Vec<Run> {
+    let mut runs: Vec<Run> = Vec::new();
+    for c in text.chars() {
+        match (runs.last_mut(), hangul_font::covers(c)) {
+            (Some(Run::Hangul(run)), true) => run.push(c),
+            (Some(Run::Base(run)), false) => run.push(printable(c)),
+            (_, true) => runs.push(Run::Hangul(c.to_string())),
+            (_, false) => runs.push(Run::Base(printable(c).to_string())),
+        }
+    }
+    runs
+}
+
+fn printable(c: char) -> char {
+    if c == ' ' || c.is_ascii_graphic() || ('\u{a1}'..='\u{ff}').contains(&c) { c } else { '?' }
+}
+
+/// Lays out lines top to bottom on a single page. Text is written out by
+/// `finish`, once it is known which Hangul the page needs.
+struct Page {
+    layer: PdfLayerReference,
+    regular: IndirectFontRef,
+    bold: IndirectFontRef,
+    texts: Vec<PlacedRun>,
+    y: f32,
+}
+
+struct PlacedRun {
+    run: Run,
+    size: f32,
+    x: Mm,
+    y: Mm,
+    bold: bool,
+}
+
+impl Page {
+    fn advance(&mut self, height: f32) {
+        self.y -= height;
+    }
+
+    /// Places `text` starting at `x`, one run per font.
+    fn draw(&mut self, text: &str, size: f32, x: Mm, bold: bool) {
+        let mut x = x;
+        for run in runs(text) {
+            let width = run.width(size);
+            self.texts.push(PlacedRun { run, size, x, y: Mm(self.y), bold });
+            x += width;
+        }
+    }
+
+    fn text(&mut self, text: &str, size: f32, bold: bool) {
+        self.draw(text, size, Mm(MARGIN), bold);
+    }
+
+    /// A label on the left and a right-aligned value.
+    fn row(&mut self, label: &str, value: &str, bold: bool) {
+        const SIZE: f32 = 10.0;
+        let value_width = runs(value).iter().fold(Mm(0.0), |width, run| width + run.width(SIZE));
+
+        self.draw(label, SIZE, Mm(MARGIN), bold);
+        self.draw(value, SIZE, Mm(AMOUNT_RIGHT) - value_width, bold);
+        self.advance(ROW_HEIGHT);
+    }
+
+    fn rule(&mut self) {
+        let y = self.y + ROW_HEIGHT - 2.0;
+        self.layer.add_line(Line {
+            points: vec![
+                (Point::new(Mm(MARGIN), Mm(y)), false),
+                (Point::new(Mm(PAGE_WIDTH - MARGIN), Mm(y)), false),
+            ],
+            is_closed: false,
+        });
+        self.advance(2.0);
+    }
+
+    /// Writes the placed text, embedding the Hangul font if any run needs it.
+    /// Hangul has no bold face and is always drawn regular.
+    fn finish(self, doc: &PdfDocumentReference) -> Result<(), ReceiptError> {
+        let hangul: String = self
+            .texts
+            .iter()
+            .filter_map(|placed| match &placed.run {
+                Run::Hangul(text) => Some(text.as_str()),
+                Run::Base(_) => None,
+            })
+            .collect();
+        let hangul_font = if hangul.is_empty() {
+            None
+        } else {
+            Some(doc.add_external_font(hangul_font::for_text(&hangul).as_slice())?)
+        };
+
+        for placed in &self.texts {
+            let font = match (&placed.run, &hangul_font) {
+                (Run::Hangul(_), Some(font)) => font,
+                (_, _) if placed.bold => &self.bold,
+                (_, _) => &self.regular,
+            };
+            self.layer.use_text(placed.run.text(), placed.size, placed.x, placed.y, font);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        models::{order::PaymentStatus, order_item::TextAnalysis, payment_method::PaymentMethod},
+    };
+    use sqlx::types::Json;
+
+    fn paid_order(payment_key: Option<&str>) -> Order {
+        Order {
+            order_id: uuid::Uuid::nil(),
+            user_id: 1,
+            user_email: "customer@example.com".to_string(),
+            list_price_amount: Money::krw(1334),
+            discount_amount: Money::krw(100),
+            discount_code_id: Some(1),
+            price_amount: Money::krw(1234),
+            net_amount: Money::krw(1122),
+            tax_amount: Money::krw(112),
+            tax_rate_percent: 10,
+            payment_status: PaymentStatus::Paid,
+            payment_key: payment_key.map(str::to_string),
+            payment_method: payment_key.map(|_| PaymentMethod::Card),
+            order_number: "ORD-1-abcd1234".to_string(),
+            created_at: time::OffsetDateTime::UNIX_EPOCH,
+            paid_at: Some(time::OffsetDateTime::UNIX_EPOCH),
+            items: Json(vec![OrderItem {
+                order_item_id: uuid::Uuid::nil(),
+                product_code: "text_analysis".to_string(),
+                product_name: "Text Analysis".to_string(),
+                quantity: 1,
+                unit_price_amount: Money::krw(1334),
+                metadata: OrderItemMetadata::TextAnalysis(TextAnalysis {
+                    filename: "에세이.txt".to_string(),
+                    file_size: 2048,
+                    text_content: "Lorem ipsum".to_string(),
+                    text_length: 1234,
+                }),
+            }]),
+        }
+    }
+
+    #[test]
+    fn test_render_produces_a_pdf() {
+        let pdf = render("My App", &paid_order(Some("tgen_20250101000000abcd")), &[]).unwrap();
+        assert!(pdf.starts_with(b"%PDF-"));
+        assert_eq!(file_name(&paid_order(None)), "receipt-ORD-1-abcd1234.pdf");
+
+        let refunds = [Refund {
+            amount: Money::krw(234),
+            reason: "Partial refund".to_string(),
+            refunded_by_email: None,
+            created_at: time::OffsetDateTime::UNIX_EPOCH,
+        }];
+        let refunded = render("My App", &paid_order(None), &refunds).unwrap();
+        assert!(refunded.starts_with(b"%PDF-"));
+    }
+
+    #[test]
+    fn test_hangul_is_drawn_with_the_embedded_font() {
+        assert_eq!(
+            runs("에세이 draft.txt"),
+            vec![Run::Hangul("에세이".to_string()), Run::Base(" draft.txt".to_string())]
+        );
+        assert_eq!(runs("日本.txt"), vec![Run::Base("??.txt".to_string())]);
+    }
+
+    #[test]
+    fn test_payment_reference_shows_only_the_key_suffix() {
+        assert_eq!(payment_reference(&paid_order(Some("tgen_20250101000000abcd"))), "Payment key ending abcd");
+        assert_eq!(payment_reference(&paid_order(Some("ab"))), "Payment key ending ab");
+        assert_eq!(payment_reference(&paid_order(None)), "Prepaid credits");
+    }
+}
@@ -4,6 +4,8 @@ use axum::{Router, routing::{delete, get, patch, post}};
 pub fn public_action_routes() -> Router<AppState> {
     Router::new()
         .route(relative::VERIFY_MAGIC_LINK, get(actions::get_actions_auth_verify))
+        .route(relative::UNSUBSCRIBE, post(actions::post_actions_unsubscribe))
+        .route(relative::PAYMENT_WEBHOOK, post(actions::post_actions_payment_webhook))
 }
 
 pub fn protected_action_routes() -> Router<AppState> {
@@ -13,4 +15,7 @@ pub fn protected_action_routes() -> Router<AppState> {
         .route(relative::TODOS_TODO_ID_TOGGLE, patch(actions::patch_actions_todos_todo_id_toggle))
         .route(relative::PAYMENT_INITIATE, post(actions::post_actions_payment_initiate))
         .route(relative::PAYMENT_VERIFY, get(actions::get_actions_payment_verify))
+        .route(relative::PAY_WITH_CREDITS, post(actions::post_actions_pay_with_credits))
+        .route(relative::CREDIT_TOP_UP_VERIFY, get(actions::get_actions_credit_top_up_verify))
+        .route(relative::SUBSCRIPTION_CARD_REGISTERED, get(actions::get_actions_subscription_card_registered))
 }
@@ -9,10 +9,33 @@ pub fn admin_routes() -> Router<AppState> {
         .route(paths::pages::admin::USER_DETAIL, get(handlers::pages::admin::get_admin_user_detail))
         .route(paths::pages::admin::ORDERS, get(handlers::pages::admin::get_admin_orders))
         .route(paths::pages::admin::ORDER_DETAIL, get(handlers::pages::admin::get_admin_order_detail))
+        .route(paths::pages::admin::INQUIRIES, get(handlers::pages::admin::get_admin_inquiries))
+        .route(paths::pages::admin::QUARANTINE, get(handlers::pages::admin::get_admin_quarantine))
+        .route(paths::pages::admin::INQUIRY_DETAIL, get(handlers::pages::admin::get_admin_inquiry_detail))
+        .route(paths::pages::admin::SUPPRESSIONS, get(handlers::pages::admin::get_admin_suppressions))
+        .route(paths::pages::admin::RECONCILIATION, get(handlers::pages::admin::get_admin_reconciliation))
+        .route(paths::pages::admin::DISCOUNTS, get(handlers::pages::admin::get_admin_discounts))
+        .route(paths::pages::admin::PRICING, get(handlers::pages::admin::get_admin_pricing))
         // Admin forms
         .route(paths::forms::admin::GRANT_ROLE, post(handlers::forms::admin::post_grant_role))
+        .route(paths::forms::admin::IMPORT_SUPPRESSIONS, post(handlers::forms::admin::post_import_suppressions))
+        .route(paths::forms::admin::INQUIRY_STATUS, post(handlers::forms::admin::post_inquiry_status))
+        .route(paths::forms::admin::INQUIRY_ASSIGN, post(handlers::forms::admin::post_inquiry_assign))
+        .route(paths::forms::admin::INQUIRY_NOTE, post(handlers::forms::admin::post_inquiry_note))
+        .route(paths::forms::admin::INQUIRY_REPLY, post(handlers::forms::admin::post_inquiry_reply))
+        .route(paths::forms::admin::INQUIRY_RELEASE, post(handlers::forms::admin::post_inquiry_release))
+        .route(paths::forms::admin::REFUND_ORDER, post(handlers::forms::admin::post_refund_order))
+        .route(paths::forms::admin::ADJUST_CREDITS, post(handlers::forms::admin::post_adjust_credits))
+        .route(paths::forms::admin::CREATE_PRICING_RULE, post(handlers::forms::admin::post_create_pricing_rule))
+        .route(paths::forms::admin::CREATE_DISCOUNT, post(handlers::forms::admin::post_create_discount))
+        .route(paths::forms::admin::TOGGLE_DISCOUNT, post(handlers::forms::admin::post_toggle_discount))
+        .route(paths::forms::admin::RUN_RECONCILIATION, post(handlers::forms::admin::post_run_reconciliation))
+        .route(paths::forms::admin::FIX_FINDING, post(handlers::forms::admin::post_fix_finding))
+        .route(paths::forms::admin::DISMISS_FINDING, post(handlers::forms::admin::post_dismiss_finding))
         // Admin actions
         .route(paths::actions::admin::REVOKE_ROLE, delete(handlers::actions::admin::delete_revoke_role))
+        .route(paths::actions::admin::DELETE_INQUIRY, delete(handlers::actions::admin::delete_inquiry))
+        .route(paths::actions::admin::DELETE_SUPPRESSION, delete(handlers::actions::admin::delete_suppression))
         // Require admin middleware for all routes
         .layer(middleware::from_fn(middlewares::require_admin))
 }
@@ -0,0 +1,17 @@
+use crate::{config::AppState, handlers::pages, middlewares, paths};
+use axum::{Router, middleware, routing::get};
+
+pub fn mailbox_routes() -> Router<AppState> {
+    Router::new()
+        .route(paths::pages::dev::MAILBOX, get(pages::dev::get_dev_mailbox))
+        .route(paths::pages::dev::MAILBOX_MESSAGE, get(pages::dev::get_dev_mailbox_message))
+}
+
+pub fn fake_payment_routes() -> Router<AppState> {
+    Router::new()
+        .route(paths::pages::dev::FAKE_CHECKOUT, get(pages::dev::get_fake_checkout))
+        .route(paths::pages::dev::FAKE_TOP_UP_CHECKOUT, get(pages::dev::get_fake_top_up_checkout))
+        .route(paths::pages::dev::FAKE_DEPOSIT, get(pages::dev::get_fake_deposit))
+        .route(paths::pages::dev::FAKE_CARD_REGISTRATION, get(pages::dev::get_fake_card_registration))
+        .layer(middleware::from_fn(middlewares::require_authentication))
+}
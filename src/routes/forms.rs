@@ -11,4 +11,13 @@ pub fn protected_form_routes() -> Router<AppState> {
     Router::new()
         .route(relative::TODOS, post(forms::post_forms_todos))
         .route(relative::TEXT_ANALYZER, post(forms::post_forms_text_analyzer))
+        .route(relative::NOTIFICATION_PREFERENCES, post(forms::post_forms_notification_preferences))
+        .route(relative::QUOTE_DISCOUNT, post(forms::post_forms_quote_discount))
+        .route(relative::QUOTE_DISCOUNT_REMOVE, post(forms::post_forms_quote_discount_remove))
+        .route(relative::CHECKOUT_CASH_RECEIPT, post(forms::post_forms_checkout_cash_receipt))
+        .route(relative::CHECKOUT_CASH_RECEIPT_REMOVE, post(forms::post_forms_checkout_cash_receipt_remove))
+        .route(relative::CREDIT_TOP_UP, post(forms::post_forms_credit_top_up))
+        .route(relative::SUBSCRIBE, post(forms::post_forms_subscribe))
+        .route(relative::SUBSCRIPTION_CANCEL, post(forms::post_forms_subscription_cancel))
+        .route(relative::SUBSCRIPTION_RESUME, post(forms::post_forms_subscription_resume))
 }
@@ -5,15 +5,16 @@
 
 mod actions;
 mod admin;
+mod dev;
 mod forms;
 mod pages;
 
-use axum::{Router, middleware};
+use axum::{Router, extract::FromRef, middleware};
 use tower_http::services::ServeDir;
 use tower_sessions::SessionManagerLayer;
 use tower_sessions_sqlx_store::PostgresStore;
 
-use crate::{config::AppState, handlers, middlewares, paths};
+use crate::{config::{AppConfig, AppState}, handlers, middlewares, paths};
 
 pub fn create_routes(state: AppState, session_layer: SessionManagerLayer<PostgresStore>) -> Router {
     Router::new()
@@ -25,11 +26,13 @@ pub fn create_routes(state: AppState, session_layer: SessionManagerLayer<Postgre
 
 fn app_routes(state: AppState, session_layer: SessionManagerLayer<PostgresStore>) -> Router {
     let state_clone = state.clone();
+    let config = AppConfig::from_ref(&state);
 
     Router::new()
         .merge(public_routes())
         .merge(protected_routes())
         .merge(admin_routes())
+        .merge(dev_routes(&config))
         .fallback(handlers::fallback::handle_404)
         .with_state(state)
         // CRITICAL: Middleware ordering matters! Layers are applied bottom-to-top (last to first).
@@ -49,6 +52,22 @@ fn admin_routes() -> Router<AppState> {
         .layer(middleware::from_fn(middlewares::require_authentication))
 }
 
+/// Development-only routes
+///
+/// The mailbox exposes every captured message (including magic links), so it must
+/// never be reachable when email is actually delivered. The mock checkout exists
+/// only while the fake payment gateway is selected.
+fn dev_routes(config: &AppConfig) -> Router<AppState> {
+    let mut router = Router::new();
+    if config.email().captures_messages() {
+        router = router.merge(dev::mailbox_routes());
+    }
+    if config.payment().is_fake() {
+        router = router.merge(dev::fake_payment_routes());
+    }
+    router
+}
+
 /// Public routes accessible to all users (authenticated and guests)
 fn public_routes() -> Router<AppState> {
     Router::new()
@@ -5,6 +5,7 @@ pub fn public_page_routes() -> Router<AppState> {
     Router::new()
         .route(paths::pages::ROOT, get(pages::get_root))
         .route(paths::pages::SIGN_IN, get(pages::get_sign_in))
+        .route(paths::pages::UNSUBSCRIBE, get(pages::get_unsubscribe))
 }
 
 pub fn protected_page_routes() -> Router<AppState> {
@@ -14,5 +15,10 @@ pub fn protected_page_routes() -> Router<AppState> {
         .route(paths::pages::TEXT_ANALYZER, get(pages::get_text_analyzer))
         .route(paths::pages::QUOTE, get(pages::get_quote))
         .route(paths::pages::CHECKOUT, get(pages::get_checkout))
+        .route(paths::pages::DEPOSIT, get(pages::get_deposit))
         .route(paths::pages::RESULT, get(pages::get_result))
+        .route(paths::pages::CREDIT_TOP_UP, get(pages::get_credit_top_up))
+        .route(paths::pages::SUBSCRIPTION, get(pages::get_subscription))
+        .route(paths::pages::SUBSCRIPTION_CARD, get(pages::get_subscription_card))
+        .route(paths::pages::RECEIPT, get(pages::get_receipt))
 }
@@ -0,0 +1,73 @@
+//! HMAC-SHA256 signing for tokens embedded in URLs and forms.
+//!
+//! Tokens have the form `payload.signature` (both URL-safe base64). Each token is
+//! signed for a purpose, so a token issued for one feature is rejected by another.
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+type HmacSha256 = Hmac<Sha256>;
+
+pub const MIN_SECRET_LENGTH: usize = 32;
+
+#[derive(Clone)]
+pub struct Signer {
+    key: Vec<u8>,
+}
+
+impl Signer {
+    pub fn new(key: impl Into<Vec<u8>>) -> Self {
+        Self { key: key.into() }
+    }
+
+    pub fn sign(&self, purpose: &str, payload: &str) -> String {
+        let signature = self.mac(purpose, payload).finalize().into_bytes();
+        format!(
+            "{}.{}",
+            URL_SAFE_NO_PAD.encode(payload),
+            URL_SAFE_NO_PAD.encode(signature)
+        )
+    }
+
+    /// Returns the signed payload if `token` was signed for `purpose` with this key.
+    pub fn verify(&self, purpose: &str, token: &str) -> Option<String> {
+        let (payload, signature) = token.split_once('.')?;
+        let payload = String::from_utf8(URL_SAFE_NO_PAD.decode(payload).ok()?).ok()?;
+        let signature = URL_SAFE_NO_PAD.decode(signature).ok()?;
+
+        self.mac(purpose, &payload).verify_slice(&signature).ok()?;
+        Some(payload)
+    }
+
+    fn mac(&self, purpose: &str, payload: &str) -> HmacSha256 {
+        let mut mac = HmacSha256::new_from_slice(&self.key).expect("HMAC accepts keys of any length");
+        mac.update(purpose.as_bytes());
+        mac.update(b"\0");
+        mac.update(payload.as_bytes());
+        mac
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verify_round_trip() {
+        let signer = Signer::new("a".repeat(MIN_SECRET_LENGTH));
+        let token = signer.sign("unsubscribe", "user@example.com");
+        assert_eq!(signer.verify("unsubscribe", &token).as_deref(), Some("user@example.com"));
+    }
+
+    #[test]
+    fn test_verify_rejects_other_purpose_and_tampering() {
+        let signer = Signer::new("a".repeat(MIN_SECRET_LENGTH));
+        let token = signer.sign("unsubscribe", "user@example.com");
+        assert_eq!(signer.verify("contact_form", &token), None);
+
+        let (_, signature) = token.split_once('.').unwrap();
+        let forged = format!("{}.{}", URL_SAFE_NO_PAD.encode("other@example.com"), signature);
+        assert_eq!(signer.verify("unsubscribe", &forged), None);
+    }
+}
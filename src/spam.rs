@@ -0,0 +1,149 @@
+//! Spam heuristics for the public contact form.
+//!
+//! Three layers, none of them relying on third-party captchas: a hidden honeypot
+//! field, a signed render timestamp (bots submit instantly or replay old forms),
+//! and a content score. Submissions over [`spam::QUARANTINE_THRESHOLD`] are kept
+//! for admin review instead of being emailed.
+//!
+//! [`spam::QUARANTINE_THRESHOLD`]: crate::constants::spam::QUARANTINE_THRESHOLD
+
+use sha2::{Digest, Sha256};
+use time::OffsetDateTime;
+
+use crate::{constants::spam, signing::Signer};
+
+const FORM_STAMP_PURPOSE: &str = "contact_form";
+
+/// Signs the time the contact form was rendered.
+pub fn issue_form_stamp(signer: &Signer, now: OffsetDateTime) -> String {
+    signer.sign(FORM_STAMP_PURPOSE, &now.unix_timestamp().to_string())
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum FormStamp {
+    Valid,
+    /// Submitted this many seconds after rendering, faster than a person can type.
+    TooFast(i64),
+    Stale,
+    Invalid,
+}
+
+pub fn check_form_stamp(signer: &Signer, stamp: &str, now: OffsetDateTime) -> FormStamp {
+    let Some(rendered_at) = signer
+        .verify(FORM_STAMP_PURPOSE, stamp)
+        .and_then(|payload| payload.parse::<i64>().ok())
+    else {
+        return FormStamp::Invalid;
+    };
+
+    let elapsed = now.unix_timestamp() - rendered_at;
+    if elapsed < spam::MIN_FILL_SECONDS {
+        FormStamp::TooFast(elapsed)
+    } else if elapsed > spam::MAX_FORM_AGE_SECONDS {
+        FormStamp::Stale
+    } else {
+        FormStamp::Valid
+    }
+}
+
+/// Hash of the message with case and whitespace normalized, used to spot repeats.
+pub fn content_hash(message: &str) -> String {
+    let normalized = message.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase();
+    format!("{:x}", Sha256::digest(normalized.as_bytes()))
+}
+
+#[derive(Debug, Default)]
+pub struct SpamAssessment {
+    pub score: i32,
+    pub reasons: Vec<String>,
+}
+
+impl SpamAssessment {
+    pub fn add(&mut self, points: i32, reason: impl Into<String>) {
+        self.score += points;
+        self.reasons.push(reason.into());
+    }
+
+    pub fn is_spam(&self) -> bool {
+        self.score >= spam::QUARANTINE_THRESHOLD
+    }
+}
+
+/// Combines the bot signals from the form with the content score.
+pub fn assess_submission(honeypot: &str, stamp: &FormStamp, message: &str, recent_duplicates: i64) -> SpamAssessment {
+    let mut assessment = score_content(message, recent_duplicates);
+
+    if !honeypot.is_empty() {
+        assessment.add(spam::BOT_SIGNAL_POINTS, "honeypot field filled");
+    }
+    match stamp {
+        FormStamp::TooFast(seconds) => assessment.add(
+            spam::BOT_SIGNAL_POINTS,
+            format!("submitted {}s after the form was rendered", seconds),
+        ),
+        FormStamp::Invalid => assessment.add(spam::BOT_SIGNAL_POINTS, "missing or invalid form stamp"),
+        FormStamp::Valid | FormStamp::Stale => {}
+    }
+
+    assessment
+}
+
+/// Scores the message content. `recent_duplicates` is how many recent inquiries share its hash.
+pub fn score_content(message: &str, recent_duplicates: i64) -> SpamAssessment {
+    let mut assessment = SpamAssessment::default();
+    let lowercase = message.to_lowercase();
+
+    let links = ["http://", "https://", "www."]
+        .iter()
+        .map(|marker| lowercase.matches(marker).count())
+        .sum::<usize>() as i32;
+    if links > spam::ALLOWED_LINKS {
+        assessment.add(
+            (links - spam::ALLOWED_LINKS) * spam::EXCESS_LINK_POINTS,
+            format!("{} links", links),
+        );
+    }
+
+    for term in spam::BLOCKLISTED_TERMS {
+        if lowercase.contains(term) {
+            assessment.add(spam::BLOCKLISTED_TERM_POINTS, format!("blocklisted term \"{}\"", term));
+        }
+    }
+
+    if recent_duplicates > 0 {
+        assessment.add(
+            spam::DUPLICATE_POINTS,
+            format!("same message sent {} time(s) in the last {} hours", recent_duplicates, spam::DUPLICATE_WINDOW_HOURS),
+        );
+    }
+
+    assessment
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_form_stamp_rejects_fast_stale_and_forged_submissions() {
+        let signer = Signer::new("test-secret-key-that-is-long-enough");
+        let rendered = OffsetDateTime::UNIX_EPOCH + time::Duration::days(20000);
+        let stamp = issue_form_stamp(&signer, rendered);
+
+        assert_eq!(check_form_stamp(&signer, &stamp, rendered + time::Duration::seconds(1)), FormStamp::TooFast(1));
+        assert_eq!(check_form_stamp(&signer, &stamp, rendered + time::Duration::minutes(1)), FormStamp::Valid);
+        assert_eq!(check_form_stamp(&signer, &stamp, rendered + time::Duration::days(1)), FormStamp::Stale);
+        assert_eq!(check_form_stamp(&signer, "forged.stamp", rendered), FormStamp::Invalid);
+    }
+
+    #[test]
+    fn test_score_content() {
+        assert!(!score_content("Hi, can you check https://example.com/order/1?", 0).is_spam());
+
+        let links = "Visit https://a.example https://b.example https://c.example https://d.example www.e.example";
+        assert!(score_content(links, 0).is_spam());
+        assert!(score_content("Buy cheap viagra now", 1).is_spam());
+        assert_eq!(content_hash("Hello   World"), content_hash("hello world"));
+        assert!(assess_submission("http://bot.example", &FormStamp::Valid, "Hello", 0).is_spam());
+    }
+}
@@ -0,0 +1,106 @@
+//! Splits order amounts into supply amount (net) and VAT.
+
+use crate::{config::ConfigError, constants::tax, money::Money};
+
+/// Whether prices from the pricing rules already contain VAT.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TaxMode {
+    /// The price is what the customer pays; VAT is carved out of it.
+    Inclusive,
+    /// The price is the supply amount; VAT is added on top.
+    Exclusive,
+}
+
+/// An amount split for a receipt: `net + tax == gross`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TaxBreakdown {
+    pub net: Money,
+    pub tax: Money,
+    /// The amount charged
+    pub gross: Money,
+    pub rate_percent: i32,
+}
+
+#[derive(Clone, Debug)]
+pub struct TaxPolicy {
+    rate_percent: i32,
+    mode: TaxMode,
+}
+
+impl TaxPolicy {
+    pub fn from_env() -> Result<Self, ConfigError> {
+        let rate_percent = match dotenvy::var("VAT_RATE_PERCENT") {
+            Ok(value) => value.parse::<i32>().ok().filter(|rate| (0..=100).contains(rate)).ok_or_else(|| {
+                ConfigError::InvalidValue("VAT_RATE_PERCENT".to_string(), "must be a whole number from 0 to 100".to_string())
+            })?,
+            Err(_) => tax::DEFAULT_RATE_PERCENT,
+        };
+        let mode = match dotenvy::var("PRICES_INCLUDE_VAT").as_deref() {
+            Ok("false") => TaxMode::Exclusive,
+            _ => TaxMode::Inclusive,
+        };
+
+        Ok(Self::new(rate_percent, mode))
+    }
+
+    fn new(rate_percent: i32, mode: TaxMode) -> Self {
+        Self { rate_percent, mode }
+    }
+
+    /// Splits a price after discounts, or `None` if the amounts overflow.
+    ///
+    /// Inclusive prices round the supply amount to the nearest won and leave the
+    /// rest as VAT; exclusive prices add VAT rounded down, so the customer is never
+    /// charged a fraction up.
+    pub fn apply(&self, price: Money) -> Option<TaxBreakdown> {
+        let amount = price.amount;
+        let rate = i64::from(self.rate_percent);
+        let (net, tax) = match self.mode {
+            TaxMode::Inclusive => {
+                let net = amount.checked_mul(200)?.checked_add(100 + rate)? / (2 * (100 + rate));
+                (net, amount.checked_sub(net)?)
+            }
+            TaxMode::Exclusive => (amount, amount.checked_mul(rate)? / 100),
+        };
+        let (net, tax) = (Money::new(net, price.currency), Money::new(tax, price.currency));
+
+        Some(TaxBreakdown {
+            net,
+            tax,
+            gross: net.checked_add(tax)?,
+            rate_percent: self.rate_percent,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn won(amount: i64) -> Money {
+        Money::krw(amount)
+    }
+
+    fn breakdown(net: i64, tax: i64, gross: i64) -> TaxBreakdown {
+        TaxBreakdown { net: won(net), tax: won(tax), gross: won(gross), rate_percent: 10 }
+    }
+
+    #[test]
+    fn test_inclusive_prices_keep_the_total() {
+        let policy = TaxPolicy::new(10, TaxMode::Inclusive);
+        assert_eq!(policy.apply(won(11_000)).unwrap(), breakdown(10_000, 1_000, 11_000));
+        assert_eq!(policy.apply(won(100)).unwrap(), breakdown(91, 9, 100));
+        assert_eq!(policy.apply(won(0)).unwrap().gross, won(0));
+    }
+
+    #[test]
+    fn test_exclusive_prices_add_tax() {
+        let policy = TaxPolicy::new(10, TaxMode::Exclusive);
+        assert_eq!(policy.apply(won(10_000)).unwrap(), breakdown(10_000, 1_000, 11_000));
+        assert_eq!(policy.apply(won(105)).unwrap(), breakdown(105, 10, 115));
+
+        let untaxed = TaxPolicy::new(0, TaxMode::Exclusive);
+        assert_eq!(untaxed.apply(won(500)).unwrap().gross, won(500));
+        assert_eq!(policy.apply(won(i64::MAX)), None);
+    }
+}
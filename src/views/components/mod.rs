@@ -1,3 +1,5 @@
 pub mod admin;
 pub mod flash;
 pub mod form;
+pub mod payment;
+pub mod proof_of_work;
@@ -0,0 +1,298 @@
+use maud::{html, Markup, PreEscaped};
+
+use crate::{
+    config::PaymentProvider,
+    constants::{cdn, deposits},
+    formatting::format_file_size,
+    models::{cash_receipt::CashReceipt, order::Order, order_item::OrderItemMetadata, payment_method::PaymentMethod},
+    money::Money,
+};
+
+/// What the payment window charges for and where it sends the customer afterwards.
+pub struct PaymentRequest<'a> {
+    pub amount: Money,
+    /// Sent to the gateway as its order ID
+    pub order_number: &'a str,
+    pub order_name: &'a str,
+    /// Gateway redirect after approval; the gateway appends the payment parameters
+    pub success_path: &'a str,
+    pub fail_path: &'a str,
+    /// Stand-in payment window used with `PAYMENT_GATEWAY=fake`; receives the chosen `method`
+    pub fake_checkout_path: &'a str,
+    /// Methods the customer can pick from; the first is preselected
+    pub methods: &'a [PaymentMethod],
+}
+
+/// The payment method picker and "Pay Now" button for the configured payment provider.
+pub fn payment_button(request: &PaymentRequest, provider: &PaymentProvider) -> Markup {
+    match provider {
+        PaymentProvider::Toss(toss) => toss_widget(request, toss.client_key()),
+        PaymentProvider::Fake => html! {
+            p class="text-sm text-gray-600 mb-3" { "Test mode: payments are simulated and no card is charged." }
+            form method="get" action=(request.fake_checkout_path) {
+                (method_picker(request.methods))
+                button
+                    type="submit"
+                    class="w-full bg-indigo-600 text-white px-3 py-2 hover:bg-indigo-700"
+                    { "Pay Now" }
+            }
+        },
+    }
+}
+
+/// Radio buttons named `method`, or a hidden field when there is nothing to choose.
+fn method_picker(methods: &[PaymentMethod]) -> Markup {
+    html! {
+        @if let [method] = methods {
+            input type="hidden" name="method" value=(method.as_str());
+        } @else {
+            fieldset class="mb-3" {
+                legend class="text-sm text-gray-600 mb-2" { "Payment method" }
+                div class="grid grid-cols-2 gap-2 text-sm" {
+                    @for (index, method) in methods.iter().enumerate() {
+                        label class="flex items-center gap-2 border px-3 py-2 cursor-pointer hover:bg-gray-50" {
+                            input type="radio" name="method" value=(method.as_str()) checked[index == 0];
+                            span { (method.display_text()) }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// The `requestPayment` method and extra parameters for a payment method.
+fn toss_request(method: PaymentMethod) -> (&'static str, String) {
+    let easy_pay = |provider: &str| format!("{{ flowMode: 'DIRECT', easyPay: '{}' }}", provider);
+    match method {
+        PaymentMethod::Card => ("카드", "{}".to_string()),
+        PaymentMethod::Transfer => ("계좌이체", "{}".to_string()),
+        PaymentMethod::VirtualAccount => ("가상계좌", format!("{{ validHours: {} }}", deposits::VALID_HOURS)),
+        PaymentMethod::MobilePhone => ("휴대폰", "{}".to_string()),
+        PaymentMethod::KakaoPay => ("카드", easy_pay("KAKAOPAY")),
+        PaymentMethod::NaverPay => ("카드", easy_pay("NAVERPAY")),
+        PaymentMethod::TossPay => ("카드", easy_pay("TOSSPAY")),
+    }
+}
+
+fn toss_widget(request: &PaymentRequest, client_key: &str) -> Markup {
+    let method_requests: String = request
+        .methods
+        .iter()
+        .map(|method| {
+            let (toss_method, params) = toss_request(*method);
+            format!("'{}': ['{}', {}],", method.as_str(), toss_method, params)
+        })
+        .collect();
+
+    html! {
+        (method_picker(request.methods))
+        div id="agreement" class="mb-3" {}
+
+        button
+            id="payment-button"
+            class="w-full bg-indigo-600 text-white px-3 py-2 hover:bg-indigo-700 disabled:bg-gray-400 disabled:cursor-not-allowed"
+            { "Pay Now" }
+
+        script src=(cdn::TOSS_PAYMENTS_SDK_URL) {}
+        script {
+            (PreEscaped(format!(r#"
+                const button = document.getElementById('payment-button');
+                const methodRequests = {{ {} }};
+
+                try {{
+                    const tossPayments = TossPayments('{}');
+                    button.disabled = false;
+
+                    button.addEventListener('click', function() {{
+                        const method = document.querySelector('input[name="method"]:checked, input[name="method"][type="hidden"]').value;
+                        const [tossMethod, methodParams] = methodRequests[method];
+                        const paymentParams = {{
+                            amount: {},
+                            orderId: '{}',
+                            orderName: '{}',
+                            successUrl: window.location.origin + '{}',
+                            failUrl: window.location.origin + '{}',
+                            ...methodParams
+                        }};
+                        console.log('Payment request parameters:', tossMethod, paymentParams);
+
+                        tossPayments.requestPayment(tossMethod, paymentParams)
+                        .catch(function(error) {{
+                            console.error('Payment request failed:', error);
+                            alert('결제 요청 실패: ' + (error.message || error.code));
+                        }});
+                    }});
+                }} catch (error) {{
+                    console.error('Toss Payments initialization failed:', error);
+                    button.disabled = true;
+                    button.textContent = 'Payment Error';
+                }}
+            "#,
+                method_requests,
+                client_key,
+                request.amount.amount,
+                request.order_number,
+                request.order_name,
+                request.success_path,
+                request.fail_path
+            )))
+        }
+    }
+}
+
+/// Where a card registration for recurring charges returns to.
+pub struct CardRegistration<'a> {
+    /// Identifies the customer to the gateway; the billing key is bound to it
+    pub customer_key: &'a str,
+    /// Gateway redirect after registration; the gateway appends `customerKey` and `authKey`
+    pub success_path: &'a str,
+    pub fail_path: &'a str,
+    /// Stand-in registration window used with `PAYMENT_GATEWAY=fake`
+    pub fake_registration_path: &'a str,
+}
+
+/// The "Register Card" button for the configured payment provider.
+pub fn card_registration_button(registration: &CardRegistration, provider: &PaymentProvider) -> Markup {
+    match provider {
+        PaymentProvider::Toss(toss) => toss_billing_widget(registration, toss.client_key()),
+        PaymentProvider::Fake => html! {
+            p class="text-sm text-gray-600 mb-3" { "Test mode: cards are simulated and no card is charged." }
+            a
+                href=(registration.fake_registration_path)
+                class="block w-full text-center bg-indigo-600 text-white px-3 py-2 hover:bg-indigo-700"
+                { "Register Card" }
+        },
+    }
+}
+
+fn toss_billing_widget(registration: &CardRegistration, client_key: &str) -> Markup {
+    html! {
+        button
+            id="card-button"
+            class="w-full bg-indigo-600 text-white px-3 py-2 hover:bg-indigo-700 disabled:bg-gray-400 disabled:cursor-not-allowed"
+            { "Register Card" }
+
+        script src=(cdn::TOSS_PAYMENTS_SDK_URL) {}
+        script {
+            (PreEscaped(format!(r#"
+                const button = document.getElementById('card-button');
+
+                try {{
+                    const tossPayments = TossPayments('{}');
+                    button.disabled = false;
+
+                    button.addEventListener('click', function() {{
+                        tossPayments.requestBillingAuth('카드', {{
+                            customerKey: '{}',
+                            successUrl: window.location.origin + '{}',
+                            failUrl: window.location.origin + '{}'
+                        }})
+                        .catch(function(error) {{
+                            console.error('Card registration failed:', error);
+                            alert('카드 등록 실패: ' + (error.message || error.code));
+                        }});
+                    }});
+                }} catch (error) {{
+                    console.error('Toss Payments initialization failed:', error);
+                    button.disabled = true;
+                    button.textContent = 'Payment Error';
+                }}
+            "#,
+                client_key,
+                registration.customer_key,
+                registration.success_path,
+                registration.fail_path
+            )))
+        }
+    }
+}
+
+/// Supply amount and VAT rows shown above an order's total.
+/// What the order is for: each item with its amount and product details.
+pub fn order_items(order: &Order) -> Markup {
+    html! {
+        div class="space-y-3 text-sm" {
+            @for item in order.items.iter() {
+                div class="space-y-1" {
+                    div class="flex justify-between" {
+                        span {
+                            (item.product_name)
+                            @if item.quantity > 1 { " × " (item.quantity) }
+                        }
+                        span { @if let Some(amount) = item.amount() { (amount) } }
+                    }
+                    @match &item.metadata {
+                        OrderItemMetadata::TextAnalysis(analysis) => {
+                            div class="flex justify-between" {
+                                span class="text-gray-600" { "File" }
+                                span { (analysis.filename) }
+                            }
+                            div class="flex justify-between" {
+                                span class="text-gray-600" { "Size" }
+                                span { (format_file_size(analysis.file_size)) }
+                            }
+                            div class="flex justify-between" {
+                                span class="text-gray-600" { "Characters" }
+                                span { (analysis.text_length) }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+pub fn tax_breakdown(order: &Order) -> Markup {
+    html! {
+        div class="space-y-1 text-sm" {
+            div class="flex justify-between" {
+                span class="text-gray-600" { "Supply amount" }
+                span { (order.net_amount) }
+            }
+            div class="flex justify-between" {
+                span class="text-gray-600" { "VAT (" (order.tax_rate_percent) "%)" }
+                span { (order.tax_amount) }
+            }
+        }
+    }
+}
+
+/// Rows describing an order's cash receipt (현금영수증).
+pub fn cash_receipt_summary(receipt: &CashReceipt) -> Markup {
+    html! {
+        div class="space-y-1 text-sm" {
+            div class="flex justify-between" {
+                span class="text-gray-600" { "Cash receipt" }
+                span { (receipt.receipt_type.display_text()) }
+            }
+            div class="flex justify-between" {
+                span class="text-gray-600" { "Issued to" }
+                span class="font-mono" { (receipt.masked_identifier()) }
+            }
+            div class="flex justify-between" {
+                span class="text-gray-600" { "Receipt status" }
+                span class=(receipt.status.css_class()) { (receipt.status.display_text()) }
+            }
+            @if let Some(issue_number) = &receipt.issue_number {
+                div class="flex justify-between" {
+                    span class="text-gray-600" { "Approval number" }
+                    span class="font-mono" { (issue_number) }
+                }
+            }
+            @if receipt.cancelled_amount.is_positive() {
+                div class="flex justify-between" {
+                    span class="text-gray-600" { "Cancelled for refunds" }
+                    span { (receipt.cancelled_amount) }
+                }
+            }
+            @if let Some(message) = &receipt.failure_message {
+                p class="text-red-600" { (message) }
+            }
+            @if let Some(receipt_url) = &receipt.receipt_url {
+                a href=(receipt_url) target="_blank" rel="noopener" class="text-indigo-600 hover:underline" { "View cash receipt" }
+            }
+        }
+    }
+}
@@ -0,0 +1,15 @@
+use maud::{html, Markup};
+
+use crate::{paths, proof_of_work::PowChallenge};
+
+/// Hidden fields and solver script for a form carrying `data-pow-*` attributes.
+/// Renders nothing when proof-of-work is disabled.
+pub fn pow_fields(challenge: Option<&PowChallenge>) -> Markup {
+    html! {
+        @if let Some(challenge) = challenge {
+            input type="hidden" name="pow_challenge" value=(challenge.token);
+            input type="hidden" name="pow_solution" value="";
+            script src=(paths::static_files::POW_SCRIPT) defer {}
+        }
+    }
+}
@@ -0,0 +1,23 @@
+use maud::html;
+
+use super::{EmailContent, layout::{email_button, email_layout}};
+
+pub fn contact_inquiry(site_name: &str, email: &str, message: &str, inquiry_url: &str) -> EmailContent {
+    let title = "New Contact Inquiry";
+
+    let content = html! {
+        p {
+            strong { "From: " }
+            (email)
+        }
+        div style="margin: 20px 0; padding: 15px; background-color: #f5f5f5; border-radius: 6px;" {
+            @for line in message.lines() {
+                (line)
+                br;
+            }
+        }
+        (email_button(inquiry_url, "Open in Inbox"))
+    };
+
+    EmailContent::new(title, email_layout(site_name, title, content, None))
+}
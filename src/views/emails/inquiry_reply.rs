@@ -0,0 +1,26 @@
+use maud::html;
+
+use super::{EmailContent, layout::email_layout};
+
+/// A staff reply to a contact inquiry, quoting the original message.
+pub fn inquiry_reply(site_name: &str, reply: &str, original_message: &str) -> EmailContent {
+    let title = "Reply to your message";
+
+    let content = html! {
+        div {
+            @for line in reply.lines() {
+                (line)
+                br;
+            }
+        }
+        p style="color: #666; font-size: 14px; margin-top: 24px;" { "You wrote:" }
+        div style="padding: 15px; background-color: #f5f5f5; border-radius: 6px; color: #666; font-size: 14px;" {
+            @for line in original_message.lines() {
+                (line)
+                br;
+            }
+        }
+    };
+
+    EmailContent::new(format!("Re: Your message to {}", site_name), email_layout(site_name, title, content, None))
+}
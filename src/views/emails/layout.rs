@@ -0,0 +1,44 @@
+use maud::{html, Markup, DOCTYPE};
+
+/// Wraps `content` in the branded email shell. Optional notifications pass an
+/// `unsubscribe_url`, which is linked in the footer.
+pub fn email_layout(site_name: &str, title: &str, content: Markup, unsubscribe_url: Option<&str>) -> Markup {
+    html! {
+        (DOCTYPE)
+        html lang="en" {
+            head {
+                meta charset="UTF-8";
+                meta name="viewport" content="width=device-width, initial-scale=1.0";
+                title { (title) " - " (site_name) }
+            }
+            body style="margin: 0; padding: 0; background-color: #f5f5f5; font-family: sans-serif;" {
+                div style="max-width: 600px; margin: 0 auto; padding: 20px;" {
+                    div style="padding: 12px 0; font-size: 18px; font-weight: bold; color: #4F46E5;" {
+                        (site_name)
+                    }
+                    div style="background-color: #ffffff; padding: 24px; border-radius: 6px;" {
+                        h2 style="margin-top: 0;" { (title) }
+                        (content)
+                    }
+                    p style="color: #999; font-size: 12px; margin-top: 24px; text-align: center;" {
+                        "Sent by " (site_name)
+                        @if let Some(unsubscribe_url) = unsubscribe_url {
+                            br;
+                            a href=(unsubscribe_url) style="color: #999;" { "Unsubscribe from these emails" }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+pub fn email_button(href: &str, label: &str) -> Markup {
+    html! {
+        p style="margin: 30px 0;" {
+            a href=(href) style="background-color: #4F46E5; color: white; padding: 12px 24px; text-decoration: none; border-radius: 6px; display: inline-block;" {
+                (label)
+            }
+        }
+    }
+}
@@ -0,0 +1,23 @@
+use maud::html;
+
+use super::{EmailContent, layout::{email_button, email_layout}};
+use crate::constants::auth::MAGIC_LINK_EXPIRY_MINUTES;
+
+pub fn magic_link_signin(site_name: &str, magic_link: &str) -> EmailContent {
+    let title = "Sign in to your account";
+
+    let content = html! {
+        p { "Click the link below to sign in. This link will expire in " (MAGIC_LINK_EXPIRY_MINUTES) " minutes." }
+        (email_button(magic_link, "Sign In"))
+        p style="color: #666; font-size: 14px;" {
+            "Or copy and paste this link into your browser:"
+            br;
+            a href=(magic_link) { (magic_link) }
+        }
+        p style="color: #999; font-size: 12px; margin-top: 40px;" {
+            "If you didn't request this email, you can safely ignore it."
+        }
+    };
+
+    EmailContent::new(title, email_layout(site_name, title, content, None))
+}
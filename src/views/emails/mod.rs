@@ -0,0 +1,191 @@
+//! Maud email templates.
+//!
+//! Each template renders an HTML body inside the shared email layout; the plain-text
+//! alternative is generated from that HTML so both parts always carry the same content.
+
+mod contact_inquiry;
+mod inquiry_reply;
+mod layout;
+mod magic_link;
+mod payment_failed;
+mod payment_receipt;
+mod plain_text;
+mod subscription_ended;
+mod subscription_payment_failed;
+
+pub use contact_inquiry::contact_inquiry;
+pub use inquiry_reply::inquiry_reply;
+pub use magic_link::magic_link_signin;
+pub use payment_failed::payment_failed;
+pub use payment_receipt::payment_receipt;
+pub use subscription_ended::subscription_ended;
+pub use subscription_payment_failed::subscription_payment_failed;
+
+use maud::Markup;
+
+/// A rendered email ready to be sent as multipart/alternative.
+pub struct EmailContent {
+    pub subject: String,
+    pub html: String,
+    pub text: String,
+}
+
+impl EmailContent {
+    pub fn new(subject: impl Into<String>, html: Markup) -> Self {
+        let html = html.into_string();
+        let text = plain_text::html_to_text(&html);
+        Self {
+            subject: subject.into(),
+            html,
+            text,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        models::{
+            order::{Order, PaymentStatus},
+            order_item::{OrderItem, OrderItemMetadata, TextAnalysis},
+            payment_method::PaymentMethod,
+            subscription::{Subscription, SubscriptionStatus},
+        },
+        money::Money,
+    };
+    use sqlx::types::Json;
+
+    fn order_fixture() -> Order {
+        Order {
+            order_id: uuid::Uuid::nil(),
+            user_id: 1,
+            user_email: "customer@example.com".to_string(),
+            list_price_amount: Money::krw(1234),
+            discount_amount: Money::krw(0),
+            discount_code_id: None,
+            price_amount: Money::krw(1234),
+            net_amount: Money::krw(1122),
+            tax_amount: Money::krw(112),
+            tax_rate_percent: 10,
+            payment_status: PaymentStatus::Paid,
+            payment_key: Some("tgen_20250101000000abcd".to_string()),
+            payment_method: Some(PaymentMethod::Card),
+            order_number: "ORD-1-abcd1234".to_string(),
+            created_at: time::OffsetDateTime::UNIX_EPOCH,
+            paid_at: Some(time::OffsetDateTime::UNIX_EPOCH),
+            items: Json(vec![OrderItem {
+                order_item_id: uuid::Uuid::nil(),
+                product_code: "text_analysis".to_string(),
+                product_name: "Text Analysis".to_string(),
+                quantity: 1,
+                unit_price_amount: Money::krw(1234),
+                metadata: OrderItemMetadata::TextAnalysis(TextAnalysis {
+                    filename: "essay.txt".to_string(),
+                    file_size: 2048,
+                    text_content: "Lorem ipsum".to_string(),
+                    text_length: 1234,
+                }),
+            }]),
+        }
+    }
+
+    fn subscription_fixture() -> Subscription {
+        Subscription {
+            subscription_id: uuid::Uuid::nil(),
+            user_id: 1,
+            user_email: "customer@example.com".to_string(),
+            plan_name: "Basic".to_string(),
+            price_amount: Money::krw(9900),
+            included_characters: 100_000,
+            status: SubscriptionStatus::PastDue,
+            customer_key: "cus_1".to_string(),
+            billing_key: Some("billing_key".to_string()),
+            card_label: Some("4242-42**-****-4242".to_string()),
+            current_period_end: Some(time::OffsetDateTime::UNIX_EPOCH),
+            characters_used: 0,
+            cancel_at_period_end: false,
+            failed_attempts: 1,
+            next_retry_at: Some(time::OffsetDateTime::UNIX_EPOCH),
+        }
+    }
+
+    #[test]
+    fn test_magic_link_signin_snapshot() {
+        let email = magic_link_signin("My App", "http://localhost:8000/actions/auth/verify?token=abc123");
+        insta::assert_snapshot!("magic_link_signin_html", email.html);
+        insta::assert_snapshot!("magic_link_signin_text", email.text);
+    }
+
+    #[test]
+    fn test_contact_inquiry_snapshot() {
+        let email = contact_inquiry(
+            "My App",
+            "visitor@example.com",
+            "Hello,\n<script>alert('x')</script> & <b>bold</b>\nThanks",
+            "http://localhost:8000/admin/inquiries/1",
+        );
+        insta::assert_snapshot!("contact_inquiry_html", email.html);
+        insta::assert_snapshot!("contact_inquiry_text", email.text);
+    }
+
+    #[test]
+    fn test_inquiry_reply_snapshot() {
+        let email = inquiry_reply(
+            "My App",
+            "Hi,\nThanks for reaching out. Your refund is on its way.",
+            "Can I get a refund for order 123?",
+        );
+        insta::assert_snapshot!("inquiry_reply_html", email.html);
+        insta::assert_snapshot!("inquiry_reply_text", email.text);
+    }
+
+    #[test]
+    fn test_payment_receipt_snapshot() {
+        let email = payment_receipt(
+            "My App",
+            &order_fixture(),
+            "http://localhost:8000/result/00000000-0000-0000-0000-000000000000",
+            "http://localhost:8000/unsubscribe?token=abc.def",
+        );
+        insta::assert_snapshot!("payment_receipt_html", email.html);
+        insta::assert_snapshot!("payment_receipt_text", email.text);
+    }
+
+    #[test]
+    fn test_payment_failed_snapshot() {
+        let email = payment_failed(
+            "My App",
+            &order_fixture(),
+            "http://localhost:8000/quote/00000000-0000-0000-0000-000000000000",
+            "http://localhost:8000/unsubscribe?token=abc.def",
+        );
+        insta::assert_snapshot!("payment_failed_html", email.html);
+        insta::assert_snapshot!("payment_failed_text", email.text);
+    }
+
+    #[test]
+    fn test_subscription_payment_failed_snapshot() {
+        let email = subscription_payment_failed(
+            "My App",
+            &subscription_fixture(),
+            time::OffsetDateTime::UNIX_EPOCH,
+            "http://localhost:8000/subscription",
+            "http://localhost:8000/unsubscribe?token=abc.def",
+        );
+        insta::assert_snapshot!("subscription_payment_failed_html", email.html);
+        insta::assert_snapshot!("subscription_payment_failed_text", email.text);
+    }
+
+    #[test]
+    fn test_subscription_ended_snapshot() {
+        let email = subscription_ended(
+            "My App",
+            &subscription_fixture(),
+            "http://localhost:8000/subscription",
+            "http://localhost:8000/unsubscribe?token=abc.def",
+        );
+        insta::assert_snapshot!("subscription_ended_html", email.html);
+        insta::assert_snapshot!("subscription_ended_text", email.text);
+    }
+}
@@ -0,0 +1,19 @@
+use maud::html;
+
+use super::{EmailContent, layout::{email_button, email_layout}};
+use crate::models::order::Order;
+
+pub fn payment_failed(site_name: &str, order: &Order, retry_url: &str, unsubscribe_url: &str) -> EmailContent {
+    let title = "Payment failed";
+
+    let content = html! {
+        p {
+            "We couldn't complete the payment of " (order.price_amount)
+            " for order " strong { (order.order_number) } "."
+        }
+        p { "You have not been charged. You can try again from your quote:" }
+        (email_button(retry_url, "Retry Payment"))
+    };
+
+    EmailContent::new(format!("Payment failed for order {}", order.order_number), email_layout(site_name, title, content, Some(unsubscribe_url)))
+}
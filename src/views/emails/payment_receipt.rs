@@ -0,0 +1,36 @@
+use maud::html;
+
+use super::{EmailContent, layout::{email_button, email_layout}};
+use crate::models::order::Order;
+
+pub fn payment_receipt(site_name: &str, order: &Order, result_url: &str, unsubscribe_url: &str) -> EmailContent {
+    let title = "Payment receipt";
+
+    let content = html! {
+        p { "Thank you for your payment. Your order is complete. A PDF receipt is attached." }
+        table style="width: 100%; font-size: 14px; border-collapse: collapse;" {
+            tr {
+                td style="color: #666; padding: 4px 0;" { "Order Number" }
+                td style="text-align: right; padding: 4px 0;" { (order.order_number) }
+            }
+            @for item in order.items.iter() {
+                tr {
+                    td style="color: #666; padding: 4px 0;" {
+                        (item.description())
+                        @if item.quantity > 1 { " × " (item.quantity) }
+                    }
+                    td style="text-align: right; padding: 4px 0;" { @if let Some(amount) = item.amount() { (amount) } }
+                }
+            }
+            tr {
+                td style="padding: 8px 0; border-top: 1px solid #ddd;" { strong { "Amount Paid" } }
+                td style="text-align: right; padding: 8px 0; border-top: 1px solid #ddd;" {
+                    strong { (order.price_amount) }
+                }
+            }
+        }
+        (email_button(result_url, "View Results"))
+    };
+
+    EmailContent::new(format!("Receipt for order {}", order.order_number), email_layout(site_name, title, content, Some(unsubscribe_url)))
+}
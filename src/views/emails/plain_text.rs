@@ -0,0 +1,137 @@
+//! Plain-text rendering of email HTML.
+//!
+//! Handles the subset of HTML our email templates produce: block elements become
+//! line breaks, links keep their target, and markup-only sections are dropped.
+
+const BLOCK_TAGS: &[&str] = &["div", "table", "ul", "ol", "hr"];
+const PARAGRAPH_TAGS: &[&str] = &["p", "h1", "h2", "h3", "h4", "h5", "h6"];
+const HIDDEN_TAGS: &[&str] = &["head", "style", "script"];
+
+pub fn html_to_text(html: &str) -> String {
+    let mut out = String::new();
+    let mut links: Vec<(String, usize)> = Vec::new();
+    let mut hidden_depth = 0usize;
+    let mut rest = html;
+
+    while let Some(start) = rest.find('<') {
+        if hidden_depth == 0 {
+            push_text(&mut out, &rest[..start]);
+        }
+        let Some(len) = rest[start..].find('>') else {
+            rest = "";
+            break;
+        };
+        let tag = &rest[start + 1..start + len];
+        rest = &rest[start + len + 1..];
+
+        let is_closing = tag.starts_with('/');
+        let name = tag
+            .trim_start_matches('/')
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or("")
+            .to_ascii_lowercase();
+
+        if HIDDEN_TAGS.contains(&name.as_str()) {
+            hidden_depth = if is_closing { hidden_depth.saturating_sub(1) } else { hidden_depth + 1 };
+            continue;
+        }
+        if hidden_depth > 0 {
+            continue;
+        }
+
+        match name.as_str() {
+            "br" => out.push('\n'),
+            "li" if !is_closing => out.push_str("\n- "),
+            "tr" if is_closing => out.push('\n'),
+            "td" | "th" if !is_closing && !out.ends_with([' ', '\n']) => out.push(' '),
+            "a" if !is_closing => {
+                let href = attribute(tag, "href").unwrap_or_default();
+                links.push((href, out.len()));
+            }
+            "a" => {
+                if let Some((href, label_start)) = links.pop()
+                    && !href.is_empty()
+                    && out[label_start..].trim() != href
+                {
+                    out.push_str(" (");
+                    out.push_str(&href);
+                    out.push(')');
+                }
+            }
+            name if PARAGRAPH_TAGS.contains(&name) => out.push_str("\n\n"),
+            name if BLOCK_TAGS.contains(&name) => out.push('\n'),
+            _ => {}
+        }
+    }
+    if hidden_depth == 0 {
+        push_text(&mut out, rest);
+    }
+
+    normalize_lines(&out)
+}
+
+fn push_text(out: &mut String, text: &str) {
+    if text.starts_with(char::is_whitespace) && !out.ends_with([' ', '\n']) {
+        out.push(' ');
+    }
+    let collapsed = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if collapsed.is_empty() {
+        return;
+    }
+    out.push_str(&decode_entities(&collapsed));
+    if text.ends_with(char::is_whitespace) {
+        out.push(' ');
+    }
+}
+
+fn attribute(tag: &str, name: &str) -> Option<String> {
+    let pattern = format!("{}=\"", name);
+    let start = tag.find(&pattern)? + pattern.len();
+    let len = tag[start..].find('"')?;
+    Some(decode_entities(&tag[start..start + len]))
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&#x27;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+/// Trims every line and collapses runs of blank lines into a single one.
+fn normalize_lines(text: &str) -> String {
+    let mut lines: Vec<&str> = Vec::new();
+    for line in text.lines().map(str::trim) {
+        if line.is_empty() && lines.last().is_none_or(|last| last.is_empty()) {
+            continue;
+        }
+        lines.push(line);
+    }
+    while lines.last().is_some_and(|last| last.is_empty()) {
+        lines.pop();
+    }
+    let mut result = lines.join("\n");
+    result.push('\n');
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_links_keep_their_target() {
+        let text = html_to_text(r#"<p>Go <a href="https://example.com/?a=1&amp;b=2">here</a> now</p>"#);
+        assert_eq!(text, "Go here (https://example.com/?a=1&b=2) now\n");
+    }
+
+    #[test]
+    fn test_head_is_dropped_and_blocks_break_lines() {
+        let text = html_to_text("<html><head><title>T</title></head><body><h2>Title</h2><p>One<br>Two</p></body></html>");
+        assert_eq!(text, "Title\n\nOne\nTwo\n");
+    }
+}
@@ -0,0 +1,19 @@
+use maud::html;
+
+use super::{EmailContent, layout::{email_button, email_layout}};
+use crate::models::subscription::Subscription;
+
+pub fn subscription_ended(site_name: &str, subscription: &Subscription, subscribe_url: &str, unsubscribe_url: &str) -> EmailContent {
+    let title = "Subscription ended";
+
+    let content = html! {
+        p {
+            "We couldn't collect the payment for your " strong { (subscription.plan_name) }
+            " plan after several attempts, so the subscription has ended."
+        }
+        p { "You have not been charged for the unpaid period. You can subscribe again at any time:" }
+        (email_button(subscribe_url, "View Plans"))
+    };
+
+    EmailContent::new(format!("Your {} plan has ended", subscription.plan_name), email_layout(site_name, title, content, Some(unsubscribe_url)))
+}
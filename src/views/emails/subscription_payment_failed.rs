@@ -0,0 +1,29 @@
+use maud::html;
+use time::OffsetDateTime;
+
+use super::{EmailContent, layout::{email_button, email_layout}};
+use crate::{formatting::format_datetime, models::subscription::Subscription};
+
+pub fn subscription_payment_failed(
+    site_name: &str,
+    subscription: &Subscription,
+    next_retry_at: OffsetDateTime,
+    manage_url: &str,
+    unsubscribe_url: &str,
+) -> EmailContent {
+    let title = "Subscription payment failed";
+
+    let content = html! {
+        p {
+            "We couldn't charge " (subscription.price_amount)
+            " for your " strong { (subscription.plan_name) } " plan."
+        }
+        p {
+            "Your included characters are paused until the payment goes through. We'll try again on "
+            strong { (format_datetime(next_retry_at)) } ". To avoid losing your plan, update your card:"
+        }
+        (email_button(manage_url, "Update Card"))
+    };
+
+    EmailContent::new(format!("Payment failed for your {} plan", subscription.plan_name), email_layout(site_name, title, content, Some(unsubscribe_url)))
+}
@@ -12,6 +12,7 @@ pub fn navbar(current_user: &CurrentUser) -> Markup {
                             CurrentUser::Authenticated { .. } => {
                                 a href=(paths::pages::DASHBOARD) class="hover:text-indigo-600" { "Dashboard" }
                                 a href=(paths::pages::TEXT_ANALYZER) class="hover:text-indigo-600" { "Text Analyzer" }
+                                a href=(paths::pages::SUBSCRIPTION) class="hover:text-indigo-600" { "Subscription" }
                                 a href=(paths::pages::TODOS) class="hover:text-indigo-600" { "Todos" }
                             }
                             CurrentUser::Guest => {}
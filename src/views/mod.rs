@@ -1,6 +1,8 @@
 //! Maud HTML templates organized by composition level:
 //! layout (base structure), components (reusable elements), and pages (full views).
+//! Email templates live in `emails` with their own layout.
 
 pub mod components;
+pub mod emails;
 pub mod layout;
 pub mod pages;
\ No newline at end of file
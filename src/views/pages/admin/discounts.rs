@@ -0,0 +1,122 @@
+use crate::{
+    auth::CurrentUser,
+    flash::FlashMessage,
+    formatting,
+    models::discount::DiscountCode,
+    paths,
+    views::layout::base::base_layout,
+};
+use maud::{html, Markup};
+
+pub fn discounts(
+    current_user: &CurrentUser,
+    flash: Option<&FlashMessage>,
+    site_name: &str,
+    codes: Vec<DiscountCode>,
+) -> Markup {
+    let content = html! {
+        div class="max-w-6xl mx-auto" {
+            h1 class="text-xl mb-6" { "Discount Codes" }
+
+            form method="post" action=(paths::forms::admin::CREATE_DISCOUNT) class="border p-4 mb-8" {
+                h2 class="text-lg mb-3" { "New Code" }
+                div class="grid grid-cols-4 gap-3 text-sm" {
+                    label class="flex flex-col gap-1" {
+                        span class="text-gray-600" { "Code" }
+                        input type="text" name="code" required class="border px-2 py-1 uppercase";
+                    }
+                    label class="flex flex-col gap-1" {
+                        span class="text-gray-600" { "Type" }
+                        select name="kind" class="border px-2 py-1" {
+                            option value="percentage" { "Percentage" }
+                            option value="fixed" { "Fixed amount (₩)" }
+                        }
+                    }
+                    label class="flex flex-col gap-1" {
+                        span class="text-gray-600" { "Value" }
+                        input type="number" name="value" min="1" required class="border px-2 py-1";
+                    }
+                    label class="flex flex-col gap-1" {
+                        span class="text-gray-600" { "Minimum order (₩)" }
+                        input type="number" name="min_order_amount" min="0" class="border px-2 py-1";
+                    }
+                    label class="flex flex-col gap-1" {
+                        span class="text-gray-600" { "Max redemptions" }
+                        input type="number" name="max_redemptions" min="1" placeholder="Unlimited" class="border px-2 py-1";
+                    }
+                    label class="flex flex-col gap-1" {
+                        span class="text-gray-600" { "Per-user limit" }
+                        input type="number" name="per_user_limit" min="1" placeholder="Unlimited" class="border px-2 py-1";
+                    }
+                    label class="flex flex-col gap-1" {
+                        span class="text-gray-600" { "Last valid day (UTC)" }
+                        input type="date" name="expires_on" class="border px-2 py-1";
+                    }
+                    div class="flex items-end" {
+                        button type="submit" class="px-3 py-1 bg-indigo-600 text-white hover:bg-indigo-700" {
+                            "Create"
+                        }
+                    }
+                }
+            }
+
+            @if codes.is_empty() {
+                p class="text-gray-500 py-4" { "No discount codes" }
+            } @else {
+                table class="w-full text-sm" {
+                    thead class="border-b" {
+                        tr {
+                            th class="text-left py-2 px-2" { "Code" }
+                            th class="text-left py-2 px-2" { "Discount" }
+                            th class="text-right py-2 px-2" { "Min. Order" }
+                            th class="text-right py-2 px-2" { "Redeemed" }
+                            th class="text-right py-2 px-2" { "Per User" }
+                            th class="text-center py-2 px-2" { "Created" }
+                            th class="text-center py-2 px-2" { "Expires" }
+                            th class="text-center py-2 px-2" { "Status" }
+                        }
+                    }
+                    tbody {
+                        @for code in &codes {
+                            (code_row(code))
+                        }
+                    }
+                }
+            }
+        }
+    };
+
+    base_layout(current_user, flash, site_name, "Discount Codes", "Promotion codes customers apply to quotes", content)
+}
+
+fn code_row(code: &DiscountCode) -> Markup {
+    html! {
+        tr class="border-b" {
+            td class="py-2 px-2 font-mono" { (code.code) }
+            td class="py-2 px-2" { (code.describe()) }
+            td class="py-2 px-2 text-right" { (code.min_order_amount) }
+            td class="py-2 px-2 text-right" {
+                (code.redemption_count)
+                @if let Some(max) = code.max_redemptions { " / " (max) }
+            }
+            td class="py-2 px-2 text-right" {
+                @if let Some(limit) = code.per_user_limit { (limit) } @else { "—" }
+            }
+            td class="py-2 px-2 text-center text-gray-600" { (formatting::format_datetime(code.created_at)) }
+            td class="py-2 px-2 text-center text-gray-600" {
+                @if let Some(expires_at) = code.expires_at { (formatting::format_datetime(expires_at)) } @else { "Never" }
+            }
+            td class="py-2 px-2 text-center" {
+                form method="post" action=(paths::helpers::toggle_discount_path(code.discount_code_id)) {
+                    @if code.is_active {
+                        span class="text-green-600" { "Active " }
+                        button type="submit" class="text-red-600 hover:text-red-700" { "Deactivate" }
+                    } @else {
+                        span class="text-gray-600" { "Inactive " }
+                        button type="submit" class="text-indigo-600 hover:text-indigo-700" { "Activate" }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,7 +1,6 @@
 use crate::{
     auth::CurrentUser,
     flash::FlashMessage,
-    formatting,
     models::admin::AdminStats,
     paths,
     views::{components::admin::stats_card, layout::base::base_layout},
@@ -18,13 +17,38 @@ pub fn home(
         div class="max-w-6xl mx-auto" {
             h1 class="text-xl mb-6" { "Admin Dashboard" }
 
-            div class="grid grid-cols-4 gap-4 mb-8" {
+            div class="grid grid-cols-5 gap-4 mb-8" {
                 (stats_card("Total Users", &stats.total_users.to_string()))
                 (stats_card("Total Orders", &stats.total_orders.to_string()))
-                (stats_card("Total Revenue", &format!("₩{}", formatting::format_price(stats.total_revenue))))
+                (stats_card("Total Revenue", &stats.total_revenue.to_string()))
+                (stats_card("Net Revenue (excl. VAT)", &stats.net_revenue.to_string()))
                 (stats_card("Orders (7d)", &stats.orders_last_7_days.to_string()))
             }
 
+            @if !stats.products.is_empty() {
+                div class="mb-8 border p-4" {
+                    h2 class="text-lg mb-3" { "Revenue by Product" }
+                    table class="w-full text-sm" {
+                        thead class="border-b" {
+                            tr {
+                                th class="text-left py-2 px-2" { "Product" }
+                                th class="text-right py-2 px-2" { "Sold" }
+                                th class="text-right py-2 px-2" { "Revenue" }
+                            }
+                        }
+                        tbody {
+                            @for product in &stats.products {
+                                tr class="border-b" {
+                                    td class="py-2 px-2" { (product.product_name) }
+                                    td class="py-2 px-2 text-right" { (product.quantity) }
+                                    td class="py-2 px-2 text-right" { (product.revenue) }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
             div class="space-y-2" {
                 h2 class="text-lg mb-3" { "Quick Links" }
                 div {
@@ -41,6 +65,41 @@ pub fn home(
                         "View All Orders"
                     }
                 }
+                div {
+                    a href=(paths::pages::admin::INQUIRIES)
+                        class="text-indigo-600 hover:underline"
+                    {
+                        "Contact Inquiries"
+                    }
+                }
+                div {
+                    a href=(paths::pages::admin::SUPPRESSIONS)
+                        class="text-indigo-600 hover:underline"
+                    {
+                        "Email Suppressions"
+                    }
+                }
+                div {
+                    a href=(paths::pages::admin::PRICING)
+                        class="text-indigo-600 hover:underline"
+                    {
+                        "Pricing"
+                    }
+                }
+                div {
+                    a href=(paths::pages::admin::DISCOUNTS)
+                        class="text-indigo-600 hover:underline"
+                    {
+                        "Discount Codes"
+                    }
+                }
+                div {
+                    a href=(paths::pages::admin::RECONCILIATION)
+                        class="text-indigo-600 hover:underline"
+                    {
+                        "Payment Reconciliation"
+                    }
+                }
             }
         }
     };
@@ -0,0 +1,118 @@
+use crate::{
+    auth::CurrentUser,
+    flash::FlashMessage,
+    formatting,
+    models::{admin::PaginatedResult, contact::{InquiryListItem, InquiryStatus}},
+    paths,
+    views::{components::admin::pagination, layout::base::base_layout},
+};
+use maud::{html, Markup};
+
+const PREVIEW_LENGTH: usize = 80;
+
+pub fn inquiries(
+    current_user: &CurrentUser,
+    flash: Option<&FlashMessage>,
+    site_name: &str,
+    paginated: PaginatedResult<InquiryListItem>,
+    filter: Option<InquiryStatus>,
+    quarantined_count: i64,
+) -> Markup {
+    let content = html! {
+        div class="max-w-6xl mx-auto" {
+            div class="flex items-center justify-between mb-6" {
+                h1 class="text-xl" { "Contact Inquiries" }
+                a href=(paths::pages::admin::QUARANTINE) class="text-indigo-600 hover:underline text-sm" {
+                    "Quarantine (" (quarantined_count) ")"
+                }
+            }
+
+            div class="flex gap-4 mb-4 text-sm" {
+                (filter_tab("All", paths::pages::admin::INQUIRIES, filter.is_none()))
+                @for status in InquiryStatus::ALL {
+                    (filter_tab(status.display_text(), &filter_path(Some(status)), filter == Some(status)))
+                }
+            }
+
+            @if paginated.items.is_empty() {
+                p class="text-gray-500 py-4" { "No inquiries found" }
+            } @else {
+                table class="w-full text-sm" {
+                    thead class="border-b" {
+                        tr {
+                            th class="text-left py-2 px-2" { "From" }
+                            th class="text-left py-2 px-2" { "Message" }
+                            th class="text-center py-2 px-2" { "Status" }
+                            th class="text-left py-2 px-2" { "Assignee" }
+                            th class="text-center py-2 px-2" { "Received" }
+                            th class="text-center py-2 px-2" { "Actions" }
+                        }
+                    }
+                    tbody {
+                        @for inquiry in &paginated.items {
+                            (inquiry_row(inquiry))
+                        }
+                    }
+                }
+
+                (pagination(
+                    &filter_path(filter),
+                    paginated.page,
+                    paginated.total_pages,
+                    paginated.has_prev(),
+                    paginated.has_next(),
+                ))
+            }
+        }
+    };
+
+    base_layout(current_user, flash, site_name, "Contact Inquiries", "Messages from the contact form", content)
+}
+
+fn filter_tab(label: &str, href: &str, is_active: bool) -> Markup {
+    if is_active {
+        html! {
+            span class="border-b-2 border-indigo-600 pb-1" { (label) }
+        }
+    } else {
+        html! {
+            a href=(href) class="text-indigo-600 hover:underline pb-1" { (label) }
+        }
+    }
+}
+
+fn filter_path(filter: Option<InquiryStatus>) -> String {
+    match filter {
+        Some(status) => paths::with_query_param(paths::pages::admin::INQUIRIES, "status", status.as_str()),
+        None => paths::pages::admin::INQUIRIES.to_string(),
+    }
+}
+
+fn inquiry_row(inquiry: &InquiryListItem) -> Markup {
+    let preview: String = inquiry.message.chars().take(PREVIEW_LENGTH).collect();
+    let truncated = inquiry.message.chars().count() > PREVIEW_LENGTH;
+
+    html! {
+        tr class="border-b" {
+            td class="py-2 px-2" { (inquiry.email) }
+            td class="py-2 px-2 text-gray-600" {
+                (preview)
+                @if truncated { "…" }
+            }
+            td class="py-2 px-2 text-center" {
+                span class={"px-2 py-1 text-xs " (inquiry.status.css_class())} {
+                    (inquiry.status.display_text())
+                }
+            }
+            td class="py-2 px-2 text-gray-600" { (inquiry.assignee_email.as_deref().unwrap_or("—")) }
+            td class="py-2 px-2 text-center text-gray-600" { (formatting::format_datetime(inquiry.created_at)) }
+            td class="py-2 px-2 text-center" {
+                a href=(paths::helpers::inquiry_detail_path(inquiry.inquiry_id))
+                    class="text-indigo-600 hover:underline text-sm"
+                {
+                    "View"
+                }
+            }
+        }
+    }
+}
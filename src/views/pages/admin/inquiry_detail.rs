@@ -0,0 +1,173 @@
+use crate::{
+    auth::CurrentUser,
+    flash::FlashMessage,
+    formatting,
+    models::{
+        admin::AdminUser,
+        contact::{ContactInquiry, InquiryEntry, InquiryEntryKind, InquiryStatus},
+    },
+    paths,
+    views::layout::base::base_layout,
+};
+use maud::{html, Markup};
+
+pub fn inquiry_detail(
+    current_user: &CurrentUser,
+    flash: Option<&FlashMessage>,
+    site_name: &str,
+    inquiry: ContactInquiry,
+    entries: Vec<InquiryEntry>,
+    admins: Vec<AdminUser>,
+) -> Markup {
+    let form_path = |path: &str| paths::with_param(path, "inquiry_id", &inquiry.inquiry_id);
+
+    let content = html! {
+        div class="max-w-6xl mx-auto" {
+            div class="mb-4" {
+                a href=(paths::pages::admin::INQUIRIES)
+                    class="text-indigo-600 hover:underline text-sm"
+                {
+                    "← Back to Inquiries"
+                }
+            }
+
+            h1 class="text-xl mb-6" { "Inquiry #" (inquiry.inquiry_id) }
+
+            @if inquiry.quarantined_at.is_some() {
+                div class="mb-8 border border-yellow-400 bg-yellow-50 p-4 text-sm" {
+                    p class="mb-2" {
+                        strong { "Quarantined as suspected spam" }
+                        " (score " (inquiry.spam_score) "). It was not emailed and is hidden from the inbox."
+                    }
+                    ul class="list-disc ml-5 mb-3 text-gray-700" {
+                        @for reason in &inquiry.spam_reasons {
+                            li { (reason) }
+                        }
+                    }
+                    form method="post" action=(form_path(paths::forms::admin::INQUIRY_RELEASE)) {
+                        button type="submit" class="text-indigo-600 hover:underline" { "Not spam — move to inbox" }
+                    }
+                }
+            }
+
+            div class="mb-8 border p-4" {
+                h2 class="text-lg mb-3" { "Inquiry Information" }
+                div class="space-y-2 text-sm" {
+                    div {
+                        span class="text-gray-600" { "From: " }
+                        @if let Some(user_id) = inquiry.user_id {
+                            a href=(paths::helpers::user_detail_path(user_id))
+                                class="text-indigo-600 hover:underline"
+                            {
+                                (inquiry.email)
+                            }
+                        } @else {
+                            span { (inquiry.email) " (guest)" }
+                        }
+                    }
+                    div {
+                        span class="text-gray-600" { "Status: " }
+                        span class={"px-2 py-1 text-xs " (inquiry.status.css_class())} {
+                            (inquiry.status.display_text())
+                        }
+                    }
+                    div {
+                        span class="text-gray-600" { "Assignee: " }
+                        span { (inquiry.assignee_email.as_deref().unwrap_or("Unassigned")) }
+                    }
+                    div {
+                        span class="text-gray-600" { "Received: " }
+                        span { (formatting::format_datetime(inquiry.created_at)) }
+                    }
+                    div {
+                        span class="text-gray-600" { "Last Updated: " }
+                        span { (formatting::format_datetime(inquiry.updated_at)) }
+                    }
+                }
+
+                div class="flex gap-6 mt-4 text-sm" {
+                    form method="post" action=(form_path(paths::forms::admin::INQUIRY_STATUS)) class="flex gap-2" {
+                        select name="status" class="border px-2 py-1" {
+                            @for status in InquiryStatus::ALL {
+                                option value=(status.as_str()) selected[status == inquiry.status] {
+                                    (status.display_text())
+                                }
+                            }
+                        }
+                        button type="submit" class="text-indigo-600 hover:underline" { "Update Status" }
+                    }
+                    form method="post" action=(form_path(paths::forms::admin::INQUIRY_ASSIGN)) class="flex gap-2" {
+                        select name="assigned_to" class="border px-2 py-1" {
+                            option value="" selected[inquiry.assigned_to.is_none()] { "Unassigned" }
+                            @for admin in &admins {
+                                option value=(admin.user_id) selected[inquiry.assigned_to == Some(admin.user_id)] {
+                                    (admin.email)
+                                }
+                            }
+                        }
+                        button type="submit" class="text-indigo-600 hover:underline" { "Assign" }
+                    }
+                }
+            }
+
+            div class="mb-8 border p-4" {
+                h2 class="text-lg mb-3" { "Thread" }
+                div class="space-y-4 text-sm" {
+                    (thread_item("Message", &inquiry.email, inquiry.created_at, &inquiry.message, "bg-gray-50"))
+                    @for entry in &entries {
+                        @let author = entry.author_email.as_deref().unwrap_or("Deleted user");
+                        @match entry.kind {
+                            InquiryEntryKind::Reply => (thread_item("Reply", author, entry.created_at, &entry.body, "bg-indigo-50")),
+                            InquiryEntryKind::Note => (thread_item("Internal note", author, entry.created_at, &entry.body, "bg-yellow-50")),
+                        }
+                    }
+                }
+            }
+
+            div class="grid grid-cols-2 gap-4" {
+                div class="border p-4" {
+                    h2 class="text-lg mb-3" { "Reply by Email" }
+                    form method="post" action=(form_path(paths::forms::admin::INQUIRY_REPLY)) class="space-y-2" {
+                        textarea name="body" rows="6" required
+                            class="w-full px-3 py-2 border focus:outline-none focus:border-indigo-600"
+                            placeholder={"Reply to " (inquiry.email)} {}
+                        button type="submit" class="bg-indigo-600 text-white px-3 py-2 hover:bg-indigo-700 text-sm" {
+                            "Send Reply"
+                        }
+                    }
+                }
+                div class="border p-4" {
+                    h2 class="text-lg mb-3" { "Internal Note" }
+                    form method="post" action=(form_path(paths::forms::admin::INQUIRY_NOTE)) class="space-y-2" {
+                        textarea name="body" rows="6" required
+                            class="w-full px-3 py-2 border focus:outline-none focus:border-indigo-600"
+                            placeholder="Visible to admins only" {}
+                        button type="submit" class="border px-3 py-2 hover:bg-gray-50 text-sm" {
+                            "Add Note"
+                        }
+                    }
+                }
+            }
+        }
+    };
+
+    base_layout(
+        current_user,
+        flash,
+        site_name,
+        "Inquiry Details",
+        &format!("Contact inquiry from {}", inquiry.email),
+        content,
+    )
+}
+
+fn thread_item(label: &str, author: &str, at: time::OffsetDateTime, body: &str, background: &str) -> Markup {
+    html! {
+        div class={"p-3 " (background)} {
+            div class="text-xs text-gray-600 mb-1" {
+                strong { (label) } " · " (author) " · " (formatting::format_datetime(at))
+            }
+            div class="whitespace-pre-wrap" { (body) }
+        }
+    }
+}
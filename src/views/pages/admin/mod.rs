@@ -1,11 +1,25 @@
+mod discounts;
 mod home;
+mod inquiries;
+mod inquiry_detail;
 mod orders;
+mod quarantine;
 mod order_detail;
+mod pricing;
+mod reconciliation;
+mod suppressions;
 mod users;
 mod user_detail;
 
+pub use discounts::discounts;
 pub use home::home;
+pub use inquiries::inquiries;
+pub use inquiry_detail::inquiry_detail;
 pub use orders::orders;
+pub use quarantine::quarantine;
 pub use order_detail::order_detail;
+pub use pricing::pricing;
+pub use reconciliation::reconciliation;
+pub use suppressions::suppressions;
 pub use users::users;
 pub use user_detail::user_detail;
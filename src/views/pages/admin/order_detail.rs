@@ -2,9 +2,14 @@ use crate::{
     auth::CurrentUser,
     flash::FlashMessage,
     formatting,
-    models::admin::OrderDetail,
+    models::{
+        admin::{OrderDetail, OrderPaymentActivity, OrderRefunds},
+        order::OrderEvent,
+        order_item::{OrderItem, OrderItemMetadata},
+        payment_attempt::PaymentAttempt,
+    },
     paths,
-    views::layout::base::base_layout,
+    views::{components::payment::cash_receipt_summary, layout::base::base_layout},
 };
 use maud::{html, Markup};
 
@@ -13,7 +18,14 @@ pub fn order_detail(
     flash: Option<&FlashMessage>,
     site_name: &str,
     order: OrderDetail,
+    refunds: OrderRefunds,
+    events: Vec<OrderEvent>,
+    payment: OrderPaymentActivity,
 ) -> Markup {
+    let attempts = payment.attempts;
+    let OrderRefunds { refunds, refundable } = refunds;
+    let refund_path = paths::with_param(paths::forms::admin::REFUND_ORDER, "order_id", &order.order_id);
+
     let content = html! {
         div class="max-w-6xl mx-auto" {
             div class="mb-4" {
@@ -43,9 +55,38 @@ pub fn order_detail(
                             (order.payment_status.display_text())
                         }
                     }
+                    div {
+                        span class="text-gray-600" { "Payment Method: " }
+                        span { (order.payment_method.map_or("—", |method| method.display_text())) }
+                    }
+                    @if order.discount_amount.is_positive() || order.discount_code.is_some() {
+                        div {
+                            span class="text-gray-600" { "List Price: " }
+                            span { (order.list_price_amount) }
+                        }
+                        div {
+                            span class="text-gray-600" { "Discount: " }
+                            span { (order.discount_amount) }
+                            @if let Some(code) = &order.discount_code {
+                                span class="font-mono text-xs" { " (" (code) ")" }
+                            }
+                        }
+                    }
                     div {
                         span class="text-gray-600" { "Amount: " }
-                        span { "₩" (formatting::format_price(order.price_amount)) }
+                        span { (order.price_amount) }
+                        span class="text-gray-600" {
+                            " (supply " (order.net_amount)
+                            " + VAT " (order.tax_rate_percent) "% " (order.tax_amount) ")"
+                        }
+                    }
+                    div {
+                        span class="text-gray-600" { "Priced with: " }
+                        a href=(format!("{}#v{}", paths::pages::admin::PRICING, order.pricing_rule_id))
+                            class="text-indigo-600 hover:underline"
+                        {
+                            "v" (order.pricing_rule_id)
+                        }
                     }
                     div {
                         span class="text-gray-600" { "Created: " }
@@ -78,7 +119,7 @@ pub fn order_detail(
                 }
             }
 
-            @if order.payment_key.is_some() {
+            @if order.payment_key.is_some() || !attempts.is_empty() || payment.cash_receipt.is_some() {
                 div class="mb-8 border p-4" {
                     h2 class="text-lg mb-3" { "Payment Information" }
                     div class="space-y-2 text-sm" {
@@ -89,23 +130,107 @@ pub fn order_detail(
                             }
                         }
                     }
+                    @if let Some(receipt) = &payment.cash_receipt {
+                        div class="mt-4 max-w-sm" {
+                            (cash_receipt_summary(receipt))
+                        }
+                    }
+                    @if !attempts.is_empty() {
+                        h3 class="mt-4 mb-2" { "Confirmation Attempts" }
+                        table class="w-full text-sm" {
+                            thead class="border-b" {
+                                tr {
+                                    th class="text-left py-2 px-2" { "Started" }
+                                    th class="text-left py-2 px-2" { "Payment Key" }
+                                    th class="text-right py-2 px-2" { "Amount" }
+                                    th class="text-left py-2 px-2" { "Result" }
+                                    th class="text-left py-2 px-2" { "Gateway Response" }
+                                }
+                            }
+                            tbody {
+                                @for attempt in &attempts {
+                                    (attempt_row(attempt))
+                                }
+                            }
+                        }
+                    }
                 }
             }
 
-            div class="border p-4" {
-                h2 class="text-lg mb-3" { "File Information" }
-                div class="space-y-2 text-sm" {
-                    div {
-                        span class="text-gray-600" { "Filename: " }
-                        span { (order.filename) }
+            @if !refunds.is_empty() || refundable.is_positive() {
+                div class="mb-8 border p-4" {
+                    h2 class="text-lg mb-3" { "Refunds" }
+                    @if !refunds.is_empty() {
+                        table class="w-full text-sm mb-4" {
+                            thead class="border-b" {
+                                tr {
+                                    th class="text-left py-2 px-2" { "Date" }
+                                    th class="text-right py-2 px-2" { "Amount" }
+                                    th class="text-left py-2 px-2" { "Reason" }
+                                    th class="text-left py-2 px-2" { "By" }
+                                }
+                            }
+                            tbody {
+                                @for refund in &refunds {
+                                    tr class="border-b" {
+                                        td class="py-2 px-2 text-gray-600" { (formatting::format_datetime(refund.created_at)) }
+                                        td class="py-2 px-2 text-right" { (refund.amount) }
+                                        td class="py-2 px-2" { (refund.reason) }
+                                        td class="py-2 px-2 text-gray-600" { (refund.refunded_by_email.as_deref().unwrap_or("Payment gateway")) }
+                                    }
+                                }
+                            }
+                        }
                     }
-                    div {
-                        span class="text-gray-600" { "Character Count: " }
-                        span { (order.text_length) }
+                    @if refundable.is_positive() {
+                        form method="post" action=(refund_path) class="space-y-2 text-sm" {
+                            div class="flex items-center gap-2" {
+                                label for="refund-amount" class="text-gray-600" { "Amount (₩)" }
+                                input id="refund-amount" type="number" name="amount" min="1" max=(refundable.amount) value=(refundable.amount) required
+                                    class="w-32 px-3 py-2 border focus:outline-none focus:border-indigo-600";
+                                span class="text-gray-600" { "of " (refundable) " refundable" }
+                            }
+                            textarea name="reason" rows="3" required
+                                class="w-full px-3 py-2 border focus:outline-none focus:border-indigo-600"
+                                placeholder="Reason (required)" {}
+                            button type="submit" class="bg-red-600 text-white px-3 py-2 hover:bg-red-700"
+                                onclick="return confirm('Refund this amount? This cannot be undone.')" {
+                                "Refund"
+                            }
+                        }
                     }
-                    div {
-                        span class="text-gray-600" { "Price Calculation: " }
-                        span { (order.text_length) " characters × ₩1 = ₩" (formatting::format_price(order.price_amount)) }
+                }
+            }
+
+            div class="mb-8 border p-4" {
+                h2 class="text-lg mb-3" { "Timeline" }
+                @if events.is_empty() {
+                    p class="text-gray-500 text-sm" { "No recorded events" }
+                } @else {
+                    ol class="border-l ml-2 space-y-3 text-sm" {
+                        @for event in &events {
+                            (timeline_entry(event))
+                        }
+                    }
+                }
+            }
+
+            div class="border p-4" {
+                h2 class="text-lg mb-3" { "Items" }
+                table class="w-full text-sm" {
+                    thead class="border-b" {
+                        tr {
+                            th class="text-left py-2 px-2" { "Product" }
+                            th class="text-left py-2 px-2" { "Details" }
+                            th class="text-right py-2 px-2" { "Quantity" }
+                            th class="text-right py-2 px-2" { "Unit Price" }
+                            th class="text-right py-2 px-2" { "Amount" }
+                        }
+                    }
+                    tbody {
+                        @for item in order.items.iter() {
+                            (item_row(item))
+                        }
                     }
                 }
             }
@@ -121,3 +246,72 @@ pub fn order_detail(
         content,
     )
 }
+
+fn timeline_entry(event: &OrderEvent) -> Markup {
+    html! {
+        li class="pl-4" {
+            div {
+                @match event.from_status {
+                    Some(from) => {
+                        span class=(from.css_class()) { (from.display_text()) }
+                        " → "
+                    }
+                    None => { "Created as " }
+                }
+                span class=(event.to_status.css_class()) { (event.to_status.display_text()) }
+            }
+            div class="text-xs text-gray-500" {
+                (formatting::format_datetime(event.created_at))
+                " · " (event.source.display_text())
+                @if let Some(actor) = &event.actor_email { " · " (actor) }
+                @if let Some(payment_key) = &event.payment_key {
+                    " · " span class="font-mono" { (payment_key) }
+                }
+            }
+            @if let Some(note) = &event.note {
+                div class="text-gray-600" { (note) }
+            }
+        }
+    }
+}
+
+fn item_row(item: &OrderItem) -> Markup {
+    html! {
+        tr class="border-b" {
+            td class="py-2 px-2" {
+                (item.product_name)
+                span class="font-mono text-xs text-gray-500" { " " (item.product_code) }
+            }
+            td class="py-2 px-2 text-gray-600" {
+                @match &item.metadata {
+                    OrderItemMetadata::TextAnalysis(analysis) => {
+                        (analysis.filename) " · " (analysis.text_length) " characters"
+                    }
+                }
+            }
+            td class="py-2 px-2 text-right" { (item.quantity) }
+            td class="py-2 px-2 text-right" { (item.unit_price_amount) }
+            td class="py-2 px-2 text-right" { @if let Some(amount) = item.amount() { (amount) } }
+        }
+    }
+}
+
+fn attempt_row(attempt: &PaymentAttempt) -> Markup {
+    html! {
+        tr class="border-b" {
+            td class="py-2 px-2 text-gray-600" { (formatting::format_datetime(attempt.created_at)) }
+            td class="py-2 px-2 font-mono text-xs" { (attempt.payment_key) }
+            td class="py-2 px-2 text-right" { (attempt.amount) }
+            td class={"py-2 px-2 " (attempt.status.css_class())} {
+                (attempt.status.display_text())
+                @if let Some(completed_at) = attempt.completed_at {
+                    span class="text-xs text-gray-500" { " · " (formatting::format_datetime(completed_at)) }
+                }
+            }
+            td class="py-2 px-2 text-gray-600" {
+                @if let Some(code) = &attempt.gateway_code { span class="font-mono text-xs" { (code) } " " }
+                @if let Some(message) = &attempt.gateway_message { (message) }
+            }
+        }
+    }
+}
@@ -3,28 +3,46 @@ use crate::{
     flash::FlashMessage,
     formatting,
     models::admin::{OrderListItem, PaginatedResult},
-    models::order::PaymentStatus,
+    models::{order::PaymentStatus, payment_method::PaymentMethod},
     paths,
     views::{components::admin::pagination, layout::base::base_layout},
 };
 use maud::{html, Markup};
 
+/// Statuses with a filter tab, in display order.
+const STATUS_TABS: [PaymentStatus; 6] = [
+    PaymentStatus::Paid,
+    PaymentStatus::Pending,
+    PaymentStatus::AwaitingDeposit,
+    PaymentStatus::Failed,
+    PaymentStatus::Refunded,
+    PaymentStatus::PartiallyRefunded,
+];
+
 pub fn orders(
     current_user: &CurrentUser,
     flash: Option<&FlashMessage>,
     site_name: &str,
     paginated: PaginatedResult<OrderListItem>,
     filter: Option<PaymentStatus>,
+    method_filter: Option<PaymentMethod>,
 ) -> Markup {
     let content = html! {
         div class="max-w-6xl mx-auto" {
             h1 class="text-xl mb-6" { "Orders" }
 
-            div class="flex gap-4 mb-4 text-sm" {
-                (filter_tab("All", paths::pages::admin::ORDERS, filter.is_none()))
-                (filter_tab("Paid", &paths::with_query_param(paths::pages::admin::ORDERS, "status", "paid"), matches!(filter, Some(PaymentStatus::Paid))))
-                (filter_tab("Pending", &paths::with_query_param(paths::pages::admin::ORDERS, "status", "pending"), matches!(filter, Some(PaymentStatus::Pending))))
-                (filter_tab("Failed", &paths::with_query_param(paths::pages::admin::ORDERS, "status", "failed"), matches!(filter, Some(PaymentStatus::Failed))))
+            div class="flex gap-4 mb-2 text-sm" {
+                (filter_tab("All", &filter_path(None, method_filter), filter.is_none()))
+                @for status in STATUS_TABS {
+                    (filter_tab(status.display_text(), &filter_path(Some(status), method_filter), filter == Some(status)))
+                }
+            }
+
+            div class="flex flex-wrap gap-4 mb-4 text-sm" {
+                (filter_tab("All methods", &filter_path(filter, None), method_filter.is_none()))
+                @for method in PaymentMethod::ALL {
+                    (filter_tab(method.display_text(), &filter_path(filter, Some(method)), method_filter == Some(method)))
+                }
             }
 
             @if paginated.items.is_empty() {
@@ -36,6 +54,7 @@ pub fn orders(
                             th class="text-left py-2 px-2" { "Order #" }
                             th class="text-left py-2 px-2" { "User" }
                             th class="text-right py-2 px-2" { "Amount" }
+                            th class="text-center py-2 px-2" { "Method" }
                             th class="text-center py-2 px-2" { "Status" }
                             th class="text-center py-2 px-2" { "Date" }
                             th class="text-center py-2 px-2" { "Actions" }
@@ -49,7 +68,7 @@ pub fn orders(
                 }
 
                 (pagination(
-                    &filter_path(filter),
+                    &filter_path(filter, method_filter),
                     paginated.page,
                     paginated.total_pages,
                     paginated.has_prev(),
@@ -74,11 +93,15 @@ fn filter_tab(label: &str, href: &str, is_active: bool) -> Markup {
     }
 }
 
-fn filter_path(filter: Option<PaymentStatus>) -> String {
-    match filter {
-        Some(status) => paths::with_query_param(paths::pages::admin::ORDERS, "status", status.as_str()),
-        None => paths::pages::admin::ORDERS.to_string(),
+fn filter_path(filter: Option<PaymentStatus>, method_filter: Option<PaymentMethod>) -> String {
+    let mut path = paths::pages::admin::ORDERS.to_string();
+    if let Some(status) = filter {
+        path = paths::with_query_param(&path, "status", status.as_str());
+    }
+    if let Some(method) = method_filter {
+        path = paths::with_query_param(&path, "method", method.as_str());
     }
+    path
 }
 
 fn order_row(order: &OrderListItem) -> Markup {
@@ -90,7 +113,10 @@ fn order_row(order: &OrderListItem) -> Markup {
         tr class="border-b" {
             td class="py-2 px-2" { (order.order_number) }
             td class="py-2 px-2 text-gray-600" { (order.user_email) }
-            td class="py-2 px-2 text-right" { "₩" (order.price_amount) }
+            td class="py-2 px-2 text-right" { (order.price_amount) }
+            td class="py-2 px-2 text-center text-gray-600" {
+                (order.payment_method.map_or("—", |method| method.display_text()))
+            }
             td class="py-2 px-2 text-center" {
                 span class={"px-2 py-1 text-xs " (status_class)} {
                     (status_text)
@@ -0,0 +1,133 @@
+use time::OffsetDateTime;
+
+use crate::{
+    auth::CurrentUser,
+    flash::FlashMessage,
+    formatting,
+    models::pricing::PricingRule,
+    money::Money,
+    paths,
+    views::layout::base::base_layout,
+};
+use maud::{html, Markup};
+
+/// Rule versions, newest first, with each version's price for `preview_characters`.
+pub fn pricing(
+    current_user: &CurrentUser,
+    flash: Option<&FlashMessage>,
+    site_name: &str,
+    rules: Vec<PricingRule>,
+    preview_characters: i32,
+) -> Markup {
+    let now = OffsetDateTime::now_utc();
+    let active_rule_id = rules
+        .iter()
+        .find(|rule| rule.effective_from <= now)
+        .map(|rule| rule.pricing_rule_id);
+
+    let content = html! {
+        div class="max-w-6xl mx-auto" {
+            h1 class="text-xl mb-6" { "Pricing" }
+
+            form method="get" action=(paths::pages::admin::PRICING) class="flex items-end gap-3 border p-4 mb-8 text-sm" {
+                label class="flex flex-col gap-1" {
+                    span class="text-gray-600" { "Preview price for characters" }
+                    input type="number" name="characters" min="0" value=(preview_characters) class="border px-2 py-1";
+                }
+                button type="submit" class="px-3 py-1 border hover:bg-gray-50" { "Calculate" }
+                @if let Some(rule) = rules.iter().find(|rule| Some(rule.pricing_rule_id) == active_rule_id) {
+                    p class="ml-auto" {
+                        "Current price: "
+                        span class="text-lg text-indigo-600" { (rule.price_for(preview_characters)) }
+                    }
+                }
+            }
+
+            form method="post" action=(paths::forms::admin::CREATE_PRICING_RULE) class="border p-4 mb-8" {
+                h2 class="text-lg mb-3" { "New Version" }
+                div class="grid grid-cols-4 gap-3 text-sm" {
+                    label class="flex flex-col gap-1" {
+                        span class="text-gray-600" { "₩ per 1,000 characters" }
+                        input type="number" name="price_per_thousand" min="0" required class="border px-2 py-1";
+                    }
+                    label class="flex flex-col gap-1" {
+                        span class="text-gray-600" { "Minimum order (₩)" }
+                        input type="number" name="minimum_amount" min="0" required class="border px-2 py-1";
+                    }
+                    label class="flex flex-col gap-1" {
+                        span class="text-gray-600" { "Effective from (UTC)" }
+                        input type="date" name="effective_on" class="border px-2 py-1";
+                    }
+                    label class="flex flex-col gap-1" {
+                        span class="text-gray-600" { "Note" }
+                        input type="text" name="note" class="border px-2 py-1";
+                    }
+                    label class="flex flex-col gap-1 col-span-3" {
+                        span class="text-gray-600" { "Volume tiers: one per line, characters from = ₩ per 1,000" }
+                        textarea name="tiers" rows="3" placeholder="50000 = 800" class="border px-2 py-1 font-mono" {}
+                    }
+                    div class="flex items-end" {
+                        button type="submit" class="px-3 py-1 bg-indigo-600 text-white hover:bg-indigo-700" {
+                            "Save Version"
+                        }
+                    }
+                }
+                p class="text-xs text-gray-500 mt-2" {
+                    "Leave the date empty to apply the version immediately. Existing quotes keep the version they were priced with."
+                }
+            }
+
+            table class="w-full text-sm" {
+                thead class="border-b" {
+                    tr {
+                        th class="text-left py-2 px-2" { "Version" }
+                        th class="text-left py-2 px-2" { "Effective From" }
+                        th class="text-left py-2 px-2" { "Rate" }
+                        th class="text-left py-2 px-2" { "Tiers" }
+                        th class="text-left py-2 px-2" { "Note" }
+                        th class="text-right py-2 px-2" { "Price for " (formatting::format_number(preview_characters)) " chars" }
+                        th class="text-center py-2 px-2" { "Status" }
+                    }
+                }
+                tbody {
+                    @for rule in &rules {
+                        (rule_row(rule, preview_characters, now, active_rule_id))
+                    }
+                }
+            }
+        }
+    };
+
+    base_layout(current_user, flash, site_name, "Pricing", "Price list versions", content)
+}
+
+fn rule_row(rule: &PricingRule, preview_characters: i32, now: OffsetDateTime, active_rule_id: Option<i32>) -> Markup {
+    html! {
+        tr class="border-b" id=(rule.label()) {
+            td class="py-2 px-2" { (rule.label()) }
+            td class="py-2 px-2 text-gray-600" { (formatting::format_datetime(rule.effective_from)) }
+            td class="py-2 px-2" { (rule.describe()) }
+            td class="py-2 px-2" {
+                @for tier in &rule.tiers {
+                    div {
+                        "from " (formatting::format_number(tier.starts_at)) ": " (Money::krw(i64::from(tier.price_per_thousand)))
+                    }
+                }
+            }
+            td class="py-2 px-2 text-gray-600" {
+                (rule.note.as_deref().unwrap_or(""))
+                div class="text-xs" { "Saved " (formatting::format_datetime(rule.created_at)) }
+            }
+            td class="py-2 px-2 text-right" { (rule.price_for(preview_characters)) }
+            td class="py-2 px-2 text-center" {
+                @if Some(rule.pricing_rule_id) == active_rule_id {
+                    span class="text-green-600" { "Active" }
+                } @else if rule.effective_from > now {
+                    span class="text-yellow-600" { "Scheduled" }
+                } @else {
+                    span class="text-gray-500" { "Superseded" }
+                }
+            }
+        }
+    }
+}
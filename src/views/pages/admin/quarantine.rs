@@ -0,0 +1,99 @@
+use crate::{
+    auth::CurrentUser,
+    flash::FlashMessage,
+    formatting,
+    models::{admin::PaginatedResult, contact::QuarantinedInquiry},
+    paths,
+    views::{components::admin::pagination, layout::base::base_layout},
+};
+use maud::{html, Markup};
+
+pub fn quarantine(
+    current_user: &CurrentUser,
+    flash: Option<&FlashMessage>,
+    site_name: &str,
+    paginated: PaginatedResult<QuarantinedInquiry>,
+) -> Markup {
+    let content = html! {
+        div class="max-w-6xl mx-auto" {
+            div class="mb-4" {
+                a href=(paths::pages::admin::INQUIRIES)
+                    class="text-indigo-600 hover:underline text-sm"
+                {
+                    "← Back to Inquiries"
+                }
+            }
+
+            h1 class="text-xl mb-2" { "Quarantined Inquiries" }
+            p class="text-sm text-gray-600 mb-6" {
+                "Contact form submissions that looked like spam. They were not emailed; release one to move it to the inbox."
+            }
+
+            @if paginated.items.is_empty() {
+                p class="text-gray-500 py-4" { "Nothing in quarantine" }
+            } @else {
+                table class="w-full text-sm" {
+                    thead class="border-b" {
+                        tr {
+                            th class="text-left py-2 px-2" { "From" }
+                            th class="text-left py-2 px-2" { "Message" }
+                            th class="text-center py-2 px-2" { "Score" }
+                            th class="text-left py-2 px-2" { "Reasons" }
+                            th class="text-center py-2 px-2" { "Received" }
+                            th class="text-center py-2 px-2" { "Actions" }
+                        }
+                    }
+                    tbody {
+                        @for inquiry in &paginated.items {
+                            (quarantine_row(inquiry))
+                        }
+                    }
+                }
+
+                (pagination(
+                    paths::pages::admin::QUARANTINE,
+                    paginated.page,
+                    paginated.total_pages,
+                    paginated.has_prev(),
+                    paginated.has_next(),
+                ))
+            }
+        }
+    };
+
+    base_layout(current_user, flash, site_name, "Quarantined Inquiries", "Review suspected spam", content)
+}
+
+fn quarantine_row(inquiry: &QuarantinedInquiry) -> Markup {
+    let row_id = format!("inquiry-{}", inquiry.inquiry_id);
+
+    html! {
+        tr class="border-b align-top" id=(row_id) {
+            td class="py-2 px-2" { (inquiry.email) }
+            td class="py-2 px-2 text-gray-600 whitespace-pre-wrap break-all" { (inquiry.message) }
+            td class="py-2 px-2 text-center" { (inquiry.spam_score) }
+            td class="py-2 px-2 text-gray-600" {
+                ul {
+                    @for reason in &inquiry.spam_reasons {
+                        li { (reason) }
+                    }
+                }
+            }
+            td class="py-2 px-2 text-center text-gray-600" { (formatting::format_datetime(inquiry.created_at)) }
+            td class="py-2 px-2 text-center space-y-1" {
+                form method="post" action=(paths::with_param(paths::forms::admin::INQUIRY_RELEASE, "inquiry_id", &inquiry.inquiry_id)) {
+                    button type="submit" class="text-indigo-600 hover:underline text-sm" { "Not spam" }
+                }
+                button
+                    hx-delete=(paths::with_param(paths::actions::admin::DELETE_INQUIRY, "inquiry_id", &inquiry.inquiry_id))
+                    hx-confirm="Delete this inquiry permanently?"
+                    hx-target={"#" (row_id)}
+                    hx-swap="outerHTML"
+                    class="text-red-600 hover:text-red-700 text-sm"
+                {
+                    "Delete"
+                }
+            }
+        }
+    }
+}
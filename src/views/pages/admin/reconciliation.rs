@@ -0,0 +1,149 @@
+use crate::{
+    auth::CurrentUser,
+    flash::FlashMessage,
+    formatting,
+    models::reconciliation::{ReconciliationFinding, ReconciliationRun},
+    paths,
+    views::layout::base::base_layout,
+};
+use maud::{html, Markup};
+
+pub fn reconciliation(
+    current_user: &CurrentUser,
+    flash: Option<&FlashMessage>,
+    site_name: &str,
+    runs: Vec<ReconciliationRun>,
+    findings: Vec<ReconciliationFinding>,
+) -> Markup {
+    let content = html! {
+        div class="max-w-6xl mx-auto" {
+            div class="flex items-center justify-between mb-6" {
+                h1 class="text-xl" { "Payment Reconciliation" }
+                form method="post" action=(paths::forms::admin::RUN_RECONCILIATION) {
+                    button type="submit" class="px-3 py-1 bg-indigo-600 text-white text-sm hover:bg-indigo-700" {
+                        "Run Now"
+                    }
+                }
+            }
+
+            div class="mb-8" {
+                h2 class="text-lg mb-3" { "Open Findings" }
+                @if findings.is_empty() {
+                    p class="text-gray-500 py-4" { "Orders match the payment gateway" }
+                } @else {
+                    table class="w-full text-sm" {
+                        thead class="border-b" {
+                            tr {
+                                th class="text-left py-2 px-2" { "Order" }
+                                th class="text-left py-2 px-2" { "Issue" }
+                                th class="text-left py-2 px-2" { "Here" }
+                                th class="text-left py-2 px-2" { "Gateway" }
+                                th class="text-center py-2 px-2" { "Found" }
+                                th class="text-center py-2 px-2" { "Actions" }
+                            }
+                        }
+                        tbody {
+                            @for finding in &findings {
+                                (finding_row(finding))
+                            }
+                        }
+                    }
+                }
+            }
+
+            div {
+                h2 class="text-lg mb-3" { "Recent Runs" }
+                @if runs.is_empty() {
+                    p class="text-gray-500 py-4" { "No reconciliation has run yet" }
+                } @else {
+                    table class="w-full text-sm" {
+                        thead class="border-b" {
+                            tr {
+                                th class="text-left py-2 px-2" { "Run" }
+                                th class="text-left py-2 px-2" { "Started" }
+                                th class="text-left py-2 px-2" { "Orders Created" }
+                                th class="text-right py-2 px-2" { "Checked" }
+                                th class="text-right py-2 px-2" { "New Findings" }
+                                th class="text-right py-2 px-2" { "Errors" }
+                            }
+                        }
+                        tbody {
+                            @for run in &runs {
+                                (run_row(run))
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    };
+
+    base_layout(current_user, flash, site_name, "Payment Reconciliation", "Orders compared with the payment gateway", content)
+}
+
+fn finding_row(finding: &ReconciliationFinding) -> Markup {
+    html! {
+        tr class="border-b" {
+            td class="py-2 px-2" {
+                a href=(paths::helpers::order_detail_path(finding.order_id))
+                    class="text-indigo-600 hover:underline"
+                {
+                    (finding.order_number)
+                }
+            }
+            td class="py-2 px-2" { (finding.kind.display_text()) }
+            td class="py-2 px-2" {
+                span class=(finding.local_status.css_class()) { (finding.local_status.display_text()) }
+                " · " (finding.local_amount)
+            }
+            td class="py-2 px-2" {
+                @if let Some(status) = &finding.gateway_status {
+                    span class="font-mono text-xs" { (status) }
+                    @if let Some(amount) = finding.gateway_amount {
+                        " · " (amount)
+                    }
+                    @if let Some(key) = &finding.gateway_payment_key {
+                        div class="font-mono text-xs text-gray-500" { (key) }
+                    }
+                } @else {
+                    span class="text-gray-500" { "No payment found" }
+                }
+            }
+            td class="py-2 px-2 text-center text-gray-600" { (formatting::format_datetime(finding.created_at)) }
+            td class="py-2 px-2 text-center" {
+                div class="flex justify-center gap-3" {
+                    @if finding.kind.is_fixable() {
+                        form method="post" action=(paths::helpers::fix_finding_path(finding.finding_id)) {
+                            button type="submit" class="text-indigo-600 hover:text-indigo-700 text-sm" {
+                                "Apply gateway status"
+                            }
+                        }
+                    }
+                    form method="post" action=(paths::helpers::dismiss_finding_path(finding.finding_id)) {
+                        button type="submit" class="text-gray-600 hover:text-gray-700 text-sm" { "Mark reviewed" }
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn run_row(run: &ReconciliationRun) -> Markup {
+    html! {
+        tr class="border-b" {
+            td class="py-2 px-2" { "#" (run.run_id) }
+            td class="py-2 px-2 text-gray-600" {
+                (formatting::format_datetime(run.started_at))
+                @if run.finished_at.is_none() {
+                    span class="text-xs text-yellow-600" { " · not finished" }
+                }
+            }
+            td class="py-2 px-2 text-gray-600" {
+                (formatting::format_datetime(run.window_start)) " – " (formatting::format_datetime(run.window_end))
+            }
+            td class="py-2 px-2 text-right" { (run.checked_count) }
+            td class="py-2 px-2 text-right" { (run.finding_count) }
+            td class={"py-2 px-2 text-right" @if run.error_count > 0 { " text-red-600" }} { (run.error_count) }
+        }
+    }
+}
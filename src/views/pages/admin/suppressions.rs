@@ -0,0 +1,89 @@
+use crate::{
+    auth::CurrentUser,
+    flash::FlashMessage,
+    formatting,
+    models::{admin::PaginatedResult, email_suppression::EmailSuppression},
+    paths,
+    views::{components::admin::pagination, layout::base::base_layout},
+};
+use maud::{html, Markup};
+
+pub fn suppressions(
+    current_user: &CurrentUser,
+    flash: Option<&FlashMessage>,
+    site_name: &str,
+    paginated: PaginatedResult<EmailSuppression>,
+) -> Markup {
+    let content = html! {
+        div class="max-w-6xl mx-auto" {
+            h1 class="text-xl mb-6" { "Email Suppressions" }
+
+            form method="post" action=(paths::forms::admin::IMPORT_SUPPRESSIONS) enctype="multipart/form-data"
+                class="flex items-center gap-3 mb-6"
+            {
+                label class="text-sm text-gray-600" for="suppressions-file" {
+                    "Import bounce/complaint CSV (columns: email, reason)"
+                }
+                input id="suppressions-file" type="file" name="file" accept=".csv,text/csv" required class="text-sm";
+                button type="submit" class="px-3 py-1 bg-indigo-600 text-white text-sm hover:bg-indigo-700" {
+                    "Import"
+                }
+            }
+
+            @if paginated.items.is_empty() {
+                p class="text-gray-500 py-4" { "No suppressed addresses" }
+            } @else {
+                table class="w-full text-sm" {
+                    thead class="border-b" {
+                        tr {
+                            th class="text-left py-2 px-2" { "Email" }
+                            th class="text-left py-2 px-2" { "Reason" }
+                            th class="text-left py-2 px-2" { "Details" }
+                            th class="text-center py-2 px-2" { "Added" }
+                            th class="text-center py-2 px-2" { "Actions" }
+                        }
+                    }
+                    tbody {
+                        @for suppression in &paginated.items {
+                            (suppression_row(suppression))
+                        }
+                    }
+                }
+
+                (pagination(
+                    paths::pages::admin::SUPPRESSIONS,
+                    paginated.page,
+                    paginated.total_pages,
+                    paginated.has_prev(),
+                    paginated.has_next(),
+                ))
+            }
+        }
+    };
+
+    base_layout(current_user, flash, site_name, "Email Suppressions", "Addresses that no longer receive notifications", content)
+}
+
+fn suppression_row(suppression: &EmailSuppression) -> Markup {
+    let row_id = format!("suppression-{}", suppression.suppression_id);
+
+    html! {
+        tr class="border-b" id=(row_id) {
+            td class="py-2 px-2" { (suppression.email) }
+            td class="py-2 px-2" { (suppression.reason.display_text()) }
+            td class="py-2 px-2 text-gray-600" { (suppression.details.as_deref().unwrap_or("")) }
+            td class="py-2 px-2 text-center text-gray-600" { (formatting::format_datetime(suppression.created_at)) }
+            td class="py-2 px-2 text-center" {
+                button
+                    hx-delete=(paths::helpers::delete_suppression_path(suppression.suppression_id))
+                    hx-confirm="Allow email to this address again?"
+                    hx-target={"#" (row_id)}
+                    hx-swap="outerHTML"
+                    class="text-red-600 hover:text-red-700 text-sm"
+                {
+                    "Remove"
+                }
+            }
+        }
+    }
+}
@@ -48,11 +48,27 @@ pub fn user_detail(
                     }
                     div {
                         span class="text-gray-600" { "Total Spent: " }
-                        span { "₩" (formatting::format_price(user.total_spent)) }
+                        span { (user.total_spent) }
                     }
                 }
             }
 
+            div class="mb-8 border p-4" {
+                h2 class="text-lg mb-3" { "Credits" }
+                p class="text-sm mb-3" {
+                    span class="text-gray-600" { "Balance: " }
+                    span { (user.credit_balance) }
+                }
+                form method="post"
+                    action=(paths::with_param(paths::forms::admin::ADJUST_CREDITS, "user_id", &user.user_id))
+                    class="flex gap-2 text-sm"
+                {
+                    input type="number" name="amount" required placeholder="Amount (negative to remove)" class="border px-2 py-1 w-56";
+                    input type="text" name="note" required placeholder="Reason" class="flex-1 border px-2 py-1";
+                    button type="submit" class="px-3 py-1 bg-indigo-600 text-white hover:bg-indigo-700" { "Adjust" }
+                }
+            }
+
             div class="mb-8 border p-4" {
                 h2 class="text-lg mb-3" { "Admin Role" }
                 @if user.is_admin {
@@ -144,7 +160,7 @@ fn order_row(order: &OrderListItem) -> Markup {
                     (order.order_number)
                 }
             }
-            td class="py-2 px-2 text-right" { "₩" (order.price_amount) }
+            td class="py-2 px-2 text-right" { (order.price_amount) }
             td class="py-2 px-2 text-center" {
                 span class={"px-2 py-1 text-xs " (status_class)} {
                     (status_text)
@@ -1,91 +1,109 @@
-use crate::{auth::CurrentUser, constants::{cdn, payment}, flash::FlashMessage, formatting::format_price, models::order::Order, paths, views::layout::base};
-use maud::{Markup, PreEscaped, html};
+use crate::{
+    auth::CurrentUser,
+    config::PaymentProvider,
+    flash::FlashMessage,
+    models::{
+        cash_receipt::{CashReceipt, CashReceiptType},
+        order::Order,
+        payment_method::PaymentMethod,
+    },
+    paths,
+    views::{
+        components::payment::{order_items, payment_button, tax_breakdown, PaymentRequest},
+        layout::base,
+    },
+};
+use maud::{Markup, html};
 
 pub fn checkout(
     current_user: &CurrentUser,
     flash: Option<&FlashMessage>,
     site_name: &str,
     order: &Order,
-    client_key: &str,
+    provider: &PaymentProvider,
+    methods: &[PaymentMethod],
+    cash_receipt: Option<&CashReceipt>,
 ) -> Markup {
-    let success_url = "/actions/payment/verify".to_string();
-    let fail_url = paths::helpers::quote_path(&order.order_id);
+    let order_name = order.order_name();
+    let fail_path = paths::helpers::quote_path(&order.order_id);
+    let fake_checkout_path = paths::helpers::fake_checkout_path(&order.order_id);
+    let request = PaymentRequest {
+        amount: order.price_amount,
+        order_number: &order.order_number,
+        order_name: &order_name,
+        success_path: paths::actions::PAYMENT_VERIFY,
+        fail_path: &fail_path,
+        fake_checkout_path: &fake_checkout_path,
+        methods,
+    };
 
     let content = html! {
         div class="max-w-lg mx-auto" {
             h1 class="text-xl mb-3" { "Checkout" }
 
             div class="space-y-3" {
-                div class="space-y-1 text-sm" {
-                    div class="flex justify-between" {
-                        span class="text-gray-600" { "File" }
-                        span { (order.filename) }
-                    }
-                    div class="flex justify-between" {
-                        span class="text-gray-600" { "Characters" }
-                        span { (order.text_length.to_string()) }
-                    }
+                (order_items(order))
+
+                div class="border-t pt-3" {
+                    (tax_breakdown(order))
                 }
 
                 div class="border-t pt-3 mb-3" {
                     div class="flex justify-between items-center" {
                         span { "Total" }
-                        span class="text-xl text-indigo-600" { "₩" (format_price(order.price_amount)) }
+                        span class="text-xl text-indigo-600" { (order.price_amount) }
                     }
                 }
 
-                div id="payment-method" class="mb-3" {}
-                div id="agreement" class="mb-3" {}
+                @if methods.iter().any(PaymentMethod::allows_cash_receipt) {
+                    div class="border-t pt-3 mb-3" {
+                        (cash_receipt_form(order, cash_receipt))
+                    }
+                }
 
-                button
-                    id="payment-button"
-                    class="w-full bg-indigo-600 text-white px-3 py-2 hover:bg-indigo-700 disabled:bg-gray-400 disabled:cursor-not-allowed"
-                    { "Pay Now" }
+                (payment_button(&request, provider))
             }
         }
+    };
 
-        script src=(cdn::TOSS_PAYMENTS_SDK_URL) {}
-        script {
-            (PreEscaped(format!(r#"
-                const button = document.getElementById('payment-button');
-
-                try {{
-                    const tossPayments = TossPayments('{}');
-                    button.disabled = false;
-
-                    const paymentParams = {{
-                        amount: {},
-                        orderId: '{}',
-                        orderName: '{} - {}',
-                        successUrl: window.location.origin + '{}',
-                        failUrl: window.location.origin + '{}'
-                    }};
+    base::base_layout(current_user, flash, site_name, "Checkout", "Complete your payment", content)
+}
 
-                    button.addEventListener('click', function() {{
-                        console.log('Payment request parameters:', paymentParams);
+fn cash_receipt_form(order: &Order, cash_receipt: Option<&CashReceipt>) -> Markup {
+    let save_path = paths::with_param(paths::forms::CHECKOUT_CASH_RECEIPT, "order_id", &order.order_id);
+    let remove_path = paths::with_param(paths::forms::CHECKOUT_CASH_RECEIPT_REMOVE, "order_id", &order.order_id);
 
-                        tossPayments.requestPayment('카드', paymentParams)
-                        .catch(function(error) {{
-                            console.error('Payment request failed:', error);
-                            alert('결제 요청 실패: ' + (error.message || error.code));
-                        }});
-                    }});
-                }} catch (error) {{
-                    console.error('Toss Payments initialization failed:', error);
-                    button.disabled = true;
-                    button.textContent = 'Payment Error';
-                }}
-            "#,
-                client_key,
-                order.price_amount,
-                order.order_number,
-                payment::ORDER_NAME_PREFIX,
-                order.filename,
-                success_url,
-                fail_url
-            )))
+    html! {
+        p class="text-sm mb-1" { "Cash receipt (현금영수증)" }
+        p class="text-xs text-gray-600 mb-2" { "Issued when you pay by account transfer or virtual account." }
+        @if let Some(receipt) = cash_receipt {
+            div class="flex justify-between items-center text-sm" {
+                span {
+                    (receipt.receipt_type.display_text()) " · "
+                    span class="font-mono" { (receipt.masked_identifier()) }
+                }
+                form method="post" action=(remove_path) {
+                    button type="submit" class="text-indigo-600 hover:underline" { "Remove" }
+                }
+            }
+        } @else {
+            form method="post" action=(save_path) class="space-y-2" {
+                select name="receipt_type" class="w-full border px-2 py-1 text-sm" {
+                    @for receipt_type in CashReceiptType::ALL {
+                        option value=(receipt_type.as_str()) { (receipt_type.display_text()) }
+                    }
+                }
+                div class="flex gap-2" {
+                    input
+                        type="text"
+                        name="identifier"
+                        inputmode="numeric"
+                        placeholder="Phone, card or business registration number"
+                        required
+                        class="flex-1 border px-2 py-1 text-sm";
+                    button type="submit" class="px-3 py-1 border text-sm hover:bg-gray-50" { "Save" }
+                }
+            }
         }
-    };
-
-    base::base_layout(current_user, flash, site_name, "Checkout", "Complete your payment", content)
+    }
 }
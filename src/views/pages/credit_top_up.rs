@@ -0,0 +1,56 @@
+use crate::{
+    auth::CurrentUser,
+    config::PaymentProvider,
+    constants::credits,
+    flash::FlashMessage,
+    models::{credit::CreditTopUp, payment_method::PaymentMethod},
+    paths,
+    views::{
+        components::payment::{payment_button, PaymentRequest},
+        layout::base::base_layout,
+    },
+};
+use maud::{Markup, html};
+
+pub fn credit_top_up(
+    current_user: &CurrentUser,
+    flash: Option<&FlashMessage>,
+    site_name: &str,
+    topup: &CreditTopUp,
+    provider: &PaymentProvider,
+) -> Markup {
+    let fake_checkout_path = paths::helpers::fake_top_up_checkout_path(&topup.topup_id);
+    let request = PaymentRequest {
+        amount: topup.amount,
+        order_number: &topup.order_number,
+        order_name: credits::TOP_UP_ORDER_NAME,
+        success_path: paths::actions::CREDIT_TOP_UP_VERIFY,
+        fail_path: paths::pages::DASHBOARD,
+        fake_checkout_path: &fake_checkout_path,
+        // Top-ups are paid by card only, so credits never wait on a transfer
+        methods: &[PaymentMethod::Card],
+    };
+
+    let content = html! {
+        div class="max-w-lg mx-auto" {
+            h1 class="text-xl mb-3" { "Top Up Credits" }
+
+            div class="space-y-3" {
+                p class="text-sm text-gray-600" {
+                    "Credits pay for orders without a checkout and are refunded to your balance."
+                }
+
+                div class="border-t pt-3 mb-3" {
+                    div class="flex justify-between items-center" {
+                        span { "Total" }
+                        span class="text-xl text-indigo-600" { (topup.amount) }
+                    }
+                }
+
+                (payment_button(&request, provider))
+            }
+        }
+    };
+
+    base_layout(current_user, flash, site_name, "Top Up Credits", "Buy prepaid credits", content)
+}
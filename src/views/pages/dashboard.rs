@@ -1,8 +1,10 @@
 use crate::{
     auth::CurrentUser,
     flash::FlashMessage,
+    constants::credits,
     formatting,
-    models::order::OrderSummary,
+    models::{credit::CreditTransaction, order::OrderSummary},
+    money::Money,
     paths,
     views::layout::base::base_layout,
 };
@@ -13,6 +15,9 @@ pub fn dashboard(
     flash: Option<&FlashMessage>,
     site_name: &str,
     recent_orders: Vec<OrderSummary>,
+    payment_emails_enabled: bool,
+    credit_balance: Money,
+    credit_history: Vec<CreditTransaction>,
 ) -> Markup {
     let content = html! {
         div class="max-w-4xl mx-auto" {
@@ -29,6 +34,7 @@ pub fn dashboard(
                                 th class="text-right py-2 px-2" { "Price" }
                                 th class="text-center py-2 px-2" { "Status" }
                                 th class="text-center py-2 px-2" { "Date" }
+                                th class="text-center py-2 px-2" { "Receipt" }
                             }
                         }
                         tbody {
@@ -39,6 +45,52 @@ pub fn dashboard(
                     }
                 }
             }
+
+            h2 class="text-lg mt-8 mb-3" { "Credits" }
+            div class="flex items-center justify-between mb-3" {
+                p { "Balance: " span class="text-indigo-600" { (credit_balance) } }
+                form method="post" action=(paths::forms::CREDIT_TOP_UP) class="flex gap-2 text-sm" {
+                    select name="amount" class="border px-2 py-1" {
+                        @for amount in credits::TOP_UP_AMOUNTS {
+                            option value=(amount.amount) { (*amount) }
+                        }
+                    }
+                    button
+                        type="submit"
+                        class="bg-indigo-600 text-white px-3 py-1 hover:bg-indigo-700"
+                        { "Top Up" }
+                }
+            }
+            @if !credit_history.is_empty() {
+                table class="w-full text-sm" {
+                    thead class="border-b" {
+                        tr {
+                            th class="text-left py-2 px-2" { "Date" }
+                            th class="text-left py-2 px-2" { "Type" }
+                            th class="text-left py-2 px-2" { "Details" }
+                            th class="text-right py-2 px-2" { "Amount" }
+                            th class="text-right py-2 px-2" { "Balance" }
+                        }
+                    }
+                    tbody {
+                        @for transaction in &credit_history {
+                            (credit_row(transaction))
+                        }
+                    }
+                }
+            }
+
+            h2 class="text-lg mt-8 mb-3" { "Notifications" }
+            form method="post" action=(paths::forms::NOTIFICATION_PREFERENCES) class="space-y-3 text-sm" {
+                label class="flex items-center gap-2" {
+                    input type="checkbox" name="payment_emails" checked[payment_emails_enabled];
+                    "Email me payment receipts and failed payment notices"
+                }
+                button
+                    type="submit"
+                    class="bg-indigo-600 text-white px-3 py-2 hover:bg-indigo-700"
+                    { "Save" }
+            }
         }
     };
 
@@ -59,13 +111,41 @@ fn order_row(order: &OrderSummary) -> Markup {
                     (order.order_number)
                 }
             }
-            td class="py-2 px-2 text-right" { "₩" (order.price_amount) }
+            td class="py-2 px-2 text-right" { (order.price_amount) }
             td class="py-2 px-2 text-center" {
                 span class={"px-2 py-1 text-xs " (status_class)} {
                     (status_text)
                 }
             }
             td class="py-2 px-2 text-center text-gray-600" { (date_display) }
+            td class="py-2 px-2 text-center" {
+                @if order.payment_status.has_receipt() {
+                    a href=(paths::helpers::receipt_path(&order.order_id)) class="text-indigo-600 hover:underline" { "PDF" }
+                }
+            }
+        }
+    }
+}
+
+fn credit_row(transaction: &CreditTransaction) -> Markup {
+    html! {
+        tr class="border-b" {
+            td class="py-2 px-2 text-gray-600" { (formatting::format_datetime(transaction.created_at)) }
+            td class="py-2 px-2" { (transaction.kind.display_text()) }
+            td class="py-2 px-2" {
+                @if let Some(order_id) = transaction.order_id {
+                    a href=(paths::helpers::quote_path(&order_id)) class="text-indigo-600 hover:underline" {
+                        (transaction.note.as_deref().unwrap_or("Order"))
+                    }
+                } @else if let Some(note) = &transaction.note {
+                    (note)
+                }
+            }
+            td class={"py-2 px-2 text-right" @if transaction.amount.is_negative() { " text-red-600" } @else { " text-green-600" }} {
+                @if transaction.amount.is_positive() { "+" } @else { "−" }
+                (transaction.amount.abs())
+            }
+            td class="py-2 px-2 text-right" { (transaction.balance_after) }
         }
     }
 }
@@ -0,0 +1,83 @@
+use crate::{
+    auth::CurrentUser,
+    config::PaymentProvider,
+    flash::FlashMessage,
+    formatting::format_datetime,
+    models::{cash_receipt::CashReceipt, order::Order, virtual_account::VirtualAccount},
+    paths,
+    views::{components::payment::cash_receipt_summary, layout::base::base_layout},
+};
+use maud::{Markup, html};
+
+pub fn deposit(
+    current_user: &CurrentUser,
+    flash: Option<&FlashMessage>,
+    site_name: &str,
+    order: &Order,
+    account: &VirtualAccount,
+    cash_receipt: Option<&CashReceipt>,
+    provider: &PaymentProvider,
+) -> Markup {
+    let content = html! {
+        div class="max-w-lg mx-auto" {
+            h1 class="text-xl mb-3" { "Bank Transfer" }
+
+            div class="space-y-3" {
+                p class="text-sm text-gray-600" {
+                    "Transfer the exact total to the account below. Your order completes as soon as the deposit arrives; "
+                    "if nothing arrives by the due date, the order is cancelled."
+                }
+
+                div class="border p-3 space-y-1 text-sm" {
+                    div class="flex justify-between" {
+                        span class="text-gray-600" { "Bank" }
+                        span { (account.bank_name()) }
+                    }
+                    div class="flex justify-between" {
+                        span class="text-gray-600" { "Account number" }
+                        span class="font-mono" { (account.account_number) }
+                    }
+                    div class="flex justify-between" {
+                        span class="text-gray-600" { "Amount" }
+                        span class="text-indigo-600" { (order.price_amount) }
+                    }
+                    div class="flex justify-between" {
+                        span class="text-gray-600" { "Due" }
+                        span { (format_datetime(account.due_at)) " (UTC)" }
+                    }
+                }
+
+                div class="space-y-1 text-sm" {
+                    div class="flex justify-between" {
+                        span class="text-gray-600" { "Order" }
+                        span class="font-mono" { (order.order_number) }
+                    }
+                    div class="flex justify-between" {
+                        span class="text-gray-600" { "Items" }
+                        span { (order.order_name()) }
+                    }
+                    div class="flex justify-between" {
+                        span class="text-gray-600" { "Status" }
+                        span class=(order.payment_status.css_class()) { (order.payment_status.display_text()) }
+                    }
+                }
+
+                @if let Some(receipt) = cash_receipt {
+                    div class="border-t pt-3" {
+                        (cash_receipt_summary(receipt))
+                    }
+                }
+
+                @if let PaymentProvider::Fake = provider {
+                    p class="text-sm text-gray-600" { "Test mode: no bank is involved." }
+                    a
+                        href=(paths::helpers::fake_deposit_path(&order.order_id))
+                        class="block w-full text-center bg-green-600 text-white px-3 py-2 hover:bg-green-700"
+                        { "Simulate Deposit" }
+                }
+            }
+        }
+    };
+
+    base_layout(current_user, flash, site_name, "Bank Transfer", "Deposit instructions", content)
+}
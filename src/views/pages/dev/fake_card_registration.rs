@@ -0,0 +1,39 @@
+use crate::{
+    auth::CurrentUser,
+    flash::FlashMessage,
+    paths,
+    payment::FakeGateway,
+    views::layout::base::base_layout,
+};
+use maud::{html, Markup};
+
+/// Stand-in for the Toss card registration window when `PAYMENT_GATEWAY=fake`.
+///
+/// Both cards register; charges on the declining one fail, which exercises dunning.
+pub fn fake_card_registration(
+    current_user: &CurrentUser,
+    flash: Option<&FlashMessage>,
+    site_name: &str,
+    success_path: &str,
+    customer_key: &str,
+) -> Markup {
+    let registered_url = |approve| {
+        let url = paths::with_query_param(success_path, "customerKey", customer_key);
+        paths::with_query_param(&url, "authKey", &FakeGateway::billing_auth_key(approve))
+    };
+
+    let content = html! {
+        div class="max-w-lg mx-auto" {
+            h1 class="text-xl mb-3" { "Fake Card Registration" }
+            p class="text-sm text-gray-600 mb-3" { "No card is stored. Choose which card to register." }
+
+            div class="space-y-2" {
+                a href=(registered_url(true)) class="block w-full text-center bg-green-600 text-white px-3 py-2 hover:bg-green-700" { "Register Card" }
+                a href=(registered_url(false)) class="block w-full text-center bg-red-600 text-white px-3 py-2 hover:bg-red-700" { "Register Declining Card" }
+                a href=(paths::pages::SUBSCRIPTION) class="block w-full text-center border px-3 py-2 hover:bg-gray-50" { "Cancel" }
+            }
+        }
+    };
+
+    base_layout(current_user, flash, site_name, "Fake Card Registration", "Simulated card registration", content)
+}
@@ -0,0 +1,61 @@
+use crate::{
+    auth::CurrentUser,
+    flash::FlashMessage,
+    models::payment_method::PaymentMethod,
+    money::Money,
+    paths,
+    payment::FakeGateway,
+    views::layout::base::base_layout,
+};
+use maud::{html, Markup};
+
+/// What the stand-in payment window charges for and where its buttons lead.
+pub struct FakeCheckoutView<'a> {
+    pub order_number: &'a str,
+    pub amount: Money,
+    /// Reported by the fake gateway as how an approved payment was made
+    pub method: PaymentMethod,
+    pub verify_path: &'a str,
+    pub cancel_path: &'a str,
+}
+
+/// Stand-in for the Toss payment window when `PAYMENT_GATEWAY=fake`.
+///
+/// Each button leaves the page the way the real widget would: approve and decline
+/// return to `verify_path` (decline fails at confirmation), cancel returns to `cancel_path`.
+pub fn fake_checkout(
+    current_user: &CurrentUser,
+    flash: Option<&FlashMessage>,
+    site_name: &str,
+    checkout: FakeCheckoutView,
+) -> Markup {
+    let FakeCheckoutView { order_number, amount, method, verify_path, cancel_path } = checkout;
+    let approve_url = paths::helpers::payment_verify_url(verify_path, order_number, &FakeGateway::payment_key(method, true), amount);
+    let decline_url = paths::helpers::payment_verify_url(verify_path, order_number, &FakeGateway::payment_key(method, false), amount);
+
+    let content = html! {
+        div class="max-w-lg mx-auto" {
+            h1 class="text-xl mb-3" { "Fake Payment Gateway" }
+            p class="text-sm text-gray-600 mb-3" { "No money moves. Choose how the payment should turn out." }
+
+            div class="space-y-1 text-sm border-t border-b py-3 mb-3" {
+                div class="flex justify-between" {
+                    span class="text-gray-600" { "Order" }
+                    span class="font-mono" { (order_number) }
+                }
+                div class="flex justify-between" {
+                    span class="text-gray-600" { "Amount" }
+                    span { (amount) }
+                }
+            }
+
+            div class="space-y-2" {
+                a href=(approve_url) class="block w-full text-center bg-green-600 text-white px-3 py-2 hover:bg-green-700" { "Approve" }
+                a href=(decline_url) class="block w-full text-center bg-red-600 text-white px-3 py-2 hover:bg-red-700" { "Decline" }
+                a href=(cancel_path) class="block w-full text-center border px-3 py-2 hover:bg-gray-50" { "Cancel" }
+            }
+        }
+    };
+
+    base_layout(current_user, flash, site_name, "Fake Checkout", "Simulated payment window", content)
+}
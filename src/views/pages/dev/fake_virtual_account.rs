@@ -0,0 +1,52 @@
+use crate::{
+    auth::CurrentUser,
+    flash::FlashMessage,
+    models::{order::Order, payment_method::PaymentMethod},
+    paths,
+    payment::FakeGateway,
+    views::layout::base::base_layout,
+};
+use maud::{html, Markup};
+
+/// Stand-in for the Toss virtual account window when `PAYMENT_GATEWAY=fake`.
+///
+/// Issuing returns to the payment verification like the real window; the account
+/// then waits for a simulated deposit from the order's deposit page.
+pub fn fake_virtual_account(
+    current_user: &CurrentUser,
+    flash: Option<&FlashMessage>,
+    site_name: &str,
+    order: &Order,
+) -> Markup {
+    let issue_url = paths::helpers::payment_verify_url(
+        paths::actions::PAYMENT_VERIFY,
+        &order.order_number,
+        &FakeGateway::payment_key(PaymentMethod::VirtualAccount, true),
+        order.price_amount,
+    );
+
+    let content = html! {
+        div class="max-w-lg mx-auto" {
+            h1 class="text-xl mb-3" { "Fake Virtual Account" }
+            p class="text-sm text-gray-600 mb-3" { "No bank is involved. Issue an account, then simulate the deposit." }
+
+            div class="space-y-1 text-sm border-t border-b py-3 mb-3" {
+                div class="flex justify-between" {
+                    span class="text-gray-600" { "Order" }
+                    span class="font-mono" { (order.order_number) }
+                }
+                div class="flex justify-between" {
+                    span class="text-gray-600" { "Amount" }
+                    span { (order.price_amount) }
+                }
+            }
+
+            div class="space-y-2" {
+                a href=(issue_url) class="block w-full text-center bg-green-600 text-white px-3 py-2 hover:bg-green-700" { "Issue Virtual Account" }
+                a href=(paths::helpers::quote_path(&order.order_id)) class="block w-full text-center border px-3 py-2 hover:bg-gray-50" { "Cancel" }
+            }
+        }
+    };
+
+    base_layout(current_user, flash, site_name, "Fake Virtual Account", "Simulated virtual account window", content)
+}
@@ -0,0 +1,123 @@
+use crate::{
+    auth::CurrentUser,
+    email_capture::CapturedEmail,
+    flash::FlashMessage,
+    paths,
+    views::layout::base::base_layout,
+};
+use maud::{html, Markup};
+
+pub fn mailbox(
+    current_user: &CurrentUser,
+    flash: Option<&FlashMessage>,
+    site_name: &str,
+    messages: Vec<CapturedEmail>,
+) -> Markup {
+    let content = html! {
+        div class="max-w-6xl mx-auto" {
+            h1 class="text-xl mb-6" { "Mailbox" }
+
+            @if messages.is_empty() {
+                p class="text-gray-500 py-4" { "No captured emails" }
+            } @else {
+                table class="w-full text-sm" {
+                    thead class="border-b" {
+                        tr {
+                            th class="text-left py-2 px-2" { "To" }
+                            th class="text-left py-2 px-2" { "Subject" }
+                            th class="text-center py-2 px-2" { "Date" }
+                            th class="text-center py-2 px-2" { "Actions" }
+                        }
+                    }
+                    tbody {
+                        @for message in &messages {
+                            (message_row(message))
+                        }
+                    }
+                }
+            }
+        }
+    };
+
+    base_layout(current_user, flash, site_name, "Mailbox", "Captured outgoing email", content)
+}
+
+fn message_row(message: &CapturedEmail) -> Markup {
+    html! {
+        tr class="border-b" {
+            td class="py-2 px-2 text-gray-600" { (message.to) }
+            td class="py-2 px-2" { (message.subject) }
+            td class="py-2 px-2 text-center text-gray-600" { (message.date.as_deref().unwrap_or("")) }
+            td class="py-2 px-2 text-center" {
+                a href=(paths::helpers::mailbox_message_path(&message.id))
+                    class="text-indigo-600 hover:underline text-sm"
+                {
+                    "View"
+                }
+            }
+        }
+    }
+}
+
+pub fn mailbox_message(
+    current_user: &CurrentUser,
+    flash: Option<&FlashMessage>,
+    site_name: &str,
+    message: CapturedEmail,
+) -> Markup {
+    let content = html! {
+        div class="max-w-6xl mx-auto" {
+            div class="mb-4" {
+                a href=(paths::pages::dev::MAILBOX)
+                    class="text-indigo-600 hover:underline text-sm"
+                {
+                    "← Back to Mailbox"
+                }
+            }
+
+            h1 class="text-xl mb-6" { (message.subject) }
+
+            div class="mb-8 border p-4" {
+                div class="space-y-2 text-sm" {
+                    div {
+                        span class="text-gray-600" { "To: " }
+                        span { (message.to) }
+                    }
+                    @if let Some(date) = &message.date {
+                        div {
+                            span class="text-gray-600" { "Date: " }
+                            span { (date) }
+                        }
+                    }
+                    @if !message.attachments.is_empty() {
+                        div {
+                            span class="text-gray-600" { "Attachments: " }
+                            span { (message.attachments.join(", ")) }
+                        }
+                    }
+                }
+            }
+
+            @if let Some(html_body) = &message.html_body {
+                div class="mb-8 border p-4" {
+                    h2 class="text-lg mb-3" { "HTML" }
+                    iframe sandbox="" srcdoc=(html_body) class="w-full h-96 border" {}
+                }
+            }
+
+            @if let Some(text_body) = &message.text_body {
+                div class="mb-8 border p-4" {
+                    h2 class="text-lg mb-3" { "Text" }
+                    pre class="text-sm whitespace-pre-wrap" { (text_body) }
+                }
+            }
+
+            details class="border p-4" {
+                summary class="text-lg cursor-pointer" { "Raw Source" }
+                pre class="mt-3 text-xs whitespace-pre-wrap font-mono" { (message.raw) }
+            }
+        }
+    };
+
+    base_layout(current_user, flash, site_name, "Mailbox", &format!("Captured email to {}", message.to), content)
+}
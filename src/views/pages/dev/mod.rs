@@ -0,0 +1,9 @@
+mod fake_card_registration;
+mod fake_checkout;
+mod fake_virtual_account;
+mod mailbox;
+
+pub use fake_card_registration::fake_card_registration;
+pub use fake_checkout::{FakeCheckoutView, fake_checkout};
+pub use fake_virtual_account::fake_virtual_account;
+pub use mailbox::{mailbox, mailbox_message};
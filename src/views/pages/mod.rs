@@ -1,23 +1,34 @@
 pub mod admin;
+pub mod dev;
 
 mod checkout;
+mod credit_top_up;
 mod dashboard;
+mod deposit;
 mod not_found;
 mod quote;
 mod result;
 mod root;
 mod server_error;
 mod sign_in;
+mod subscription;
+mod subscription_card;
 mod text_analyzer;
 mod todos;
+mod unsubscribe;
 
 pub use checkout::checkout;
+pub use credit_top_up::credit_top_up;
 pub use dashboard::dashboard;
+pub use deposit::deposit;
 pub use not_found::not_found;
 pub use quote::quote;
 pub use result::result;
-pub use root::root;
+pub use root::{ContactFormView, root};
 pub use server_error::server_error;
 pub use sign_in::sign_in;
+pub use subscription::subscription;
+pub use subscription_card::subscription_card;
 pub use text_analyzer::text_analyzer;
-pub use todos::{todo_item, todos};
\ No newline at end of file
+pub use todos::{todo_item, todos};
+pub use unsubscribe::{unsubscribe, unsubscribe_invalid, unsubscribed};
\ No newline at end of file
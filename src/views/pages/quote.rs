@@ -1,54 +1,74 @@
-use crate::{auth::CurrentUser, flash::FlashMessage, formatting::format_price, models::order::Order, paths, views::layout::base::base_layout};
+use crate::{auth::CurrentUser, flash::FlashMessage, formatting::format_datetime, models::{discount::DiscountCode, order::Order}, money::Money, paths, views::{components::payment::{order_items, tax_breakdown}, layout::base::base_layout}};
 use maud::{Markup, html};
+use time::Duration;
 
 pub fn quote(
     current_user: &CurrentUser,
     flash: Option<&FlashMessage>,
     site_name: &str,
     order: &Order,
+    discount: Option<&DiscountCode>,
+    validity: Duration,
+    credit_balance: Money,
 ) -> Markup {
+    let expired = order.is_quote_expired(validity);
     let content = html! {
         div class="max-w-lg mx-auto" {
             h1 class="text-xl mb-3" { "Quote" }
 
             div class="space-y-3" {
-                div class="space-y-1 text-sm" {
-                    div class="flex justify-between" {
-                        span class="text-gray-600" { "File" }
-                        span { (order.filename) }
-                    }
-                    div class="flex justify-between" {
-                        span class="text-gray-600" { "Size" }
-                        span {
-                            @let bytes = order.file_size as f64;
-                            @if bytes < 1024.0 {
-                                (format!("{} B", bytes))
-                            } @else if bytes < 1024.0 * 1024.0 {
-                                (format!("{:.2} KB", bytes / 1024.0))
-                            } @else {
-                                (format!("{:.2} MB", bytes / (1024.0 * 1024.0)))
+                (order_items(order))
+
+                @if order.discount_code_id.is_some() {
+                    div class="border-t pt-3 space-y-1 text-sm" {
+                        div class="flex justify-between" {
+                            span class="text-gray-600" { "List price" }
+                            span { (order.list_price_amount) }
+                        }
+                        div class="flex justify-between" {
+                            span class="text-gray-600" {
+                                "Discount"
+                                @if let Some(discount) = discount {
+                                    " (" (discount.code) ", " (discount.describe()) ")"
+                                }
                             }
+                            span class="text-green-600" { "−" (order.discount_amount) }
                         }
                     }
-                    div class="flex justify-between" {
-                        span class="text-gray-600" { "Characters" }
-                        span { (order.text_length.to_string()) }
-                    }
+                }
+
+                div class="border-t pt-3" {
+                    (tax_breakdown(order))
                 }
 
                 div class="border-t pt-3" {
                     div class="flex justify-between items-center" {
                         span { "Total" }
-                        span class="text-xl text-indigo-600" { "₩" (format_price(order.price_amount)) }
+                        span class="text-xl text-indigo-600" { (order.price_amount) }
                     }
                 }
 
-                form method="post" action=(paths::actions::PAYMENT_INITIATE) {
-                    input type="hidden" name="order_id" value=(order.order_id.to_string());
-                    button
-                        type="submit"
-                        class="w-full bg-indigo-600 text-white px-3 py-2 hover:bg-indigo-700"
-                        { "Pay Now" }
+                @if expired {
+                    p class="text-sm text-red-600" { "This quote has expired." }
+                    a
+                        href=(paths::pages::TEXT_ANALYZER)
+                        class="block w-full text-center bg-indigo-600 text-white px-3 py-2 hover:bg-indigo-700"
+                        { "Get a New Quote" }
+                } @else {
+                    @if order.payment_status.is_payable() {
+                        p class="text-sm text-gray-600" { "Valid until " (format_datetime(order.quote_expires_at(validity))) " (UTC)" }
+                        (discount_form(order))
+                    }
+                    form method="post" action=(paths::actions::PAYMENT_INITIATE) {
+                        input type="hidden" name="order_id" value=(order.order_id.to_string());
+                        button
+                            type="submit"
+                            class="w-full bg-indigo-600 text-white px-3 py-2 hover:bg-indigo-700"
+                            { "Pay Now" }
+                    }
+                    @if order.payment_status.is_payable() && credit_balance.is_positive() {
+                        (credit_form(order, credit_balance))
+                    }
                 }
             }
         }
@@ -56,3 +76,45 @@ pub fn quote(
 
     base_layout(current_user, flash, site_name, "Quote", "Review your quote", content)
 }
+
+fn credit_form(order: &Order, credit_balance: Money) -> Markup {
+    html! {
+        @if credit_balance >= order.price_amount {
+            form method="post" action=(paths::actions::PAY_WITH_CREDITS) {
+                input type="hidden" name="order_id" value=(order.order_id.to_string());
+                button
+                    type="submit"
+                    class="w-full border border-indigo-600 text-indigo-600 px-3 py-2 hover:bg-indigo-50"
+                    { "Pay with Credits (" (credit_balance) " available)" }
+            }
+        } @else {
+            p class="text-sm text-gray-600" {
+                "Your " (credit_balance) " in credits does not cover this order. "
+                a href=(paths::pages::DASHBOARD) class="text-indigo-600 hover:underline" { "Top up" }
+            }
+        }
+    }
+}
+
+fn discount_form(order: &Order) -> Markup {
+    let apply_path = paths::with_param(paths::forms::QUOTE_DISCOUNT, "order_id", &order.order_id);
+    let remove_path = paths::with_param(paths::forms::QUOTE_DISCOUNT_REMOVE, "order_id", &order.order_id);
+
+    html! {
+        @if order.discount_code_id.is_some() {
+            form method="post" action=(remove_path) class="text-sm" {
+                button type="submit" class="text-indigo-600 hover:underline" { "Remove discount code" }
+            }
+        } @else {
+            form method="post" action=(apply_path) class="flex gap-2" {
+                input
+                    type="text"
+                    name="code"
+                    placeholder="Discount code"
+                    required
+                    class="flex-1 border px-2 py-1 text-sm";
+                button type="submit" class="px-3 py-1 border text-sm hover:bg-gray-50" { "Apply" }
+            }
+        }
+    }
+}
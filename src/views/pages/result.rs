@@ -1,4 +1,10 @@
-use crate::{auth::CurrentUser, flash::FlashMessage, models::order::Order, paths, views::layout::base::base_layout};
+use crate::{
+    auth::CurrentUser,
+    flash::FlashMessage,
+    models::{cash_receipt::CashReceipt, order::Order},
+    paths,
+    views::{components::payment::{cash_receipt_summary, tax_breakdown}, layout::base::base_layout},
+};
 use maud::{Markup, html};
 
 pub fn result(
@@ -6,9 +12,8 @@ pub fn result(
     flash: Option<&FlashMessage>,
     site_name: &str,
     order: &Order,
+    cash_receipt: Option<&CashReceipt>,
 ) -> Markup {
-    let word_count = order.text_content.split_whitespace().count();
-
     let content = html! {
         div class="max-w-lg mx-auto" {
             p class="text-green-700 mb-3" { "✓ Payment successful" }
@@ -16,14 +21,34 @@ pub fn result(
             h1 class="text-xl mb-3" { "Results" }
 
             div class="space-y-3" {
-                div class="grid grid-cols-2 gap-3 text-sm" {
-                    div class="text-center py-3 border" {
-                        p class="text-2xl" { (order.text_length) }
-                        p class="text-gray-600 mt-1" { "Characters" }
+                @if let Some(analysis) = order.text_analysis() {
+                    div class="grid grid-cols-2 gap-3 text-sm" {
+                        div class="text-center py-3 border" {
+                            p class="text-2xl" { (analysis.text_length) }
+                            p class="text-gray-600 mt-1" { "Characters" }
+                        }
+                        div class="text-center py-3 border" {
+                            p class="text-2xl" { (analysis.word_count()) }
+                            p class="text-gray-600 mt-1" { "Words" }
+                        }
+                    }
+                }
+
+                div class="border-t pt-3 space-y-1" {
+                    p class="text-sm" { "Receipt " span class="font-mono text-gray-600" { (order.order_number) } }
+                    (tax_breakdown(order))
+                    div class="flex justify-between text-sm" {
+                        span { "Total paid" }
+                        span { (order.price_amount) }
                     }
-                    div class="text-center py-3 border" {
-                        p class="text-2xl" { (word_count) }
-                        p class="text-gray-600 mt-1" { "Words" }
+                    a href=(paths::helpers::receipt_path(&order.order_id)) class="text-sm text-indigo-600 hover:underline" {
+                        "Download receipt (PDF)"
+                    }
+                }
+
+                @if let Some(receipt) = cash_receipt.filter(|_| order.allows_cash_receipt()) {
+                    div class="border-t pt-3" {
+                        (cash_receipt_summary(receipt))
                     }
                 }
 
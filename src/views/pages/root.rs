@@ -1,20 +1,40 @@
-use crate::{auth::CurrentUser, flash::FlashMessage, paths, views::layout::base::base_layout};
+use crate::{
+    auth::CurrentUser,
+    constants::spam,
+    flash::FlashMessage,
+    paths,
+    proof_of_work::PowChallenge,
+    views::{components::proof_of_work::pow_fields, layout::base::base_layout},
+};
 use maud::{Markup, html};
 
+/// Values and errors for (re-)rendering the contact form.
+pub struct ContactFormView<'a> {
+    pub email: Option<&'a str>,
+    pub message: Option<&'a str>,
+    pub email_error: Option<&'a str>,
+    pub message_error: Option<&'a str>,
+    pub form_stamp: &'a str,
+    pub pow: Option<&'a PowChallenge>,
+}
+
 pub fn root(
     current_user: &CurrentUser,
     flash: Option<&FlashMessage>,
     site_name: &str,
-    email: Option<&str>,
-    message: Option<&str>,
-    email_error: Option<&str>,
-    message_error: Option<&str>,
+    form: ContactFormView,
 ) -> Markup {
+    let ContactFormView { email, message, email_error, message_error, form_stamp, pow } = form;
+
     let content = html! {
         div class="max-w-lg mx-auto" {
             h1 class="text-xl mb-3" { "Contact" }
 
-            form method="post" action=(paths::forms::CONTACT) class="space-y-3" {
+            form method="post" action=(paths::forms::CONTACT) class="space-y-3"
+                data-pow-challenge=[pow.map(|challenge| &challenge.token)]
+                data-pow-difficulty=[pow.map(|challenge| challenge.difficulty)]
+            {
+                    (pow_fields(pow))
                     @match current_user {
                         CurrentUser::Authenticated { .. } => {
                             div {
@@ -64,6 +84,13 @@ pub fn root(
                         }
                     }
 
+                    // Honeypot: off-screen rather than display:none, which some bots skip
+                    div class="absolute -left-[9999px]" aria-hidden="true" {
+                        label for=(spam::HONEYPOT_FIELD) { "Leave this field empty" }
+                        input type="text" id=(spam::HONEYPOT_FIELD) name=(spam::HONEYPOT_FIELD) tabindex="-1" autocomplete="off";
+                    }
+                    input type="hidden" name="form_stamp" value=(form_stamp);
+
                     button
                         type="submit"
                         class="w-full bg-indigo-600 text-white px-3 py-2 hover:bg-indigo-700"
@@ -3,7 +3,8 @@ use crate::{
     flash::FlashMessage,
     models::user::FIELD_EMAIL,
     paths,
-    views::{components::form, layout::base::base_layout},
+    proof_of_work::PowChallenge,
+    views::{components::{form, proof_of_work::pow_fields}, layout::base::base_layout},
 };
 use maud::{html, Markup};
 
@@ -13,12 +14,17 @@ pub fn sign_in(
     site_name: &str,
     email_value: Option<&str>,
     email_error: Option<&str>,
+    pow: Option<&PowChallenge>,
 ) -> Markup {
     let content = html! {
         div class="max-w-sm mx-auto" {
             h1 class="text-xl mb-3" { "Sign In" }
 
-            form method="POST" action=(paths::forms::SIGN_IN) class="space-y-3" {
+            form method="POST" action=(paths::forms::SIGN_IN) class="space-y-3"
+                data-pow-challenge=[pow.map(|challenge| &challenge.token)]
+                data-pow-difficulty=[pow.map(|challenge| challenge.difficulty)]
+            {
+                (pow_fields(pow))
                 (form::input("email", FIELD_EMAIL, "Email", email_value, email_error))
                 (form::submit_button("Send Magic Link"))
             }
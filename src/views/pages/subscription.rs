@@ -0,0 +1,136 @@
+use crate::{
+    auth::CurrentUser,
+    flash::FlashMessage,
+    formatting::{format_datetime, format_number},
+    models::subscription::{Subscription, SubscriptionCharge, SubscriptionPlan, SubscriptionStatus},
+    paths,
+    views::layout::base::base_layout,
+};
+use maud::{Markup, html};
+
+pub fn subscription(
+    current_user: &CurrentUser,
+    flash: Option<&FlashMessage>,
+    site_name: &str,
+    plans: &[SubscriptionPlan],
+    subscription: Option<&Subscription>,
+    charges: &[SubscriptionCharge],
+) -> Markup {
+    let content = html! {
+        div class="max-w-lg mx-auto" {
+            h1 class="text-xl mb-3" { "Subscription" }
+
+            @match subscription {
+                Some(subscription) => (subscription_details(subscription)),
+                None => (plan_list(plans)),
+            }
+
+            @if !charges.is_empty() {
+                h2 class="text-lg mt-8 mb-3" { "Payments" }
+                table class="w-full text-sm" {
+                    thead class="border-b" {
+                        tr {
+                            th class="text-left py-2 px-2" { "Date" }
+                            th class="text-left py-2 px-2" { "Payment" }
+                            th class="text-right py-2 px-2" { "Amount" }
+                            th class="text-center py-2 px-2" { "Status" }
+                        }
+                    }
+                    tbody {
+                        @for charge in charges {
+                            tr class="border-b" {
+                                td class="py-2 px-2 text-gray-600" { (format_datetime(charge.created_at)) }
+                                td class="py-2 px-2 font-mono text-xs" { (charge.order_number) }
+                                td class="py-2 px-2 text-right" { (charge.amount) }
+                                td class="py-2 px-2 text-center" {
+                                    span class={"text-xs " (charge.status.css_class())} { (charge.status.display_text()) }
+                                    @if let Some(message) = &charge.failure_message {
+                                        p class="text-xs text-gray-500" { (message) }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    };
+
+    base_layout(current_user, flash, site_name, "Subscription", "Monthly plans", content)
+}
+
+fn plan_list(plans: &[SubscriptionPlan]) -> Markup {
+    html! {
+        p class="text-sm text-gray-600 mb-3" {
+            "A plan includes a number of characters of analysis every month. Files that fit in "
+            "what is left are analyzed without a checkout."
+        }
+        div class="space-y-3" {
+            @for plan in plans {
+                form method="post" action=(paths::forms::SUBSCRIBE) class="border p-3 flex justify-between items-center" {
+                    input type="hidden" name="plan_id" value=(plan.plan_id);
+                    div {
+                        p { (plan.name) }
+                        p class="text-sm text-gray-600" { (plan.describe()) }
+                    }
+                    button type="submit" class="bg-indigo-600 text-white px-3 py-1 hover:bg-indigo-700" { "Subscribe" }
+                }
+            }
+        }
+    }
+}
+
+fn subscription_details(subscription: &Subscription) -> Markup {
+    let period_end = subscription.current_period_end.map(format_datetime).unwrap_or_default();
+
+    html! {
+        div class="border p-3 space-y-2 text-sm" {
+            div class="flex justify-between" {
+                span { (subscription.plan_name) }
+                span class=(subscription.status.css_class()) { (subscription.status.display_text()) }
+            }
+            div class="flex justify-between" {
+                span class="text-gray-600" { "Characters used" }
+                span { (format_number(subscription.characters_used)) " / " (format_number(subscription.included_characters)) }
+            }
+            div class="flex justify-between" {
+                span class="text-gray-600" {
+                    @if subscription.cancel_at_period_end { "Ends" } @else { "Renews" }
+                }
+                span { (period_end) }
+            }
+            @if let Some(card_label) = &subscription.card_label {
+                div class="flex justify-between" {
+                    span class="text-gray-600" { "Card" }
+                    span class="font-mono" { (card_label) }
+                }
+            }
+        }
+
+        @if subscription.status == SubscriptionStatus::PastDue {
+            div class="border border-red-300 bg-red-50 p-3 mt-3 text-sm" {
+                p {
+                    "Your last payment failed (attempt " (subscription.failed_attempts) "), so your included characters are paused."
+                    @if let Some(next_retry_at) = subscription.next_retry_at {
+                        " We'll try again on " (format_datetime(next_retry_at)) "."
+                    }
+                }
+                a href=(paths::helpers::subscription_card_path(&subscription.subscription_id)) class="text-indigo-600 hover:underline" {
+                    "Update card and pay now"
+                }
+            }
+        }
+
+        div class="mt-3" {
+            @if subscription.cancel_at_period_end {
+                form method="post" action=(paths::forms::SUBSCRIPTION_RESUME) {
+                    button type="submit" class="w-full bg-indigo-600 text-white px-3 py-2 hover:bg-indigo-700" { "Resume Subscription" }
+                }
+            } @else {
+                form method="post" action=(paths::forms::SUBSCRIPTION_CANCEL) {
+                    button type="submit" class="w-full border px-3 py-2 hover:bg-gray-50" { "Cancel Subscription" }
+                }
+            }
+        }
+    }
+}
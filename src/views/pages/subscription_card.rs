@@ -0,0 +1,53 @@
+use crate::{
+    auth::CurrentUser,
+    config::PaymentProvider,
+    flash::FlashMessage,
+    models::subscription::Subscription,
+    paths,
+    views::{
+        components::payment::{card_registration_button, CardRegistration},
+        layout::base::base_layout,
+    },
+};
+use maud::{Markup, html};
+
+pub fn subscription_card(
+    current_user: &CurrentUser,
+    flash: Option<&FlashMessage>,
+    site_name: &str,
+    subscription: &Subscription,
+    provider: &PaymentProvider,
+) -> Markup {
+    let success_path = paths::helpers::card_registered_path(&subscription.subscription_id);
+    let fake_registration_path = paths::helpers::fake_card_registration_path(&subscription.subscription_id);
+    let registration = CardRegistration {
+        customer_key: &subscription.customer_key,
+        success_path: &success_path,
+        fail_path: paths::pages::SUBSCRIPTION,
+        fake_registration_path: &fake_registration_path,
+    };
+
+    let content = html! {
+        div class="max-w-lg mx-auto" {
+            h1 class="text-xl mb-3" { "Register a Card" }
+
+            div class="space-y-3" {
+                p class="text-sm text-gray-600" {
+                    "Your card is charged now and then every 30 days until you cancel. "
+                    "You can cancel at any time; the plan stays active until the paid period ends."
+                }
+
+                div class="border-t pt-3 mb-3" {
+                    div class="flex justify-between items-center" {
+                        span { (subscription.order_name()) }
+                        span class="text-xl text-indigo-600" { (subscription.price_amount) }
+                    }
+                }
+
+                (card_registration_button(&registration, provider))
+            }
+        }
+    };
+
+    base_layout(current_user, flash, site_name, "Register a Card", "Card for subscription payments", content)
+}
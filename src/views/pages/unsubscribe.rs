@@ -0,0 +1,56 @@
+use crate::{auth::CurrentUser, flash::FlashMessage, paths, views::layout::base::base_layout};
+use maud::{Markup, html};
+
+/// Confirmation step for links followed from an email body.
+pub fn unsubscribe(
+    current_user: &CurrentUser,
+    flash: Option<&FlashMessage>,
+    site_name: &str,
+    email: &str,
+    token: &str,
+) -> Markup {
+    let action = paths::with_query_param(paths::actions::UNSUBSCRIBE, "token", token);
+
+    let content = html! {
+        div class="max-w-md mx-auto space-y-4" {
+            h1 class="text-xl" { "Unsubscribe" }
+            p {
+                "Stop sending payment notifications to "
+                strong { (email) }
+                "? Sign-in links will still be delivered."
+            }
+            form method="post" action=(action) {
+                button type="submit" class="px-4 py-2 bg-indigo-600 text-white hover:bg-indigo-700" {
+                    "Unsubscribe"
+                }
+            }
+        }
+    };
+
+    base_layout(current_user, flash, site_name, "Unsubscribe", "Manage email notifications", content)
+}
+
+pub fn unsubscribed(current_user: &CurrentUser, site_name: &str, email: &str) -> Markup {
+    let content = html! {
+        div class="max-w-md mx-auto space-y-4" {
+            h1 class="text-xl" { "You're unsubscribed" }
+            p {
+                strong { (email) }
+                " will no longer receive payment notifications."
+            }
+        }
+    };
+
+    base_layout(current_user, None, site_name, "Unsubscribed", "Manage email notifications", content)
+}
+
+pub fn unsubscribe_invalid(current_user: &CurrentUser, site_name: &str, message: &str) -> Markup {
+    let content = html! {
+        div class="max-w-md mx-auto space-y-4" {
+            h1 class="text-xl" { "Unsubscribe" }
+            p class="text-red-600" { (message) }
+        }
+    };
+
+    base_layout(current_user, None, site_name, "Unsubscribe", "Manage email notifications", content)
+}